@@ -64,6 +64,17 @@ define_set_collection_element_command!(
     }
 );
 
+define_set_collection_element_command!(
+    SetBlendAdditivePoseSourceCommand<Handle<PoseNode>, Handle<PoseNode>>(self, context) {
+        let machine = fetch_machine(context, self.node_handle);
+        if let PoseNode::BlendAdditive(ref mut definition) = machine.layers_mut()[self.layer_index].nodes_mut()[self.handle] {
+            if let Some(input) = definition.input_mut(self.index) {
+                std::mem::swap(input, &mut self.value);
+            }
+        }
+    }
+);
+
 define_set_collection_element_command!(
     SetBlendSpacePointPositionCommand<Handle<PoseNode>, Vector2<f32>>(self, context) {
         let machine = fetch_machine(context, self.node_handle);
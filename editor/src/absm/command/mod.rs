@@ -770,6 +770,11 @@ define_absm_swap_command!(SetStateRootPoseCommand<Handle<State>, Handle<PoseNode
     &mut machine.layers_mut()[self.layer_index].states_mut()[self.handle].root
 });
 
+define_absm_swap_command!(SetSubMachineEntryStateCommand<Handle<State>, Handle<State>>[layer_index: usize](self, context) {
+    let machine = fetch_machine(context, self.node_handle);
+    &mut machine.layers_mut()[self.layer_index].states_mut()[self.handle].entry_state
+});
+
 #[derive(Debug)]
 pub struct SetLayerNameCommand {
     pub absm_node_handle: Handle<Node>,
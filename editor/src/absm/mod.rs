@@ -382,8 +382,11 @@ impl AbsmEditor {
                     while let Some(event) = layer.pop_event() {
                         match event {
                             Event::ActiveStateChanged { new: state, .. } => {
-                                self.state_graph_viewer
-                                    .activate_state(&engine.user_interface, state);
+                                self.state_graph_viewer.activate_state(
+                                    &engine.user_interface,
+                                    layer,
+                                    state,
+                                );
                             }
                             Event::ActiveTransitionChanged(transition) => {
                                 self.state_graph_viewer
@@ -528,12 +531,23 @@ impl AbsmEditor {
                             .query_component::<AbsmNode<State>>()
                         {
                             if let Some(layer_index) = selection.layer {
-                                self.state_viewer.set_state(
-                                    node.model_handle,
-                                    absm_node,
-                                    layer_index,
-                                    ui,
-                                );
+                                let is_sub_machine = absm_node.machine().layers()[layer_index]
+                                    .states()
+                                    .try_borrow(node.model_handle)
+                                    .is_some_and(|state| state.sub_machine);
+
+                                if is_sub_machine {
+                                    // Sub-machines do not have their own pose graph, show their nested
+                                    // graphs instead.
+                                    self.state_graph_viewer.enter_graph(node.model_handle);
+                                } else {
+                                    self.state_viewer.set_state(
+                                        node.model_handle,
+                                        absm_node,
+                                        layer_index,
+                                        ui,
+                                    );
+                                }
                                 sender.send(Message::ForceSync);
                             }
                         }
@@ -563,8 +577,8 @@ impl AbsmEditor {
                                     [node.model_handle];
 
                                 match model_ref {
-                                    PoseNode::PlayAnimation(_) => {
-                                        // No input sockets
+                                    PoseNode::PlayAnimation(_) | PoseNode::BlendAdditive(_) => {
+                                        // No input sockets or fixed amount of input sockets.
                                     }
                                     PoseNode::BlendAnimations(_) => {
                                         sender.do_scene_command(AddPoseSourceCommand::new(
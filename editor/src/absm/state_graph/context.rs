@@ -3,7 +3,7 @@ use crate::{
         canvas::{AbsmCanvas, AbsmCanvasMessage, Mode},
         command::{
             AddStateCommand, AddTransitionCommand, DeleteStateCommand, DeleteTransitionCommand,
-            SetMachineEntryStateCommand,
+            SetMachineEntryStateCommand, SetSubMachineEntryStateCommand,
         },
         node::{AbsmNode, AbsmNodeMessage},
         selection::SelectedEntity,
@@ -34,6 +34,7 @@ use super::fetch_state_node_model_handle;
 
 pub struct CanvasContextMenu {
     create_state: Handle<UiNode>,
+    create_sub_machine: Handle<UiNode>,
     connect_all_nodes: Handle<UiNode>,
     pub menu: RcUiNodeHandle,
     pub canvas: Handle<UiNode>,
@@ -43,6 +44,7 @@ pub struct CanvasContextMenu {
 impl CanvasContextMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_state;
+        let create_sub_machine;
         let connect_all_nodes;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
@@ -51,6 +53,10 @@ impl CanvasContextMenu {
                         create_state = create_menu_item("Create State", vec![], ctx);
                         create_state
                     },
+                    {
+                        create_sub_machine = create_menu_item("Create Sub-Machine", vec![], ctx);
+                        create_sub_machine
+                    },
                     {
                         connect_all_nodes = create_menu_item("Connect all nodes", vec![], ctx);
                         connect_all_nodes
@@ -63,6 +69,7 @@ impl CanvasContextMenu {
 
         Self {
             create_state,
+            create_sub_machine,
             connect_all_nodes,
             menu,
             canvas: Default::default(),
@@ -77,22 +84,23 @@ impl CanvasContextMenu {
         ui: &mut UserInterface,
         absm_node_handle: Handle<Node>,
         layer_index: usize,
+        current_graph: Handle<State>,
     ) {
         if let Some(MenuItemMessage::Click) = message.data() {
-            if message.destination() == self.create_state {
+            if message.destination() == self.create_state
+                || message.destination() == self.create_sub_machine
+            {
                 let screen_position = ui.node(self.menu.handle()).screen_position();
 
-                sender.do_scene_command(AddStateCommand::new(
-                    absm_node_handle,
-                    layer_index,
-                    State {
-                        position: ui.node(self.canvas).screen_to_local(screen_position),
-                        name: "New State".to_string(),
-                        on_enter_actions: Default::default(),
-                        on_leave_actions: Default::default(),
-                        root: Default::default(),
-                    },
-                ));
+                let mut state = if message.destination() == self.create_sub_machine {
+                    State::new_sub_machine("New Sub-Machine")
+                } else {
+                    State::new("New State", Default::default())
+                }
+                .with_parent(current_graph);
+                state.position = ui.node(self.canvas).screen_to_local(screen_position);
+
+                sender.do_scene_command(AddStateCommand::new(absm_node_handle, layer_index, state));
             } else if message.destination() == self.connect_all_nodes {
                 let canvas = ui
                     .node(self.canvas)
@@ -202,6 +210,7 @@ impl NodeContextMenu {
         absm_node: &AnimationBlendingStateMachine,
         layer_index: usize,
         editor_selection: &Selection,
+        current_graph: Handle<State>,
     ) {
         let machine = absm_node.machine();
         if let Some(MenuItemMessage::Click) = message.data() {
@@ -230,20 +239,37 @@ impl NodeContextMenu {
                         })
                         .collect::<Vec<_>>();
 
-                    // Gather every transition that leads from/to any of states to remove.
-                    let transitions_to_remove = machine.layers()[layer_index]
-                        .transitions()
+                    // States of nested graphs of sub-machines must be removed too.
+                    let layer = &machine.layers()[layer_index];
+                    let states_to_remove = layer
+                        .states()
                         .pair_iter()
-                        .filter_map(|(handle, transition)| {
-                            if states_to_remove.iter().cloned().any(|state_to_remove| {
-                                state_to_remove == transition.source()
-                                    || state_to_remove == transition.dest()
+                        .filter_map(|(handle, _)| {
+                            if states_to_remove.iter().any(|state_to_remove| {
+                                layer.is_state_inside(handle, *state_to_remove)
                             }) {
                                 Some(handle)
                             } else {
                                 None
                             }
-                        });
+                        })
+                        .collect::<Vec<_>>();
+
+                    // Gather every transition that leads from/to any of states to remove.
+                    let transitions_to_remove =
+                        layer
+                            .transitions()
+                            .pair_iter()
+                            .filter_map(|(handle, transition)| {
+                                if states_to_remove.iter().cloned().any(|state_to_remove| {
+                                    state_to_remove == transition.source()
+                                        || state_to_remove == transition.dest()
+                                }) {
+                                    Some(handle)
+                                } else {
+                                    None
+                                }
+                            });
 
                     let mut new_selection = selection.clone();
                     new_selection.entities.clear();
@@ -272,15 +298,26 @@ impl NodeContextMenu {
                     sender.do_scene_command(CommandGroup::from(group));
                 }
             } else if message.destination() == self.set_as_entry_state {
-                sender.do_scene_command(SetMachineEntryStateCommand {
-                    node_handle: absm_node_handle,
-                    layer: layer_index,
-                    entry: ui
-                        .node(self.placement_target)
-                        .query_component::<AbsmNode<State>>()
-                        .unwrap()
-                        .model_handle,
-                });
+                let entry = ui
+                    .node(self.placement_target)
+                    .query_component::<AbsmNode<State>>()
+                    .unwrap()
+                    .model_handle;
+
+                if current_graph.is_some() {
+                    sender.do_scene_command(SetSubMachineEntryStateCommand {
+                        node_handle: absm_node_handle,
+                        handle: current_graph,
+                        value: entry,
+                        layer_index,
+                    });
+                } else {
+                    sender.do_scene_command(SetMachineEntryStateCommand {
+                        node_handle: absm_node_handle,
+                        layer: layer_index,
+                        entry,
+                    });
+                }
             } else if message.destination == self.enter_state {
                 ui.send_message(AbsmNodeMessage::enter(
                     self.placement_target,
@@ -14,17 +14,22 @@ use crate::{
         commands::{ChangeSelectionCommand, CommandGroup, GameSceneCommand},
         Selection,
     },
-    send_sync_message,
+    send_sync_message, Message,
 };
 use fyrox::{
     animation::machine::{MachineLayer, State, Transition},
     core::pool::Handle,
     gui::{
         border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
-        BuildContext, Thickness, UiNode, UserInterface,
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
     scene::{animation::absm::AnimationBlendingStateMachine, node::Node},
 };
@@ -39,6 +44,11 @@ pub struct StateGraphViewer {
     node_context_menu: NodeContextMenu,
     transition_context_menu: TransitionContextMenu,
     last_selection: AbsmSelection,
+    up: Handle<UiNode>,
+    path: Handle<UiNode>,
+    // A sub-machine which nested graph is currently shown, `Handle::NONE` - top-level graph of a layer.
+    current_graph: Handle<State>,
+    synced_graph: Handle<State>,
 }
 
 fn fetch_state_node_model_handle(handle: Handle<UiNode>, ui: &UserInterface) -> Handle<State> {
@@ -59,16 +69,58 @@ impl StateGraphViewer {
         )
         .build(ctx);
 
+        let up;
+        let path;
+        let navigation_panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .with_child({
+                    up = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_width(40.0)
+                            .with_enabled(false)
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Leave the nested graph of the current sub-machine",
+                            )),
+                    )
+                    .with_text("Up")
+                    .build(ctx);
+                    up
+                })
+                .with_child({
+                    path = TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(5.0)))
+                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                        .with_text("Root")
+                        .build(ctx);
+                    path
+                }),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
         let window = WindowBuilder::new(WidgetBuilder::new())
             .with_title(WindowTitle::text("State Graph"))
             .can_close(false)
             .can_minimize(false)
             .with_content(
-                BorderBuilder::new(
+                GridBuilder::new(
                     WidgetBuilder::new()
-                        .with_margin(Thickness::uniform(1.0))
-                        .with_child(canvas),
+                        .with_child(navigation_panel)
+                        .with_child(
+                            BorderBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .with_child(canvas),
+                            )
+                            .build(ctx),
+                        ),
                 )
+                .add_row(Row::strict(24.0))
+                .add_row(Row::stretch())
+                .add_column(Column::stretch())
                 .build(ctx),
             )
             .build(ctx);
@@ -84,9 +136,19 @@ impl StateGraphViewer {
             canvas_context_menu,
             transition_context_menu,
             last_selection: AbsmSelection::default(),
+            up,
+            path,
+            current_graph: Handle::NONE,
+            synced_graph: Handle::NONE,
         }
     }
 
+    /// Shows the nested graph of the given sub-machine, `Handle::NONE` shows the top-level graph of a layer. Actual
+    /// content of the canvas will be updated on next sync.
+    pub fn enter_graph(&mut self, graph: Handle<State>) {
+        self.current_graph = graph;
+    }
+
     pub fn clear(&self, ui: &UserInterface) {
         for &child in ui.node(self.canvas).children() {
             ui.send_message(WidgetMessage::remove(child, MessageDirection::ToWidget));
@@ -108,7 +170,21 @@ impl StateGraphViewer {
         }
     }
 
-    pub fn activate_state(&self, ui: &UserInterface, state: Handle<State>) {
+    pub fn activate_state(
+        &self,
+        ui: &UserInterface,
+        machine_layer: &MachineLayer,
+        mut state: Handle<State>,
+    ) {
+        // The state could belong to a nested graph, in this case a sub-machine of the current graph
+        // that contains the state must be highlighted.
+        while let Some(state_ref) = machine_layer.states().try_borrow(state) {
+            if state_ref.parent == self.current_graph {
+                break;
+            }
+            state = state_ref.parent;
+        }
+
         for (state_view_handle, state_view_ref) in ui
             .node(self.canvas)
             .children()
@@ -138,6 +214,17 @@ impl StateGraphViewer {
         layer_index: usize,
         editor_selection: &Selection,
     ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.up {
+                self.current_graph = absm_node.machine().layers()[layer_index]
+                    .states()
+                    .try_borrow(self.current_graph)
+                    .map(|state| state.parent)
+                    .unwrap_or_default();
+                sender.send(Message::ForceSync);
+            }
+        }
+
         if message.destination() == self.canvas {
             if let Some(msg) = message.data::<AbsmCanvasMessage>() {
                 match msg {
@@ -243,6 +330,7 @@ impl StateGraphViewer {
             absm_node,
             layer_index,
             editor_selection,
+            self.current_graph,
         );
         self.canvas_context_menu.handle_ui_message(
             sender,
@@ -250,6 +338,7 @@ impl StateGraphViewer {
             ui,
             absm_node_handle,
             layer_index,
+            self.current_graph,
         );
         self.transition_context_menu.handle_ui_message(
             message,
@@ -261,6 +350,30 @@ impl StateGraphViewer {
         );
     }
 
+    fn sync_navigation(&self, machine_layer: &MachineLayer, ui: &UserInterface) {
+        let mut path = Vec::new();
+        let mut graph = self.current_graph;
+        while let Some(state) = machine_layer.states().try_borrow(graph) {
+            path.push(state.name.as_str());
+            graph = state.parent;
+        }
+        path.push("Root");
+        path.reverse();
+
+        send_sync_message(
+            ui,
+            TextMessage::text(self.path, MessageDirection::ToWidget, path.join(" / ")),
+        );
+        send_sync_message(
+            ui,
+            WidgetMessage::enabled(
+                self.up,
+                MessageDirection::ToWidget,
+                self.current_graph.is_some(),
+            ),
+        );
+    }
+
     pub fn sync_to_model(
         &mut self,
         machine_layer: &MachineLayer,
@@ -274,13 +387,30 @@ impl StateGraphViewer {
 
         let current_selection = fetch_selection(editor_selection);
 
+        if self.last_selection.layer != current_selection.layer
+            || self.last_selection.absm_node_handle != current_selection.absm_node_handle
+        {
+            self.current_graph = Handle::NONE;
+        }
+
+        // The sub-machine could be removed (or the removal of its parent could be undone).
+        if !machine_layer
+            .states()
+            .try_borrow(self.current_graph)
+            .is_some_and(|state| state.sub_machine)
+        {
+            self.current_graph = Handle::NONE;
+        }
+
         let mut states = Vec::new();
         let mut transitions = Vec::new();
         if self.last_selection.layer != current_selection.layer
             || self.last_selection.absm_node_handle != current_selection.absm_node_handle
+            || self.synced_graph != self.current_graph
         {
             self.last_selection = current_selection.clone();
-            // Remove content of the previous layer/absm.
+            self.synced_graph = self.current_graph;
+            // Remove content of the previous layer/absm/graph.
             self.clear(ui);
         } else {
             states = canvas
@@ -298,13 +428,43 @@ impl StateGraphViewer {
                 .collect::<Vec<_>>();
         }
 
-        match states
-            .len()
-            .cmp(&(machine_layer.states().alive_count() as usize))
-        {
+        self.sync_navigation(machine_layer, ui);
+
+        let current_graph = self.current_graph;
+        let entry_state = if let Some(graph) = machine_layer.states().try_borrow(current_graph) {
+            if graph.entry_state.is_some() {
+                graph.entry_state
+            } else {
+                machine_layer
+                    .states()
+                    .pair_iter()
+                    .find_map(|(h, s)| {
+                        if s.parent == current_graph {
+                            Some(h)
+                        } else {
+                            None
+                        }
+                    })
+                    .unwrap_or_default()
+            }
+        } else {
+            machine_layer.entry_state()
+        };
+
+        let graph_states_count = machine_layer
+            .states()
+            .iter()
+            .filter(|state| state.parent == current_graph)
+            .count();
+
+        match states.len().cmp(&graph_states_count) {
             Ordering::Less => {
                 // A state was added.
-                for (state_handle, state) in machine_layer.states().pair_iter() {
+                for (state_handle, state) in machine_layer
+                    .states()
+                    .pair_iter()
+                    .filter(|(_, state)| state.parent == current_graph)
+                {
                     if states.iter().all(|state_view| {
                         ui.node(*state_view)
                             .query_component::<AbsmNode<State>>()
@@ -317,12 +477,12 @@ impl StateGraphViewer {
                                 .with_context_menu(self.node_context_menu.menu.clone())
                                 .with_desired_position(state.position),
                         )
-                        .with_normal_color(if state_handle == machine_layer.entry_state() {
+                        .with_normal_color(if state_handle == entry_state {
                             NORMAL_ROOT_COLOR
                         } else {
                             NORMAL_BACKGROUND
                         })
-                        .with_selected_color(if state_handle == machine_layer.entry_state() {
+                        .with_selected_color(if state_handle == entry_state {
                             SELECTED_ROOT_COLOR
                         } else {
                             SELECTED_BACKGROUND
@@ -411,7 +571,7 @@ impl StateGraphViewer {
                 AbsmNodeMessage::normal_color(
                     *state,
                     MessageDirection::ToWidget,
-                    if state_model_handle == machine_layer.entry_state() {
+                    if state_model_handle == entry_state {
                         NORMAL_ROOT_COLOR
                     } else {
                         NORMAL_BACKGROUND
@@ -423,7 +583,7 @@ impl StateGraphViewer {
                 AbsmNodeMessage::selected_color(
                     *state,
                     MessageDirection::ToWidget,
-                    if state_model_handle == machine_layer.entry_state() {
+                    if state_model_handle == entry_state {
                         SELECTED_ROOT_COLOR
                     } else {
                         SELECTED_BACKGROUND
@@ -435,14 +595,33 @@ impl StateGraphViewer {
         // Force update layout to be able to fetch positions of nodes for transitions.
         ui.update(ui.screen_size(), 0.0);
 
+        // Only transitions between the states of the current graph are shown.
+        let is_graph_transition = |transition: &Transition| {
+            [transition.source(), transition.dest()]
+                .into_iter()
+                .all(|state| {
+                    machine_layer
+                        .states()
+                        .try_borrow(state)
+                        .is_some_and(|state| state.parent == current_graph)
+                })
+        };
+
+        let graph_transitions_count = machine_layer
+            .transitions()
+            .iter()
+            .filter(|transition| is_graph_transition(transition))
+            .count();
+
         // Sync transitions.
-        match transitions
-            .len()
-            .cmp(&(machine_layer.transitions().alive_count() as usize))
-        {
+        match transitions.len().cmp(&graph_transitions_count) {
             Ordering::Less => {
                 // A transition was added.
-                for (transition_handle, transition) in machine_layer.transitions().pair_iter() {
+                for (transition_handle, transition) in machine_layer
+                    .transitions()
+                    .pair_iter()
+                    .filter(|(_, transition)| is_graph_transition(transition))
+                {
                     if transitions.iter().all(|transition_view| {
                         ui.node(*transition_view)
                             .query_component::<TransitionView>()
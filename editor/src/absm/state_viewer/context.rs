@@ -2,7 +2,7 @@ use crate::{
     absm::{
         command::{
             blend::{
-                SetBlendAnimationByIndexInputPoseSourceCommand,
+                SetBlendAdditivePoseSourceCommand, SetBlendAnimationByIndexInputPoseSourceCommand,
                 SetBlendAnimationsPoseSourceCommand, SetBlendSpacePoseSourceCommand,
             },
             AddPoseNodeCommand, DeletePoseNodeCommand, SetStateRootPoseCommand,
@@ -21,6 +21,7 @@ use crate::{
 use fyrox::{
     animation::machine::{
         node::{
+            blend::BlendAdditive,
             blendspace::{BlendSpace, BlendSpacePoint},
             BasePoseNode,
         },
@@ -43,6 +44,7 @@ pub struct CanvasContextMenu {
     create_blend_animations: Handle<UiNode>,
     create_blend_by_index: Handle<UiNode>,
     create_blend_space: Handle<UiNode>,
+    create_blend_additive: Handle<UiNode>,
     pub menu: RcUiNodeHandle,
     pub canvas: Handle<UiNode>,
     pub node_context_menu: Option<RcUiNodeHandle>,
@@ -54,6 +56,7 @@ impl CanvasContextMenu {
         let create_blend_animations;
         let create_blend_by_index;
         let create_blend_space;
+        let create_blend_additive;
         let menu = PopupBuilder::new(
             WidgetBuilder::new()
                 .with_enabled(false) // Disabled by default.
@@ -77,6 +80,10 @@ impl CanvasContextMenu {
                     .with_child({
                         create_blend_space = create_menu_item("Blend Space", vec![], ctx);
                         create_blend_space
+                    })
+                    .with_child({
+                        create_blend_additive = create_menu_item("Blend Additive", vec![], ctx);
+                        create_blend_additive
                     }),
             )
             .build(ctx),
@@ -89,6 +96,7 @@ impl CanvasContextMenu {
             create_blend_animations,
             create_blend_by_index,
             create_blend_space,
+            create_blend_additive,
             menu,
             canvas: Default::default(),
             node_context_menu: Default::default(),
@@ -164,6 +172,13 @@ impl CanvasContextMenu {
                 ]);
 
                 Some(PoseNode::BlendSpace(blend_space))
+            } else if message.destination() == self.create_blend_additive {
+                let mut blend_additive = BlendAdditive::default();
+
+                blend_additive.position = position;
+                blend_additive.parent_state = current_state;
+
+                Some(PoseNode::BlendAdditive(blend_additive))
             } else {
                 None
             };
@@ -362,6 +377,15 @@ impl ConnectionContextMenu {
                             value: Default::default(),
                         })
                     }
+                    PoseNode::BlendAdditive(_) => {
+                        sender.do_scene_command(SetBlendAdditivePoseSourceCommand {
+                            node_handle: absm_node_handle,
+                            layer_index,
+                            handle: model_handle,
+                            index,
+                            value: Default::default(),
+                        })
+                    }
                 }
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
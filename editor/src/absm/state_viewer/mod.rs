@@ -4,7 +4,7 @@ use crate::{
         canvas::{AbsmCanvasBuilder, AbsmCanvasMessage},
         command::{
             blend::{
-                SetBlendAnimationByIndexInputPoseSourceCommand,
+                SetBlendAdditivePoseSourceCommand, SetBlendAnimationByIndexInputPoseSourceCommand,
                 SetBlendAnimationsPoseSourceCommand, SetBlendSpacePoseSourceCommand,
             },
             MovePoseNodeCommand,
@@ -133,6 +133,7 @@ fn make_pose_node_name(
         PoseNode::BlendSpace(blend_space) => {
             format!("Blend Space: {:?} animations", blend_space.points().len())
         }
+        PoseNode::BlendAdditive(_) => "Blend Additive".to_string(),
     }
 }
 
@@ -342,6 +343,15 @@ impl StateViewer {
                                         value: source_node,
                                     });
                                 }
+                                PoseNode::BlendAdditive(_) => {
+                                    sender.do_scene_command(SetBlendAdditivePoseSourceCommand {
+                                        node_handle: absm_node_handle,
+                                        layer_index,
+                                        handle: dest_node,
+                                        index: dest_socket_ref.index,
+                                        value: source_node,
+                                    });
+                                }
                             }
                         }
                         _ => (),
@@ -472,6 +482,10 @@ impl StateViewer {
                                     PoseNode::BlendSpace(blend_space) => {
                                         (blend_space.points().len(), "Blend Space", true, true)
                                     }
+                                    PoseNode::BlendAdditive(_) => {
+                                        // Source, additive and reference poses.
+                                        (3, "Blend Additive", false, false)
+                                    }
                                 };
 
                             let node_view = AbsmNodeBuilder::new(
@@ -332,12 +332,23 @@ impl Toolbar {
                 && message.direction() == MessageDirection::FromWidget
             {
                 if let Some(layer_index) = selection.layer {
-                    let new_mask = LayerMask::from(
+                    let mut new_mask = LayerMask::from(
                         mask_selection
                             .iter()
                             .map(|h| Handle::<Node>::from(*h))
                             .collect::<Vec<_>>(),
                     );
+                    // Keep per-bone weights, they're not editable via node selector.
+                    if let Some(layer) = graph
+                        .try_get_of_type::<AnimationBlendingStateMachine>(
+                            selection.absm_node_handle,
+                        )
+                        .and_then(|absm| absm.machine().layers().get(layer_index))
+                    {
+                        for bone_weight in layer.mask().bone_weights() {
+                            new_mask.set_bone_weight(bone_weight.bone, bone_weight.weight);
+                        }
+                    }
                     sender.do_scene_command(SetLayerMaskCommand {
                         absm_node_handle: selection.absm_node_handle,
                         layer_index,
@@ -19,7 +19,7 @@ use crate::{
     send_sync_message,
 };
 use fyrox::{
-    animation::{Animation, RootMotionSettings},
+    animation::{retarget::AnimationRetargeter, Animation, RootMotionSettings},
     asset::manager::ResourceManager,
     core::{algebra::Vector2, futures::executor::block_on, log::Log, math::Rect, pool::Handle},
     gui::{
@@ -69,6 +69,7 @@ pub struct Toolbar {
     pub time_slice_end: Handle<UiNode>,
     pub import: Handle<UiNode>,
     pub reimport: Handle<UiNode>,
+    pub humanoid_import: Handle<UiNode>,
     pub node_selector: Handle<UiNode>,
     pub import_file_selector: Handle<UiNode>,
     pub selected_import_root: Handle<Node>,
@@ -77,6 +78,7 @@ pub struct Toolbar {
     root_motion_dropdown_area: RootMotionDropdownArea,
     pub root_motion: Handle<UiNode>,
     import_mode: ImportMode,
+    humanoid_import_enabled: bool,
 }
 
 struct RootMotionDropdownArea {
@@ -386,6 +388,7 @@ impl Toolbar {
         let time_slice_end;
         let import;
         let reimport;
+        let humanoid_import;
         let looping;
         let enabled;
         let root_motion;
@@ -483,6 +486,33 @@ impl Toolbar {
                                 .build(ctx);
                                 reimport
                             })
+                            .with_child({
+                                humanoid_import = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Humanoid Retargeting.\n\
+                                            When enabled, imported animations are retargeted \
+                                            using humanoid bone template, it allows to import \
+                                            animations made for skeletons with different naming \
+                                            conventions and proportions. Otherwise, bones are \
+                                            matched by their names and animations are imported \
+                                            as is.",
+                                        )),
+                                )
+                                .checked(Some(false))
+                                .with_content(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_vertical_alignment(VerticalAlignment::Center),
+                                    )
+                                    .with_text("Humanoid")
+                                    .build(ctx),
+                                )
+                                .build(ctx);
+                                humanoid_import
+                            })
                             .with_child({
                                 rename_current_animation = ButtonBuilder::new(
                                     WidgetBuilder::new()
@@ -791,10 +821,11 @@ impl Toolbar {
         )
         .with_filter(Filter::new(|p: &Path| {
             if let Some(ext) = p.extension() {
-                // TODO: Here we allow importing only FBX files, but they can contain
+                // TODO: Here we allow importing only FBX and glTF files, but they can contain
                 // multiple animations and it might be good to also add animation selector
                 // that will be used to select a particular animation to import.
-                ext.to_string_lossy().as_ref() == "fbx"
+                let ext = ext.to_string_lossy();
+                ext == "fbx" || (cfg!(feature = "gltf") && matches!(ext.as_ref(), "gltf" | "glb"))
             } else {
                 p.is_dir()
             }
@@ -819,6 +850,7 @@ impl Toolbar {
             clone_current_animation,
             import,
             reimport,
+            humanoid_import,
             node_selector,
             import_file_selector: file_selector,
             selected_import_root: Default::default(),
@@ -827,6 +859,7 @@ impl Toolbar {
             root_motion,
             root_motion_dropdown_area,
             import_mode: ImportMode::Import,
+            humanoid_import_enabled: false,
         }
     }
 
@@ -1035,6 +1068,12 @@ impl Toolbar {
                     self.import_mode = ImportMode::Import;
                 }
             }
+        } else if let Some(CheckBoxMessage::Check(Some(checked))) = message.data() {
+            if message.destination() == self.humanoid_import
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.humanoid_import_enabled = *checked;
+            }
         } else if let Some(NodeSelectorMessage::Selection(selected_nodes)) = message.data() {
             if message.destination() == self.node_selector
                 && message.direction() == MessageDirection::FromWidget
@@ -1058,8 +1097,20 @@ impl Toolbar {
             if message.destination() == self.import_file_selector {
                 match block_on(resource_manager.request::<Model>(path)) {
                     Ok(model) => {
-                        let mut animations = model
-                            .retarget_animations_directly(self.selected_import_root, &scene.graph);
+                        // Humanoid template allows to import animations made for skeletons with different
+                        // naming conventions and proportions.
+                        let mut animations = if self.humanoid_import_enabled {
+                            model.retarget_animations_with(
+                                self.selected_import_root,
+                                &scene.graph,
+                                &AnimationRetargeter::humanoid(),
+                            )
+                        } else {
+                            model.retarget_animations_directly(
+                                self.selected_import_root,
+                                &scene.graph,
+                            )
+                        };
 
                         let file_stem = path
                             .file_stem()
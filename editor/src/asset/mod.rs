@@ -387,6 +387,7 @@ impl ResourceCreator {
                             let resource = UntypedResource(Arc::new(Mutex::new(ResourceHeader {
                                 kind: ResourceKind::External(path.clone()),
                                 type_uuid: instance.type_uuid(),
+                                resource_uuid: Default::default(),
                                 state: ResourceState::Ok(instance),
                             })));
 
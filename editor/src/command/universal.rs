@@ -15,6 +15,9 @@ macro_rules! define_universal_commands {
                 fyrox::gui::inspector::PropertyAction::RemoveItem { index } => Some(<$command_wrapper>::new(
                     RemoveCollectionItemCommand::new($handle_ident, property_changed.path(), index, $($field_name),*)
                 )),
+                fyrox::gui::inspector::PropertyAction::MoveItem { from, to } => Some(<$command_wrapper>::new(
+                    MoveCollectionItemCommand::new($handle_ident, property_changed.path(), from, to, $($field_name),*)
+                )),
                 // Must be handled outside, there is not enough context and it near to impossible to create universal reversion
                 // for InheritableVariable<T>.
                 fyrox::gui::inspector::PropertyAction::Revert => None
@@ -244,5 +247,58 @@ macro_rules! define_universal_commands {
                 })
             }
         }
+
+        #[derive(Debug)]
+        pub struct MoveCollectionItemCommand {
+            #[allow(dead_code)]
+            $handle_ident: $handle,
+            path: String,
+            from: usize,
+            to: usize,
+            $($field_name: $field_type),*
+        }
+
+        impl MoveCollectionItemCommand {
+            pub fn new($handle_ident: $handle, path: String, from: usize, to: usize, $($field_name: $field_type),*) -> Self {
+                Self {
+                    $handle_ident,
+                    path,
+                    from,
+                    to,
+                    $($field_name),*
+                }
+            }
+
+            fn move_item(&mut $self, $ctx_ident: &mut $ctx, from: usize, to: usize) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            if !fyrox::gui::inspector::move_list_item(list, from, to) {
+                                fyrox::core::log::Log::err(format!(
+                                    "Failed to move item {} to {} in {} collection!",
+                                    from, to, $self.path
+                                ))
+                            }
+                        } else {
+                            fyrox::core::log::Log::err(format!("Property {} is not a collection!", $self.path))
+                        }
+                    })
+                })
+            }
+        }
+
+        impl $command for MoveCollectionItemCommand {
+            fn name(&mut $self, _: &$ctx) -> String {
+                format!("Move collection {} item {} to {}", $self.path, $self.from, $self.to)
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                $self.move_item($ctx_ident, $self.from, $self.to)
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                $self.move_item($ctx_ident, $self.to, $self.from)
+            }
+        }
     };
 }
@@ -19,6 +19,7 @@ use crate::{
 use fyrox::asset::manager::ResourceManager;
 use fyrox::{
     animation::{
+        compression::AnimationCompressionOptions,
         machine::{
             node::{
                 blendspace::{BlendSpace, BlendSpacePoint},
@@ -56,11 +57,13 @@ use fyrox::{
         },
     },
     scene::{
-        base::{Base, LevelOfDetail, LodGroup, Mobility, Property, PropertyValue},
+        animation::ik::{ChainAlgorithm, ChainIk, IkSolver, IkSolverKind, LookAtIk, TwoBoneIk},
+        base::{Base, LevelOfDetail, LodGroup, Mobility, Property, PropertyValue, ScriptRecord},
         camera::{
             ColorGradingLut, Exposure, OrthographicProjection, PerspectiveProjection, Projection,
             SkyBox,
         },
+        character_controller::CharacterAutostep,
         collider::{
             BallShape, BitMask, CapsuleShape, ColliderShape, ConeShape, ConvexPolyhedronShape,
             CuboidShape, CylinderShape, GeometrySource, HeightfieldShape, InteractionGroups,
@@ -89,6 +92,11 @@ use fyrox::{
         rigidbody::RigidBodyType,
         sound::{
             self,
+            chorus::Chorus,
+            compressor::Compressor,
+            delay::Delay,
+            distortion::{Distortion, DistortionShape},
+            equalizer::{Equalizer, EqualizerBand, EqualizerBandKind},
             filter::{
                 AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect,
                 HighShelfFilterEffect, LowPassFilterEffect, LowShelfFilterEffect,
@@ -264,6 +272,14 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<PrismaticJoint>();
     container.register_inheritable_inspectable::<dim2::joint::PrismaticJoint>();
 
+    container.register_inheritable_enum::<IkSolverKind, _>();
+    container.register_inheritable_enum::<ChainAlgorithm, _>();
+    container.register_inheritable_inspectable::<TwoBoneIk>();
+    container.register_inheritable_inspectable::<ChainIk>();
+    container.register_inheritable_inspectable::<LookAtIk>();
+    container.register_inheritable_inspectable::<IkSolver>();
+    container.register_inheritable_vec_collection::<IkSolver>();
+
     container.register_inheritable_inspectable::<Base>();
     container.register_inheritable_inspectable::<BaseLight>();
 
@@ -278,6 +294,15 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InspectablePropertyEditorDefinition::<LowShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<HighShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<Reverb>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Compressor>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Delay>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Chorus>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Distortion>::new());
+    container.insert(EnumPropertyEditorDefinition::<DistortionShape>::new());
+    container.insert(InspectablePropertyEditorDefinition::<Equalizer>::new());
+    container.insert(InspectablePropertyEditorDefinition::<EqualizerBand>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<EqualizerBand>::new());
+    container.insert(EnumPropertyEditorDefinition::<EqualizerBandKind>::new());
 
     container.register_inheritable_enum::<Emitter, _>();
 
@@ -318,11 +343,14 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_enum::<Exposure, _>();
     container.register_inheritable_enum::<FrustumSplitOptions, _>();
     container.register_inheritable_enum::<MaterialSearchOptions, _>();
+    container.register_inheritable_option::<AnimationCompressionOptions>();
     container.register_inheritable_enum::<DistanceModel, _>();
     container.register_inheritable_enum::<sound::Renderer, _>();
     container.register_inheritable_enum::<RenderPath, _>();
 
     container.insert(ScriptPropertyEditorDefinition {});
+    container.register_inheritable_vec_collection::<ScriptRecord>();
+    container.register_inheritable_inspectable::<ScriptRecord>();
     container.insert(BitFieldPropertyEditorDefinition::<BitMask>::new());
 
     container.register_inheritable_inspectable::<BallShape>();
@@ -390,6 +418,12 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<Limb>();
     container.insert(VecCollectionPropertyEditorDefinition::<Limb>::new());
 
+    container.register_inheritable_inspectable::<CharacterAutostep>();
+    container.insert(EnumPropertyEditorDefinition::<CharacterAutostep>::new_optional());
+    container.insert(InheritablePropertyEditorDefinition::<
+        Option<CharacterAutostep>,
+    >::new());
+
     container.insert(PathPropertyEditorDefinition);
 
     container
@@ -32,7 +32,7 @@ impl SceneNodePropertyChangedHandler {
                     CollectionChanged::Remove(index) => Some(GameSceneCommand::new(
                         DeleteTerrainLayerCommand::new(handle, index),
                     )),
-                    CollectionChanged::ItemChanged { .. } => None,
+                    CollectionChanged::Move { .. } | CollectionChanged::ItemChanged { .. } => None,
                 },
                 _ => None,
             }
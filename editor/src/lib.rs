@@ -1973,6 +1973,12 @@ impl Editor {
 
         engine.resource_manager.state().destroy_unused_resources();
 
+        // Make sure that every resource in the project has a persistent id, so references to resources will
+        // survive moving or renaming.
+        Log::verify(block_on(
+            engine.resource_manager.update_registry("./", true),
+        ));
+
         graphics_context.renderer.flush();
 
         self.asset_browser
@@ -2069,6 +2075,10 @@ impl Editor {
             _ => {}
         }
 
+        if self.engine.has_changed_dynamic_plugins() {
+            self.reload_dynamic_plugins();
+        }
+
         self.log.update(&mut self.engine);
         self.material_editor.update(&mut self.engine);
         self.asset_browser.update(&mut self.engine);
@@ -2357,6 +2367,35 @@ impl Editor {
         self.engine.add_plugin_constructor(plugin)
     }
 
+    /// Adds a game plugin from a shared library at the given path. The plugin will be reloaded automatically
+    /// every time when the library is rebuilt. See [`Engine::add_dynamic_plugin`] for more info.
+    ///
+    /// # Safety
+    ///
+    /// See [`Engine::add_dynamic_plugin`].
+    pub unsafe fn add_dynamic_game_plugin<P: AsRef<Path>>(&mut self, path: P) {
+        Log::verify(self.engine.add_dynamic_plugin(path, true));
+    }
+
+    fn reload_dynamic_plugins(&mut self) {
+        // Commands and clipboard could hold objects (scripts) created by the plugin, they must be destroyed
+        // before the plugin is unloaded.
+        for entry in self.scenes.entries.iter_mut() {
+            entry
+                .controller
+                .clear_command_stack(&mut entry.selection, &mut self.engine);
+            if let Some(game_scene) = entry.controller.downcast_mut::<GameScene>() {
+                game_scene.clipboard.clear();
+            }
+        }
+
+        if let Err(err) = self.engine.reload_dynamic_plugins(None) {
+            Log::err(format!("Unable to reload dynamic plugins. Reason: {err}"));
+        }
+
+        self.message_sender.send(Message::ForceSync);
+    }
+
     pub fn add_editor_plugin<P>(&mut self, plugin: P)
     where
         P: EditorPlugin + 'static,
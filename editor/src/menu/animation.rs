@@ -4,7 +4,10 @@ use fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
-        animation::{absm::AnimationBlendingStateMachineBuilder, AnimationPlayerBuilder},
+        animation::{
+            absm::AnimationBlendingStateMachineBuilder, ik::InverseKinematicsBuilder,
+            AnimationPlayerBuilder,
+        },
         base::BaseBuilder,
         node::Node,
     },
@@ -14,12 +17,14 @@ pub struct AnimationMenu {
     pub menu: Handle<UiNode>,
     create_animation_player: Handle<UiNode>,
     create_absm: Handle<UiNode>,
+    create_ik: Handle<UiNode>,
 }
 
 impl AnimationMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_animation_player;
         let create_absm;
+        let create_ik;
 
         let menu = create_menu_item(
             "Animation",
@@ -32,6 +37,10 @@ impl AnimationMenu {
                     create_absm = create_menu_item("Animation Blending State Machine", vec![], ctx);
                     create_absm
                 },
+                {
+                    create_ik = create_menu_item("Inverse Kinematics", vec![], ctx);
+                    create_ik
+                },
             ],
             ctx,
         );
@@ -40,6 +49,7 @@ impl AnimationMenu {
             menu,
             create_animation_player,
             create_absm,
+            create_ik,
         }
     }
 
@@ -64,6 +74,12 @@ impl AnimationMenu {
                 .with_machine(machine)
                 .build_node();
                 Some(node)
+            } else if message.destination() == self.create_ik {
+                let node = InverseKinematicsBuilder::new(
+                    BaseBuilder::new().with_name("Inverse Kinematics"),
+                )
+                .build_node();
+                Some(node)
             } else {
                 None
             }
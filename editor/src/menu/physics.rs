@@ -2,7 +2,10 @@ use crate::menu::create_menu_item;
 use fyrox::{
     core::pool::Handle,
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
-    scene::{base::BaseBuilder, collider::*, joint::*, node::Node, rigidbody::RigidBodyBuilder},
+    scene::{
+        base::BaseBuilder, character_controller::CharacterControllerBuilder, collider::*, joint::*,
+        node::Node, rigidbody::RigidBodyBuilder,
+    },
 };
 
 pub struct PhysicsMenu {
@@ -13,6 +16,7 @@ pub struct PhysicsMenu {
     create_prismatic_joint: Handle<UiNode>,
     create_fixed_joint: Handle<UiNode>,
     create_collider: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
 }
 
 impl PhysicsMenu {
@@ -23,6 +27,7 @@ impl PhysicsMenu {
         let create_ball_joint;
         let create_prismatic_joint;
         let create_fixed_joint;
+        let create_character_controller;
         let menu = create_menu_item(
             "Physics",
             vec![
@@ -50,6 +55,11 @@ impl PhysicsMenu {
                     create_fixed_joint = create_menu_item("Fixed Joint", vec![], ctx);
                     create_fixed_joint
                 },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
             ],
             ctx,
         );
@@ -62,6 +72,7 @@ impl PhysicsMenu {
             create_prismatic_joint,
             create_fixed_joint,
             create_collider,
+            create_character_controller,
         }
     }
 
@@ -99,6 +110,13 @@ impl PhysicsMenu {
                         .with_shape(ColliderShape::Cuboid(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller"),
+                    )
+                    .build_node(),
+                )
             } else {
                 None
             }
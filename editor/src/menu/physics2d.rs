@@ -4,7 +4,10 @@ use fyrox::{
     gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
     scene::{
         base::BaseBuilder,
-        dim2::{collider::*, joint::*, rigidbody::RigidBodyBuilder},
+        dim2::{
+            character_controller::CharacterControllerBuilder, collider::*, joint::*,
+            rigidbody::RigidBodyBuilder,
+        },
         node::Node,
     },
 };
@@ -16,6 +19,7 @@ pub struct Physics2dMenu {
     create_prismatic_joint: Handle<UiNode>,
     create_fixed_joint: Handle<UiNode>,
     create_collider: Handle<UiNode>,
+    create_character_controller: Handle<UiNode>,
 }
 
 impl Physics2dMenu {
@@ -25,6 +29,7 @@ impl Physics2dMenu {
         let create_ball_joint;
         let create_prismatic_joint;
         let create_fixed_joint;
+        let create_character_controller;
         let menu = create_menu_item(
             "Physics 2D",
             vec![
@@ -48,6 +53,11 @@ impl Physics2dMenu {
                     create_fixed_joint = create_menu_item("Fixed Joint", vec![], ctx);
                     create_fixed_joint
                 },
+                {
+                    create_character_controller =
+                        create_menu_item("Character Controller", vec![], ctx);
+                    create_character_controller
+                },
             ],
             ctx,
         );
@@ -59,6 +69,7 @@ impl Physics2dMenu {
             create_prismatic_joint,
             create_fixed_joint,
             create_collider,
+            create_character_controller,
         }
     }
 
@@ -93,6 +104,13 @@ impl Physics2dMenu {
                         .with_shape(ColliderShape::Cuboid(Default::default()))
                         .build_node(),
                 )
+            } else if message.destination == self.create_character_controller {
+                Some(
+                    CharacterControllerBuilder::new(
+                        BaseBuilder::new().with_name("Character Controller 2D"),
+                    )
+                    .build_node(),
+                )
             } else {
                 None
             }
@@ -189,7 +189,12 @@ impl GameScene {
 
             let mut visitor = Visitor::new();
             pure_scene.save("Scene", &mut visitor).unwrap();
-            if let Err(e) = visitor.save_binary(path) {
+            let result = if settings.general.save_scenes_as_text {
+                visitor.save_ascii(path)
+            } else {
+                visitor.save_binary(path)
+            };
+            if let Err(e) = result {
                 Err(format!("Failed to save scene! Reason: {}", e))
             } else {
                 if settings.debugging.save_scene_in_text_form {
@@ -14,6 +14,13 @@ pub struct GeneralSettings {
     )]
     #[serde(default = "default_suspension_state")]
     pub suspend_unfocused_editor: bool,
+
+    #[reflect(
+        description = "When set, scenes are saved in human-readable text format instead of binary. Text scenes \
+    are bigger and slower to load, but they can be diffed and merged by version control systems."
+    )]
+    #[serde(default)]
+    pub save_scenes_as_text: bool,
 }
 
 fn default_suspension_state() -> bool {
@@ -25,6 +32,7 @@ impl Default for GeneralSettings {
         Self {
             show_node_removal_dialog: true,
             suspend_unfocused_editor: default_suspension_state(),
+            save_scenes_as_text: false,
         }
     }
 }
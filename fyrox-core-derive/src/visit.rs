@@ -27,12 +27,16 @@ fn impl_visit_struct(
         let field_visits =
             utils::create_field_visits(true, field_args.fields.iter(), field_args.style);
 
+        let (visit_version, migrate) = utils::create_version_visit(ty_args);
+
         quote! {
             let mut region = match visitor.enter_region(name) {
                 Ok(x) => x,
                 Err(err) => return Err(err),
             };
+            #visit_version
             #(#field_visits)*
+            #migrate
             Ok(())
         }
     };
@@ -185,6 +189,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
         }
     });
 
+    let (visit_version, migrate) = utils::create_version_visit(ty_args);
+
     utils::create_impl(
         ty_args,
         variant_args.iter().flat_map(|v| v.fields.iter()).cloned(),
@@ -194,6 +200,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  Err(err) => return Err(err),
              };
 
+             #visit_version
+
              let mut id = id(self);
              if let Err(err) = id.visit("Id", &mut region) {
                  return Err(err);
@@ -210,6 +218,8 @@ fn impl_visit_enum(ty_args: &args::TypeArgs, variant_args: &[args::VariantArgs])
                  #(#variant_visits)*
              }
 
+             #migrate
+
              return Ok(());
 
              #fn_id
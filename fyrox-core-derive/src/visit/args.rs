@@ -9,6 +9,17 @@ pub struct TypeArgs {
     pub generics: Generics,
     pub data: ast::Data<VariantArgs, FieldArgs>,
     // attrs: Vec<Attribute>
+    // ---
+    /// `#[visit(version = 3)]`
+    ///
+    /// Store this version in the region of the type
+    pub version: Option<u32>,
+
+    /// `#[visit(migrate = "Self::migrate")]`
+    ///
+    /// Call `fn(&mut Self, old_version: u32, region: &mut Visitor) -> VisitResult` after reading
+    /// data of an older version
+    pub migrate: Option<Path>,
 }
 
 /// Parsed from struct's or enum variant's field
@@ -37,6 +48,12 @@ pub struct FieldArgs {
     /// Ignore missing field
     #[darling(default)]
     pub optional: bool,
+
+    /// `#[visit(alias = "..")]`
+    ///
+    /// Read the field using this name, if there's no field with the actual name
+    #[darling(multiple)]
+    pub alias: Vec<String>,
 }
 
 #[derive(FromVariant)]
@@ -104,22 +104,37 @@ pub fn create_field_visits<'a>(
                 None => name,
             };
 
-            (ident, name, field.optional)
+            (ident, name, field.optional, &field.alias)
         })
         .collect::<Vec<_>>();
 
     let mut no_dup = FxHashSet::default();
-    for name in visit_args.iter().map(|(_, name, _)| name) {
+    for name in visit_args.iter().map(|(_, name, _, _)| name) {
         if !no_dup.insert(name) {
             panic!("duplicate visiting names detected!");
         }
     }
+    for alias in visit_args
+        .iter()
+        .flat_map(|(_, _, _, aliases)| aliases.iter())
+    {
+        if !no_dup.insert(alias) {
+            panic!("alias `{}` duplicates another visiting name!", alias);
+        }
+    }
 
     let prefix = if is_struct { Some(quote!(self.)) } else { None };
 
     visit_args
         .iter()
-        .map(|(ident, name, optional)| {
+        .map(|(ident, name, optional, aliases)| {
+            let name = if aliases.is_empty() {
+                quote!(#name)
+            } else {
+                // read the data saved with an old name
+                quote!(region.resolve_alias(#name, &[#(#aliases),*]))
+            };
+
             if *optional {
                 quote! {
                     #prefix #ident.visit(#name, &mut region).ok();
@@ -134,3 +149,39 @@ pub fn create_field_visits<'a>(
         })
         .collect::<Vec<_>>()
 }
+
+/// `let version = region.visit_version(..)?;` and migration hook call, if the type has a version
+pub fn create_version_visit(ty_args: &args::TypeArgs) -> (TokenStream2, TokenStream2) {
+    let version = match ty_args.version {
+        Some(version) => version,
+        None => {
+            assert!(
+                ty_args.migrate.is_none(),
+                "migration hook requires `#[visit(version = ..)]` attribute!"
+            );
+            return (quote!(), quote!());
+        }
+    };
+
+    let visit_version = quote! {
+        let version = match region.visit_version(#version) {
+            Ok(x) => x,
+            Err(err) => return Err(err),
+        };
+    };
+
+    let migrate = match &ty_args.migrate {
+        Some(migrate) => quote! {
+            if region.is_reading() && version < #version {
+                if let Err(err) = #migrate(self, version, &mut region) {
+                    return Err(err);
+                }
+            }
+        },
+        None => quote! {
+            let _ = version;
+        },
+    };
+
+    (visit_version, migrate)
+}
@@ -1,4 +1,4 @@
-//! Fight the compatibility hell with attributes! .. someday :)
+//! Fight the compatibility hell with attributes!
 
 use fyrox_core::visitor::prelude::*;
 
@@ -25,3 +25,119 @@ fn rename() {
 
     assert_eq!(data, data_default);
 }
+
+/// Version 0 had `velocity` field instead of `speed`, stored angle in degrees and health as `f32` in
+/// `Hp` field. Version 1 stored angle in degrees.
+#[derive(Debug, Clone, PartialEq, Visit)]
+#[visit(version = 2, migrate = "Self::migrate")]
+pub struct Versioned {
+    #[visit(alias = "Velocity")]
+    pub speed: f32,
+    pub angle: f32,
+    #[visit(optional)]
+    pub health: u32,
+}
+
+impl Versioned {
+    fn migrate(&mut self, version: u32, region: &mut Visitor) -> VisitResult {
+        if version < 1 {
+            let mut hp = 0.0f32;
+            hp.visit("Hp", region)?;
+            self.health = hp as u32;
+        }
+        if version < 2 {
+            self.angle = self.angle.to_radians();
+        }
+        Ok(())
+    }
+}
+
+impl Default for Versioned {
+    fn default() -> Self {
+        Self {
+            speed: 0.0,
+            angle: 0.0,
+            health: 100,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Visit)]
+#[visit(version = 1)]
+pub enum VersionedEnum {
+    #[default]
+    None,
+    Sphere {
+        #[visit(alias = "Distance")]
+        radius: f32,
+    },
+}
+
+fn load_corpus<T: Visit + Default>(data: &[u8]) -> T {
+    let mut visitor = Visitor::load_from_memory(data).unwrap();
+    let mut value = T::default();
+    value.visit("Data", &mut visitor).unwrap();
+    value
+}
+
+#[test]
+fn version() {
+    let mut data = Versioned {
+        speed: 1.0,
+        angle: 0.5,
+        health: 10,
+    };
+    let mut data_default = Versioned::default();
+
+    super::save_load("version", &mut data, &mut data_default);
+
+    assert_eq!(data, data_default);
+
+    let mut visitor = Visitor::new();
+    data.visit("Data", &mut visitor).unwrap();
+    assert!(visitor
+        .save_ascii_to_string()
+        .contains(&format!("\"{}\": u32 = 2", Visitor::VERSION_FIELD)));
+}
+
+#[test]
+fn version_corpus() {
+    let expected = Versioned {
+        speed: 2.5,
+        angle: std::f32::consts::PI,
+        health: 75,
+    };
+    assert_eq!(
+        load_corpus::<Versioned>(include_bytes!("corpus/versioned_v0.txt")),
+        expected
+    );
+
+    let expected = Versioned {
+        angle: std::f32::consts::FRAC_PI_2,
+        ..expected
+    };
+    assert_eq!(
+        load_corpus::<Versioned>(include_bytes!("corpus/versioned_v1.txt")),
+        expected
+    );
+
+    assert_eq!(
+        load_corpus::<VersionedEnum>(include_bytes!("corpus/versioned_enum_v0.txt")),
+        VersionedEnum::Sphere { radius: 10.0 }
+    );
+}
+
+#[test]
+fn stored_version() {
+    let mut visitor = Visitor::load_from_memory(include_bytes!("corpus/versioned_v1.txt")).unwrap();
+    let region = visitor.enter_region("Data").unwrap();
+    assert_eq!(region.version(), 1);
+    assert_eq!(region.resolve_alias("Speed", &["Velocity"]), "Speed");
+    assert_eq!(region.resolve_alias("Velocity", &["Angle"]), "Angle");
+    drop(region);
+
+    let mut visitor = Visitor::load_from_memory(include_bytes!("corpus/versioned_v0.txt")).unwrap();
+    let mut region = visitor.enter_region("Data").unwrap();
+    assert_eq!(region.version(), 0);
+    assert_eq!(region.visit_version(2).unwrap(), 0);
+}
@@ -0,0 +1,193 @@
+//! Task pool allows you to run asynchronous tasks off the main thread (on PC) or as JS micro-tasks (on WebAssembly).
+//! Results of the tasks could be obtained later on the main thread, see [`TaskPool::spawn_with_result`] and
+//! [`TaskPool::next_task_result`].
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::futures::executor::ThreadPool;
+use crate::{parking_lot::Mutex, uuid::Uuid};
+use std::{
+    any::Any,
+    future::Future,
+    sync::mpsc::{channel, Receiver, Sender},
+};
+
+/// A result of an asynchronous task. Any `'static` type (which is also `Send` on PC) could be used as a result.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait AsyncTaskResult: Any + Send + 'static {
+    /// Converts boxed result into boxed `Any`, it is used for downcasting.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+/// A result of an asynchronous task. Any `'static` type (which is also `Send` on PC) could be used as a result.
+#[cfg(target_arch = "wasm32")]
+pub trait AsyncTaskResult: Any + 'static {
+    /// Converts boxed result into boxed `Any`, it is used for downcasting.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T> AsyncTaskResult for T
+where
+    T: Any + Send + 'static,
+{
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<T> AsyncTaskResult for T
+where
+    T: Any + 'static,
+{
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl dyn AsyncTaskResult {
+    /// Tries to downcast the result to a particular type.
+    pub fn downcast<T: AsyncTaskResult>(self: Box<Self>) -> Result<Box<T>, Box<dyn Any>> {
+        self.into_any().downcast()
+    }
+}
+
+/// An asynchronous task, that produces a result of the given type. It is implemented for any `'static` future
+/// (which is also `Send` on PC).
+#[cfg(not(target_arch = "wasm32"))]
+pub trait AsyncTask<R: AsyncTaskResult>: Future<Output = R> + Send + 'static {}
+
+/// An asynchronous task, that produces a result of the given type. It is implemented for any `'static` future
+/// (which is also `Send` on PC).
+#[cfg(target_arch = "wasm32")]
+pub trait AsyncTask<R: AsyncTaskResult>: Future<Output = R> + 'static {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T, R> AsyncTask<R> for T
+where
+    T: Future<Output = R> + Send + 'static,
+    R: AsyncTaskResult,
+{
+}
+
+#[cfg(target_arch = "wasm32")]
+impl<T, R> AsyncTask<R> for T
+where
+    T: Future<Output = R> + 'static,
+    R: AsyncTaskResult,
+{
+}
+
+/// A result of a task, that was spawned using [`TaskPool::spawn_with_result`].
+pub struct TaskResult {
+    /// Id of the task. It is the same id, that was returned by [`TaskPool::spawn_with_result`].
+    pub id: Uuid,
+    /// Actual result of the task.
+    pub payload: Box<dyn AsyncTaskResult>,
+}
+
+/// See module docs.
+pub struct TaskPool {
+    #[cfg(not(target_arch = "wasm32"))]
+    thread_pool: ThreadPool,
+    sender: Sender<TaskResult>,
+    receiver: Mutex<Receiver<TaskResult>>,
+}
+
+impl Default for TaskPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskPool {
+    /// Creates a new task pool. On PC it creates a thread pool with a thread per each logical CPU core.
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            thread_pool: ThreadPool::new().unwrap(),
+            sender,
+            receiver: Mutex::new(receiver),
+        }
+    }
+
+    /// Spawns a new task, that does not produce any result.
+    #[cfg(target_arch = "wasm32")]
+    pub fn spawn_task<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        crate::wasm_bindgen_futures::spawn_local(future);
+    }
+
+    /// Spawns a new task, that does not produce any result.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn_task<F>(&self, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.thread_pool.spawn_ok(future);
+    }
+
+    /// Spawns a new task, that produces a result. The result could be obtained later using
+    /// [`Self::next_task_result`] by the returned id.
+    pub fn spawn_with_result<F, T>(&self, future: F) -> Uuid
+    where
+        F: AsyncTask<T>,
+        T: AsyncTaskResult,
+    {
+        let id = Uuid::new_v4();
+        let sender = self.sender.clone();
+        self.spawn_task(async move {
+            let result = future.await;
+            // The pool could be already destroyed, there's nobody to receive the result in this case.
+            let _ = sender.send(TaskResult {
+                id,
+                payload: Box::new(result),
+            });
+        });
+        id
+    }
+
+    /// Returns a result of the next completed task (if any). This method does not block.
+    pub fn next_task_result(&self) -> Option<TaskResult> {
+        self.receiver.lock().try_recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{futures::executor::block_on, task::TaskPool};
+    use std::time::Duration;
+
+    #[test]
+    fn test_task_results() {
+        let pool = TaskPool::new();
+
+        let a = pool.spawn_with_result(async { 123u32 });
+        let b = pool.spawn_with_result(async { String::from("foo") });
+
+        let mut results = Vec::new();
+        while results.len() < 2 {
+            match pool.next_task_result() {
+                Some(result) => results.push(result),
+                None => std::thread::sleep(Duration::from_millis(1)),
+            }
+        }
+
+        for result in results {
+            if result.id == a {
+                assert_eq!(*result.payload.downcast::<u32>().unwrap(), 123);
+            } else {
+                assert_eq!(result.id, b);
+                assert_eq!(*result.payload.downcast::<String>().unwrap(), "foo");
+            }
+        }
+
+        // Tasks without results are just executed.
+        let (tx, rx) = std::sync::mpsc::channel();
+        pool.spawn_task(async move { tx.send(1).unwrap() });
+        assert_eq!(block_on(async { rx.recv().unwrap() }), 1);
+    }
+}
@@ -630,13 +630,100 @@ pub fn mark_inheritable_properties_modified(object: &mut dyn Reflect, ignored_ty
     );
 }
 
+/// Marks every inheritable variable of the given object as modified if its value differs from the value of the
+/// corresponding variable of the `original` object, and as non-modified otherwise. Since non-modified variables
+/// are not serialized, this function could be used to save only the changes made to the object after it was in
+/// the `original` state. Both objects must have the same type, otherwise the function does nothing. Items of
+/// collections of different length are always marked as modified.
+pub fn mark_inheritable_properties_modified_since(
+    object: &mut dyn Reflect,
+    original: &dyn Reflect,
+    ignored_types: &[TypeId],
+) {
+    let type_id = (*object).type_id();
+    if ignored_types.contains(&type_id) || type_id != (*original).type_id() {
+        return;
+    }
+
+    let mut handled = false;
+
+    object.as_inheritable_variable_mut(&mut |variable| {
+        if let Some(variable) = variable {
+            original.as_inheritable_variable(&mut |original_variable| {
+                if let Some(original_variable) = original_variable {
+                    handled = true;
+
+                    if variable.value_equals(original_variable) {
+                        variable.reset_modified_flag();
+                    } else {
+                        variable.mark_modified();
+                    }
+
+                    mark_inheritable_properties_modified_since(
+                        variable.inner_value_mut(),
+                        original_variable.inner_value_ref(),
+                        ignored_types,
+                    );
+                }
+            })
+        }
+    });
+
+    if handled {
+        return;
+    }
+
+    object.as_array_mut(&mut |collection| {
+        if let Some(collection) = collection {
+            original.as_array(&mut |original_collection| {
+                if let Some(original_collection) = original_collection {
+                    handled = true;
+
+                    let same_length = collection.reflect_len() == original_collection.reflect_len();
+                    for i in 0..collection.reflect_len() {
+                        let Some(item) = collection.reflect_index_mut(i) else {
+                            continue;
+                        };
+
+                        match original_collection.reflect_index(i) {
+                            Some(original_item) if same_length => {
+                                mark_inheritable_properties_modified_since(
+                                    item,
+                                    original_item,
+                                    ignored_types,
+                                )
+                            }
+                            _ => mark_inheritable_properties_modified(item, ignored_types),
+                        }
+                    }
+                }
+            })
+        }
+    });
+
+    if handled {
+        return;
+    }
+
+    object.fields_mut(&mut |fields| {
+        original.fields(&mut |original_fields| {
+            for (field, original_field) in fields.iter_mut().zip(original_fields) {
+                mark_inheritable_properties_modified_since(*field, *original_field, ignored_types);
+            }
+        })
+    });
+}
+
 #[cfg(test)]
 mod test {
     use std::{cell::Cell, ops::DerefMut};
 
     use crate::{
         reflect::{prelude::*, ReflectInheritableVariable},
-        variable::{try_inherit_properties, InheritableVariable, VariableFlags},
+        variable::{
+            mark_inheritable_properties_modified_since, try_inherit_properties,
+            InheritableVariable, VariableFlags,
+        },
         visitor::{Visit, Visitor},
     };
 
@@ -684,6 +771,25 @@ mod test {
         assert_eq!(child.foo.value.value, 3.21);
     }
 
+    #[test]
+    fn test_mark_modified_since() {
+        let original = Bar {
+            foo: Foo {
+                value: InheritableVariable::new_modified(1.23),
+            },
+            other_value: InheritableVariable::new_modified("Foobar".to_string()),
+        };
+
+        let mut changed = original.clone();
+        changed
+            .other_value
+            .set_value_and_mark_modified("Baz".to_string());
+
+        mark_inheritable_properties_modified_since(&mut changed, &original, &[]);
+        assert!(!changed.foo.value.is_modified());
+        assert!(changed.other_value.is_modified());
+    }
+
     #[test]
     fn test_inheritable_variable_equality() {
         let va = InheritableVariable::new_non_modified(1.23);
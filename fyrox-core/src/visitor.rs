@@ -10,6 +10,8 @@
 
 pub use fyrox_core_derive::Visit;
 
+mod ascii;
+
 pub mod prelude {
     //! Types to use `#[derive(Visit)]`
     pub use super::{Visit, VisitError, VisitResult, Visitor};
@@ -566,6 +568,11 @@ pub enum VisitError {
     UnexpectedRcNullIndex,
     PoisonedMutex,
     FileLoadError(FileLoadError),
+    ParseError {
+        line: usize,
+        column: usize,
+        message: String,
+    },
 }
 
 impl Error for VisitError {}
@@ -590,6 +597,11 @@ impl Display for VisitError {
             Self::UnexpectedRcNullIndex => write!(f, "unexpected rc null index"),
             Self::PoisonedMutex => write!(f, "attempt to lock poisoned mutex"),
             Self::FileLoadError(e) => write!(f, "file load error: {:?}", e),
+            Self::ParseError {
+                line,
+                column,
+                message,
+            } => write!(f, "parse error at {}:{}: {}", line, column, message),
         }
     }
 }
@@ -1154,6 +1166,9 @@ impl Default for Visitor {
 impl Visitor {
     pub const MAGIC: &'static str = "RG3D";
 
+    /// Name of a field, that holds version of a region. See [`Self::visit_version`].
+    pub const VERSION_FIELD: &'static str = "__Version";
+
     pub fn new() -> Self {
         let mut nodes = Pool::new();
         let root = nodes.spawn(VisitorNode::new("__ROOT__", Handle::NONE));
@@ -1227,6 +1242,108 @@ impl Visitor {
             .map(|n| n.name.as_str())
     }
 
+    /// Returns `true` if the current region has a field with the given name.
+    pub fn has_field(&self, name: &str) -> bool {
+        self.nodes
+            .try_borrow(self.current_node)
+            .is_some_and(|node| node.fields.iter().any(|f| f.name == name))
+    }
+
+    /// Returns `true` if the current region has a child region with the given name.
+    pub fn has_region(&self, name: &str) -> bool {
+        self.nodes
+            .try_borrow(self.current_node)
+            .is_some_and(|node| {
+                node.children
+                    .iter()
+                    .any(|child| self.nodes.borrow(*child).name == name)
+            })
+    }
+
+    /// Returns a name, that should be used to visit an entry (a field or a region) of the current region.
+    /// When reading, if the current region has no entry with the given name, the first alias that exists
+    /// in the region is returned. Otherwise the name itself is returned. Aliases are used to load data that
+    /// was saved before the entry was renamed:
+    ///
+    /// ```rust
+    /// # use fyrox_core::visitor::prelude::*;
+    /// struct Foo {
+    ///     speed: f32,
+    /// }
+    ///
+    /// impl Visit for Foo {
+    ///     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+    ///         let mut region = visitor.enter_region(name)?;
+    ///         // The field was called "Velocity" before.
+    ///         let name = region.resolve_alias("Speed", &["Velocity"]);
+    ///         self.speed.visit(name, &mut region)
+    ///     }
+    /// }
+    /// ```
+    pub fn resolve_alias<'a>(&self, name: &'a str, aliases: &[&'a str]) -> &'a str {
+        if self.reading && !self.has_field(name) && !self.has_region(name) {
+            if let Some(alias) = aliases
+                .iter()
+                .find(|alias| self.has_field(alias) || self.has_region(alias))
+            {
+                return alias;
+            }
+        }
+        name
+    }
+
+    /// Writes the given version of the current region (when writing) or reads the stored version of the
+    /// current region (when reading). Returns the version of the data in the region: the given version
+    /// when writing, the stored version when reading. Regions that were saved without version have
+    /// version `0`. The version could be used to migrate data saved in an older format:
+    ///
+    /// ```rust
+    /// # use fyrox_core::visitor::prelude::*;
+    /// struct Foo {
+    ///     // Was stored in degrees in version 0, radians since version 1.
+    ///     angle: f32,
+    /// }
+    ///
+    /// impl Visit for Foo {
+    ///     fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+    ///         let mut region = visitor.enter_region(name)?;
+    ///         let version = region.visit_version(1)?;
+    ///         self.angle.visit("Angle", &mut region)?;
+    ///         if region.is_reading() && version < 1 {
+    ///             self.angle = self.angle.to_radians();
+    ///         }
+    ///         Ok(())
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// `#[derive(Visit)]` does the same when a type has `#[visit(version = N)]` attribute.
+    pub fn visit_version(&mut self, current_version: u32) -> Result<u32, VisitError> {
+        if self.reading {
+            Ok(self.version())
+        } else {
+            let mut version = current_version;
+            version.visit(Self::VERSION_FIELD, self)?;
+            Ok(version)
+        }
+    }
+
+    /// Returns a version of the current region, stored by [`Self::visit_version`]. Returns `0` if the
+    /// region has no version.
+    pub fn version(&self) -> u32 {
+        self.nodes
+            .try_borrow(self.current_node)
+            .and_then(|node| {
+                node.fields
+                    .iter()
+                    .find(|field| field.name == Self::VERSION_FIELD)
+            })
+            .map_or(0, |field| match field.kind {
+                FieldKind::U32(version) => version,
+                _ => 0,
+            })
+    }
+
     fn leave_region(&mut self) -> VisitResult {
         self.current_node = self.nodes.borrow(self.current_node).parent;
         if self.current_node.is_none() {
@@ -1337,7 +1454,61 @@ impl Visitor {
         Self::load_from_memory(&io::load_file(path).await?)
     }
 
+    /// Loads a visitor from the given data, the data could be either in binary or in text format. The
+    /// format is detected automatically.
     pub fn load_from_memory(data: &[u8]) -> Result<Self, VisitError> {
+        if Self::is_ascii_data(data) {
+            Self::load_ascii_from_memory(data)
+        } else {
+            Self::load_binary_from_memory(data)
+        }
+    }
+
+    /// Checks whether the given data is in text format produced by [`Self::save_ascii`].
+    pub fn is_ascii_data(data: &[u8]) -> bool {
+        // Skip optional byte order mark and leading whitespace that text editors could add.
+        let data = data.strip_prefix("\u{feff}".as_bytes()).unwrap_or(data);
+        let offset = data
+            .iter()
+            .position(|b| !b.is_ascii_whitespace())
+            .unwrap_or(data.len());
+        data[offset..].starts_with(ascii::MAGIC.as_bytes())
+    }
+
+    /// Serializes the visitor tree into a human-readable text that can be loaded back using
+    /// [`Self::load_ascii_from_memory`] (or [`Self::load_from_memory`]). Unlike [`Self::save_text`],
+    /// the output preserves every field exactly.
+    pub fn save_ascii_to_string(&self) -> String {
+        ascii::write(self)
+    }
+
+    /// Writes the visitor tree in text format (see [`Self::save_ascii_to_string`]) into the given writer.
+    pub fn save_ascii_to_memory<W: Write>(&self, mut writer: W) -> VisitResult {
+        writer.write_all(self.save_ascii_to_string().as_bytes())?;
+        Ok(())
+    }
+
+    /// Saves the visitor tree in text format (see [`Self::save_ascii_to_string`]) into a file at the given path.
+    pub fn save_ascii<P: AsRef<Path>>(&self, path: P) -> VisitResult {
+        let writer = BufWriter::new(File::create(path)?);
+        self.save_ascii_to_memory(writer)
+    }
+
+    /// Loads a visitor from a file at the given path. The file must be in text format, produced by
+    /// [`Self::save_ascii`]. Use [`Self::load_binary`] if the format is not known in advance.
+    pub async fn load_from_ascii<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
+        Self::load_ascii_from_memory(&io::load_file(path).await?)
+    }
+
+    /// Loads a visitor from the given data in text format, produced by [`Self::save_ascii_to_string`].
+    /// Returns an error with the line and column of the first invalid token, if the data is malformed.
+    pub fn load_ascii_from_memory(data: &[u8]) -> Result<Self, VisitError> {
+        ascii::read(data)
+    }
+
+    /// Loads a visitor from the given data in binary format, produced by [`Self::save_binary_to_memory`].
+    /// Returns [`VisitError::NotSupportedFormat`] if the data does not start with the binary magic bytes.
+    pub fn load_binary_from_memory(data: &[u8]) -> Result<Self, VisitError> {
         let mut reader = Cursor::new(data);
         let mut magic: [u8; 4] = Default::default();
         reader.read_exact(&mut magic)?;
@@ -2016,6 +2187,127 @@ mod test {
         }
     }
 
+    #[test]
+    fn visitor_ascii_round_trip() {
+        let mut visitor = Visitor::new();
+        let all_kinds = [
+            FieldKind::Bool(true),
+            FieldKind::U8(u8::MAX),
+            FieldKind::I8(i8::MIN),
+            FieldKind::U16(u16::MAX),
+            FieldKind::I16(i16::MIN),
+            FieldKind::U32(u32::MAX),
+            FieldKind::I32(i32::MIN),
+            FieldKind::U64(u64::MAX),
+            FieldKind::I64(i64::MIN),
+            FieldKind::F32(0.1),
+            FieldKind::F64(-1.0e-300),
+            FieldKind::UnitQuaternion(UnitQuaternion::from_euler_angles(0.1, 0.2, 0.3)),
+            FieldKind::Matrix4(Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0))),
+            FieldKind::BinaryBlob(vec![0, 1, 2, 255]),
+            FieldKind::Matrix3(Matrix3::new(1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0)),
+            FieldKind::Uuid(Uuid::new_v4()),
+            FieldKind::UnitComplex(UnitComplex::new(0.5)),
+            FieldKind::PodArray {
+                type_id: 8,
+                element_size: 4,
+                bytes: vec![1, 2, 3, 4, 5, 6, 7, 8],
+            },
+            FieldKind::Matrix2(Matrix2::new(1.0, 2.0, 3.0, 4.0)),
+            FieldKind::Vector2F32(Vector2::new(f32::INFINITY, f32::NEG_INFINITY)),
+            FieldKind::Vector3F32(Vector3::new(-0.0, f32::MIN_POSITIVE, f32::MAX)),
+            FieldKind::Vector4F32(Vector4::new(1.0, 2.0, 3.0, 4.0)),
+            FieldKind::Vector2F64(Vector2::new(0.1, 0.2)),
+            FieldKind::Vector3F64(Vector3::new(0.1, 0.2, 0.3)),
+            FieldKind::Vector4F64(Vector4::new(0.1, 0.2, 0.3, 0.4)),
+            FieldKind::Vector2U8(Vector2::new(1, 2)),
+            FieldKind::Vector3U8(Vector3::new(1, 2, 3)),
+            FieldKind::Vector4U8(Vector4::new(1, 2, 3, 4)),
+            FieldKind::Vector2I8(Vector2::new(-1, 2)),
+            FieldKind::Vector3I8(Vector3::new(-1, 2, 3)),
+            FieldKind::Vector4I8(Vector4::new(-1, 2, 3, 4)),
+            FieldKind::Vector2U16(Vector2::new(1, 2)),
+            FieldKind::Vector3U16(Vector3::new(1, 2, 3)),
+            FieldKind::Vector4U16(Vector4::new(1, 2, 3, 4)),
+            FieldKind::Vector2I16(Vector2::new(-1, 2)),
+            FieldKind::Vector3I16(Vector3::new(-1, 2, 3)),
+            FieldKind::Vector4I16(Vector4::new(-1, 2, 3, 4)),
+            FieldKind::Vector2U32(Vector2::new(1, 2)),
+            FieldKind::Vector3U32(Vector3::new(1, 2, 3)),
+            FieldKind::Vector4U32(Vector4::new(1, 2, 3, 4)),
+            FieldKind::Vector2I32(Vector2::new(-1, 2)),
+            FieldKind::Vector3I32(Vector3::new(-1, 2, 3)),
+            FieldKind::Vector4I32(Vector4::new(-1, 2, 3, 4)),
+            FieldKind::Vector2U64(Vector2::new(1, 2)),
+            FieldKind::Vector3U64(Vector3::new(1, 2, 3)),
+            FieldKind::Vector4U64(Vector4::new(1, 2, 3, 4)),
+            FieldKind::Vector2I64(Vector2::new(-1, 2)),
+            FieldKind::Vector3I64(Vector3::new(-1, 2, 3)),
+            FieldKind::Vector4I64(Vector4::new(-1, 2, 3, 4)),
+        ];
+        {
+            let mut region = visitor.enter_region("Kinds \"quoted\"\n\\").unwrap();
+            for (i, kind) in all_kinds.into_iter().enumerate() {
+                region
+                    .current_node()
+                    .fields
+                    .push(Field::new(&format!("Field{}", i), kind));
+            }
+        }
+
+        let mut nan = f32::from_bits(0x7fc0_1234);
+        nan.visit("Nan", &mut visitor).unwrap();
+        let mut negative_zero = -0.0f64;
+        negative_zero.visit("NegativeZero", &mut visitor).unwrap();
+        let mut resource = Rc::new(Resource::new(ResourceKind::Texture(Texture {
+            data: vec![1, 2, 3],
+        })));
+        resource.visit("Resource", &mut visitor).unwrap();
+
+        let text = visitor.save_ascii_to_string();
+        assert!(Visitor::is_ascii_data(text.as_bytes()));
+        assert!(!Visitor::is_ascii_data(
+            &visitor.save_binary_to_vec().unwrap()
+        ));
+
+        // Format must be detected automatically.
+        let mut loaded = Visitor::load_from_memory(text.as_bytes()).unwrap();
+        assert_eq!(loaded.save_ascii_to_string(), text);
+
+        let mut nan = 0.0f32;
+        nan.visit("Nan", &mut loaded).unwrap();
+        assert_eq!(nan.to_bits(), 0x7fc0_1234);
+        let mut negative_zero = 0.0f64;
+        negative_zero.visit("NegativeZero", &mut loaded).unwrap();
+        assert_eq!(negative_zero.to_bits(), (-0.0f64).to_bits());
+        let mut resource = Rc::new(Resource::default());
+        resource.visit("Resource", &mut loaded).unwrap();
+        match &resource.kind {
+            ResourceKind::Texture(texture) => assert_eq!(texture.data, vec![1, 2, 3]),
+            _ => panic!("invalid resource kind"),
+        }
+    }
+
+    #[test]
+    fn visitor_ascii_errors() {
+        assert!(matches!(
+            Visitor::load_ascii_from_memory(b"RG3D_ASCII \"__ROOT__\" { \"A\": u32 = -1 }"),
+            Err(VisitError::ParseError { line: 1, .. })
+        ));
+        assert!(matches!(
+            Visitor::load_ascii_from_memory(b"RG3D_ASCII\n\"__ROOT__\" {\n\"A\": foo = 1 }"),
+            Err(VisitError::ParseError { line: 3, .. })
+        ));
+        assert!(matches!(
+            Visitor::load_ascii_from_memory(b"RG3D_ASCII \"__ROOT__\" {"),
+            Err(VisitError::ParseError { .. })
+        ));
+        assert!(matches!(
+            Visitor::load_ascii_from_memory(b"\"__ROOT__\" {}"),
+            Err(VisitError::NotSupportedFormat)
+        ));
+    }
+
     #[test]
     fn pod_vec_view_from_pod_vec() {
         // Pod for u8
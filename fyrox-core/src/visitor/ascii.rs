@@ -0,0 +1,636 @@
+//! Human-readable text representation of visitor tree.
+//!
+//! Unlike [`Visitor::save_text`], this format can be loaded back and it preserves every field exactly,
+//! so it is suitable for storing assets in version control systems. The format looks like this:
+//!
+//! ```text
+//! RG3D_ASCII
+//! "__ROOT__" {
+//!     "Length": u32 = 2
+//!     "Position": vec3f32 = (1, 2.5, -3)
+//!     "Data": data = "AQID"
+//!     "Item0" {
+//!         "Name": data = "Rm9v"
+//!     }
+//! }
+//! ```
+//!
+//! Every node is a quoted name followed by a block with fields and child nodes. Every field is a quoted
+//! name, a type tag and a value. Floating-point numbers are written in the shortest form that can be
+//! parsed back to the same value, NaNs are written as hexadecimal bit patterns to keep their payload.
+//! Binary data is encoded using base64.
+
+use crate::{
+    algebra::{
+        Complex, Const, Matrix, Matrix2, Matrix3, Matrix4, Quaternion, RawStorage, RawStorageMut,
+        Scalar, UnitComplex, UnitQuaternion, U1,
+    },
+    pool::{Handle, Pool},
+    visitor::{Blackboard, Field, FieldKind, VisitError, Visitor, VisitorNode},
+};
+use base64::Engine;
+use std::{fmt::Write, str::FromStr};
+use uuid::Uuid;
+
+pub(super) const MAGIC: &str = "RG3D_ASCII";
+
+const INDENT: &str = "    ";
+
+trait TextScalar: Scalar + Default + Copy {
+    fn write(&self, out: &mut String);
+    fn parse(word: &str) -> Option<Self>;
+}
+
+macro_rules! impl_text_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl TextScalar for $ty {
+                fn write(&self, out: &mut String) {
+                    let _ = write!(out, "{}", self);
+                }
+
+                fn parse(word: &str) -> Option<Self> {
+                    word.parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_text_integer!(u8, i8, u16, i16, u32, i32, u64, i64);
+
+macro_rules! impl_text_float {
+    ($($ty:ty, $bits:ty),*) => {
+        $(
+            impl TextScalar for $ty {
+                fn write(&self, out: &mut String) {
+                    if self.is_nan() {
+                        let _ = write!(out, "0x{:x}", self.to_bits());
+                    } else {
+                        // Display implementation of floats is guaranteed to round-trip.
+                        let _ = write!(out, "{}", self);
+                    }
+                }
+
+                fn parse(word: &str) -> Option<Self> {
+                    match word.strip_prefix("0x") {
+                        Some(bits) => <$bits>::from_str_radix(bits, 16).ok().map(<$ty>::from_bits),
+                        None => word.parse().ok(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_text_float!(f32, u32, f64, u64);
+
+fn write_string(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{{{:x}}}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_tuple<'a, T: TextScalar + 'a>(out: &mut String, values: impl IntoIterator<Item = &'a T>) {
+    out.push('(');
+    for (i, value) in values.into_iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        value.write(out);
+    }
+    out.push(')');
+}
+
+fn write_base64(out: &mut String, bytes: &[u8]) {
+    out.push('"');
+    out.push_str(&base64::engine::general_purpose::STANDARD.encode(bytes));
+    out.push('"');
+}
+
+fn write_field_kind(out: &mut String, kind: &FieldKind) {
+    macro_rules! tuple {
+        ($tag:literal, $data:expr) => {{
+            out.push_str(concat!($tag, " = "));
+            write_tuple(out, $data.iter());
+        }};
+    }
+
+    match kind {
+        FieldKind::Bool(data) => {
+            let _ = write!(out, "bool = {}", data);
+        }
+        FieldKind::U8(data) => {
+            out.push_str("u8 = ");
+            data.write(out);
+        }
+        FieldKind::I8(data) => {
+            out.push_str("i8 = ");
+            data.write(out);
+        }
+        FieldKind::U16(data) => {
+            out.push_str("u16 = ");
+            data.write(out);
+        }
+        FieldKind::I16(data) => {
+            out.push_str("i16 = ");
+            data.write(out);
+        }
+        FieldKind::U32(data) => {
+            out.push_str("u32 = ");
+            data.write(out);
+        }
+        FieldKind::I32(data) => {
+            out.push_str("i32 = ");
+            data.write(out);
+        }
+        FieldKind::U64(data) => {
+            out.push_str("u64 = ");
+            data.write(out);
+        }
+        FieldKind::I64(data) => {
+            out.push_str("i64 = ");
+            data.write(out);
+        }
+        FieldKind::F32(data) => {
+            out.push_str("f32 = ");
+            data.write(out);
+        }
+        FieldKind::F64(data) => {
+            out.push_str("f64 = ");
+            data.write(out);
+        }
+        FieldKind::UnitQuaternion(data) => {
+            out.push_str("quat = ");
+            write_tuple(out, &[data.i, data.j, data.k, data.w]);
+        }
+        // Matrices are written in column-major order.
+        FieldKind::Matrix4(data) => tuple!("mat4", data),
+        FieldKind::Matrix3(data) => tuple!("mat3", data),
+        FieldKind::Matrix2(data) => tuple!("mat2", data),
+        FieldKind::BinaryBlob(data) => {
+            out.push_str("data = ");
+            write_base64(out, data);
+        }
+        FieldKind::Uuid(uuid) => {
+            let _ = write!(out, "uuid = \"{}\"", uuid);
+        }
+        FieldKind::UnitComplex(data) => {
+            out.push_str("complex = ");
+            write_tuple(out, &[data.re, data.im]);
+        }
+        FieldKind::PodArray {
+            type_id,
+            element_size,
+            bytes,
+        } => {
+            let _ = write!(out, "podarray = ({}, {}, ", type_id, element_size);
+            write_base64(out, bytes);
+            out.push(')');
+        }
+        FieldKind::Vector2F32(data) => tuple!("vec2f32", data),
+        FieldKind::Vector3F32(data) => tuple!("vec3f32", data),
+        FieldKind::Vector4F32(data) => tuple!("vec4f32", data),
+        FieldKind::Vector2F64(data) => tuple!("vec2f64", data),
+        FieldKind::Vector3F64(data) => tuple!("vec3f64", data),
+        FieldKind::Vector4F64(data) => tuple!("vec4f64", data),
+        FieldKind::Vector2U8(data) => tuple!("vec2u8", data),
+        FieldKind::Vector3U8(data) => tuple!("vec3u8", data),
+        FieldKind::Vector4U8(data) => tuple!("vec4u8", data),
+        FieldKind::Vector2I8(data) => tuple!("vec2i8", data),
+        FieldKind::Vector3I8(data) => tuple!("vec3i8", data),
+        FieldKind::Vector4I8(data) => tuple!("vec4i8", data),
+        FieldKind::Vector2U16(data) => tuple!("vec2u16", data),
+        FieldKind::Vector3U16(data) => tuple!("vec3u16", data),
+        FieldKind::Vector4U16(data) => tuple!("vec4u16", data),
+        FieldKind::Vector2I16(data) => tuple!("vec2i16", data),
+        FieldKind::Vector3I16(data) => tuple!("vec3i16", data),
+        FieldKind::Vector4I16(data) => tuple!("vec4i16", data),
+        FieldKind::Vector2U32(data) => tuple!("vec2u32", data),
+        FieldKind::Vector3U32(data) => tuple!("vec3u32", data),
+        FieldKind::Vector4U32(data) => tuple!("vec4u32", data),
+        FieldKind::Vector2I32(data) => tuple!("vec2i32", data),
+        FieldKind::Vector3I32(data) => tuple!("vec3i32", data),
+        FieldKind::Vector4I32(data) => tuple!("vec4i32", data),
+        FieldKind::Vector2U64(data) => tuple!("vec2u64", data),
+        FieldKind::Vector3U64(data) => tuple!("vec3u64", data),
+        FieldKind::Vector4U64(data) => tuple!("vec4u64", data),
+        FieldKind::Vector2I64(data) => tuple!("vec2i64", data),
+        FieldKind::Vector3I64(data) => tuple!("vec3i64", data),
+        FieldKind::Vector4I64(data) => tuple!("vec4i64", data),
+    }
+}
+
+fn write_node(
+    visitor: &Visitor,
+    node_handle: Handle<VisitorNode>,
+    nesting: usize,
+    out: &mut String,
+) {
+    let node = visitor.nodes.borrow(node_handle);
+
+    let offset = INDENT.repeat(nesting);
+    out.push_str(&offset);
+    write_string(out, &node.name);
+    out.push_str(" {\n");
+
+    for field in node.fields.iter() {
+        out.push_str(&offset);
+        out.push_str(INDENT);
+        write_string(out, &field.name);
+        out.push_str(": ");
+        write_field_kind(out, &field.kind);
+        out.push('\n');
+    }
+
+    for child_handle in node.children.iter() {
+        write_node(visitor, *child_handle, nesting + 1, out);
+    }
+
+    out.push_str(&offset);
+    out.push_str("}\n");
+}
+
+pub(super) fn write(visitor: &Visitor) -> String {
+    let mut out = String::new();
+    out.push_str(MAGIC);
+    out.push('\n');
+    write_node(visitor, visitor.root, 0, &mut out);
+    out
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    /// Quoted string with all escape sequences resolved.
+    String(String),
+    /// Unquoted sequence of characters, such as numbers, type tags or boolean values.
+    Word(String),
+    Punct(char),
+    End,
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+    peeked: Option<Token>,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '+' | '-')
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+            peeked: None,
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> VisitError {
+        VisitError::ParseError {
+            line: self.line,
+            column: self.column,
+            message: message.into(),
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn read_escape(&mut self) -> Result<char, VisitError> {
+        match self.bump() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('0') => Ok('\0'),
+            Some('u') => {
+                if self.bump() != Some('{') {
+                    return Err(self.error("expected { after \\u"));
+                }
+                let mut code = String::new();
+                loop {
+                    match self.bump() {
+                        Some('}') => break,
+                        Some(c) if c.is_ascii_hexdigit() => code.push(c),
+                        _ => return Err(self.error("invalid unicode escape sequence")),
+                    }
+                }
+                u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error(format!("invalid unicode code point {}", code)))
+            }
+            Some(c) => Err(self.error(format!("unknown escape sequence \\{}", c))),
+            None => Err(self.error("unterminated string")),
+        }
+    }
+
+    fn read_token(&mut self) -> Result<Token, VisitError> {
+        while let Some(c) = self.chars.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+
+        match self.chars.peek().cloned() {
+            None => Ok(Token::End),
+            Some('"') => {
+                self.bump();
+                let mut string = String::new();
+                loop {
+                    match self.bump() {
+                        Some('"') => break,
+                        Some('\\') => string.push(self.read_escape()?),
+                        Some(c) => string.push(c),
+                        None => return Err(self.error("unterminated string")),
+                    }
+                }
+                Ok(Token::String(string))
+            }
+            Some(c) if is_word_char(c) => {
+                let mut word = String::new();
+                while let Some(c) = self.chars.peek().cloned() {
+                    if is_word_char(c) {
+                        word.push(c);
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                Ok(Token::Word(word))
+            }
+            Some(c @ ('{' | '}' | '(' | ')' | ',' | ':' | '=')) => {
+                self.bump();
+                Ok(Token::Punct(c))
+            }
+            Some(c) => Err(self.error(format!("unexpected character {:?}", c))),
+        }
+    }
+
+    fn peek(&mut self) -> Result<&Token, VisitError> {
+        if self.peeked.is_none() {
+            self.peeked = Some(self.read_token()?);
+        }
+        Ok(self.peeked.as_ref().unwrap())
+    }
+
+    fn next(&mut self) -> Result<Token, VisitError> {
+        match self.peeked.take() {
+            Some(token) => Ok(token),
+            None => self.read_token(),
+        }
+    }
+
+    fn expect_punct(&mut self, expected: char) -> Result<(), VisitError> {
+        match self.next()? {
+            Token::Punct(c) if c == expected => Ok(()),
+            token => Err(self.error(format!("expected {:?}, got {:?}", expected, token))),
+        }
+    }
+
+    fn expect_string(&mut self) -> Result<String, VisitError> {
+        match self.next()? {
+            Token::String(string) => Ok(string),
+            token => Err(self.error(format!("expected a string, got {:?}", token))),
+        }
+    }
+
+    fn expect_word(&mut self) -> Result<String, VisitError> {
+        match self.next()? {
+            Token::Word(word) => Ok(word),
+            token => Err(self.error(format!("expected a value, got {:?}", token))),
+        }
+    }
+
+    fn scalar<T: TextScalar>(&mut self) -> Result<T, VisitError> {
+        let word = self.expect_word()?;
+        T::parse(&word).ok_or_else(|| {
+            self.error(format!(
+                "{} is not a valid {} value",
+                word,
+                std::any::type_name::<T>()
+            ))
+        })
+    }
+
+    fn tuple<T: TextScalar, const N: usize>(&mut self) -> Result<[T; N], VisitError> {
+        let mut values = [T::default(); N];
+        self.expect_punct('(')?;
+        for (i, value) in values.iter_mut().enumerate() {
+            if i > 0 {
+                self.expect_punct(',')?;
+            }
+            *value = self.scalar()?;
+        }
+        self.expect_punct(')')?;
+        Ok(values)
+    }
+
+    fn vector<T, S, const N: usize>(&mut self) -> Result<Matrix<T, Const<N>, U1, S>, VisitError>
+    where
+        T: TextScalar,
+        S: RawStorage<T, Const<N>> + RawStorageMut<T, Const<N>> + Default,
+    {
+        let values = self.tuple::<T, N>()?;
+        let mut vec = Matrix::<T, Const<N>, U1, S>::default();
+        for (dest, value) in vec.iter_mut().zip(values) {
+            *dest = value;
+        }
+        Ok(vec)
+    }
+
+    fn base64(&mut self) -> Result<Vec<u8>, VisitError> {
+        let string = self.expect_string()?;
+        base64::engine::general_purpose::STANDARD
+            .decode(string)
+            .map_err(|e| self.error(format!("invalid base64 data: {}", e)))
+    }
+
+    fn field_kind(&mut self, tag: &str) -> Result<FieldKind, VisitError> {
+        Ok(match tag {
+            "bool" => match self.expect_word()?.as_str() {
+                "true" => FieldKind::Bool(true),
+                "false" => FieldKind::Bool(false),
+                word => return Err(self.error(format!("{} is not a valid bool value", word))),
+            },
+            "u8" => FieldKind::U8(self.scalar()?),
+            "i8" => FieldKind::I8(self.scalar()?),
+            "u16" => FieldKind::U16(self.scalar()?),
+            "i16" => FieldKind::I16(self.scalar()?),
+            "u32" => FieldKind::U32(self.scalar()?),
+            "i32" => FieldKind::I32(self.scalar()?),
+            "u64" => FieldKind::U64(self.scalar()?),
+            "i64" => FieldKind::I64(self.scalar()?),
+            "f32" => FieldKind::F32(self.scalar()?),
+            "f64" => FieldKind::F64(self.scalar()?),
+            "quat" => {
+                let [i, j, k, w] = self.tuple()?;
+                FieldKind::UnitQuaternion(UnitQuaternion::new_unchecked(Quaternion::new(
+                    w, i, j, k,
+                )))
+            }
+            "mat4" => FieldKind::Matrix4(Matrix4::from_column_slice(&self.tuple::<f32, 16>()?)),
+            "mat3" => FieldKind::Matrix3(Matrix3::from_column_slice(&self.tuple::<f32, 9>()?)),
+            "mat2" => FieldKind::Matrix2(Matrix2::from_column_slice(&self.tuple::<f32, 4>()?)),
+            "data" => FieldKind::BinaryBlob(self.base64()?),
+            "uuid" => {
+                let string = self.expect_string()?;
+                FieldKind::Uuid(
+                    Uuid::from_str(&string)
+                        .map_err(|e| self.error(format!("invalid uuid {}: {}", string, e)))?,
+                )
+            }
+            "complex" => {
+                let [re, im] = self.tuple()?;
+                FieldKind::UnitComplex(UnitComplex::new_unchecked(Complex::new(re, im)))
+            }
+            "podarray" => {
+                self.expect_punct('(')?;
+                let type_id = self.scalar()?;
+                self.expect_punct(',')?;
+                let element_size = self.scalar()?;
+                self.expect_punct(',')?;
+                let bytes = self.base64()?;
+                self.expect_punct(')')?;
+                FieldKind::PodArray {
+                    type_id,
+                    element_size,
+                    bytes,
+                }
+            }
+            "vec2f32" => FieldKind::Vector2F32(self.vector()?),
+            "vec3f32" => FieldKind::Vector3F32(self.vector()?),
+            "vec4f32" => FieldKind::Vector4F32(self.vector()?),
+            "vec2f64" => FieldKind::Vector2F64(self.vector()?),
+            "vec3f64" => FieldKind::Vector3F64(self.vector()?),
+            "vec4f64" => FieldKind::Vector4F64(self.vector()?),
+            "vec2u8" => FieldKind::Vector2U8(self.vector()?),
+            "vec3u8" => FieldKind::Vector3U8(self.vector()?),
+            "vec4u8" => FieldKind::Vector4U8(self.vector()?),
+            "vec2i8" => FieldKind::Vector2I8(self.vector()?),
+            "vec3i8" => FieldKind::Vector3I8(self.vector()?),
+            "vec4i8" => FieldKind::Vector4I8(self.vector()?),
+            "vec2u16" => FieldKind::Vector2U16(self.vector()?),
+            "vec3u16" => FieldKind::Vector3U16(self.vector()?),
+            "vec4u16" => FieldKind::Vector4U16(self.vector()?),
+            "vec2i16" => FieldKind::Vector2I16(self.vector()?),
+            "vec3i16" => FieldKind::Vector3I16(self.vector()?),
+            "vec4i16" => FieldKind::Vector4I16(self.vector()?),
+            "vec2u32" => FieldKind::Vector2U32(self.vector()?),
+            "vec3u32" => FieldKind::Vector3U32(self.vector()?),
+            "vec4u32" => FieldKind::Vector4U32(self.vector()?),
+            "vec2i32" => FieldKind::Vector2I32(self.vector()?),
+            "vec3i32" => FieldKind::Vector3I32(self.vector()?),
+            "vec4i32" => FieldKind::Vector4I32(self.vector()?),
+            "vec2u64" => FieldKind::Vector2U64(self.vector()?),
+            "vec3u64" => FieldKind::Vector3U64(self.vector()?),
+            "vec4u64" => FieldKind::Vector4U64(self.vector()?),
+            "vec2i64" => FieldKind::Vector2I64(self.vector()?),
+            "vec3i64" => FieldKind::Vector3I64(self.vector()?),
+            "vec4i64" => FieldKind::Vector4I64(self.vector()?),
+            _ => return Err(self.error(format!("unknown field type {}", tag))),
+        })
+    }
+}
+
+/// Reads content of a node with the given name. The opening brace must be already consumed.
+fn read_node(
+    lexer: &mut Lexer,
+    nodes: &mut Pool<VisitorNode>,
+    name: String,
+) -> Result<Handle<VisitorNode>, VisitError> {
+    let mut node = VisitorNode {
+        name,
+        ..VisitorNode::default()
+    };
+
+    let mut children = Vec::new();
+    loop {
+        if lexer.peek()? == &Token::Punct('}') {
+            lexer.next()?;
+            break;
+        }
+
+        let name = lexer.expect_string()?;
+        match lexer.next()? {
+            Token::Punct(':') => {
+                let tag = lexer.expect_word()?;
+                lexer.expect_punct('=')?;
+                let kind = lexer.field_kind(&tag)?;
+                node.fields.push(Field { name, kind });
+            }
+            Token::Punct('{') => children.push(read_node(lexer, nodes, name)?),
+            token => {
+                return Err(lexer.error(format!("expected ':' or '{{', got {:?}", token)));
+            }
+        }
+    }
+
+    node.children = children.clone();
+    let handle = nodes.spawn(node);
+    for child_handle in children.iter() {
+        nodes.borrow_mut(*child_handle).parent = handle;
+    }
+
+    Ok(handle)
+}
+
+pub(super) fn read(data: &[u8]) -> Result<Visitor, VisitError> {
+    let text = std::str::from_utf8(data).map_err(|e| VisitError::ParseError {
+        line: 0,
+        column: 0,
+        message: format!("the data is not a valid UTF-8 text: {}", e),
+    })?;
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+    let text = text
+        .strip_prefix(MAGIC)
+        .ok_or(VisitError::NotSupportedFormat)?;
+
+    let mut lexer = Lexer::new(text);
+    let mut nodes = Pool::new();
+    let name = lexer.expect_string()?;
+    lexer.expect_punct('{')?;
+    let root = read_node(&mut lexer, &mut nodes, name)?;
+    match lexer.next()? {
+        Token::End => (),
+        token => return Err(lexer.error(format!("unexpected {:?} after the root node", token))),
+    }
+
+    Ok(Visitor {
+        nodes,
+        rc_map: Default::default(),
+        arc_map: Default::default(),
+        reading: true,
+        current_node: root,
+        root,
+        blackboard: Blackboard::new(),
+    })
+}
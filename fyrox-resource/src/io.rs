@@ -1,6 +1,7 @@
 //! Provides an interface for IO operations that a resource loader will use, this facilliates
 //! things such as loading assets within archive files
 
+use fxhash::FxHashSet;
 use fyrox_core::io::FileLoadError;
 use std::future::{ready, Future};
 use std::iter::empty;
@@ -8,9 +9,32 @@ use std::pin::Pin;
 use std::{
     fmt::Debug,
     io::{Cursor, Read, Seek},
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
 };
 
+/// Lexically normalizes the given path: removes every `.` component and resolves every `..` component (if
+/// possible). For example, `./foo/bar/../baz.png` will be converted to `foo/baz.png`. Unlike
+/// [`std::fs::canonicalize`], this function does not access the file system.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
 /// Trait for files readers ensuring they implement the required traits
 pub trait FileReader: Debug + Send + Read + Seek + 'static {}
 
@@ -33,6 +57,21 @@ pub trait ResourceIo: Send + Sync + 'static {
         dest: &'a Path,
     ) -> ResourceIoFuture<'a, Result<(), FileLoadError>>;
 
+    /// Attempts to write the given data to a file at the given path. The file is created if it does not exist
+    /// and truncated otherwise.
+    ///
+    /// Default implementation returns an error, which is suitable for read-only sources (such as archives).
+    fn write_file<'a>(
+        &'a self,
+        path: &'a Path,
+        #[allow(unused)] data: Vec<u8>,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(ready(Err(FileLoadError::Custom(format!(
+            "Unable to write {}, the resource io is read-only!",
+            path.display()
+        )))))
+    }
+
     /// Tries to convert the path to its canonical form (normalize it in other terms). This method
     /// should guarantee correct behaviour for relative paths. Symlinks aren't mandatory to
     /// follow.
@@ -131,6 +170,17 @@ impl ResourceIo for FsResourceIo {
         })
     }
 
+    fn write_file<'a>(
+        &'a self,
+        path: &'a Path,
+        data: Vec<u8>,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            std::fs::write(path, data)?;
+            Ok(())
+        })
+    }
+
     fn canonicalize_path<'a>(
         &'a self,
         path: &'a Path,
@@ -208,3 +258,292 @@ impl ResourceIo for FsResourceIo {
         Box::pin(fyrox_core::io::is_dir(path))
     }
 }
+
+struct ResourceIoLayer {
+    io: Arc<dyn ResourceIo>,
+    root: PathBuf,
+}
+
+impl ResourceIoLayer {
+    fn map(&self, path: &Path) -> PathBuf {
+        self.root.join(path)
+    }
+
+    fn unmap(&self, path: PathBuf) -> PathBuf {
+        match path.strip_prefix(&self.root) {
+            Ok(stripped) => stripped.to_path_buf(),
+            Err(_) => path,
+        }
+    }
+}
+
+/// Resource IO, that combines multiple resource IOs into layers. Every file is searched in the layers in the
+/// order of their addition, the first layer that has the file is used. Directory listings contain files of every
+/// layer. Each layer could have its own root directory, which is prepended to every path before passing it to the
+/// layer.
+///
+/// ## Usage
+///
+/// The most common use case is to let mods override files of the base game archive:
+///
+/// ```rust,no_run
+/// # use fyrox_resource::{
+/// #     io::{FsResourceIo, LayeredResourceIo},
+/// #     manager::ResourceManager,
+/// #     pak::PakResourceIo,
+/// # };
+/// # use std::sync::Arc;
+/// fn use_mods(resource_manager: &ResourceManager) {
+///     let io = LayeredResourceIo::new()
+///         // `mods/my_mod/data/foo.png` overrides `data/foo.png` from the archive.
+///         .with_layer(Arc::new(FsResourceIo), "mods/my_mod")
+///         .with_layer(Arc::new(PakResourceIo::open("data.pak").unwrap()), "");
+///     resource_manager.state().set_resource_io(Arc::new(io));
+/// }
+/// ```
+#[derive(Default)]
+pub struct LayeredResourceIo {
+    layers: Vec<ResourceIoLayer>,
+}
+
+impl LayeredResourceIo {
+    /// Creates new resource IO without any layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds new layer with the given root directory. The layer has lower priority than every previously added
+    /// layer.
+    pub fn add_layer(&mut self, io: Arc<dyn ResourceIo>, root: impl AsRef<Path>) {
+        self.layers.push(ResourceIoLayer {
+            io,
+            root: root.as_ref().to_path_buf(),
+        });
+    }
+
+    /// Same as [`Self::add_layer`], but could be used in method chains.
+    pub fn with_layer(mut self, io: Arc<dyn ResourceIo>, root: impl AsRef<Path>) -> Self {
+        self.add_layer(io, root);
+        self
+    }
+
+    /// Returns amount of layers.
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    async fn find_layer(&self, path: &Path) -> Option<(&ResourceIoLayer, PathBuf)> {
+        for layer in self.layers.iter() {
+            let mapped = layer.map(path);
+            if layer.io.exists(&mapped).await {
+                return Some((layer, mapped));
+            }
+        }
+        None
+    }
+
+    async fn list_directory(
+        &self,
+        path: &Path,
+        recursive: bool,
+    ) -> Result<PathIter, FileLoadError> {
+        let mut paths = Vec::new();
+        let mut unique = FxHashSet::default();
+        for layer in self.layers.iter() {
+            let mapped = layer.map(path);
+            if !layer.io.is_dir(&mapped).await {
+                continue;
+            }
+            let iter = if recursive {
+                layer.io.walk_directory(&mapped).await?
+            } else {
+                layer.io.read_directory(&mapped).await?
+            };
+            for item in iter {
+                let item = layer.unmap(item);
+                if unique.insert(normalize_path(&item)) {
+                    paths.push(item);
+                }
+            }
+        }
+        let iter: PathIter = Box::new(paths.into_iter());
+        Ok(iter)
+    }
+}
+
+fn not_found(path: &Path) -> FileLoadError {
+    FileLoadError::Io(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("There's no {} file in any layer!", path.display()),
+    ))
+}
+
+impl ResourceIo for LayeredResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            let (layer, mapped) = self.find_layer(path).await.ok_or_else(|| not_found(path))?;
+            layer.io.load_file(&mapped).await
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            let (layer, mapped) = self
+                .find_layer(source)
+                .await
+                .ok_or_else(|| not_found(source))?;
+            layer.io.move_file(&mapped, &layer.map(dest)).await
+        })
+    }
+
+    fn write_file<'a>(
+        &'a self,
+        path: &'a Path,
+        data: Vec<u8>,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            // Existing files are overwritten in their own layer, new files are written to the top-most layer.
+            let (layer, mapped) = match self.find_layer(path).await {
+                Some(found) => found,
+                None => {
+                    let layer = self.layers.first().ok_or_else(|| not_found(path))?;
+                    (layer, layer.map(path))
+                }
+            };
+            layer.io.write_file(&mapped, data).await
+        })
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(ready(Ok(normalize_path(path))))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(self.list_directory(path, false))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(self.list_directory(path, true))
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(async move {
+            let (layer, mapped) = self.find_layer(path).await.ok_or_else(|| not_found(path))?;
+            layer.io.file_reader(&mapped).await
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.find_layer(path).await.is_some() })
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            match self.find_layer(path).await {
+                Some((layer, mapped)) => layer.io.is_file(&mapped).await,
+                None => false,
+            }
+        })
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            match self.find_layer(path).await {
+                Some((layer, mapped)) => layer.io.is_dir(&mapped).await,
+                None => false,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pak::{pack_directory, test::make_test_directory, PakResourceIo};
+    use fyrox_core::futures::executor::block_on;
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(Path::new("./foo/bar/../baz.png")),
+            Path::new("foo/baz.png")
+        );
+        assert_eq!(normalize_path(Path::new("../foo")), Path::new("../foo"));
+        assert_eq!(normalize_path(Path::new("foo/..")), Path::new(""));
+    }
+
+    #[test]
+    fn test_layered_resource_io() {
+        let dir = make_test_directory(
+            "fyrox_layered_resource_io",
+            &[
+                ("base/data/a.txt", "base a"),
+                ("base/data/b.txt", "base b"),
+                ("mods/foo/data/a.txt", "mod a"),
+                ("mods/foo/data/c.txt", "mod c"),
+            ],
+        );
+        let archive = dir.join("data.pak");
+        pack_directory(dir.join("base/data"), &archive).unwrap();
+
+        let io = LayeredResourceIo::new()
+            .with_layer(Arc::new(FsResourceIo), dir.join("mods/foo"))
+            .with_layer(Arc::new(PakResourceIo::open(&archive).unwrap()), "");
+        assert_eq!(io.layer_count(), 2);
+
+        let path = |name: &str| Path::new("data").join(name);
+
+        // Files of the mod override files of the archive.
+        assert_eq!(block_on(io.load_file(&path("a.txt"))).unwrap(), b"mod a");
+        assert_eq!(block_on(io.load_file(&path("b.txt"))).unwrap(), b"base b");
+        assert!(block_on(io.load_file(&path("d.txt"))).is_err());
+
+        let mut content = String::new();
+        block_on(io.file_reader(&path("c.txt")))
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "mod c");
+
+        assert!(block_on(io.is_file(&path("b.txt"))));
+        assert!(block_on(io.is_dir(Path::new("data"))));
+        assert!(!block_on(io.exists(&path("d.txt"))));
+
+        let mut files = block_on(io.read_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        files.sort();
+        assert_eq!(files, vec![path("a.txt"), path("b.txt"), path("c.txt")]);
+
+        let files = block_on(io.walk_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(files.len(), 4);
+
+        // Moving is delegated to the layer that has the file.
+        block_on(io.move_file(&path("c.txt"), &path("d.txt"))).unwrap();
+        assert!(dir.join("mods/foo/data/d.txt").exists());
+        assert!(block_on(io.move_file(&path("b.txt"), &path("e.txt"))).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -40,9 +40,11 @@ pub mod graph;
 pub mod io;
 pub mod loader;
 pub mod manager;
+pub mod metadata;
 pub mod options;
+pub mod pak;
+pub mod registry;
 pub mod state;
-mod task;
 pub mod untyped;
 
 /// Type UUID of texture resource. It is defined here to load old versions of resources.
@@ -6,11 +6,13 @@ use crate::{
     constructor::ResourceConstructorContainer,
     core::{
         append_extension,
-        futures::future::join_all,
+        futures::{executor::block_on, future::join_all},
         io::FileLoadError,
         log::Log,
         make_relative_path, notify,
         parking_lot::{Mutex, MutexGuard},
+        task::TaskPool,
+        uuid::Uuid,
         watcher::FileSystemWatcher,
         TypeUuidProvider,
     },
@@ -18,9 +20,10 @@ use crate::{
     event::{ResourceEvent, ResourceEventBroadcaster},
     io::{FsResourceIo, ResourceIo},
     loader::{ResourceLoader, ResourceLoadersContainer},
+    metadata::{ResourceMetadata, METADATA_EXTENSION},
     options::OPTIONS_EXTENSION,
+    registry::ResourceRegistry,
     state::{LoadError, ResourceState},
-    task::TaskPool,
     Resource, ResourceData, TypedResourceData, UntypedResource,
 };
 use fxhash::{FxHashMap, FxHashSet};
@@ -28,7 +31,7 @@ use rayon::prelude::*;
 use std::{
     fmt::{Debug, Display, Formatter},
     marker::PhantomData,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     sync::Arc,
 };
 
@@ -69,6 +72,12 @@ pub struct ResourceManagerState {
     resources: Vec<TimedEntry<UntypedResource>>,
     task_pool: Arc<TaskPool>,
     watcher: Option<FileSystemWatcher>,
+    registry: ResourceRegistry,
+    // Amount of registry updates that are currently running.
+    registry_updates: usize,
+    // Resources requested by their persistent ids while the registry was being updated. They will be loaded
+    // once the registry is ready.
+    deferred: Vec<UntypedResource>,
 }
 
 /// See module docs.
@@ -123,6 +132,12 @@ impl ResourceManager {
         self.state.lock()
     }
 
+    /// Returns the task pool used by this resource manager. It could be used to run other asynchronous tasks
+    /// on the same threads.
+    pub fn task_pool(&self) -> Arc<TaskPool> {
+        self.state().task_pool.clone()
+    }
+
     /// Returns the ResourceIo used by this resource manager
     pub fn resource_io(&self) -> Arc<dyn ResourceIo> {
         let state = self.state();
@@ -203,6 +218,34 @@ impl ResourceManager {
         self.state().request(path)
     }
 
+    /// Same as [`Self::request_untyped`], but tries to find the resource by its persistent id (see
+    /// [`crate::registry::ResourceRegistry`]) first. The given path is used only if there's no resource with
+    /// such id in the registry, for example if the id is nil. If the registry is being updated (see
+    /// [`Self::spawn_registry_update`]) and it does not have the id yet, loading of the resource is postponed
+    /// until the update is finished.
+    pub fn request_untyped_by_uuid<P>(
+        &self,
+        resource_uuid: Uuid,
+        fallback_path: P,
+    ) -> UntypedResource
+    where
+        P: AsRef<Path>,
+    {
+        let mut state = self.state();
+        if let Some(path) = state.registry.path_of(resource_uuid) {
+            let path = path.to_path_buf();
+            return state.request(path);
+        }
+
+        if !resource_uuid.is_nil() && state.registry_updates > 0 {
+            if let Some(resource) = state.request_deferred(resource_uuid, fallback_path.as_ref()) {
+                return resource;
+            }
+        }
+
+        state.request(fallback_path)
+    }
+
     /// Saves given resources in the specified path and registers it in resource manager, so
     /// it will be accessible through it later.
     pub fn register<P, F>(
@@ -225,6 +268,7 @@ impl ResourceManager {
                 if !on_register(&mut **data, path.as_ref()) {
                     Err(ResourceRegistrationError::UnableToRegister)
                 } else {
+                    header.resource_uuid = state.assign_uuid(path.as_ref());
                     drop(header);
                     state.push(resource);
                     Ok(())
@@ -235,7 +279,12 @@ impl ResourceManager {
         }
     }
 
-    /// Attempts to move a resource from its current location to the new path.
+    /// Attempts to move a resource from its current location to the new path. The resource is moved together
+    /// with its import options and metadata files, so every reference to the resource that was saved with its
+    /// persistent id will stay valid (see [`crate::registry::ResourceRegistry`]). References saved before the
+    /// persistent ids were introduced contain only the path of the resource, so every resource in the working
+    /// directory (that passes the filter) that references the moved resource is saved back with the new path
+    /// and the id.
     pub async fn move_resource(
         &self,
         resource: UntypedResource,
@@ -303,10 +352,26 @@ impl ResourceManager {
             *used_resources = used_resources_with_references;
         }
 
+        // Move the file with its optional import options and metadata.
+        io.move_file(&existing_path, &new_path).await?;
+        for extension in [OPTIONS_EXTENSION, METADATA_EXTENSION] {
+            let path = append_extension(&existing_path, extension);
+            if io.exists(&path).await {
+                let new_path = append_extension(&new_path, extension);
+                io.move_file(&path, &new_path).await?;
+            }
+        }
+
+        resource.set_kind(ResourceKind::External(new_path.clone()));
+        let resource_uuid = self.state().registry.move_path(&existing_path, &new_path);
+
         for (loaded_resource, used_resources) in pairs {
             if !used_resources.is_empty() {
                 for resource in used_resources {
                     resource.set_kind(ResourceKind::External(new_path.clone()));
+                    if let Some(resource_uuid) = resource_uuid {
+                        resource.set_resource_uuid(resource_uuid);
+                    }
                 }
 
                 let mut header = loaded_resource.0.lock();
@@ -328,14 +393,114 @@ impl ResourceManager {
             }
         }
 
-        // Move the file with its optional import options.
-        io.move_file(&existing_path, &new_path).await?;
-        let options_path = append_extension(&existing_path, OPTIONS_EXTENSION);
-        if io.exists(&options_path).await {
-            let new_options_path = append_extension(&new_path, OPTIONS_EXTENSION);
-            io.move_file(&options_path, &new_options_path).await?;
+        Ok(())
+    }
+
+    /// Scans the given directory for resources and rebuilds the registry of persistent resource ids (see
+    /// [`crate::registry::ResourceRegistry`]) using metadata files of the resources. Hidden directories (the
+    /// ones that start with `.`) and `target` directories are skipped, they contain build artifacts and other
+    /// non-asset files. If `write_missing_metadata` is `true`, then every resource without metadata (or with an
+    /// id that is already used by some other resource) will get new metadata file with new id. It is useful for
+    /// the editor, but games should not write anything in their data directory, so they should use `false`.
+    pub async fn update_registry(
+        &self,
+        root: impl AsRef<Path>,
+        write_missing_metadata: bool,
+    ) -> Result<(), FileLoadError> {
+        self.state().registry_updates += 1;
+        let result = self
+            .update_registry_internal(root.as_ref(), write_missing_metadata)
+            .await;
+        self.state().finish_registry_update();
+        result
+    }
+
+    /// Same as [`Self::update_registry`], but the update is performed in the background using the task pool
+    /// of the resource manager. Resources requested by their persistent ids during the update will be loaded
+    /// once the update is finished.
+    pub fn spawn_registry_update(&self, root: impl AsRef<Path>, write_missing_metadata: bool) {
+        let mut state = self.state();
+        // Increase the counter immediately, so the requests made right after this call will be postponed.
+        state.registry_updates += 1;
+        let resource_manager = self.clone();
+        let root = root.as_ref().to_path_buf();
+        state.task_pool.spawn_task(async move {
+            Log::verify(
+                resource_manager
+                    .update_registry_internal(&root, write_missing_metadata)
+                    .await,
+            );
+            resource_manager.state().finish_registry_update();
+        });
+    }
+
+    async fn update_registry_internal(
+        &self,
+        root: &Path,
+        write_missing_metadata: bool,
+    ) -> Result<(), FileLoadError> {
+        let io = self.state().resource_io.clone();
+        let paths = io.walk_directory(root).await?.collect::<Vec<_>>();
+        let paths = {
+            let state = self.state();
+            paths
+                .into_iter()
+                .filter(|path| !is_ignored_by_registry(path.strip_prefix(root).unwrap_or(path)))
+                .filter(|path| state.find_loader(path).is_some())
+                .collect::<Vec<_>>()
+        };
+
+        let mut registry = ResourceRegistry::default();
+        for path in paths {
+            let metadata_path = ResourceMetadata::path_for(&path);
+            let metadata = match ResourceMetadata::load_from_file_async(&metadata_path, &*io)
+                .await
+                .ok()
+            {
+                Some(metadata) if registry.path_of(metadata.resource_id).is_none() => metadata,
+                existing => {
+                    if let Some(existing) = existing {
+                        // Most likely the resource was copied together with its metadata.
+                        Log::warn(format!(
+                            "Resource {} has the same id {} as {} resource!",
+                            path.display(),
+                            existing.resource_id,
+                            registry
+                                .path_of(existing.resource_id)
+                                .unwrap_or(Path::new(""))
+                                .display()
+                        ));
+                    }
+
+                    if !write_missing_metadata {
+                        continue;
+                    }
+
+                    let metadata = ResourceMetadata::new_with_random_id();
+                    if let Err(err) = metadata.save_async(&metadata_path, &*io).await {
+                        Log::err(format!(
+                            "Unable to write metadata for {} resource. Reason: {:?}",
+                            path.display(),
+                            err
+                        ));
+                        continue;
+                    }
+                    metadata
+                }
+            };
+
+            registry.register(metadata.resource_id, &path);
         }
 
+        let mut state = self.state();
+        for resource in state.resources.iter() {
+            let mut header = resource.0.lock();
+            if let Some(resource_uuid) = header.kind.path().and_then(|path| registry.id_of(path)) {
+                header.resource_uuid = resource_uuid;
+            }
+        }
+        state.registry = registry;
+
         Ok(())
     }
 
@@ -348,6 +513,13 @@ impl ResourceManager {
     }
 }
 
+fn is_ignored_by_registry(path: &Path) -> bool {
+    path.components().any(|component| match component {
+        Component::Normal(name) => name == "target" || name.to_string_lossy().starts_with('.'),
+        _ => false,
+    })
+}
+
 impl ResourceManagerState {
     pub(crate) fn new() -> Self {
         Self {
@@ -360,6 +532,9 @@ impl ResourceManagerState {
             built_in_resources: Default::default(),
             // Use the file system resource io by default
             resource_io: Arc::new(FsResourceIo),
+            registry: Default::default(),
+            registry_updates: 0,
+            deferred: Default::default(),
         }
     }
 
@@ -377,6 +552,11 @@ impl ResourceManagerState {
         self.watcher = watcher;
     }
 
+    /// Returns a reference to the registry of persistent resource ids.
+    pub fn registry(&self) -> &ResourceRegistry {
+        &self.registry
+    }
+
     /// Returns total amount of registered resources.
     pub fn count_registered_resources(&self) -> usize {
         self.resources.len()
@@ -546,6 +726,9 @@ impl ResourceManagerState {
 
                 if let Some(loader) = self.find_loader(path.as_ref()) {
                     let resource = UntypedResource::new_pending(kind, loader.data_type_uuid());
+                    if let Some(resource_uuid) = self.registry.id_of(&path) {
+                        resource.set_resource_uuid(resource_uuid);
+                    }
                     self.spawn_loading_task(path, resource.clone(), loader, false);
                     self.push(resource.clone());
                     resource
@@ -558,6 +741,75 @@ impl ResourceManagerState {
         }
     }
 
+    // Creates a pending resource, that will be loaded when the registry update is finished. Returns `None` if
+    // there's no loader for the resource.
+    fn request_deferred(
+        &mut self,
+        resource_uuid: Uuid,
+        fallback_path: &Path,
+    ) -> Option<UntypedResource> {
+        if let Some(existing) = self
+            .resources
+            .iter()
+            .find(|resource| resource.resource_uuid() == resource_uuid)
+        {
+            return Some(existing.value.clone());
+        }
+
+        let loader = self.find_loader(fallback_path)?;
+        let resource = UntypedResource::new_pending(
+            ResourceKind::External(fallback_path.to_path_buf()),
+            loader.data_type_uuid(),
+        );
+        resource.set_resource_uuid(resource_uuid);
+        self.deferred.push(resource.clone());
+        self.push(resource.clone());
+        Some(resource)
+    }
+
+    fn finish_registry_update(&mut self) {
+        self.registry_updates = self.registry_updates.saturating_sub(1);
+        if self.registry_updates > 0 {
+            return;
+        }
+
+        for resource in std::mem::take(&mut self.deferred) {
+            let Some(fallback_path) = resource.kind().into_path() else {
+                continue;
+            };
+            let path = self
+                .registry
+                .path_of(resource.resource_uuid())
+                .map(|path| path.to_path_buf())
+                .unwrap_or(fallback_path);
+            resource.set_kind(ResourceKind::External(path.clone()));
+            match self.find_loader(&path) {
+                Some(loader) => self.spawn_loading_task(path, resource, loader, false),
+                None => resource.commit_error(format!(
+                    "There's no resource loader for {} resource!",
+                    path.display()
+                )),
+            }
+        }
+    }
+
+    /// Returns persistent id of a resource at the given path. If the resource has no id yet, a new one will be
+    /// generated and written to the metadata file of the resource (if the resource file exists).
+    fn assign_uuid(&mut self, path: &Path) -> Uuid {
+        if let Some(resource_uuid) = self.registry.id_of(path) {
+            return resource_uuid;
+        }
+
+        let metadata = ResourceMetadata::new_with_random_id();
+        if block_on(self.resource_io.exists(path)) {
+            Log::verify(block_on(
+                metadata.save_async(&ResourceMetadata::path_for(path), &*self.resource_io),
+            ));
+        }
+        self.registry.register(metadata.resource_id, path);
+        metadata.resource_id
+    }
+
     fn find_loader(&self, path: &Path) -> Option<&dyn ResourceLoader> {
         path.extension().and_then(|extension| {
             self.loaders
@@ -694,6 +946,7 @@ mod test {
 
     use fyrox_core::uuid::{uuid, Uuid};
     use fyrox_core::{
+        futures::executor::block_on,
         reflect::{FieldInfo, Reflect},
         visitor::{Visit, VisitResult, Visitor},
         TypeUuidProvider,
@@ -948,6 +1201,117 @@ mod test {
         let resource = UntypedResource::new_ok(Default::default(), Stub {});
         let res = manager.register(resource.clone(), path.clone(), |_, __| true);
         assert!(res.is_ok());
+        assert!(!resource.resource_uuid().is_nil());
+
+        let _ = std::fs::remove_file(ResourceMetadata::path_for(&path));
+    }
+
+    #[test]
+    fn resource_manager_registry() {
+        let dir = std::env::temp_dir().join("fyrox_resource_manager_registry");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.txt");
+        File::create(&path).unwrap();
+
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(Stub {});
+        manager.state().constructors_container.add::<Stub>();
+
+        // Games do not write metadata.
+        block_on(manager.update_registry(&dir, false)).unwrap();
+        assert!(manager.state().registry().is_empty());
+
+        block_on(manager.update_registry(&dir, true)).unwrap();
+        let metadata_path = ResourceMetadata::path_for(&path);
+        assert!(metadata_path.exists());
+        let resource_uuid = manager.state().registry().id_of(&path).unwrap();
+
+        let resource = block_on(manager.request_untyped(&path)).unwrap();
+        assert_eq!(resource.resource_uuid(), resource_uuid);
+
+        // Serialize a reference to the resource, it must contain both the path and the id.
+        let mut visitor = Visitor::new();
+        visitor.blackboard.register(Arc::new(manager.clone()));
+        let mut reference = resource.clone();
+        reference.visit("Resource", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        // Move the resource, the reference must still be valid, even though it stores the old path.
+        let new_path = dir.join("b.txt");
+        block_on(manager.move_resource(resource.clone(), &new_path, &dir, |_| true)).unwrap();
+        assert!(!path.exists() && !metadata_path.exists());
+        assert!(ResourceMetadata::path_for(&new_path).exists());
+        assert_eq!(resource.kind(), ResourceKind::External(new_path.clone()));
+        assert_eq!(
+            manager.state().registry().path_of(resource_uuid),
+            Some(new_path.as_path())
+        );
+
+        // Forget the loaded resource to make sure that it is found using the registry.
+        drop(resource);
+        manager.state().unregister(&new_path);
+
+        let mut visitor = Visitor::load_from_memory(&data).unwrap();
+        visitor.blackboard.register(Arc::new(manager.clone()));
+        let mut reference = UntypedResource::default();
+        reference.visit("Resource", &mut visitor).unwrap();
+        assert_eq!(reference.kind(), ResourceKind::External(new_path));
+        assert_eq!(reference.resource_uuid(), resource_uuid);
+
+        // A copy of a resource together with its metadata gets a new id.
+        std::fs::copy(dir.join("b.txt"), dir.join("c.txt")).unwrap();
+        std::fs::copy(
+            ResourceMetadata::path_for(&dir.join("b.txt")),
+            ResourceMetadata::path_for(&dir.join("c.txt")),
+        )
+        .unwrap();
+        block_on(manager.update_registry(&dir, true)).unwrap();
+        let registry = manager.state().registry().clone();
+        assert_eq!(registry.len(), 2);
+        assert_ne!(
+            registry.id_of(&dir.join("b.txt")),
+            registry.id_of(&dir.join("c.txt"))
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn resource_manager_deferred_request() {
+        let dir = std::env::temp_dir().join("fyrox_resource_manager_deferred_request");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("target")).unwrap();
+        let path = dir.join("a.txt");
+        File::create(&path).unwrap();
+        File::create(dir.join("target").join("b.txt")).unwrap();
+
+        let resource_uuid = Uuid::new_v4();
+        block_on(
+            ResourceMetadata {
+                resource_id: resource_uuid,
+            }
+            .save_async(&ResourceMetadata::path_for(&path), &FsResourceIo),
+        )
+        .unwrap();
+
+        let manager = ResourceManager::new();
+        manager.state().loaders.set(Stub {});
+        manager.state().constructors_container.add::<Stub>();
+
+        // The old path of the resource does not exist anymore, it must be found by its id once the registry
+        // is ready.
+        manager.spawn_registry_update(&dir, true);
+        let resource = manager.request_untyped_by_uuid(resource_uuid, dir.join("old.txt"));
+        assert!(block_on(resource.clone()).is_ok());
+        assert_eq!(resource.kind(), ResourceKind::External(path));
+        assert_eq!(resource.resource_uuid(), resource_uuid);
+
+        // Build artifacts are not assets.
+        assert!(!ResourceMetadata::path_for(&dir.join("target").join("b.txt")).exists());
+        assert_eq!(manager.state().registry().len(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 
     #[test]
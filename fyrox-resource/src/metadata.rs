@@ -0,0 +1,62 @@
+//! Resource metadata is stored in a sidecar file next to a resource file (`<resource>.<ext>.meta`). It holds a
+//! persistent unique id of the resource, which is used to reference the resource independently of its path. See
+//! [`crate::registry::ResourceRegistry`] docs for more info.
+
+use crate::{
+    core::{append_extension, io::FileLoadError, uuid::Uuid},
+    io::ResourceIo,
+};
+use ron::ser::PrettyConfig;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Extension of resource metadata file.
+pub const METADATA_EXTENSION: &str = "meta";
+
+/// Persistent information about a resource, that is stored in a sidecar file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ResourceMetadata {
+    /// Unique id of the resource. It stays the same when the resource is moved or renamed.
+    pub resource_id: Uuid,
+}
+
+impl ResourceMetadata {
+    /// Creates new metadata with random resource id.
+    pub fn new_with_random_id() -> Self {
+        Self {
+            resource_id: Uuid::new_v4(),
+        }
+    }
+
+    /// Returns a path of the metadata file of a resource at the given path.
+    pub fn path_for(resource_path: &Path) -> PathBuf {
+        append_extension(resource_path, METADATA_EXTENSION)
+    }
+
+    /// Tries to load metadata from the given file.
+    pub async fn load_from_file_async(
+        path: &Path,
+        io: &dyn ResourceIo,
+    ) -> Result<Self, FileLoadError> {
+        let bytes = io.load_file(path).await?;
+        ron::de::from_bytes(&bytes).map_err(|err| {
+            FileLoadError::Custom(format!(
+                "Malformed metadata file {}. Reason: {:?}",
+                path.display(),
+                err
+            ))
+        })
+    }
+
+    /// Saves metadata to the given file.
+    pub async fn save_async(&self, path: &Path, io: &dyn ResourceIo) -> Result<(), FileLoadError> {
+        let string = ron::ser::to_string_pretty(self, PrettyConfig::default()).map_err(|err| {
+            FileLoadError::Custom(format!(
+                "Unable to write metadata file {}. Reason: {:?}",
+                path.display(),
+                err
+            ))
+        })?;
+        io.write_file(path, string.into_bytes()).await
+    }
+}
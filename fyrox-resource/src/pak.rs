@@ -0,0 +1,489 @@
+//! Packed archive support. Shipped games could pack their data folders into a few archives instead of thousands
+//! of loose files. See [`PakResourceIo`] and [`pack_directory`] docs for more info.
+//!
+//! # Format
+//!
+//! All numbers are little-endian.
+//!
+//! - Magic `FPAK` (4 bytes) and format version (`u32`).
+//! - Amount of files (`u32`).
+//! - Index, for each file: length of its path (`u32`), UTF-8 path with `/` separators, offset of the file data
+//!   relative to the end of the index (`u64`) and size of the file data (`u64`).
+//! - Data of every file.
+
+use crate::{
+    core::io::FileLoadError,
+    io::{normalize_path as normalize, FileReader, PathIter, ResourceIo, ResourceIoFuture},
+};
+use fxhash::{FxHashMap, FxHashSet};
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+const MAGIC: [u8; 4] = *b"FPAK";
+const VERSION: u32 = 1;
+
+/// Extension of packed archives.
+pub const PAK_EXTENSION: &str = "pak";
+
+fn archive_path(path: &Path) -> String {
+    path.components()
+        .map(|component| match component {
+            // Separator will be added by `join`.
+            Component::RootDir => Default::default(),
+            _ => component.as_os_str().to_string_lossy(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn not_found(path: &Path) -> FileLoadError {
+    FileLoadError::Io(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("There's no {} file in the archive!", path.display()),
+    ))
+}
+
+#[derive(Copy, Clone, Debug)]
+struct PakEntry {
+    offset: u64,
+    size: u64,
+}
+
+#[derive(Clone, Debug)]
+struct SharedBytes(Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for SharedBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[derive(Clone, Debug)]
+enum PakSource {
+    File(PathBuf),
+    Memory(SharedBytes),
+}
+
+/// A reader, that reads a single file from an archive.
+#[derive(Debug)]
+struct EntryReader<R> {
+    inner: R,
+    entry: PakEntry,
+    position: u64,
+}
+
+impl<R: Read + Seek> Read for EntryReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.entry.size.saturating_sub(self.position);
+        let len = (buf.len() as u64).min(remaining) as usize;
+        if len == 0 {
+            return Ok(0);
+        }
+        self.inner
+            .seek(SeekFrom::Start(self.entry.offset + self.position))?;
+        let read = self.inner.read(&mut buf[..len])?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Read + Seek> Seek for EntryReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.entry.size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+        self.position = position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid seek to a negative or overflowing position!",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
+
+/// Resource IO, that reads files from a packed archive (see module docs for the format). Paths in the archive are
+/// relative, `./data/foo.png` and `data/foo.png` are the same path. The archive is read-only, so
+/// [`ResourceIo::move_file`] always fails.
+///
+/// ## Usage
+///
+/// ```rust,no_run
+/// # use fyrox_resource::{manager::ResourceManager, pak::PakResourceIo};
+/// # use std::sync::Arc;
+/// fn use_archive(resource_manager: &ResourceManager) {
+///     let io = PakResourceIo::open("data.pak").unwrap();
+///     resource_manager.state().set_resource_io(Arc::new(io));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct PakResourceIo {
+    source: PakSource,
+    entries: FxHashMap<PathBuf, PakEntry>,
+    directories: FxHashSet<PathBuf>,
+}
+
+impl PakResourceIo {
+    /// Opens an archive at the given path. Only the index of the archive is read, file data is read on demand.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FileLoadError> {
+        let path = path.as_ref();
+        let mut reader = BufReader::new(File::open(path)?);
+        Self::from_source(PakSource::File(path.to_path_buf()), &mut reader)
+    }
+
+    /// Creates new archive IO from the archive data in memory. Could be useful on platforms without file system,
+    /// such as WebAssembly.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, FileLoadError> {
+        let bytes = SharedBytes(Arc::new(bytes));
+        let mut reader = Cursor::new(bytes.clone());
+        Self::from_source(PakSource::Memory(bytes), &mut reader)
+    }
+
+    fn from_source(source: PakSource, reader: &mut impl Read) -> Result<Self, FileLoadError> {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(FileLoadError::Custom("Not a packed archive!".to_string()));
+        }
+        let version = read_u32(reader)?;
+        if version != VERSION {
+            return Err(FileLoadError::Custom(format!(
+                "Unsupported archive version {version}!"
+            )));
+        }
+
+        let count = read_u32(reader)?;
+        // Magic + version + count.
+        let mut index_size = 12u64;
+        let mut index = Vec::new();
+        for _ in 0..count {
+            let len = read_u32(reader)?;
+            if len > u16::MAX as u32 {
+                return Err(FileLoadError::Custom(
+                    "Malformed archive index!".to_string(),
+                ));
+            }
+            let mut path = vec![0; len as usize];
+            reader.read_exact(&mut path)?;
+            let path = String::from_utf8(path)
+                .map_err(|_| FileLoadError::Custom("Malformed archive index!".to_string()))?;
+            let offset = read_u64(reader)?;
+            let size = read_u64(reader)?;
+            index_size += 4 + len as u64 + 16;
+            index.push((path, PakEntry { offset, size }));
+        }
+
+        let mut entries = FxHashMap::default();
+        let mut directories = FxHashSet::default();
+        directories.insert(PathBuf::new());
+        for (path, mut entry) in index {
+            entry.offset += index_size;
+            let path = normalize(Path::new(&path));
+            for ancestor in path.ancestors().skip(1) {
+                directories.insert(ancestor.to_path_buf());
+            }
+            entries.insert(path, entry);
+        }
+
+        Ok(Self {
+            source,
+            entries,
+            directories,
+        })
+    }
+
+    /// Returns an iterator over the paths of all files in the archive.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(|path| path.as_path())
+    }
+
+    fn entry(&self, path: &Path) -> Result<PakEntry, FileLoadError> {
+        self.entries
+            .get(&normalize(path))
+            .cloned()
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn reader(&self, entry: PakEntry) -> Result<Box<dyn FileReader>, FileLoadError> {
+        Ok(match self.source {
+            PakSource::File(ref path) => Box::new(BufReader::new(EntryReader {
+                inner: File::open(path)?,
+                entry,
+                position: 0,
+            })),
+            PakSource::Memory(ref bytes) => Box::new(EntryReader {
+                inner: Cursor::new(bytes.clone()),
+                entry,
+                position: 0,
+            }),
+        })
+    }
+
+    fn children(&self, path: &Path, recursive: bool) -> Vec<PathBuf> {
+        let path = normalize(path);
+        let is_child = |candidate: &Path| {
+            if recursive {
+                candidate.starts_with(&path)
+            } else {
+                candidate.parent() == Some(&path)
+            }
+        };
+        let is_child = |candidate: &Path| candidate != path && is_child(candidate);
+        let mut children = self
+            .entries
+            .keys()
+            .chain(self.directories.iter())
+            .filter(|candidate| !candidate.as_os_str().is_empty() && is_child(candidate))
+            .cloned()
+            .collect::<Vec<_>>();
+        children.sort();
+        children
+    }
+}
+
+impl ResourceIo for PakResourceIo {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            let entry = self.entry(path)?;
+            let mut bytes = Vec::with_capacity(entry.size as usize);
+            self.reader(entry)?.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        })
+    }
+
+    fn move_file<'a>(
+        &'a self,
+        source: &'a Path,
+        _dest: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<(), FileLoadError>> {
+        Box::pin(async move {
+            Err(FileLoadError::Custom(format!(
+                "Unable to move {}, packed archives are read-only!",
+                source.display()
+            )))
+        })
+    }
+
+    fn canonicalize_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathBuf, FileLoadError>> {
+        Box::pin(async move { Ok(normalize(path)) })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(async move {
+            let iter: PathIter = Box::new(self.children(path, false).into_iter());
+            Ok(iter)
+        })
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(async move {
+            let mut paths = self.children(path, true);
+            // Include the directory itself, the same way as file system walking does.
+            if self.directories.contains(&normalize(path)) {
+                paths.insert(0, path.to_path_buf());
+            }
+            let iter: PathIter = Box::new(paths.into_iter());
+            Ok(iter)
+        })
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(async move { self.reader(self.entry(path)?) })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            let path = normalize(path);
+            self.entries.contains_key(&path) || self.directories.contains(&path)
+        })
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.entries.contains_key(&normalize(path)) })
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move { self.directories.contains(&normalize(path)) })
+    }
+}
+
+/// Packs every file of the given directory into an archive at the given path. Files are stored with their paths
+/// relative to the parent of the directory, so the archive could be used as a drop-in replacement of the
+/// directory. For example, `textures/foo.png` file of `/home/user/game/data` directory will be stored as
+/// `data/textures/foo.png`. Returns the amount of packed files.
+pub fn pack_directory(
+    directory: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+) -> Result<usize, FileLoadError> {
+    let output = output.as_ref();
+    let canonical_output = output.canonicalize().ok();
+    let base = normalize(directory.as_ref())
+        .parent()
+        .map(|parent| parent.to_path_buf())
+        .unwrap_or_default();
+
+    let mut files = Vec::new();
+    for entry in walkdir::WalkDir::new(directory.as_ref()) {
+        let entry = entry.map_err(|err| FileLoadError::Custom(err.to_string()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        // Do not pack the archive into itself.
+        if canonical_output.is_some() && entry.path().canonicalize().ok() == canonical_output {
+            continue;
+        }
+        let size = entry
+            .metadata()
+            .map_err(|err| FileLoadError::Custom(err.to_string()))?
+            .len();
+        let path = normalize(entry.path());
+        files.push((
+            archive_path(path.strip_prefix(&base).unwrap_or(&path)),
+            entry.into_path(),
+            size,
+        ));
+    }
+    // Sort files to make archives reproducible.
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut writer = BufWriter::new(File::create(output)?);
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&(files.len() as u32).to_le_bytes())?;
+    let mut offset = 0u64;
+    for (path, _, size) in files.iter() {
+        writer.write_all(&(path.len() as u32).to_le_bytes())?;
+        writer.write_all(path.as_bytes())?;
+        writer.write_all(&offset.to_le_bytes())?;
+        writer.write_all(&size.to_le_bytes())?;
+        offset += size;
+    }
+    for (path, disk_path, size) in files.iter() {
+        let copied = io::copy(&mut File::open(disk_path)?.take(*size), &mut writer)?;
+        if copied != *size {
+            return Err(FileLoadError::Custom(format!(
+                "File {path} was changed while packing!"
+            )));
+        }
+    }
+    writer.flush()?;
+
+    Ok(files.len())
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use fyrox_core::futures::executor::block_on;
+
+    pub(crate) fn make_test_directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&dir);
+        for (path, content) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_pak_resource_io() {
+        let dir = make_test_directory(
+            "fyrox_pak_resource_io",
+            &[
+                ("data/a.txt", "foo"),
+                ("data/textures/b.txt", "0123456789"),
+                ("data/textures/c.txt", ""),
+            ],
+        );
+        let archive = dir.join("data.pak");
+        assert_eq!(pack_directory(dir.join("data"), &archive).unwrap(), 3);
+
+        let bytes = std::fs::read(&archive).unwrap();
+        for io in [
+            PakResourceIo::open(&archive).unwrap(),
+            PakResourceIo::from_bytes(bytes).unwrap(),
+        ] {
+            let a = Path::new("data/a.txt");
+            let b = Path::new("data/textures/b.txt");
+            assert_eq!(block_on(io.load_file(a)).unwrap(), b"foo");
+            assert_eq!(block_on(io.load_file(b)).unwrap(), b"0123456789");
+            assert!(block_on(io.load_file(Path::new("data/d.txt"))).is_err());
+            assert!(block_on(io.move_file(a, b)).is_err());
+
+            assert!(block_on(io.is_file(a)));
+            assert!(!block_on(io.is_dir(a)));
+            assert!(block_on(io.is_dir(Path::new("data/textures"))));
+            assert!(block_on(io.exists(Path::new("./data/textures/c.txt"))));
+            assert!(!block_on(io.exists(Path::new("data/e.txt"))));
+
+            // Streaming.
+            let mut reader = block_on(io.file_reader(b)).unwrap();
+            let mut buf = [0; 3];
+            reader.seek(SeekFrom::Start(4)).unwrap();
+            reader.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf, b"456");
+            reader.seek(SeekFrom::End(-2)).unwrap();
+            let mut rest = Vec::new();
+            reader.read_to_end(&mut rest).unwrap();
+            assert_eq!(rest, b"89");
+            assert!(reader.seek(SeekFrom::Current(-100)).is_err());
+
+            let children = block_on(io.read_directory(Path::new("data")))
+                .unwrap()
+                .collect::<Vec<_>>();
+            assert_eq!(
+                children,
+                vec![a.to_path_buf(), PathBuf::from("data/textures")]
+            );
+
+            let all = block_on(io.walk_directory(Path::new("./data")))
+                .unwrap()
+                .collect::<Vec<_>>();
+            assert_eq!(all.len(), 5);
+            assert_eq!(all[0], Path::new("./data"));
+        }
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_invalid_archive() {
+        assert!(PakResourceIo::from_bytes(b"not an archive".to_vec()).is_err());
+        assert!(PakResourceIo::from_bytes(Vec::new()).is_err());
+    }
+}
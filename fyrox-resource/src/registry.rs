@@ -0,0 +1,113 @@
+//! Resource registry is a bidirectional map between persistent resource ids and resource paths. It allows
+//! resources to be referenced by their ids, so the references will stay valid when a resource is moved or renamed.
+//!
+//! The registry is built by scanning a data directory for resource metadata files (see
+//! [`crate::manager::ResourceManager::update_registry`]) and it is kept in sync when a resource is moved using
+//! [`crate::manager::ResourceManager::move_resource`].
+
+use crate::{core::uuid::Uuid, io::normalize_path as normalize};
+use fxhash::FxHashMap;
+use std::path::{Path, PathBuf};
+
+/// See module docs.
+#[derive(Default, Debug, Clone)]
+pub struct ResourceRegistry {
+    paths: FxHashMap<Uuid, PathBuf>,
+    ids: FxHashMap<PathBuf, Uuid>,
+}
+
+impl ResourceRegistry {
+    /// Registers a resource at the given path with the given id. Previous registration of the id or the path
+    /// will be discarded.
+    pub fn register(&mut self, id: Uuid, path: &Path) {
+        let path = normalize(path);
+        self.unregister_path(&path);
+        if let Some(old_path) = self.paths.insert(id, path.clone()) {
+            self.ids.remove(&old_path);
+        }
+        self.ids.insert(path, id);
+    }
+
+    /// Removes a resource at the given path from the registry. Returns id of the resource (if any).
+    pub fn unregister_path(&mut self, path: &Path) -> Option<Uuid> {
+        let id = self.ids.remove(&normalize(path))?;
+        self.paths.remove(&id);
+        Some(id)
+    }
+
+    /// Changes path of a resource at the given path. Does nothing if there's no such resource in the registry.
+    /// Returns id of the resource (if any).
+    pub fn move_path(&mut self, old_path: &Path, new_path: &Path) -> Option<Uuid> {
+        let id = self.unregister_path(old_path)?;
+        self.register(id, new_path);
+        Some(id)
+    }
+
+    /// Returns a path of a resource with the given id.
+    pub fn path_of(&self, id: Uuid) -> Option<&Path> {
+        self.paths.get(&id).map(|path| path.as_path())
+    }
+
+    /// Returns an id of a resource at the given path.
+    pub fn id_of(&self, path: &Path) -> Option<Uuid> {
+        self.ids.get(&normalize(path)).cloned()
+    }
+
+    /// Returns total amount of registered resources.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Returns `true` if the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Removes every resource from the registry.
+    pub fn clear(&mut self) {
+        self.paths.clear();
+        self.ids.clear();
+    }
+
+    /// Returns an iterator over every `(id, path)` pair in the registry.
+    pub fn iter(&self) -> impl Iterator<Item = (Uuid, &Path)> {
+        self.paths.iter().map(|(id, path)| (*id, path.as_path()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        let mut registry = ResourceRegistry::default();
+        assert!(registry.is_empty());
+
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        registry.register(a, Path::new("./data/a.png"));
+        registry.register(b, Path::new("data/b.png"));
+        assert_eq!(registry.len(), 2);
+
+        assert_eq!(registry.path_of(a), Some(Path::new("data/a.png")));
+        assert_eq!(registry.id_of(Path::new("data/a.png")), Some(a));
+        assert_eq!(registry.id_of(Path::new("./data/b.png")), Some(b));
+
+        assert_eq!(
+            registry.move_path(Path::new("data/a.png"), Path::new("data/c.png")),
+            Some(a)
+        );
+        assert_eq!(registry.path_of(a), Some(Path::new("data/c.png")));
+        assert_eq!(registry.id_of(Path::new("data/a.png")), None);
+
+        // Re-registration of an id discards its old path.
+        registry.register(b, Path::new("data/d.png"));
+        assert_eq!(registry.id_of(Path::new("data/b.png")), None);
+        assert_eq!(registry.len(), 2);
+
+        assert_eq!(registry.unregister_path(Path::new("data/d.png")), Some(b));
+        assert_eq!(registry.path_of(b), None);
+        assert_eq!(registry.len(), 1);
+    }
+}
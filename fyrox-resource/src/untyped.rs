@@ -51,7 +51,10 @@ fn guess_uuid(region: &mut RegionGuard) -> Uuid {
     let mut path = PathBuf::new();
     if path.visit("Path", &mut region).is_ok() {
         let ext = path.extension().unwrap_or_default().to_ascii_lowercase();
-        if ext == OsStr::new("rgs") || ext == OsStr::new("fbx") {
+        if ext == OsStr::new("rgs")
+            || ext == OsStr::new("fbx")
+            || (cfg!(feature = "gltf") && (ext == OsStr::new("gltf") || ext == OsStr::new("glb")))
+        {
             return MODEL_RESOURCE_UUID;
         } else if ext == OsStr::new("shader")
             || path == OsStr::new("Standard")
@@ -152,6 +155,10 @@ impl Display for ResourceKind {
 #[derive(Reflect, Debug)]
 pub struct ResourceHeader {
     pub type_uuid: Uuid,
+    /// Persistent unique id of an external resource (see [`crate::metadata::ResourceMetadata`]). It is used to
+    /// find the resource on deserialization, even if it was moved. Nil id means that the resource can be found
+    /// only by its path.
+    pub resource_uuid: Uuid,
     pub kind: ResourceKind,
     pub state: ResourceState,
 }
@@ -223,6 +230,8 @@ impl Visit for ResourceHeader {
         self.kind.visit("Kind", &mut region)?;
         self.type_uuid.visit("TypeUuid", &mut region)?;
         self.state.visit("State", &mut region)?;
+        // Resources saved by older versions have no persistent id, the path will be used to find them.
+        let _ = self.resource_uuid.visit("ResourceUuid", &mut region);
 
         Ok(())
     }
@@ -254,8 +263,13 @@ impl Visit for UntypedResource {
                 .get::<ResourceManager>()
                 .expect("Resource manager must be available when deserializing resources!");
 
-            let path = self.kind().path_owned().unwrap();
-            self.0 = resource_manager.request_untyped(path).0;
+            let (resource_uuid, path) = {
+                let header = self.0.lock();
+                (header.resource_uuid, header.kind.path_owned().unwrap())
+            };
+            self.0 = resource_manager
+                .request_untyped_by_uuid(resource_uuid, path)
+                .0;
         }
 
         Ok(())
@@ -267,6 +281,7 @@ impl Default for UntypedResource {
         Self(Arc::new(Mutex::new(ResourceHeader {
             kind: Default::default(),
             type_uuid: Default::default(),
+            resource_uuid: Default::default(),
             state: ResourceState::new_load_error(LoadError::new(
                 "Default resource state of unknown type.",
             )),
@@ -300,6 +315,7 @@ impl UntypedResource {
         Self(Arc::new(Mutex::new(ResourceHeader {
             kind,
             type_uuid,
+            resource_uuid: Default::default(),
             state: ResourceState::new_pending(),
         })))
     }
@@ -313,6 +329,7 @@ impl UntypedResource {
         Self(Arc::new(Mutex::new(ResourceHeader {
             kind,
             type_uuid: data.type_uuid(),
+            resource_uuid: Default::default(),
             state: ResourceState::new_ok(data),
         })))
     }
@@ -322,6 +339,7 @@ impl UntypedResource {
         Self(Arc::new(Mutex::new(ResourceHeader {
             kind,
             type_uuid,
+            resource_uuid: Default::default(),
             state: ResourceState::new_load_error(error),
         })))
     }
@@ -364,6 +382,17 @@ impl UntypedResource {
         self.0.lock().kind = new_kind;
     }
 
+    /// Returns persistent unique id of the resource. It is nil for embedded resources and for external resources
+    /// without metadata.
+    pub fn resource_uuid(&self) -> Uuid {
+        self.0.lock().resource_uuid
+    }
+
+    /// Sets new persistent unique id of the resource.
+    pub fn set_resource_uuid(&self, resource_uuid: Uuid) {
+        self.0.lock().resource_uuid = resource_uuid;
+    }
+
     /// Tries to cast untyped resource to a particular type.
     pub fn try_cast<T>(&self) -> Option<Resource<T>>
     where
@@ -570,6 +599,7 @@ mod test {
         let mut r = UntypedResource(Arc::new(Mutex::new(ResourceHeader {
             kind: path.clone().into(),
             type_uuid: Uuid::default(),
+            resource_uuid: Uuid::default(),
             state: ResourceState::Ok(Box::new(stub)),
         })));
         assert!(Pin::new(&mut r).poll(&mut cx).is_ready());
@@ -577,6 +607,7 @@ mod test {
         let mut r = UntypedResource(Arc::new(Mutex::new(ResourceHeader {
             kind: path.clone().into(),
             type_uuid: Uuid::default(),
+            resource_uuid: Uuid::default(),
             state: ResourceState::LoadError {
                 error: Default::default(),
             },
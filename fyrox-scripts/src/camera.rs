@@ -13,11 +13,10 @@ use fyrox::{
         visitor::prelude::*,
         TypeUuidProvider,
     },
-    event::{DeviceEvent, ElementState, Event, WindowEvent},
+    event::{DeviceEvent, Event},
     gui::{key::KeyBinding, message::KeyCode},
     impl_component_provider,
     script::{ScriptContext, ScriptTrait},
-    utils,
 };
 use std::ops::Range;
 
@@ -204,39 +203,34 @@ impl TypeUuidProvider for FlyingCameraController {
 
 impl ScriptTrait for FlyingCameraController {
     fn on_os_event(&mut self, event: &Event<()>, context: &mut ScriptContext) {
-        match event {
-            Event::WindowEvent {
-                event: WindowEvent::KeyboardInput { event, .. },
-                ..
-            } => {
-                for (binding, state) in [
-                    (&self.move_forward_key, &mut self.move_forward),
-                    (&self.move_backward_key, &mut self.move_backward),
-                    (&self.move_left_key, &mut self.move_left),
-                    (&self.move_right_key, &mut self.move_right),
-                ] {
-                    if let KeyBinding::Some(key_code) = **binding {
-                        if utils::translate_key_from_ui(key_code) == event.physical_key {
-                            *state = event.state == ElementState::Pressed;
-                        }
-                    }
-                }
-            }
-            Event::DeviceEvent {
-                event: DeviceEvent::MouseMotion { delta, .. },
-                ..
-            } => {
-                let speed = *self.sensitivity * context.dt;
-                *self.yaw -= (delta.0 as f32) * speed;
-                *self.pitch = (*self.pitch + delta.1 as f32 * speed)
-                    .max(self.pitch_limit.start)
-                    .min(self.pitch_limit.end);
-            }
-            _ => {}
+        if let Event::DeviceEvent {
+            event: DeviceEvent::MouseMotion { delta, .. },
+            ..
+        } = event
+        {
+            let speed = *self.sensitivity * context.dt;
+            *self.yaw -= (delta.0 as f32) * speed;
+            *self.pitch = (*self.pitch + delta.1 as f32 * speed)
+                .max(self.pitch_limit.start)
+                .min(self.pitch_limit.end);
         }
     }
 
     fn on_update(&mut self, context: &mut ScriptContext) {
+        // Keys are read from the input state instead of OS events, so the movement is replayed precisely
+        // (see `fyrox::engine::replay` docs).
+        for (binding, state) in [
+            (&self.move_forward_key, &mut self.move_forward),
+            (&self.move_backward_key, &mut self.move_backward),
+            (&self.move_left_key, &mut self.move_left),
+            (&self.move_right_key, &mut self.move_right),
+        ] {
+            *state = match **binding {
+                KeyBinding::Some(key_code) => context.input.is_key_down(key_code),
+                KeyBinding::NotSet => false,
+            };
+        }
+
         let mut new_velocity = Vector3::default();
 
         let this = &mut context.scene.graph[context.handle];
@@ -459,7 +459,51 @@ impl AudioBusGraph {
         }
     }
 
+    // Copies sidechain signals into the buffers of compressors. The buffers are reused between renders, so
+    // nothing is allocated on the render thread once the buffers have grown to the size of the output buffer.
+    fn fetch_sidechain_signals(&mut self) {
+        for i in 0..self.buses.get_capacity() {
+            let handle = self.buses.handle_from_index(i);
+            if !self.buses.is_valid_handle(handle) {
+                continue;
+            }
+
+            for index in 0..self.buses[handle].effects.len() {
+                let Effect::Compressor(compressor) = &self.buses[handle].effects[index] else {
+                    continue;
+                };
+                if compressor.sidechain_bus().is_empty() {
+                    continue;
+                }
+
+                // Compressor will use its own input if there's no such bus.
+                let source = self
+                    .buses
+                    .pair_iter()
+                    .find(|(_, bus)| bus.name == compressor.sidechain_bus())
+                    .map(|(source, _)| source);
+
+                let Effect::Compressor(compressor) = &mut self.buses[handle].effects[index] else {
+                    continue;
+                };
+                let mut signal = std::mem::take(&mut compressor.sidechain_signal);
+                signal.clear();
+                if let Some(source) = source {
+                    signal.extend_from_slice(self.buses[source].ping_pong_buffer.input_ref());
+                }
+
+                if let Effect::Compressor(compressor) = &mut self.buses[handle].effects[index] {
+                    compressor.sidechain_signal = signal;
+                }
+            }
+        }
+    }
+
     pub(crate) fn end_render(&mut self, output_device_buffer: &mut [(f32, f32)]) {
+        // Sidechain signals must be fetched before any effect is applied, otherwise the result would depend on
+        // the order of buses.
+        self.fetch_sidechain_signals();
+
         let mut leafs = Vec::new();
         for (handle, bus) in self.buses.pair_iter_mut() {
             bus.apply_effects();
@@ -13,6 +13,8 @@
 use crate::bus::AudioBusGraph;
 use crate::{
     listener::Listener,
+    math,
+    offline::{self, OfflineRenderOptions, RenderedSound},
     pool::Ticket,
     renderer::{render_source_default, Renderer},
     source::{SoundSource, Status},
@@ -111,7 +113,7 @@ pub struct SerializationOptions {
 }
 
 /// Internal state of context.
-#[derive(Default, Debug, Clone, Reflect)]
+#[derive(Debug, Clone, Reflect)]
 pub struct State {
     sources: Pool<SoundSource>,
     listener: Listener,
@@ -120,13 +122,37 @@ pub struct State {
     bus_graph: AudioBusGraph,
     distance_model: DistanceModel,
     paused: bool,
+    #[reflect(min_value = 0.0, step = 0.05)]
+    doppler_factor: f32,
+    #[reflect(min_value = 0.0, step = 1.0)]
+    speed_of_sound: f32,
     /// A set of flags, that can be used to define what should be skipped during the
     /// serialization of a sound context.
     #[reflect(hidden)]
     pub serialization_options: SerializationOptions,
 }
 
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            sources: Default::default(),
+            listener: Default::default(),
+            render_duration: Default::default(),
+            renderer: Default::default(),
+            bus_graph: Default::default(),
+            distance_model: Default::default(),
+            paused: false,
+            doppler_factor: 1.0,
+            speed_of_sound: State::DEFAULT_SPEED_OF_SOUND,
+            serialization_options: Default::default(),
+        }
+    }
+}
+
 impl State {
+    /// Speed of sound in the air (in meters per second) at 20 degrees Celsius.
+    pub const DEFAULT_SPEED_OF_SOUND: f32 = 343.3;
+
     /// Extracts a source from the context and reserves its handle. It is used to temporarily take
     /// ownership over source, and then put node back using given ticket.
     pub fn take_reserve(
@@ -170,6 +196,29 @@ impl State {
         self.distance_model
     }
 
+    /// Sets Doppler factor. It is used to exaggerate (values larger than 1.0) or diminish (values less than 1.0)
+    /// pitch shift caused by relative velocity of sound sources and the listener. Zero disables Doppler effect
+    /// completely. Default value is 1.0, which gives physically correct results.
+    pub fn set_doppler_factor(&mut self, doppler_factor: f32) {
+        self.doppler_factor = doppler_factor.max(0.0);
+    }
+
+    /// Returns current Doppler factor.
+    pub fn doppler_factor(&self) -> f32 {
+        self.doppler_factor
+    }
+
+    /// Sets speed of sound (in units per second) that is used to calculate Doppler effect. Default value
+    /// is [`Self::DEFAULT_SPEED_OF_SOUND`], which is speed of sound in the air in meters per second.
+    pub fn set_speed_of_sound(&mut self, speed_of_sound: f32) {
+        self.speed_of_sound = speed_of_sound.max(f32::EPSILON);
+    }
+
+    /// Returns current speed of sound.
+    pub fn speed_of_sound(&self) -> f32 {
+        self.speed_of_sound
+    }
+
     /// Normalizes given frequency using context's sampling rate. Normalized frequency then can be used
     /// to create filters.
     pub fn normalize_frequency(&self, f: f32) -> f32 {
@@ -275,6 +324,17 @@ impl State {
             {
                 if let Some(bus_input_buffer) = self.bus_graph.try_get_bus_input_buffer(&source.bus)
                 {
+                    // Doppler effect makes sense only for spatial sounds.
+                    source.doppler_pitch = math::lerpf(
+                        1.0,
+                        source.calculate_doppler_pitch(
+                            &self.listener,
+                            self.doppler_factor,
+                            self.speed_of_sound,
+                        ) as f32,
+                        source.spatial_blend(),
+                    ) as f64;
+
                     source.render(output_device_buffer.len());
 
                     match self.renderer {
@@ -329,6 +389,8 @@ impl SoundContext {
                 bus_graph: AudioBusGraph::new(),
                 distance_model: DistanceModel::InverseDistance,
                 paused: false,
+                doppler_factor: 1.0,
+                speed_of_sound: State::DEFAULT_SPEED_OF_SOUND,
                 serialization_options: Default::default(),
             }))),
         }
@@ -362,6 +424,17 @@ impl SoundContext {
         }
     }
 
+    /// Renders the context for the given duration without any audio output device (faster than real-time).
+    /// Rendering moves playback position of every playing sound source. See [`crate::offline`] module docs
+    /// for more info.
+    ///
+    /// ## Deadlocks
+    ///
+    /// This method internally locks the context, so it must be unlocked before calling this method.
+    pub fn render_offline(&self, options: &OfflineRenderOptions) -> RenderedSound {
+        offline::render(std::slice::from_ref(self), options)
+    }
+
     /// Returns true if context is corrupted.
     pub fn is_invalid(&self) -> bool {
         self.state.is_none()
@@ -387,6 +460,9 @@ impl Visit for State {
         self.renderer.visit("Renderer", &mut region)?;
         self.paused.visit("Paused", &mut region)?;
         self.distance_model.visit("DistanceModel", &mut region)?;
+        // Backward compatibility.
+        let _ = self.doppler_factor.visit("DopplerFactor", &mut region);
+        let _ = self.speed_of_sound.visit("SpeedOfSound", &mut region);
 
         Ok(())
     }
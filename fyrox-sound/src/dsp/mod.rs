@@ -89,3 +89,13 @@ pub fn hann_window(i: usize, sample_count: usize) -> f32 {
 pub fn make_window<W: Fn(usize, usize) -> f32>(sample_count: usize, func: W) -> Vec<f32> {
     (0..sample_count).map(|i| func(i, sample_count)).collect()
 }
+
+/// Converts a value in decibels to a linear amplitude multiplier.
+pub fn db_to_linear(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
+
+/// Converts a linear amplitude multiplier to decibels.
+pub fn linear_to_db(linear: f32) -> f32 {
+    20.0 * linear.max(f32::MIN_POSITIVE).log10()
+}
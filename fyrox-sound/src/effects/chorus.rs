@@ -0,0 +1,333 @@
+//! Chorus and flanger module.
+//!
+//! # Overview
+//!
+//! Both effects mix the input signal with its copy delayed by a periodically changing amount of time. Chorus uses
+//! relatively long delays (tens of milliseconds) which makes a single sound source to sound like a group of
+//! sources. Flanger uses very short delays (a few milliseconds) with feedback which gives the famous "jet plane"
+//! sweeping sound. Delay of the right channel is modulated with 90 degrees phase shift, which makes the effect
+//! wider.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::{
+//!     context::SoundContext,
+//!     effects::{chorus::Chorus, Effect},
+//! };
+//!
+//! fn add_flanger(context: &SoundContext) {
+//!     context
+//!         .state()
+//!         .bus_graph_mut()
+//!         .primary_bus_mut()
+//!         .add_effect(Effect::Chorus(Chorus::flanger()));
+//! }
+//! ```
+
+use crate::{context::SAMPLE_RATE, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+/// Delay line that can be read at any (fractional) position using linear interpolation.
+#[derive(Debug, Clone, PartialEq, Default)]
+struct ModulatedDelayLine {
+    samples: Vec<f32>,
+    pos: usize,
+}
+
+impl ModulatedDelayLine {
+    fn new(len: usize) -> Self {
+        Self {
+            samples: vec![0.0; len.max(3)],
+            pos: 0,
+        }
+    }
+
+    /// Reads a sample that was written `delay` samples ago. Delay must be in `[1.0..len - 1]` range.
+    fn read(&self, delay: f32) -> f32 {
+        let len = self.samples.len();
+        let whole = delay.floor();
+        let fract = delay - whole;
+        // The most recent sample is at `pos` and it was written one sample ago.
+        let a = self.samples[(self.pos + len + 1 - whole as usize) % len];
+        let b = self.samples[(self.pos + len - whole as usize) % len];
+        a + (b - a) * fract
+    }
+
+    fn write(&mut self, sample: f32) {
+        self.pos = (self.pos + 1) % self.samples.len();
+        self.samples[self.pos] = sample;
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Chorus {
+    #[reflect(
+        description = "Average delay (in seconds) of the processed signal.",
+        setter = "set_delay_time",
+        min_value = 0.0,
+        max_value = 0.1
+    )]
+    delay_time: f32,
+
+    #[reflect(
+        description = "Max deviation (in seconds) of the delay from its average value.",
+        setter = "set_depth",
+        min_value = 0.0,
+        max_value = 0.1
+    )]
+    depth: f32,
+
+    #[reflect(
+        description = "Frequency (in Hertz) of the delay modulation.",
+        setter = "set_rate",
+        min_value = 0.0
+    )]
+    rate: f32,
+
+    #[reflect(
+        description = "Amount of the processed signal that is fed back to the input. Typically it is non-zero \
+        only for flanger.",
+        setter = "set_feedback",
+        min_value = 0.0,
+        max_value = 0.95
+    )]
+    feedback: f32,
+
+    #[reflect(
+        description = "Gain of the unprocessed signal.",
+        setter = "set_dry",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    dry: f32,
+
+    #[reflect(
+        description = "Gain of the processed signal.",
+        setter = "set_wet",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    wet: f32,
+
+    #[reflect(hidden)]
+    phase: f32,
+    #[reflect(hidden)]
+    left: ModulatedDelayLine,
+    #[reflect(hidden)]
+    right: ModulatedDelayLine,
+}
+
+impl Visit for Chorus {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.delay_time.visit("DelayTime", &mut region)?;
+        self.depth.visit("Depth", &mut region)?;
+        self.rate.visit("Rate", &mut region)?;
+        self.feedback.visit("Feedback", &mut region)?;
+        self.dry.visit("Dry", &mut region)?;
+        self.wet.visit("Wet", &mut region)?;
+
+        if region.is_reading() {
+            self.reset_lines();
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Chorus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Chorus {
+    /// Max delay time (and max depth) in seconds.
+    pub const MAX_DELAY_TIME: f32 = 0.1;
+
+    /// Creates new chorus with 20 ms delay, 3 ms depth and 0.8 Hz modulation rate.
+    pub fn new() -> Self {
+        let mut chorus = Self {
+            delay_time: 0.02,
+            depth: 0.003,
+            rate: 0.8,
+            feedback: 0.0,
+            dry: 1.0,
+            wet: 0.7,
+            phase: 0.0,
+            left: Default::default(),
+            right: Default::default(),
+        };
+        chorus.reset_lines();
+        chorus
+    }
+
+    /// Creates new flanger with 2 ms delay, 1.5 ms depth, 0.25 Hz modulation rate and 0.6 feedback.
+    pub fn flanger() -> Self {
+        let mut flanger = Self {
+            delay_time: 0.002,
+            depth: 0.0015,
+            rate: 0.25,
+            feedback: 0.6,
+            ..Self::new()
+        };
+        flanger.reset_lines();
+        flanger
+    }
+
+    fn reset_lines(&mut self) {
+        // Extra samples for interpolation and the minimal delay of one sample.
+        let len = ((self.delay_time + self.depth) * SAMPLE_RATE as f32).ceil() as usize + 3;
+        self.left = ModulatedDelayLine::new(len);
+        self.right = ModulatedDelayLine::new(len);
+    }
+
+    /// Sets average delay (in seconds) of the processed signal.
+    pub fn set_delay_time(&mut self, delay_time: f32) {
+        self.delay_time = delay_time.clamp(0.0, Self::MAX_DELAY_TIME);
+        self.reset_lines();
+    }
+
+    /// Returns current delay time in seconds.
+    pub fn delay_time(&self) -> f32 {
+        self.delay_time
+    }
+
+    /// Sets max deviation (in seconds) of the delay from its average value.
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth.clamp(0.0, Self::MAX_DELAY_TIME);
+        self.reset_lines();
+    }
+
+    /// Returns current depth in seconds.
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    /// Sets frequency (in Hertz) of the delay modulation.
+    pub fn set_rate(&mut self, rate: f32) {
+        self.rate = rate.max(0.0);
+    }
+
+    /// Returns current modulation rate in Hertz.
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+
+    /// Sets amount of the processed signal that is fed back to the input. It is clamped to `[0.0..0.95]` range
+    /// to keep the effect stable.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.95);
+    }
+
+    /// Returns current feedback.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets gain of the processed signal.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+}
+
+impl EffectRenderTrait for Chorus {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let sample_rate = SAMPLE_RATE as f32;
+        let max_delay = (self.left.samples.len() - 2) as f32;
+        let phase_step = TAU * self.rate / sample_rate;
+
+        for (&(input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            let delay = |phase: f32| {
+                ((self.delay_time + self.depth * phase.sin()) * sample_rate).clamp(1.0, max_delay)
+            };
+            let delay_left = delay(self.phase);
+            let delay_right = delay(self.phase + FRAC_PI_2);
+
+            let delayed_left = self.left.read(delay_left);
+            let delayed_right = self.right.read(delay_right);
+
+            self.left.write(input_left + self.feedback * delayed_left);
+            self.right
+                .write(input_right + self.feedback * delayed_right);
+
+            *output_left = self.dry * input_left + self.wet * delayed_left;
+            *output_right = self.dry * input_right + self.wet * delayed_right;
+
+            self.phase = (self.phase + phase_step) % TAU;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{
+        chorus::Chorus,
+        test::{peak, process, sine},
+    };
+
+    #[test]
+    fn test_unmodulated_chorus_is_delay() {
+        let mut chorus = Chorus::new();
+        chorus.set_depth(0.0);
+        chorus.set_delay_time(0.01);
+        chorus.set_dry(0.0);
+        chorus.set_wet(1.0);
+
+        let input = sine(440.0, 1.0, 4096);
+        let output = process(&mut chorus, &input);
+
+        // 10 ms at 44100 Hz.
+        let delay = 441;
+        for (i, (left, right)) in output.iter().enumerate().skip(delay) {
+            let expected = input[i - delay];
+            assert!((left - expected.0).abs() < 1.0e-5);
+            assert!((right - expected.1).abs() < 1.0e-5);
+        }
+    }
+
+    #[test]
+    fn test_modulation() {
+        let input = sine(440.0, 1.0, 44100);
+
+        let mut plain = Chorus::new();
+        plain.set_depth(0.0);
+        let mut modulated = Chorus::new();
+        modulated.set_rate(5.0);
+
+        let plain = process(&mut plain, &input);
+        let modulated = process(&mut modulated, &input);
+        assert_ne!(plain, modulated);
+
+        // Delay of the right channel must be modulated with a phase shift.
+        assert!(modulated.iter().any(|(l, r)| (l - r).abs() > 0.1));
+    }
+
+    #[test]
+    fn test_flanger_stability() {
+        let mut flanger = Chorus::flanger();
+        flanger.set_feedback(1.0);
+        let output = process(&mut flanger, &sine(440.0, 1.0, 44100 * 2));
+        assert!(peak(&output).is_finite());
+        assert!(peak(&output) < 50.0);
+    }
+}
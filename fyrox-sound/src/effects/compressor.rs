@@ -0,0 +1,332 @@
+//! Dynamic range compressor module.
+//!
+//! # Overview
+//!
+//! Compressor reduces the volume of loud sounds, thus narrowing the dynamic range of a signal. It tracks the
+//! level of the signal and when the level goes above the threshold, it reduces the gain in accordance with the
+//! ratio. With a very high ratio the compressor becomes a limiter - the signal won't (almost) exceed the
+//! threshold.
+//!
+//! # Sidechain
+//!
+//! By default, the compressor tracks the level of the signal it processes. It is also possible to use the signal
+//! of some other audio bus to control the gain reduction (so called sidechain compression). A typical example is
+//! "ducking" - music gets quieter when there's some dialog playing. In this case the compressor should be added
+//! to a music bus and the name of the dialog bus should be used as a sidechain bus.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::{
+//!     bus::AudioBus,
+//!     context::SoundContext,
+//!     effects::{compressor::Compressor, Effect},
+//! };
+//!
+//! fn duck_music(context: &SoundContext) {
+//!     let mut state = context.state();
+//!     let graph = state.bus_graph_mut();
+//!     let primary = graph.primary_bus_handle();
+//!
+//!     let mut compressor = Compressor::new();
+//!     compressor.set_threshold_db(-30.0);
+//!     compressor.set_ratio(8.0);
+//!     compressor.set_sidechain_bus("Dialog".to_string());
+//!
+//!     let mut music = AudioBus::new("Music".to_string());
+//!     music.add_effect(Effect::Compressor(compressor));
+//!     graph.add_bus(music, primary);
+//!     graph.add_bus(AudioBus::new("Dialog".to_string()), primary);
+//! }
+//! ```
+
+use crate::{
+    context::SAMPLE_RATE,
+    dsp::{db_to_linear, linear_to_db},
+    effects::EffectRenderTrait,
+};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct Compressor {
+    #[reflect(
+        description = "Level (in decibels) above which the gain reduction starts.",
+        setter = "set_threshold_db",
+        max_value = 0.0
+    )]
+    threshold_db: f32,
+
+    #[reflect(
+        description = "Amount of gain reduction. For example, with ratio 4, every 4 decibels above the threshold \
+        will result in only 1 decibel of the output signal.",
+        setter = "set_ratio",
+        min_value = 1.0
+    )]
+    ratio: f32,
+
+    #[reflect(
+        description = "Time (in seconds) that is needed for the compressor to react on rising signal level.",
+        setter = "set_attack_time",
+        min_value = 0.0
+    )]
+    attack_time: f32,
+
+    #[reflect(
+        description = "Time (in seconds) that is needed for the compressor to stop the gain reduction after the \
+        signal level has fallen.",
+        setter = "set_release_time",
+        min_value = 0.0
+    )]
+    release_time: f32,
+
+    #[reflect(
+        description = "Gain (in decibels) that is applied to the compressed signal.",
+        setter = "set_makeup_gain_db"
+    )]
+    makeup_gain_db: f32,
+
+    #[reflect(
+        description = "Name of an audio bus which signal will be used to control the gain reduction. Empty name \
+        means that the signal of the bus with the compressor will be used.",
+        setter = "set_sidechain_bus"
+    )]
+    sidechain_bus: String,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    envelope: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) sidechain_signal: Vec<(f32, f32)>,
+}
+
+impl Default for Compressor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compressor {
+    /// Ratio that is used by [`Compressor::limiter`].
+    pub const LIMITER_RATIO: f32 = 1000.0;
+
+    /// Creates new compressor with -20 dB threshold, 4:1 ratio, 10 ms attack and 100 ms release times.
+    pub fn new() -> Self {
+        Self {
+            threshold_db: -20.0,
+            ratio: 4.0,
+            attack_time: 0.01,
+            release_time: 0.1,
+            makeup_gain_db: 0.0,
+            sidechain_bus: Default::default(),
+            envelope: 0.0,
+            sidechain_signal: Default::default(),
+        }
+    }
+
+    /// Creates new limiter with the given threshold (in decibels). Limiter is a compressor with very high ratio
+    /// and instant attack, it does not let the signal to exceed the threshold.
+    pub fn limiter(threshold_db: f32) -> Self {
+        Self {
+            threshold_db: threshold_db.min(0.0),
+            ratio: Self::LIMITER_RATIO,
+            attack_time: 0.0,
+            release_time: 0.05,
+            ..Self::new()
+        }
+    }
+
+    /// Sets a level (in decibels) above which the gain reduction starts.
+    pub fn set_threshold_db(&mut self, threshold_db: f32) {
+        self.threshold_db = threshold_db.min(0.0);
+    }
+
+    /// Returns current threshold in decibels.
+    pub fn threshold_db(&self) -> f32 {
+        self.threshold_db
+    }
+
+    /// Sets amount of gain reduction. For example, with ratio 4, every 4 decibels above the threshold will result
+    /// in only 1 decibel of the output signal. Ratio of 1 means no compression.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    /// Returns current ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets time (in seconds) that is needed for the compressor to react on rising signal level.
+    pub fn set_attack_time(&mut self, attack_time: f32) {
+        self.attack_time = attack_time.max(0.0);
+    }
+
+    /// Returns current attack time in seconds.
+    pub fn attack_time(&self) -> f32 {
+        self.attack_time
+    }
+
+    /// Sets time (in seconds) that is needed for the compressor to stop the gain reduction after the signal level
+    /// has fallen.
+    pub fn set_release_time(&mut self, release_time: f32) {
+        self.release_time = release_time.max(0.0);
+    }
+
+    /// Returns current release time in seconds.
+    pub fn release_time(&self) -> f32 {
+        self.release_time
+    }
+
+    /// Sets gain (in decibels) that is applied to the compressed signal. It is used to compensate the loss of
+    /// loudness caused by the compression.
+    pub fn set_makeup_gain_db(&mut self, makeup_gain_db: f32) {
+        self.makeup_gain_db = makeup_gain_db;
+    }
+
+    /// Returns current makeup gain in decibels.
+    pub fn makeup_gain_db(&self) -> f32 {
+        self.makeup_gain_db
+    }
+
+    /// Sets a name of an audio bus which signal will be used to control the gain reduction. Empty name means that
+    /// the signal of the bus with the compressor will be used. The signal of the sidechain bus is taken before
+    /// its effects are applied and contains only the sound sources that are bound to the bus.
+    pub fn set_sidechain_bus(&mut self, name: String) {
+        self.sidechain_bus = name;
+    }
+
+    /// Returns a name of the sidechain bus, empty string means no sidechain.
+    pub fn sidechain_bus(&self) -> &str {
+        &self.sidechain_bus
+    }
+
+    fn coefficient(time: f32) -> f32 {
+        if time > 0.0 {
+            (-1.0 / (time * SAMPLE_RATE as f32)).exp()
+        } else {
+            0.0
+        }
+    }
+}
+
+impl EffectRenderTrait for Compressor {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let attack = Self::coefficient(self.attack_time);
+        let release = Self::coefficient(self.release_time);
+        let slope = 1.0 - 1.0 / self.ratio;
+
+        let key = if self.sidechain_bus.is_empty() || self.sidechain_signal.len() != input.len() {
+            input
+        } else {
+            &self.sidechain_signal
+        };
+
+        for ((&(input_left, input_right), &(key_left, key_right)), (output_left, output_right)) in
+            input.iter().zip(key).zip(output)
+        {
+            let level = key_left.abs().max(key_right.abs());
+            let k = if level > self.envelope {
+                attack
+            } else {
+                release
+            };
+            self.envelope = k * self.envelope + (1.0 - k) * level;
+
+            let overshoot = linear_to_db(self.envelope) - self.threshold_db;
+            let reduction = if overshoot > 0.0 {
+                overshoot * slope
+            } else {
+                0.0
+            };
+            let gain = db_to_linear(self.makeup_gain_db - reduction);
+
+            *output_left = input_left * gain;
+            *output_right = input_right * gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        bus::{AudioBus, AudioBusGraph},
+        dsp::db_to_linear,
+        effects::{
+            compressor::Compressor,
+            test::{peak, process, sine},
+            Effect,
+        },
+    };
+
+    #[test]
+    fn test_signal_below_threshold_is_unchanged() {
+        let input = sine(440.0, db_to_linear(-30.0), 4096);
+        let output = process(&mut Compressor::new(), &input);
+        for (a, b) in input.iter().zip(output.iter()) {
+            assert!((a.0 - b.0).abs() < 1.0e-6);
+            assert!((a.1 - b.1).abs() < 1.0e-6);
+        }
+    }
+
+    #[test]
+    fn test_compression() {
+        let mut compressor = Compressor::new();
+        compressor.set_threshold_db(-20.0);
+        compressor.set_ratio(4.0);
+        compressor.set_release_time(0.5);
+
+        // 0 dB signal is 20 dB above the threshold, so the output should be at -15 dB.
+        let output = process(&mut compressor, &sine(440.0, 1.0, 44100));
+        let level = peak(&output[22050..]);
+        assert!((level - db_to_linear(-15.0)).abs() < 0.02, "{}", level);
+    }
+
+    #[test]
+    fn test_limiter() {
+        let mut limiter = Compressor::limiter(-6.0);
+        let output = process(&mut limiter, &sine(440.0, 1.0, 44100));
+        assert!(peak(&output) <= db_to_linear(-6.0) * 1.01);
+    }
+
+    #[test]
+    fn test_sidechain() {
+        let mut output_buffer = vec![(0.0f32, 0.0f32); 512];
+
+        let mut graph = AudioBusGraph::new();
+        let primary = graph.primary_bus_handle();
+
+        let mut compressor = Compressor::limiter(-40.0);
+        compressor.set_sidechain_bus("Key".to_string());
+        graph
+            .primary_bus_mut()
+            .add_effect(Effect::Compressor(compressor));
+
+        // The key bus is used only to control the compressor, it must not be heard.
+        let mut key = AudioBus::new("Key".to_string());
+        key.set_gain(0.0);
+        let key = graph.add_bus(key, primary);
+
+        // Quiet signal alone passes through as is, loud signal of the key bus ducks it.
+        for key_level in [0.0, 1.0] {
+            output_buffer.fill((0.0, 0.0));
+            graph.begin_render(output_buffer.len());
+            graph.primary_bus_mut().input_buffer().fill((0.005, 0.005));
+            graph
+                .try_get_bus_mut(key)
+                .unwrap()
+                .input_buffer()
+                .fill((key_level, key_level));
+            graph.end_render(&mut output_buffer);
+
+            let level = peak(&output_buffer[256..]);
+            if key_level == 0.0 {
+                assert!((level - 0.005).abs() < 1.0e-6);
+            } else {
+                assert!(level < 0.005 * db_to_linear(-30.0));
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Feedback delay module.
+//!
+//! # Overview
+//!
+//! Delay repeats the input signal after a given amount of time, each repetition is quieter than the previous one
+//! (if feedback is less than one). The delay time is set in seconds, it does not depend on any tempo.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::{
+//!     context::SoundContext,
+//!     effects::{delay::Delay, Effect},
+//! };
+//!
+//! fn add_echo(context: &SoundContext) {
+//!     let mut delay = Delay::new();
+//!     delay.set_delay_time(0.25);
+//!     delay.set_feedback(0.5);
+//!     context
+//!         .state()
+//!         .bus_graph_mut()
+//!         .primary_bus_mut()
+//!         .add_effect(Effect::Delay(delay));
+//! }
+//! ```
+
+use crate::{context::SAMPLE_RATE, dsp::DelayLine, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct Delay {
+    #[reflect(
+        description = "Time (in seconds) between repetitions.",
+        setter = "set_delay_time",
+        min_value = 0.0,
+        max_value = 10.0
+    )]
+    delay_time: f32,
+
+    #[reflect(
+        description = "Gain of each next repetition relative to the previous one.",
+        setter = "set_feedback",
+        min_value = 0.0,
+        max_value = 0.99
+    )]
+    feedback: f32,
+
+    #[reflect(
+        description = "Gain of the unprocessed signal.",
+        setter = "set_dry",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    dry: f32,
+
+    #[reflect(
+        description = "Gain of the repetitions.",
+        setter = "set_wet",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    wet: f32,
+
+    #[reflect(hidden)]
+    left: DelayLine,
+    #[reflect(hidden)]
+    right: DelayLine,
+}
+
+impl Visit for Delay {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.delay_time.visit("DelayTime", &mut region)?;
+        self.feedback.visit("Feedback", &mut region)?;
+        self.dry.visit("Dry", &mut region)?;
+        self.wet.visit("Wet", &mut region)?;
+
+        if region.is_reading() {
+            self.reset_lines();
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Delay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delay {
+    /// Max delay time in seconds.
+    pub const MAX_DELAY_TIME: f32 = 10.0;
+
+    /// Creates new delay with 300 ms delay time and 0.4 feedback.
+    pub fn new() -> Self {
+        let mut delay = Self {
+            delay_time: 0.3,
+            feedback: 0.4,
+            dry: 1.0,
+            wet: 0.5,
+            left: Default::default(),
+            right: Default::default(),
+        };
+        delay.reset_lines();
+        delay
+    }
+
+    fn reset_lines(&mut self) {
+        // Delay lines are read one sample before feeding them (see `render`), which adds one more sample of delay.
+        let len = ((self.delay_time * SAMPLE_RATE as f32).round() as usize)
+            .saturating_sub(1)
+            .max(1);
+        self.left = DelayLine::new(len);
+        self.right = DelayLine::new(len);
+    }
+
+    /// Sets time (in seconds) between repetitions. Changing the delay time will discard every pending repetition.
+    pub fn set_delay_time(&mut self, delay_time: f32) {
+        self.delay_time = delay_time.clamp(0.0, Self::MAX_DELAY_TIME);
+        self.reset_lines();
+    }
+
+    /// Returns current delay time in seconds.
+    pub fn delay_time(&self) -> f32 {
+        self.delay_time
+    }
+
+    /// Sets gain of each next repetition relative to the previous one. It is clamped to `[0.0..0.99]` range to
+    /// keep the effect stable.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.99);
+    }
+
+    /// Returns current feedback.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of input signal should be passed to output without any processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets gain of the repetitions.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns wet part.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+}
+
+impl EffectRenderTrait for Delay {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        for (&(input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            let delayed_left = self.left.last();
+            let delayed_right = self.right.last();
+
+            self.left.feed(input_left + self.feedback * delayed_left);
+            self.right.feed(input_right + self.feedback * delayed_right);
+
+            *output_left = self.dry * input_left + self.wet * delayed_left;
+            *output_right = self.dry * input_right + self.wet * delayed_right;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{delay::Delay, test::process};
+
+    #[test]
+    fn test_delay_repetitions() {
+        let mut delay = Delay::new();
+        delay.set_delay_time(0.01);
+        delay.set_feedback(0.5);
+        delay.set_dry(1.0);
+        delay.set_wet(1.0);
+
+        let mut input = vec![(0.0, 0.0); 2048];
+        input[0] = (1.0, -1.0);
+
+        let output = process(&mut delay, &input);
+
+        // 10 ms at 44100 Hz.
+        let period = 441;
+        for (i, &(left, right)) in output.iter().enumerate() {
+            let expected = if i % period == 0 {
+                0.5f32.powi((i / period) as i32 - 1).min(1.0)
+            } else {
+                0.0
+            };
+            assert!((left - expected).abs() < 1.0e-6);
+            assert!((right + expected).abs() < 1.0e-6);
+        }
+    }
+}
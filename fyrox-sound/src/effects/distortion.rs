@@ -0,0 +1,239 @@
+//! Waveshaping distortion module.
+//!
+//! # Overview
+//!
+//! Distortion amplifies the input signal and then passes it through a non-linear shaping function, which limits
+//! the amplitude of the signal and adds new harmonics to it. See [`DistortionShape`] docs for available shaping
+//! functions.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::{
+//!     context::SoundContext,
+//!     effects::{
+//!         distortion::{Distortion, DistortionShape},
+//!         Effect,
+//!     },
+//! };
+//!
+//! fn add_distortion(context: &SoundContext) {
+//!     let mut distortion = Distortion::new();
+//!     distortion.set_shape(DistortionShape::HardClip);
+//!     distortion.set_drive(10.0);
+//!     context
+//!         .state()
+//!         .bus_graph_mut()
+//!         .primary_bus_mut()
+//!         .add_effect(Effect::Distortion(distortion));
+//! }
+//! ```
+
+use crate::effects::EffectRenderTrait;
+use fyrox_core::{reflect::prelude::*, uuid_provider, visitor::prelude::*};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Shaping function of the distortion. Each function maps amplified input signal into `[-1.0..1.0]` range.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Reflect,
+    Visit,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum DistortionShape {
+    /// Smooth saturation using hyperbolic tangent, gives "warm" overdrive-like sound.
+    #[default]
+    SoftClip = 0,
+
+    /// Cuts every sample outside of `[-1.0..1.0]` range, gives harsh fuzz-like sound.
+    HardClip = 1,
+
+    /// Reflects every sample outside of `[-1.0..1.0]` range back into the range, gives metallic sound with lots
+    /// of high harmonics.
+    Foldback = 2,
+}
+
+uuid_provider!(DistortionShape = "0d8a6a28-1e38-4b0d-9a0e-5d3c3d0c8b5e");
+
+impl DistortionShape {
+    fn apply(self, sample: f32) -> f32 {
+        match self {
+            DistortionShape::SoftClip => sample.tanh(),
+            DistortionShape::HardClip => sample.clamp(-1.0, 1.0),
+            DistortionShape::Foldback => {
+                let folded = (sample + 1.0).rem_euclid(4.0);
+                if folded > 2.0 {
+                    3.0 - folded
+                } else {
+                    folded - 1.0
+                }
+            }
+        }
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct Distortion {
+    #[reflect(description = "Shaping function of the distortion.")]
+    shape: DistortionShape,
+
+    #[reflect(
+        description = "Amplification of the input signal before shaping. The higher the value, the more \
+        distorted the sound will be.",
+        setter = "set_drive",
+        min_value = 0.0
+    )]
+    drive: f32,
+
+    #[reflect(
+        description = "Amount of the distorted signal in the output, zero means no distortion at all.",
+        setter = "set_mix",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    mix: f32,
+
+    #[reflect(
+        description = "Gain of the output signal.",
+        setter = "set_output_gain",
+        min_value = 0.0
+    )]
+    output_gain: f32,
+}
+
+impl Default for Distortion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Distortion {
+    /// Creates new soft clipping distortion with drive of 4.0 and 0.5 output gain.
+    pub fn new() -> Self {
+        Self {
+            shape: DistortionShape::SoftClip,
+            drive: 4.0,
+            mix: 1.0,
+            output_gain: 0.5,
+        }
+    }
+
+    /// Sets new shaping function of the distortion.
+    pub fn set_shape(&mut self, shape: DistortionShape) {
+        self.shape = shape;
+    }
+
+    /// Returns current shaping function.
+    pub fn shape(&self) -> DistortionShape {
+        self.shape
+    }
+
+    /// Sets amplification of the input signal before shaping.
+    pub fn set_drive(&mut self, drive: f32) {
+        self.drive = drive.max(0.0);
+    }
+
+    /// Returns current drive.
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    /// Sets amount of the distorted signal in the output. It is clamped to `[0.0..1.0]` range.
+    pub fn set_mix(&mut self, mix: f32) {
+        self.mix = mix.clamp(0.0, 1.0);
+    }
+
+    /// Returns current mix.
+    pub fn mix(&self) -> f32 {
+        self.mix
+    }
+
+    /// Sets gain of the output signal.
+    pub fn set_output_gain(&mut self, output_gain: f32) {
+        self.output_gain = output_gain.max(0.0);
+    }
+
+    /// Returns current output gain.
+    pub fn output_gain(&self) -> f32 {
+        self.output_gain
+    }
+}
+
+impl EffectRenderTrait for Distortion {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let process = |sample: f32| {
+            let distorted = self.shape.apply(sample * self.drive);
+            (sample + (distorted - sample) * self.mix) * self.output_gain
+        };
+
+        for (&(input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            *output_left = process(input_left);
+            *output_right = process(input_right);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{
+        distortion::{Distortion, DistortionShape},
+        test::{peak, process, sine},
+    };
+
+    fn distortion(shape: DistortionShape) -> Distortion {
+        let mut distortion = Distortion::new();
+        distortion.set_shape(shape);
+        distortion.set_drive(10.0);
+        distortion.set_output_gain(1.0);
+        distortion
+    }
+
+    #[test]
+    fn test_clipping() {
+        let input = sine(440.0, 1.0, 4096);
+
+        let output = process(&mut distortion(DistortionShape::HardClip), &input);
+        assert!((peak(&output) - 1.0).abs() < 1.0e-6);
+        // Most of the samples of the heavily amplified sine are clipped.
+        let clipped = output.iter().filter(|(l, _)| l.abs() == 1.0).count();
+        assert!(clipped > output.len() / 2);
+
+        let output = process(&mut distortion(DistortionShape::SoftClip), &input);
+        assert!(peak(&output) <= 1.0);
+        assert!(peak(&output) > 0.99);
+        // Shaping is symmetric, so the distorted sine must be symmetric too.
+        for (a, b) in input.iter().zip(output.iter()) {
+            assert_eq!(a.0.signum(), b.0.signum());
+        }
+    }
+
+    #[test]
+    fn test_foldback() {
+        let output = process(
+            &mut distortion(DistortionShape::Foldback),
+            &[(0.05, -0.05), (0.15, -0.15), (0.25, -0.25)],
+        );
+        let expected = [0.5, 0.5, -0.5];
+        for ((left, right), expected) in output.iter().zip(expected) {
+            assert!((left - expected).abs() < 1.0e-5);
+            assert!((right + expected).abs() < 1.0e-5);
+        }
+    }
+
+    #[test]
+    fn test_dry_mix() {
+        let input = sine(440.0, 1.0, 1024);
+        let mut distortion = distortion(DistortionShape::HardClip);
+        distortion.set_mix(0.0);
+        assert_eq!(process(&mut distortion, &input), input);
+    }
+}
@@ -0,0 +1,312 @@
+//! Parametric equalizer module.
+//!
+//! # Overview
+//!
+//! Equalizer boosts or reduces amplitude of specific frequency ranges of a signal. It consists of a set of bands,
+//! each band is a second order filter with its own kind, frequency, gain and quality. Bands are applied one after
+//! another.
+//!
+//! # Usage
+//!
+//! ```
+//! use fyrox_sound::{
+//!     context::SoundContext,
+//!     effects::{
+//!         equalizer::{Equalizer, EqualizerBand, EqualizerBandKind},
+//!         Effect,
+//!     },
+//! };
+//!
+//! fn add_equalizer(context: &SoundContext) {
+//!     let equalizer = Equalizer::new(vec![
+//!         EqualizerBand::new(EqualizerBandKind::LowShelf, 120.0, 4.0, 0.7),
+//!         EqualizerBand::new(EqualizerBandKind::Peaking, 2500.0, -3.0, 1.5),
+//!     ]);
+//!     context
+//!         .state()
+//!         .bus_graph_mut()
+//!         .primary_bus_mut()
+//!         .add_effect(Effect::Equalizer(equalizer));
+//! }
+//! ```
+
+use crate::{
+    context::SAMPLE_RATE,
+    dsp::filters::{Biquad, BiquadKind},
+    effects::EffectRenderTrait,
+};
+use fyrox_core::{reflect::prelude::*, uuid_provider, visitor::prelude::*};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Kind of an equalizer band.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Eq,
+    PartialEq,
+    Reflect,
+    Visit,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum EqualizerBandKind {
+    /// Changes amplitude of every frequency below the band frequency.
+    LowShelf = 0,
+
+    /// Changes amplitude of frequencies around the band frequency.
+    #[default]
+    Peaking = 1,
+
+    /// Changes amplitude of every frequency above the band frequency.
+    HighShelf = 2,
+}
+
+uuid_provider!(EqualizerBandKind = "6b0f4e0e-8f5b-4f51-9a3c-3f6f0b5d2e71");
+
+/// A single band of [`Equalizer`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct EqualizerBand {
+    #[reflect(description = "Kind of the band.", setter = "set_kind")]
+    kind: EqualizerBandKind,
+
+    #[reflect(
+        description = "Center frequency (for peaking bands) or corner frequency (for shelf bands) in Hertz.",
+        setter = "set_frequency_hz",
+        min_value = 0.0
+    )]
+    frequency_hz: f32,
+
+    #[reflect(
+        description = "Gain of the band in decibels. Positive values boost frequencies, negative - reduce.",
+        setter = "set_gain_db"
+    )]
+    gain_db: f32,
+
+    #[reflect(
+        description = "Band width, the higher the value the narrower the band.",
+        setter = "set_quality",
+        min_value = 0.01
+    )]
+    quality: f32,
+
+    #[reflect(hidden)]
+    left: Biquad,
+    #[reflect(hidden)]
+    right: Biquad,
+}
+
+impl Visit for EqualizerBand {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.kind.visit("Kind", &mut region)?;
+        self.frequency_hz.visit("FrequencyHz", &mut region)?;
+        self.gain_db.visit("GainDb", &mut region)?;
+        self.quality.visit("Quality", &mut region)?;
+
+        if region.is_reading() {
+            self.update();
+        }
+
+        Ok(())
+    }
+}
+
+uuid_provider!(EqualizerBand = "d1c7a0a4-3b0e-4c4e-8f6c-6d3f3b7e0a19");
+
+impl Default for EqualizerBand {
+    fn default() -> Self {
+        Self::new(EqualizerBandKind::Peaking, 1000.0, 0.0, 1.0)
+    }
+}
+
+impl EqualizerBand {
+    /// Creates new band with the given parameters. See respective setters docs for more info.
+    pub fn new(kind: EqualizerBandKind, frequency_hz: f32, gain_db: f32, quality: f32) -> Self {
+        let mut band = Self {
+            kind,
+            frequency_hz,
+            gain_db,
+            quality: quality.max(0.01),
+            left: Default::default(),
+            right: Default::default(),
+        };
+        band.update();
+        band
+    }
+
+    /// Sets new kind of the band.
+    pub fn set_kind(&mut self, kind: EqualizerBandKind) {
+        self.kind = kind;
+        self.update();
+    }
+
+    /// Returns current kind of the band.
+    pub fn kind(&self) -> EqualizerBandKind {
+        self.kind
+    }
+
+    /// Sets center frequency (for peaking bands) or corner frequency (for shelf bands) in Hertz.
+    pub fn set_frequency_hz(&mut self, frequency_hz: f32) {
+        self.frequency_hz = frequency_hz;
+        self.update();
+    }
+
+    /// Returns current frequency of the band in Hertz.
+    pub fn frequency_hz(&self) -> f32 {
+        self.frequency_hz
+    }
+
+    /// Sets gain of the band in decibels. Positive values boost frequencies, negative - reduce.
+    pub fn set_gain_db(&mut self, gain_db: f32) {
+        self.gain_db = gain_db;
+        self.update();
+    }
+
+    /// Returns current gain of the band in decibels.
+    pub fn gain_db(&self) -> f32 {
+        self.gain_db
+    }
+
+    /// Quality defines a width of the band, the lower it will be, the wider band will be and vice versa. See more
+    /// info [here](https://ccrma.stanford.edu/~jos/filters/Quality_Factor_Q.html)
+    pub fn set_quality(&mut self, quality: f32) {
+        self.quality = quality.max(0.01);
+        self.update();
+    }
+
+    /// Returns current quality of the band.
+    pub fn quality(&self) -> f32 {
+        self.quality
+    }
+
+    fn update(&mut self) {
+        let kind = || match self.kind {
+            EqualizerBandKind::LowShelf => BiquadKind::LowShelf,
+            EqualizerBandKind::Peaking => BiquadKind::Peaking,
+            EqualizerBandKind::HighShelf => BiquadKind::HighShelf,
+        };
+        // Biquad filters use square root of the linear gain as the gain parameter.
+        let gain = 10.0f32.powf(self.gain_db / 40.0);
+        let fc = self.frequency_hz / SAMPLE_RATE as f32;
+
+        self.left.tune(kind(), fc, gain, self.quality);
+        self.right.tune(kind(), fc, gain, self.quality);
+    }
+}
+
+/// See module docs.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct Equalizer {
+    #[reflect(description = "A set of bands, that are applied one after another.")]
+    bands: Vec<EqualizerBand>,
+}
+
+impl Default for Equalizer {
+    fn default() -> Self {
+        Self::new(vec![
+            EqualizerBand::new(EqualizerBandKind::LowShelf, 100.0, 0.0, 0.7),
+            EqualizerBand::new(EqualizerBandKind::Peaking, 500.0, 0.0, 1.0),
+            EqualizerBand::new(EqualizerBandKind::Peaking, 2000.0, 0.0, 1.0),
+            EqualizerBand::new(EqualizerBandKind::HighShelf, 8000.0, 0.0, 0.7),
+        ])
+    }
+}
+
+impl Equalizer {
+    /// Creates new equalizer with the given set of bands.
+    pub fn new(bands: Vec<EqualizerBand>) -> Self {
+        Self { bands }
+    }
+
+    /// Adds new band to the equalizer.
+    pub fn add_band(&mut self, band: EqualizerBand) {
+        self.bands.push(band);
+    }
+
+    /// Removes a band at the given index.
+    pub fn remove_band(&mut self, index: usize) -> EqualizerBand {
+        self.bands.remove(index)
+    }
+
+    /// Returns a reference to the bands of the equalizer.
+    pub fn bands(&self) -> &[EqualizerBand] {
+        &self.bands
+    }
+
+    /// Returns a reference to the bands of the equalizer.
+    pub fn bands_mut(&mut self) -> &mut [EqualizerBand] {
+        &mut self.bands
+    }
+}
+
+impl EffectRenderTrait for Equalizer {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        for (&(input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            let mut left = input_left;
+            let mut right = input_right;
+            for band in self.bands.iter_mut() {
+                left = band.left.feed(left);
+                right = band.right.feed(right);
+            }
+            *output_left = left;
+            *output_right = right;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        dsp::db_to_linear,
+        effects::{
+            equalizer::{Equalizer, EqualizerBand, EqualizerBandKind},
+            test::{peak, process, sine},
+        },
+    };
+
+    fn level(equalizer: &mut Equalizer, frequency: f32) -> f32 {
+        let output = process(equalizer, &sine(frequency, 0.25, 44100));
+        // Skip transient response.
+        peak(&output[22050..]) / 0.25
+    }
+
+    #[test]
+    fn test_flat_equalizer() {
+        let mut equalizer = Equalizer::default();
+        for frequency in [50.0, 440.0, 5000.0, 12000.0] {
+            assert!((level(&mut equalizer, frequency) - 1.0).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_peaking_band() {
+        let mut equalizer = Equalizer::new(vec![EqualizerBand::new(
+            EqualizerBandKind::Peaking,
+            1000.0,
+            6.0,
+            2.0,
+        )]);
+        assert!((level(&mut equalizer, 1000.0) - db_to_linear(6.0)).abs() < 0.02);
+        // Frequencies far from the band are not affected.
+        assert!((level(&mut equalizer, 10000.0) - 1.0).abs() < 0.05);
+
+        equalizer.bands_mut()[0].set_gain_db(-12.0);
+        assert!((level(&mut equalizer, 1000.0) - db_to_linear(-12.0)).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_shelf_bands() {
+        let mut equalizer = Equalizer::new(vec![
+            EqualizerBand::new(EqualizerBandKind::LowShelf, 200.0, -12.0, 0.7),
+            EqualizerBand::new(EqualizerBandKind::HighShelf, 4000.0, 6.0, 0.7),
+        ]);
+        assert!((level(&mut equalizer, 40.0) - db_to_linear(-12.0)).abs() < 0.03);
+        assert!((level(&mut equalizer, 15000.0) - db_to_linear(6.0)).abs() < 0.1);
+        assert!((level(&mut equalizer, 1000.0) - 1.0).abs() < 0.15);
+    }
+}
@@ -1,15 +1,25 @@
 //! Contins everything related to audio effects that can be applied to an audio bus.
 
-use crate::{
-    effects::filter::{
+use crate::effects::{
+    chorus::Chorus,
+    compressor::Compressor,
+    delay::Delay,
+    distortion::Distortion,
+    equalizer::Equalizer,
+    filter::{
         AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect, HighShelfFilterEffect,
         LowPassFilterEffect, LowShelfFilterEffect,
     },
-    effects::reverb::Reverb,
+    reverb::Reverb,
 };
 use fyrox_core::{reflect::prelude::*, uuid_provider, visitor::prelude::*};
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub mod chorus;
+pub mod compressor;
+pub mod delay;
+pub mod distortion;
+pub mod equalizer;
 pub mod filter;
 pub mod reverb;
 
@@ -66,6 +76,16 @@ pub enum Effect {
     LowShelfFilter(LowShelfFilterEffect),
     /// See [`HighShelfFilterEffect`] docs for more info.
     HighShelfFilter(HighShelfFilterEffect),
+    /// See [`Compressor`] docs for more info.
+    Compressor(Compressor),
+    /// See [`Delay`] docs for more info.
+    Delay(Delay),
+    /// See [`Chorus`] docs for more info.
+    Chorus(Chorus),
+    /// See [`Distortion`] docs for more info.
+    Distortion(Distortion),
+    /// See [`Equalizer`] docs for more info.
+    Equalizer(Equalizer),
 }
 
 uuid_provider!(Effect = "fc52e441-d1ec-4881-937c-9e2e53a6d621");
@@ -91,6 +111,11 @@ macro_rules! static_dispatch {
             Effect::AllPassFilter(v) => v.$func($($args),*),
             Effect::LowShelfFilter(v) => v.$func($($args),*),
             Effect::HighShelfFilter(v) => v.$func($($args),*),
+            Effect::Compressor(v) => v.$func($($args),*),
+            Effect::Delay(v) => v.$func($($args),*),
+            Effect::Chorus(v) => v.$func($($args),*),
+            Effect::Distortion(v) => v.$func($($args),*),
+            Effect::Equalizer(v) => v.$func($($args),*),
         }
     };
 }
@@ -100,3 +125,35 @@ impl EffectRenderTrait for Effect {
         static_dispatch!(self, render, input, output)
     }
 }
+
+#[cfg(test)]
+pub(crate) mod test {
+    use crate::{context::SAMPLE_RATE, effects::EffectRenderTrait};
+
+    /// Generates a stereo sine wave with the given frequency (in Hertz) and amplitude.
+    pub fn sine(frequency: f32, amplitude: f32, len: usize) -> Vec<(f32, f32)> {
+        (0..len)
+            .map(|i| {
+                let sample = amplitude
+                    * (i as f32 * frequency * std::f32::consts::TAU / SAMPLE_RATE as f32).sin();
+                (sample, sample)
+            })
+            .collect()
+    }
+
+    /// Returns the maximum absolute value of the samples of both channels.
+    pub fn peak(samples: &[(f32, f32)]) -> f32 {
+        samples.iter().fold(0.0f32, |peak, (left, right)| {
+            peak.max(left.abs()).max(right.abs())
+        })
+    }
+
+    /// Passes the given signal through the effect, using the same block size as the sound context.
+    pub fn process<E: EffectRenderTrait>(effect: &mut E, input: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        let mut output = vec![(0.0, 0.0); input.len()];
+        for (input, output) in input.chunks(512).zip(output.chunks_mut(512)) {
+            effect.render(input, output);
+        }
+        output
+    }
+}
@@ -4,7 +4,10 @@
 //!
 //! Sound engine manages contexts, feeds output device with data.
 
-use crate::context::{SoundContext, SAMPLE_RATE};
+use crate::{
+    context::{SoundContext, SAMPLE_RATE},
+    offline::{self, OfflineRenderOptions, RenderedSound},
+};
 use fyrox_core::visitor::{Visit, VisitResult, Visitor};
 use std::error::Error;
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -136,6 +139,19 @@ impl State {
         self.render_inner(buf);
     }
 
+    /// Renders every context of the engine for the given duration (faster than real-time) and mixes them
+    /// together. This method must be used if and only if the engine was created via
+    /// [`SoundEngine::without_device`], otherwise the output device will render the same contexts at the same
+    /// time. See [`crate::offline`] module docs for more info.
+    ///
+    /// ## Deadlocks
+    ///
+    /// This method internally locks added sound contexts so it must be called when all the contexts
+    /// are unlocked or you'll get a deadlock.
+    pub fn render_offline(&mut self, options: &OfflineRenderOptions) -> RenderedSound {
+        offline::render(&self.contexts, options)
+    }
+
     fn render_inner(&mut self, buf: &mut [(f32, f32)]) {
         for context in self.contexts.iter_mut() {
             context.state().render(buf);
@@ -53,6 +53,9 @@ pub enum SoundError {
 
     /// A buffer is not loaded yet, consider to `await` it before use.
     BufferIsNotLoaded,
+
+    /// WAV encoder specific error, can occur when writing rendered sound to a file.
+    WavEncoderError(hound::Error),
 }
 
 impl From<std::io::Error> for SoundError {
@@ -67,6 +70,15 @@ impl<'a, T> From<std::sync::PoisonError<std::sync::MutexGuard<'a, T>>> for Sound
     }
 }
 
+impl From<hound::Error> for SoundError {
+    fn from(e: hound::Error) -> Self {
+        match e {
+            hound::Error::IoError(io) => SoundError::Io(io),
+            _ => SoundError::WavEncoderError(e),
+        }
+    }
+}
+
 impl From<lewton::VorbisError> for SoundError {
     fn from(ve: VorbisError) -> Self {
         SoundError::DecoderError(DecoderError::Ogg(ve))
@@ -93,6 +105,7 @@ impl Display for SoundError {
             SoundError::DecoderError(de) => write!(f, "internal decoder error: {:?}", de),
             SoundError::BufferFailedToLoad => write!(f, "a buffer failed to load"),
             SoundError::BufferIsNotLoaded => write!(f, "a buffer is not loaded yet"),
+            SoundError::WavEncoderError(e) => write!(f, "wav encoder error: {}", e),
         }
     }
 }
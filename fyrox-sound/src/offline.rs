@@ -0,0 +1,231 @@
+//! Offline rendering module.
+//!
+//! # Overview
+//!
+//! Offline rendering allows you to render sound contexts faster than real-time without any audio output device.
+//! Rendered samples can be used directly or written to a WAV file. It is useful for automated tests (comparison
+//! of rendered sound with a reference file) or to bake complex sound scenes into a single sound.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use fyrox_sound::{
+//!     context::SoundContext,
+//!     offline::OfflineRenderOptions,
+//! };
+//! use std::time::Duration;
+//!
+//! fn bake(context: &SoundContext) {
+//!     let rendered = context.render_offline(&OfflineRenderOptions {
+//!         duration: Duration::from_secs(10),
+//!         deterministic: true,
+//!     });
+//!
+//!     rendered.save_wav("ambient.wav").unwrap();
+//! }
+//! ```
+
+use crate::{
+    buffer::SoundBufferResource,
+    context::{self, SoundContext},
+    error::SoundError,
+    futures::executor::block_on,
+    renderer::{hrtf::HrirSphereResource, Renderer},
+};
+use std::{
+    fs::File,
+    io::{BufWriter, Seek, Write},
+    path::Path,
+    time::Duration,
+};
+
+/// A set of options for offline rendering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OfflineRenderOptions {
+    /// Duration of the rendered sound.
+    pub duration: Duration,
+
+    /// If set, the renderer will wait until every resource (sound buffers, HRIR spheres) used by the rendered
+    /// contexts is fully loaded. This way the result does not depend on how fast the resources are loaded, so
+    /// multiple renders of the same sound context will produce exactly the same samples. Keep in mind, that a
+    /// resource that is never loaded will block rendering forever.
+    pub deterministic: bool,
+}
+
+impl Default for OfflineRenderOptions {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(1),
+            deterministic: true,
+        }
+    }
+}
+
+/// Result of offline rendering - a set of interleaved stereo samples at [`context::SAMPLE_RATE`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderedSound {
+    samples: Vec<(f32, f32)>,
+}
+
+impl RenderedSound {
+    /// Returns sample rate of the sound.
+    pub fn sample_rate(&self) -> u32 {
+        context::SAMPLE_RATE
+    }
+
+    /// Returns rendered samples, where each pair is a sample of left and right channel.
+    pub fn samples(&self) -> &[(f32, f32)] {
+        &self.samples
+    }
+
+    /// Returns rendered samples, where each pair is a sample of left and right channel.
+    pub fn into_samples(self) -> Vec<(f32, f32)> {
+        self.samples
+    }
+
+    /// Returns duration of the sound.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.samples.len() as f64 / self.sample_rate() as f64)
+    }
+
+    /// Writes the sound in WAV format (stereo, 32-bit float samples) to the given writer.
+    pub fn write_wav<W: Write + Seek>(&self, writer: W) -> Result<(), SoundError> {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: self.sample_rate(),
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+
+        let mut wav_writer = hound::WavWriter::new(writer, spec)?;
+        for &(left, right) in self.samples.iter() {
+            wav_writer.write_sample(left)?;
+            wav_writer.write_sample(right)?;
+        }
+        wav_writer.finalize()?;
+
+        Ok(())
+    }
+
+    /// Saves the sound in WAV format (stereo, 32-bit float samples) to the given path.
+    pub fn save_wav<P: AsRef<Path>>(&self, path: P) -> Result<(), SoundError> {
+        self.write_wav(BufWriter::new(File::create(path)?))
+    }
+}
+
+fn wait_for_resources(contexts: &[SoundContext]) {
+    let mut buffers = Vec::<SoundBufferResource>::new();
+    let mut hrir_spheres = Vec::<HrirSphereResource>::new();
+
+    for context in contexts {
+        let state = context.state();
+        buffers.extend(state.sources().iter().filter_map(|s| s.buffer()));
+        if let Renderer::HrtfRenderer(hrtf) = state.renderer() {
+            hrir_spheres.extend(hrtf.hrir_sphere_resource());
+        }
+    }
+
+    // Contexts must be unlocked at this moment, waiting while holding a lock could cause deadlocks. Loading
+    // errors are ignored, such resources will be just silent (the same as in real-time rendering).
+    for buffer in buffers {
+        let _ = block_on(buffer);
+    }
+    for hrir_sphere in hrir_spheres {
+        let _ = block_on(hrir_sphere);
+    }
+}
+
+/// Renders the given contexts (sound of all of them is mixed) using the given options.
+pub(crate) fn render(contexts: &[SoundContext], options: &OfflineRenderOptions) -> RenderedSound {
+    if options.deterministic {
+        wait_for_resources(contexts);
+    }
+
+    let total = (options.duration.as_secs_f64() * context::SAMPLE_RATE as f64).round() as usize;
+
+    let mut samples = Vec::with_capacity(total);
+    // Always render by blocks of the same size as in real-time rendering, some renderers (HRTF) can work
+    // only with blocks of specific size.
+    let mut block = vec![(0.0, 0.0); SoundContext::SAMPLES_PER_CHANNEL];
+    while samples.len() < total {
+        block.fill((0.0, 0.0));
+        for context in contexts {
+            context.state().render(&mut block);
+        }
+        let count = (total - samples.len()).min(block.len());
+        samples.extend_from_slice(&block[..count]);
+    }
+
+    RenderedSound { samples }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource, SoundBufferResourceExtension},
+        context::{SoundContext, SAMPLE_RATE},
+        effects::{Attenuate, Effect},
+        offline::OfflineRenderOptions,
+        source::{SoundSourceBuilder, Status},
+    };
+    use std::{io::Cursor, time::Duration};
+
+    fn make_context() -> SoundContext {
+        let samples = (0..SAMPLE_RATE)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / SAMPLE_RATE as f32).sin())
+            .collect::<Vec<_>>();
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples,
+        })
+        .unwrap();
+
+        let context = SoundContext::new();
+        let source = SoundSourceBuilder::new()
+            .with_buffer(buffer)
+            .with_spatial_blend_factor(0.0)
+            .with_looping(true)
+            .with_status(Status::Playing)
+            .build()
+            .unwrap();
+        context.state().add_source(source);
+        context
+    }
+
+    #[test]
+    fn test_offline_rendering() {
+        let options = OfflineRenderOptions {
+            duration: Duration::from_millis(250),
+            deterministic: true,
+        };
+
+        let first = make_context().render_offline(&options);
+        assert_eq!(first.samples().len(), SAMPLE_RATE as usize / 4);
+        assert!(first.samples().iter().any(|(l, r)| *l != 0.0 && *r != 0.0));
+
+        // The same context must produce exactly the same sound.
+        let second = make_context().render_offline(&options);
+        assert_eq!(first, second);
+
+        // Effects of audio buses must be applied.
+        let context = make_context();
+        context
+            .state()
+            .bus_graph_mut()
+            .primary_bus_mut()
+            .add_effect(Effect::Attenuate(Attenuate::new(0.5)));
+        let attenuated = context.render_offline(&options);
+        for ((l, r), (al, ar)) in first.samples().iter().zip(attenuated.samples()) {
+            assert!((l * 0.5 - al).abs() < 1.0e-6);
+            assert!((r * 0.5 - ar).abs() < 1.0e-6);
+        }
+
+        let mut wav = Cursor::new(Vec::new());
+        first.write_wav(&mut wav).unwrap();
+        wav.set_position(0);
+        let reader = hound::WavReader::new(wav).unwrap();
+        assert_eq!(reader.spec().channels, 2);
+        assert_eq!(reader.duration() as usize, first.samples().len());
+    }
+}
@@ -151,7 +151,9 @@ impl HrtfRenderer {
         // Then add HRTF part with k = spatial_blend
         let new_distance_gain = source.gain()
             * source.spatial_blend()
-            * source.calculate_distance_gain(listener, distance_model);
+            * source.calculate_distance_gain(listener, distance_model)
+            * source.calculate_cone_gain(listener)
+            * source.calculate_occlusion_gain();
         let new_sampling_vector = source.calculate_sampling_vector(listener);
 
         if let Some(processor) = self.processor.as_mut() {
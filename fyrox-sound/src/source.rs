@@ -31,12 +31,14 @@
 use crate::{
     buffer::{streaming::StreamingBuffer, SoundBuffer, SoundBufferResource},
     bus::AudioBusGraph,
-    context::DistanceModel,
+    context::{DistanceModel, SAMPLE_RATE},
+    dsp::filters::OnePole,
     error::SoundError,
     listener::Listener,
 };
 use fyrox_core::{
     algebra::Vector3,
+    math::lerpf,
     reflect::prelude::*,
     uuid_provider,
     visitor::{Visit, VisitResult, Visitor},
@@ -125,6 +127,36 @@ pub struct SoundSource {
     max_distance: f32,
     #[reflect(min_value = 0.0, step = 0.05)]
     rolloff_factor: f32,
+    #[visit(optional)]
+    velocity: Vector3<f32>,
+    #[visit(optional)]
+    direction: Vector3<f32>,
+    #[visit(optional)]
+    #[reflect(min_value = 0.0, step = 0.05)]
+    cone_inner_angle: f32,
+    #[visit(optional)]
+    #[reflect(min_value = 0.0, step = 0.05)]
+    cone_outer_angle: f32,
+    #[visit(optional)]
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    cone_outer_gain: f32,
+    #[visit(optional)]
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    occlusion: f32,
+    #[visit(optional)]
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    occlusion_gain: f32,
+    #[visit(optional)]
+    #[reflect(min_value = 0.0, step = 10.0)]
+    occlusion_cutoff_frequency_hz: f32,
+    // Low-pass filters that are used to muffle occluded source.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_filters: (OnePole, OnePole),
+    // Pitch multiplier caused by Doppler effect, it is calculated by the context right before rendering.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) doppler_pitch: f64,
     // Some data that needed for iterative overlap-save convolution.
     #[reflect(hidden)]
     #[visit(skip)]
@@ -164,6 +196,16 @@ impl Default for SoundSource {
             position: Vector3::new(0.0, 0.0, 0.0),
             max_distance: f32::MAX,
             rolloff_factor: 1.0,
+            velocity: Vector3::new(0.0, 0.0, 0.0),
+            direction: Vector3::new(0.0, 0.0, 0.0),
+            cone_inner_angle: std::f32::consts::TAU,
+            cone_outer_angle: std::f32::consts::TAU,
+            cone_outer_gain: 0.0,
+            occlusion: 0.0,
+            occlusion_gain: SoundSource::DEFAULT_OCCLUSION_GAIN,
+            occlusion_cutoff_frequency_hz: SoundSource::DEFAULT_OCCLUSION_CUTOFF_FREQUENCY_HZ,
+            occlusion_filters: Default::default(),
+            doppler_pitch: 1.0,
             prev_left_samples: Default::default(),
             prev_right_samples: Default::default(),
             prev_sampling_vector: Vector3::new(0.0, 0.0, 1.0),
@@ -173,6 +215,15 @@ impl Default for SoundSource {
 }
 
 impl SoundSource {
+    /// Default gain multiplier of fully occluded source.
+    pub const DEFAULT_OCCLUSION_GAIN: f32 = 0.25;
+
+    /// Default cutoff frequency (in Hertz) of the low-pass filter of fully occluded source.
+    pub const DEFAULT_OCCLUSION_CUTOFF_FREQUENCY_HZ: f32 = 800.0;
+
+    // Cutoff frequency of the low-pass filter when there's no occlusion at all.
+    const UNOCCLUDED_CUTOFF_FREQUENCY_HZ: f32 = 20000.0;
+
     /// Sets new name of the sound source.
     pub fn set_name<N: AsRef<str>>(&mut self, name: N) {
         self.name = name.as_ref().to_owned();
@@ -395,6 +446,106 @@ impl SoundSource {
         self.max_distance
     }
 
+    /// Sets velocity of source in world space (in units per second). Velocity is used only to calculate
+    /// Doppler effect, it does not change position of the source. See [`crate::context::State::set_doppler_factor`]
+    /// for more info.
+    pub fn set_velocity(&mut self, velocity: Vector3<f32>) -> &mut Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Returns velocity of source.
+    pub fn velocity(&self) -> Vector3<f32> {
+        self.velocity
+    }
+
+    /// Sets direction of source in world space. Direction is used together with sound cone to make the source
+    /// directional (such as loudspeaker). Zero vector makes the source omnidirectional, which is default.
+    pub fn set_direction(&mut self, direction: Vector3<f32>) -> &mut Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Returns direction of source.
+    pub fn direction(&self) -> Vector3<f32> {
+        self.direction
+    }
+
+    /// Sets angle (in radians) of the inner cone of the source. The source is heard at full volume inside
+    /// of the inner cone. The angle is measured between opposite sides of the cone, so 2*PI (which is
+    /// default) means that there's no attenuation at all. Has no effect if direction of the source is zero.
+    pub fn set_cone_inner_angle(&mut self, angle: f32) -> &mut Self {
+        self.cone_inner_angle = angle.clamp(0.0, std::f32::consts::TAU);
+        self
+    }
+
+    /// Returns angle (in radians) of the inner cone of the source.
+    pub fn cone_inner_angle(&self) -> f32 {
+        self.cone_inner_angle
+    }
+
+    /// Sets angle (in radians) of the outer cone of the source. The source is heard at [`Self::cone_outer_gain`]
+    /// outside of the outer cone, volume is interpolated between the inner and the outer cone. Has no effect if
+    /// direction of the source is zero.
+    pub fn set_cone_outer_angle(&mut self, angle: f32) -> &mut Self {
+        self.cone_outer_angle = angle.clamp(0.0, std::f32::consts::TAU);
+        self
+    }
+
+    /// Returns angle (in radians) of the outer cone of the source.
+    pub fn cone_outer_angle(&self) -> f32 {
+        self.cone_outer_angle
+    }
+
+    /// Sets gain multiplier, that is applied to the source when listener is outside of the outer cone.
+    pub fn set_cone_outer_gain(&mut self, gain: f32) -> &mut Self {
+        self.cone_outer_gain = gain.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns gain multiplier, that is applied to the source when listener is outside of the outer cone.
+    pub fn cone_outer_gain(&self) -> f32 {
+        self.cone_outer_gain
+    }
+
+    /// Sets occlusion factor of the source in `[0.0..1.0]` range. It defines how much of the direct path from the
+    /// source to the listener is blocked by obstacles. Occluded source is attenuated (see [`Self::set_occlusion_gain`])
+    /// and muffled (see [`Self::set_occlusion_cutoff_frequency_hz`]). The sound library does not know anything
+    /// about obstacles, so the factor must be calculated by the user (for example, using ray casts).
+    pub fn set_occlusion(&mut self, occlusion: f32) -> &mut Self {
+        self.occlusion = occlusion.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns occlusion factor of the source.
+    pub fn occlusion(&self) -> f32 {
+        self.occlusion
+    }
+
+    /// Sets gain multiplier, that is applied to fully occluded source. Partially occluded source uses
+    /// interpolated value between 1.0 and the gain.
+    pub fn set_occlusion_gain(&mut self, gain: f32) -> &mut Self {
+        self.occlusion_gain = gain.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Returns gain multiplier, that is applied to fully occluded source.
+    pub fn occlusion_gain(&self) -> f32 {
+        self.occlusion_gain
+    }
+
+    /// Sets cutoff frequency (in Hertz) of the low-pass filter, that is applied to fully occluded source. Partially
+    /// occluded source uses higher cutoff frequency.
+    pub fn set_occlusion_cutoff_frequency_hz(&mut self, frequency: f32) -> &mut Self {
+        self.occlusion_cutoff_frequency_hz = frequency.max(0.0);
+        self
+    }
+
+    /// Returns cutoff frequency (in Hertz) of the low-pass filter, that is applied to fully occluded source.
+    pub fn occlusion_cutoff_frequency_hz(&self) -> f32 {
+        self.occlusion_cutoff_frequency_hz
+    }
+
     /// Sets new name of the target audio bus. The name must be valid, otherwise the sound won't play!
     /// Default is [`AudioBusGraph::PRIMARY_BUS`].
     pub fn set_bus<S: AsRef<str>>(&mut self, bus: S) {
@@ -430,6 +581,87 @@ impl SoundSource {
         }
     }
 
+    // Sound cones were taken from OpenAL Specification as well.
+    pub(crate) fn calculate_cone_gain(&self, listener: &Listener) -> f32 {
+        let (Some(direction), Some(to_listener)) = (
+            self.direction.try_normalize(f32::EPSILON),
+            (listener.position() - self.position).try_normalize(f32::EPSILON),
+        ) else {
+            return 1.0;
+        };
+
+        let angle = direction.dot(&to_listener).clamp(-1.0, 1.0).acos();
+        let inner = self.cone_inner_angle * 0.5;
+        let outer = (self.cone_outer_angle * 0.5).max(inner);
+        if angle <= inner {
+            1.0
+        } else if angle >= outer {
+            self.cone_outer_gain
+        } else {
+            let t = (angle - inner) / (outer - inner);
+            1.0 + (self.cone_outer_gain - 1.0) * t
+        }
+    }
+
+    pub(crate) fn calculate_occlusion_gain(&self) -> f32 {
+        lerpf(1.0, self.occlusion_gain, self.occlusion)
+    }
+
+    fn apply_occlusion_filter(&mut self) {
+        // Occlusion affects only spatial part of the sound, the same as any other spatial parameter.
+        let occlusion = self.occlusion * self.spatial_blend;
+        if occlusion <= 0.0 {
+            // Reset the state of the filters, so they won't produce clicks when the occlusion will be enabled.
+            self.occlusion_filters = Default::default();
+            return;
+        }
+
+        // Interpolate in logarithmic scale, because this is how we perceive frequencies.
+        let min = self.occlusion_cutoff_frequency_hz.max(1.0).ln();
+        let max = Self::UNOCCLUDED_CUTOFF_FREQUENCY_HZ.ln();
+        let fc = lerpf(max, min, occlusion).exp() / SAMPLE_RATE as f32;
+
+        let (left_filter, right_filter) = &mut self.occlusion_filters;
+        left_filter.set_fc(fc);
+        right_filter.set_fc(fc);
+        for (left, right) in self.frame_samples.iter_mut() {
+            *left = left_filter.feed(*left);
+            *right = right_filter.feed(*right);
+        }
+    }
+
+    // Doppler effect formula is taken from OpenAL Specification.
+    pub(crate) fn calculate_doppler_pitch(
+        &self,
+        listener: &Listener,
+        doppler_factor: f32,
+        speed_of_sound: f32,
+    ) -> f64 {
+        if doppler_factor <= 0.0 || speed_of_sound <= 0.0 {
+            return 1.0;
+        }
+
+        let Some(source_to_listener) =
+            (listener.position() - self.position).try_normalize(f32::EPSILON)
+        else {
+            return 1.0;
+        };
+
+        // Velocities can't exceed speed of sound, otherwise the source will be "inaudible".
+        let limit = speed_of_sound / doppler_factor;
+        let listener_speed = source_to_listener.dot(&listener.velocity()).min(limit);
+        let source_speed = source_to_listener.dot(&self.velocity).min(limit);
+
+        let shift = (speed_of_sound - doppler_factor * listener_speed)
+            / (speed_of_sound - doppler_factor * source_speed);
+
+        if shift.is_finite() {
+            shift.max(0.0) as f64
+        } else {
+            1.0
+        }
+    }
+
     pub(crate) fn calculate_panning(&self, listener: &Listener) -> f32 {
         (listener.position() - self.position)
             .try_normalize(f32::EPSILON)
@@ -504,6 +736,8 @@ impl SoundSource {
         }
         // Fill the remaining part of frame_samples.
         self.frame_samples.resize(amount, (0.0, 0.0));
+
+        self.apply_occlusion_filter();
     }
 
     fn render_playing(&mut self, buffer: &mut SoundBuffer, amount: usize) {
@@ -543,7 +777,7 @@ impl SoundSource {
     // Renders until the end of the block or until amount samples is written and returns
     // the number of written samples.
     fn render_until_block_end(&mut self, buffer: &mut SoundBuffer, mut amount: usize) -> usize {
-        let step = self.pitch * self.resampling_multiplier;
+        let step = self.pitch * self.doppler_pitch * self.resampling_multiplier;
         if step == 1.0 {
             if self.buf_read_pos < 0.0 {
                 // This can theoretically happen if we change pitch on the fly.
@@ -715,6 +949,13 @@ pub struct SoundSourceBuilder {
     rolloff_factor: f32,
     spatial_blend: f32,
     bus: String,
+    velocity: Vector3<f32>,
+    direction: Vector3<f32>,
+    cone_inner_angle: f32,
+    cone_outer_angle: f32,
+    cone_outer_gain: f32,
+    occlusion_gain: f32,
+    occlusion_cutoff_frequency_hz: f32,
 }
 
 impl Default for SoundSourceBuilder {
@@ -742,6 +983,13 @@ impl SoundSourceBuilder {
             rolloff_factor: 1.0,
             spatial_blend: 1.0,
             bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            velocity: Vector3::new(0.0, 0.0, 0.0),
+            direction: Vector3::new(0.0, 0.0, 0.0),
+            cone_inner_angle: std::f32::consts::TAU,
+            cone_outer_angle: std::f32::consts::TAU,
+            cone_outer_gain: 0.0,
+            occlusion_gain: SoundSource::DEFAULT_OCCLUSION_GAIN,
+            occlusion_cutoff_frequency_hz: SoundSource::DEFAULT_OCCLUSION_CUTOFF_FREQUENCY_HZ,
         }
     }
 
@@ -835,6 +1083,48 @@ impl SoundSourceBuilder {
         self
     }
 
+    /// See [`SoundSource::set_velocity`]
+    pub fn with_velocity(mut self, velocity: Vector3<f32>) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// See [`SoundSource::set_direction`]
+    pub fn with_direction(mut self, direction: Vector3<f32>) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// See [`SoundSource::set_cone_inner_angle`]
+    pub fn with_cone_inner_angle(mut self, angle: f32) -> Self {
+        self.cone_inner_angle = angle.clamp(0.0, std::f32::consts::TAU);
+        self
+    }
+
+    /// See [`SoundSource::set_cone_outer_angle`]
+    pub fn with_cone_outer_angle(mut self, angle: f32) -> Self {
+        self.cone_outer_angle = angle.clamp(0.0, std::f32::consts::TAU);
+        self
+    }
+
+    /// See [`SoundSource::set_cone_outer_gain`]
+    pub fn with_cone_outer_gain(mut self, gain: f32) -> Self {
+        self.cone_outer_gain = gain.clamp(0.0, 1.0);
+        self
+    }
+
+    /// See [`SoundSource::set_occlusion_gain`]
+    pub fn with_occlusion_gain(mut self, gain: f32) -> Self {
+        self.occlusion_gain = gain.clamp(0.0, 1.0);
+        self
+    }
+
+    /// See [`SoundSource::set_occlusion_cutoff_frequency_hz`]
+    pub fn with_occlusion_cutoff_frequency_hz(mut self, frequency: f32) -> Self {
+        self.occlusion_cutoff_frequency_hz = frequency.max(0.0);
+        self
+    }
+
     /// Sets desired output bus for the sound source.
     pub fn with_bus<S: AsRef<str>>(mut self, bus: S) -> Self {
         self.bus = bus.as_ref().to_string();
@@ -858,6 +1148,14 @@ impl SoundSourceBuilder {
             max_distance: self.max_distance,
             rolloff_factor: self.rolloff_factor,
             spatial_blend: self.spatial_blend,
+            velocity: self.velocity,
+            direction: self.direction,
+            cone_inner_angle: self.cone_inner_angle,
+            cone_outer_angle: self.cone_outer_angle,
+            cone_outer_gain: self.cone_outer_gain,
+            occlusion_gain: self.occlusion_gain,
+            occlusion_cutoff_frequency_hz: self.occlusion_cutoff_frequency_hz,
+            occlusion_filters: Default::default(),
             prev_left_samples: Default::default(),
             prev_right_samples: Default::default(),
             bus: self.bus,
@@ -870,3 +1168,113 @@ impl SoundSourceBuilder {
         Ok(source)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{algebra::Vector3, context::State, listener::Listener, source::SoundSourceBuilder};
+
+    #[test]
+    fn test_doppler_pitch() {
+        let listener = Listener::new();
+        let speed_of_sound = State::DEFAULT_SPEED_OF_SOUND;
+
+        let mut source = SoundSourceBuilder::new()
+            .with_position(Vector3::new(0.0, 0.0, 10.0))
+            .build()
+            .unwrap();
+
+        // Static source has no pitch shift.
+        assert_eq!(
+            source.calculate_doppler_pitch(&listener, 1.0, speed_of_sound),
+            1.0
+        );
+
+        // Approaching source has higher pitch.
+        source.set_velocity(Vector3::new(0.0, 0.0, -20.0));
+        let approaching = source.calculate_doppler_pitch(&listener, 1.0, speed_of_sound);
+        assert!(approaching > 1.0);
+        assert!((approaching - (speed_of_sound / (speed_of_sound - 20.0)) as f64).abs() < 1.0e-5);
+
+        // Receding source has lower pitch.
+        source.set_velocity(Vector3::new(0.0, 0.0, 20.0));
+        assert!(source.calculate_doppler_pitch(&listener, 1.0, speed_of_sound) < 1.0);
+
+        // Movement perpendicular to the line of sight does not change pitch.
+        source.set_velocity(Vector3::new(20.0, 0.0, 0.0));
+        assert_eq!(
+            source.calculate_doppler_pitch(&listener, 1.0, speed_of_sound),
+            1.0
+        );
+
+        // Zero Doppler factor disables the effect.
+        source.set_velocity(Vector3::new(0.0, 0.0, -20.0));
+        assert_eq!(
+            source.calculate_doppler_pitch(&listener, 0.0, speed_of_sound),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_cone_gain() {
+        let listener = Listener::new();
+
+        let mut source = SoundSourceBuilder::new()
+            .with_position(Vector3::new(0.0, 0.0, 10.0))
+            .with_cone_inner_angle(90.0f32.to_radians())
+            .with_cone_outer_angle(180.0f32.to_radians())
+            .with_cone_outer_gain(0.25)
+            .build()
+            .unwrap();
+
+        // Omnidirectional source.
+        assert_eq!(source.calculate_cone_gain(&listener), 1.0);
+
+        // Listener is inside of the inner cone.
+        source.set_direction(Vector3::new(0.0, 0.0, -1.0));
+        assert_eq!(source.calculate_cone_gain(&listener), 1.0);
+
+        // Listener is behind the source.
+        source.set_direction(Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!(source.calculate_cone_gain(&listener), 0.25);
+
+        // Listener is between the inner and the outer cone (67.5 degrees off-axis).
+        source.set_direction(Vector3::new(
+            67.5f32.to_radians().sin(),
+            0.0,
+            -67.5f32.to_radians().cos(),
+        ));
+        assert!((source.calculate_cone_gain(&listener) - 0.625).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn test_occlusion() {
+        let mut source = SoundSourceBuilder::new()
+            .with_occlusion_gain(0.2)
+            .with_occlusion_cutoff_frequency_hz(500.0)
+            .build()
+            .unwrap();
+
+        // Signal at Nyquist frequency, it must be almost completely removed by the occlusion filter.
+        let signal = (0..512)
+            .map(|i| if i % 2 == 0 { (1.0, 1.0) } else { (-1.0, -1.0) })
+            .collect::<Vec<_>>();
+
+        // Not occluded source is not changed at all.
+        assert_eq!(source.calculate_occlusion_gain(), 1.0);
+        source.frame_samples = signal.clone();
+        source.apply_occlusion_filter();
+        assert_eq!(source.frame_samples, signal);
+
+        source.set_occlusion(0.5);
+        assert!((source.calculate_occlusion_gain() - 0.6).abs() < 1.0e-6);
+
+        source.set_occlusion(1.0);
+        assert!((source.calculate_occlusion_gain() - 0.2).abs() < 1.0e-6);
+        source.frame_samples = signal;
+        source.apply_occlusion_filter();
+        let peak = source.frame_samples[256..]
+            .iter()
+            .fold(0.0f32, |peak, (left, _)| peak.max(left.abs()));
+        assert!(peak < 0.05);
+    }
+}
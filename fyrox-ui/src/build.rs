@@ -2,8 +2,8 @@
 //! more info.
 
 use crate::{
-    core::pool::Handle, font::FontResource, message::UiMessage, RestrictionEntry, UiNode,
-    UserInterface,
+    core::pool::Handle, font::FontResource, localization::Localization, message::UiMessage,
+    RestrictionEntry, UiNode, UserInterface,
 };
 use std::{
     ops::{Index, IndexMut},
@@ -98,6 +98,11 @@ impl<'a> BuildContext<'a> {
         self.ui.default_font.clone()
     }
 
+    /// Returns localization of the UI. It could be used to resolve localized strings.
+    pub fn localization(&self) -> &Localization {
+        &self.ui.localization
+    }
+
     /// Returns current message sender of the UI, that is used for message passing mechanism. You can
     /// send messages for your widgets inside your builders, however this has limited use and should
     /// be avoided in the favor of explicit state modification to not overload message pipeline.
@@ -9,6 +9,7 @@ use crate::{
     decorator::DecoratorBuilder,
     define_constructor,
     font::FontResource,
+    localization::LocalizedString,
     message::{MessageDirection, UiMessage},
     text::TextBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -140,6 +141,13 @@ impl Control for Button {
                         ui.release_mouse_capture();
                         message.set_handled(true);
                     }
+                    WidgetMessage::Activate if message.destination() == self.handle() => {
+                        ui.send_message(ButtonMessage::click(
+                            self.handle(),
+                            MessageDirection::FromWidget,
+                        ));
+                        message.set_handled(true);
+                    }
                     _ => (),
                 }
             }
@@ -180,6 +188,14 @@ pub enum ButtonContent {
         /// Optional font of the button. If [`None`], the default font will be used.
         font: Option<FontResource>,
     },
+    /// A shortcut to create a [crate::text::Text] widget with localized text as the button content. The text
+    /// will be updated automatically when the active locale changes.
+    LocalizedText {
+        /// Localized text of the button.
+        text: LocalizedString,
+        /// Optional font of the button. If [`None`], the default font will be used.
+        font: Option<FontResource>,
+    },
     /// Arbitrary widget handle. It could be any widget handle, for example a handle of [`crate::image::Image`]
     /// widget.
     Node(Handle<UiNode>),
@@ -202,6 +218,11 @@ impl ButtonContent {
         }
     }
 
+    /// Creates [`ButtonContent::LocalizedText`] with default font.
+    pub fn localized_text(text: LocalizedString) -> Self {
+        Self::LocalizedText { text, font: None }
+    }
+
     /// Creates [`ButtonContent::Node`].
     pub fn node(node: Handle<UiNode>) -> Self {
         Self::Node(node)
@@ -215,6 +236,12 @@ impl ButtonContent {
                 .with_vertical_text_alignment(VerticalAlignment::Center)
                 .with_font(font.clone().unwrap_or_else(|| ctx.default_font()))
                 .build(ctx),
+            Self::LocalizedText { text, font } => TextBuilder::new(WidgetBuilder::new())
+                .with_localized_text(text.clone())
+                .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_font(font.clone().unwrap_or_else(|| ctx.default_font()))
+                .build(ctx),
             Self::Node(node) => *node,
         }
     }
@@ -249,6 +276,12 @@ impl ButtonBuilder {
         self
     }
 
+    /// Sets the content of the button to be [`ButtonContent::LocalizedText`] (localized text with the default font).
+    pub fn with_localized_text(mut self, text: LocalizedString) -> Self {
+        self.content = Some(ButtonContent::localized_text(text));
+        self
+    }
+
     /// Sets the content of the button to be [`ButtonContent::Node`] (arbitrary widget handle).
     pub fn with_content(mut self, node: Handle<UiNode>) -> Self {
         self.content = Some(ButtonContent::Node(node));
@@ -287,7 +320,11 @@ impl ButtonBuilder {
         }
 
         UiNode::new(Button {
-            widget: self.widget_builder.with_child(back).build(),
+            widget: self
+                .widget_builder
+                .with_default_tab_stop(true)
+                .with_child(back)
+                .build(),
             decorator: back,
             content,
         })
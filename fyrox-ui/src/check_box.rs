@@ -148,6 +148,26 @@ crate::define_widget_deref!(CheckBox);
 
 uuid_provider!(CheckBox = "3a866ba8-7682-4ce7-954a-46360f5837dc");
 
+impl CheckBox {
+    fn toggle(&self, ui: &UserInterface) {
+        if let Some(value) = self.checked {
+            // Invert state if it is defined.
+            ui.send_message(CheckBoxMessage::checked(
+                self.handle(),
+                MessageDirection::ToWidget,
+                Some(!value),
+            ));
+        } else {
+            // Switch from undefined state to checked.
+            ui.send_message(CheckBoxMessage::checked(
+                self.handle(),
+                MessageDirection::ToWidget,
+                Some(true),
+            ));
+        }
+    }
+}
+
 impl Control for CheckBox {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -182,24 +202,13 @@ impl Control for CheckBox {
                             || self.widget.has_descendant(message.destination(), ui))
                     {
                         ui.release_mouse_capture();
-
-                        if let Some(value) = self.checked {
-                            // Invert state if it is defined.
-                            ui.send_message(CheckBoxMessage::checked(
-                                self.handle(),
-                                MessageDirection::ToWidget,
-                                Some(!value),
-                            ));
-                        } else {
-                            // Switch from undefined state to checked.
-                            ui.send_message(CheckBoxMessage::checked(
-                                self.handle(),
-                                MessageDirection::ToWidget,
-                                Some(true),
-                            ));
-                        }
+                        self.toggle(ui);
                     }
                 }
+                WidgetMessage::Activate if message.destination() == self.handle() => {
+                    self.toggle(ui);
+                    message.set_handled(true);
+                }
                 _ => (),
             }
         } else if let Some(&CheckBoxMessage::Check(value)) = message.data::<CheckBoxMessage>() {
@@ -418,7 +427,11 @@ impl CheckBoxBuilder {
         .build(ctx);
 
         let cb = CheckBox {
-            widget: self.widget_builder.with_child(grid).build(),
+            widget: self
+                .widget_builder
+                .with_default_tab_stop(true)
+                .with_child(grid)
+                .build(),
             checked: self.checked,
             check_mark,
             uncheck_mark,
@@ -3,6 +3,7 @@ use crate::{
         algebra::Vector2, pool::Handle, reflect::Reflect, scope_profile, uuid::Uuid, visitor::Visit,
     },
     draw::DrawingContext,
+    localization::Localization,
     message::{OsEvent, UiMessage},
     widget::Widget,
     NodeHandleMapping, UiNode, UserInterface,
@@ -320,6 +321,10 @@ pub trait Control: BaseControl + Deref<Target = Widget> + DerefMut + Reflect + V
     ) {
     }
 
+    /// This method is called when the active locale of the UI (or its set of string tables) was changed. It
+    /// should be used to resolve every [`crate::localization::LocalizedString`] of the widget again.
+    fn on_locale_changed(&mut self, #[allow(unused_variables)] localization: &Localization) {}
+
     /// Performs event-specific actions. Must call widget.handle_message()!
     ///
     /// # Notes
@@ -86,14 +86,30 @@ impl Control for DropdownList {
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
-        if let Some(WidgetMessage::MouseDown { .. }) = message.data::<WidgetMessage>() {
-            if message.destination() == self.handle()
-                || self.widget.has_descendant(message.destination(), ui)
-            {
-                ui.send_message(DropdownListMessage::open(
-                    self.handle,
-                    MessageDirection::ToWidget,
-                ));
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { .. }
+                    if message.destination() == self.handle()
+                        || self.widget.has_descendant(message.destination(), ui) =>
+                {
+                    ui.send_message(DropdownListMessage::open(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                    ));
+                }
+                WidgetMessage::Activate if message.destination() == self.handle() => {
+                    ui.send_message(DropdownListMessage::open(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                    ));
+                    // Move focus to the list, so the user will be able to select an item using keyboard.
+                    ui.send_message(WidgetMessage::focus(
+                        self.list_view,
+                        MessageDirection::ToWidget,
+                    ));
+                    message.set_handled(true);
+                }
+                _ => (),
             }
         } else if let Some(msg) = message.data::<DropdownListMessage>() {
             if message.destination() == self.handle()
@@ -147,7 +163,9 @@ impl Control for DropdownList {
 
                             self.sync_selected_item_preview(ui);
 
-                            if self.close_on_selection {
+                            // Keep the list open while the user walks through the items using keyboard,
+                            // selection will be confirmed by activation.
+                            if self.close_on_selection && !ui.is_keyboard_navigation_active() {
                                 ui.send_message(PopupMessage::close(
                                     self.popup,
                                     MessageDirection::ToWidget,
@@ -178,6 +196,20 @@ impl Control for DropdownList {
                     *selection,
                 ));
             }
+        } else if let Some(WidgetMessage::Activate) = message.data::<WidgetMessage>() {
+            if message.destination() == self.list_view
+                || ui
+                    .try_get_node(self.list_view)
+                    .is_some_and(|list| list.has_descendant(message.destination(), ui))
+            {
+                // Confirm the selection and return focus back to the drop-down list.
+                ui.send_message(PopupMessage::close(self.popup, MessageDirection::ToWidget));
+                ui.send_message(WidgetMessage::focus(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                ));
+                message.set_handled(true);
+            }
         } else if let Some(msg) = message.data::<PopupMessage>() {
             if message.destination() == self.popup {
                 match msg {
@@ -320,6 +352,7 @@ impl DropdownListBuilder {
         let dropdown_list = UiNode::new(DropdownList {
             widget: self
                 .widget_builder
+                .with_default_tab_stop(true)
                 .with_preview_messages(true)
                 .with_child(
                     BorderBuilder::new(
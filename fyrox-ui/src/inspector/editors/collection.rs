@@ -18,6 +18,7 @@ use crate::{
     },
     message::{MessageDirection, UiMessage},
     stack_panel::StackPanelBuilder,
+    utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
     VerticalAlignment,
@@ -35,6 +36,8 @@ use std::{
 #[derive(Clone, Debug, PartialEq, Default, Visit, Reflect)]
 pub struct Item {
     editor_instance: PropertyEditorInstance,
+    move_up: Handle<UiNode>,
+    move_down: Handle<UiNode>,
     remove: Handle<UiNode>,
 }
 
@@ -122,16 +125,29 @@ impl<T: CollectionItem> Control for CollectionEditor<T> {
         self.widget.handle_routed_message(ui, message);
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
-            if let Some(index) = self
-                .items
-                .iter()
-                .position(|i| i.remove == message.destination())
-            {
-                ui.send_message(CollectionChanged::remove(
-                    self.handle,
-                    MessageDirection::FromWidget,
-                    index,
-                ));
+            for (index, item) in self.items.iter().enumerate() {
+                let msg = if item.remove == message.destination() {
+                    CollectionChanged::remove(self.handle, MessageDirection::FromWidget, index)
+                } else if item.move_up == message.destination() && index > 0 {
+                    CollectionChanged::move_item(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        index,
+                        index - 1,
+                    )
+                } else if item.move_down == message.destination() && index + 1 < self.items.len() {
+                    CollectionChanged::move_item(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        index,
+                        index + 1,
+                    )
+                } else {
+                    continue;
+                };
+
+                ui.send_message(msg);
+                break;
             }
         } else if let Some(msg) = message.data::<CollectionEditorMessage>() {
             if message.destination == self.handle {
@@ -211,11 +227,15 @@ fn create_item_views(items: &[Item], ctx: &mut BuildContext) -> Vec<Handle<UiNod
                         PropertyEditorInstance::Simple { editor } => editor,
                         PropertyEditorInstance::Custom { container, .. } => container,
                     })
+                    .with_child(item.move_up)
+                    .with_child(item.move_down)
                     .with_child(item.remove),
             )
             .add_row(Row::stretch())
             .add_column(Column::stretch())
             .add_column(Column::auto())
+            .add_column(Column::auto())
+            .add_column(Column::auto())
             .build(ctx)
         })
         .collect::<Vec<_>>()
@@ -287,21 +307,31 @@ where
                 ctx[editor].set_margin(make_property_margin(layer_index + 1));
             }
 
-            let remove = ButtonBuilder::new(
+            let make_button = |column: usize| {
                 WidgetBuilder::new()
                     .with_visibility(!immutable_collection)
                     .with_margin(Thickness::uniform(1.0))
                     .with_vertical_alignment(VerticalAlignment::Top)
                     .with_horizontal_alignment(HorizontalAlignment::Right)
-                    .on_column(1)
+                    .on_column(column)
                     .with_width(16.0)
-                    .with_height(16.0),
-            )
-            .with_text("-")
-            .build(ctx);
+                    .with_height(16.0)
+            };
+
+            let move_up = ButtonBuilder::new(make_button(1))
+                .with_content(make_arrow(ctx, ArrowDirection::Top, 6.0))
+                .build(ctx);
+
+            let move_down = ButtonBuilder::new(make_button(2))
+                .with_content(make_arrow(ctx, ArrowDirection::Bottom, 6.0))
+                .build(ctx);
+
+            let remove = ButtonBuilder::new(make_button(3)).with_text("-").build(ctx);
 
             items.push(Item {
                 editor_instance: editor,
+                move_up,
+                move_down,
                 remove,
             });
         } else {
@@ -0,0 +1,67 @@
+use crate::{
+    inspector::{
+        editors::{
+            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            PropertyEditorMessageContext, PropertyEditorTranslationContext,
+        },
+        FieldKind, InspectorError, PropertyChanged,
+    },
+    localization::{
+        editor::{LocalizedStringEditorBuilder, LocalizedStringEditorMessage},
+        LocalizedString,
+    },
+    message::{MessageDirection, UiMessage},
+    widget::WidgetBuilder,
+    Thickness, VerticalAlignment,
+};
+use std::any::TypeId;
+
+#[derive(Debug)]
+pub struct LocalizedStringPropertyEditorDefinition;
+
+impl PropertyEditorDefinition for LocalizedStringPropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<LocalizedString>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = ctx.property_info.cast_value::<LocalizedString>()?;
+        Ok(PropertyEditorInstance::Simple {
+            editor: LocalizedStringEditorBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_vertical_alignment(VerticalAlignment::Center),
+            )
+            .with_value(value.clone())
+            .build(ctx.build_context),
+        })
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = ctx.property_info.cast_value::<LocalizedString>()?;
+        Ok(Some(LocalizedStringEditorMessage::value(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            value.clone(),
+        )))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            if let Some(LocalizedStringEditorMessage::Value(value)) = ctx.message.data() {
+                return Some(PropertyChanged {
+                    name: ctx.name.to_string(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::object(value.clone()),
+                });
+            }
+        }
+        None
+    }
+}
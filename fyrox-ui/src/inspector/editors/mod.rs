@@ -33,6 +33,7 @@ use crate::{
             inherit::InheritablePropertyEditorDefinition,
             inspectable::InspectablePropertyEditorDefinition,
             key::KeyBindingPropertyEditorDefinition,
+            localized_string::LocalizedStringPropertyEditorDefinition,
             numeric::NumericPropertyEditorDefinition,
             quat::QuatPropertyEditorDefinition,
             range::RangePropertyEditorDefinition,
@@ -51,6 +52,7 @@ use crate::{
     key::KeyBinding,
     key::{HotKeyEditor, KeyBindingEditor},
     list_view::{ListView, ListViewItem},
+    localization::{editor::LocalizedStringEditor, LocalizedString},
     menu::{Menu, MenuItem},
     message::CursorIcon,
     message::UiMessage,
@@ -94,6 +96,7 @@ pub mod enumeration;
 pub mod inherit;
 pub mod inspectable;
 pub mod key;
+pub mod localized_string;
 pub mod numeric;
 pub mod path;
 pub mod quat;
@@ -320,6 +323,11 @@ impl PropertyEditorDefinitionContainer {
         container.insert(KeyBindingPropertyEditorDefinition);
         container.insert(InheritablePropertyEditorDefinition::<KeyBinding>::new());
 
+        // LocalizedString + InheritableVariable<LocalizedString> + Option<LocalizedString>
+        container.insert(LocalizedStringPropertyEditorDefinition);
+        container.insert(InheritablePropertyEditorDefinition::<LocalizedString>::new());
+        container.insert(EnumPropertyEditorDefinition::<LocalizedString>::new_optional());
+
         // Curve
         container.insert(CurvePropertyEditorDefinition);
         container.insert(InheritablePropertyEditorDefinition::<Curve>::new());
@@ -364,6 +372,7 @@ impl PropertyEditorDefinitionContainer {
             HotKeyEditor,
             KeyBindingEditor,
             ListViewItem,
+            LocalizedStringEditor,
             ListView,
             Menu,
             MenuItem,
@@ -46,6 +46,13 @@ pub enum CollectionChanged {
     Add(ObjectValue),
     /// An item in the collection should be removed.
     Remove(usize),
+    /// An item in the collection should be moved to a new position.
+    Move {
+        /// Current index of the item.
+        from: usize,
+        /// New index of the item.
+        to: usize,
+    },
     /// An item in the collection has changed one of its properties.
     ItemChanged {
         /// Index of an item in the collection.
@@ -57,6 +64,7 @@ pub enum CollectionChanged {
 impl CollectionChanged {
     define_constructor!(CollectionChanged:Add => fn add(ObjectValue), layout: false);
     define_constructor!(CollectionChanged:Remove => fn remove(usize), layout: false);
+    define_constructor!(CollectionChanged:Move => fn move_item(from: usize, to: usize), layout: false);
     define_constructor!(CollectionChanged:ItemChanged => fn item_changed(index: usize, property: FieldKind), layout: false);
 }
 
@@ -91,6 +99,13 @@ pub enum PropertyAction {
         /// Index of an item.
         index: usize,
     },
+    /// An item of a collection property needs to be moved to a new position.
+    MoveItem {
+        /// Current index of the item.
+        from: usize,
+        /// New index of the item.
+        to: usize,
+    },
     /// Revert value to parent.
     Revert,
 }
@@ -109,6 +124,7 @@ impl PropertyAction {
                     value: value.clone().into_box_reflect(),
                 },
                 CollectionChanged::Remove(index) => Self::RemoveItem { index },
+                CollectionChanged::Move { from, to } => Self::MoveItem { from, to },
                 CollectionChanged::ItemChanged { ref property, .. } => {
                     Self::from_field_kind(property)
                 }
@@ -184,6 +200,23 @@ impl PropertyAction {
                     result_callback(Err(Self::RemoveItem { index }))
                 }
             }),
+            PropertyAction::MoveItem { from, to } => target.resolve_path_mut(path, &mut |result| {
+                if let Ok(field) = result {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            if move_list_item(list, from, to) {
+                                result_callback(Ok(None))
+                            } else {
+                                result_callback(Err(Self::MoveItem { from, to }))
+                            }
+                        } else {
+                            result_callback(Err(Self::MoveItem { from, to }))
+                        }
+                    })
+                } else {
+                    result_callback(Err(Self::MoveItem { from, to }))
+                }
+            }),
             PropertyAction::Revert => {
                 // Unsupported due to lack of context (a reference to parent entity).
                 result_callback(Err(Self::Revert))
@@ -192,6 +225,19 @@ impl PropertyAction {
     }
 }
 
+/// Moves an item of the list from `from` position to `to` position, the items in between are shifted. Returns
+/// `false` if any of the indices is out of bounds.
+pub fn move_list_item(list: &mut dyn ReflectList, from: usize, to: usize) -> bool {
+    if from >= list.reflect_len() || to >= list.reflect_len() {
+        return false;
+    }
+
+    match list.reflect_remove(from) {
+        Some(item) => list.reflect_insert(to, item).is_ok(),
+        None => false,
+    }
+}
+
 pub trait Value: Reflect + Debug + Send {
     fn clone_box(&self) -> Box<dyn Value>;
 
@@ -316,6 +362,7 @@ impl PropertyChanged {
             FieldKind::Collection(ref collection_changed) => match **collection_changed {
                 CollectionChanged::Add(_) => false,
                 CollectionChanged::Remove(_) => false,
+                CollectionChanged::Move { .. } => false,
                 CollectionChanged::ItemChanged { ref property, .. } => match property {
                     FieldKind::Inspectable(inspectable) => inspectable.is_inheritable(),
                     FieldKind::Inheritable(_) => true,
@@ -205,9 +205,11 @@ pub mod inspector;
 pub mod key;
 pub mod list_view;
 pub mod loader;
+pub mod localization;
 pub mod menu;
 pub mod message;
 pub mod messagebox;
+pub mod navigation;
 pub mod nine_patch;
 mod node;
 pub mod numeric;
@@ -253,11 +255,14 @@ use crate::{
     draw::{CommandTexture, Draw, DrawingContext},
     font::FontResource,
     font::BUILT_IN_FONT,
+    localization::Localization,
     message::{
-        ButtonState, CursorIcon, KeyboardModifiers, MessageDirection, MouseButton, OsEvent,
-        UiMessage,
+        ButtonState, CursorIcon, KeyCode, KeyboardModifiers, MessageDirection, MouseButton,
+        OsEvent, UiMessage,
     },
+    navigation::NavigationDirection,
     popup::{Placement, PopupMessage},
+    scroll_viewer::{ScrollViewer, ScrollViewerMessage},
     widget::{Widget, WidgetBuilder, WidgetMessage},
 };
 use copypasta::ClipboardContext;
@@ -614,6 +619,9 @@ pub struct UserInterface {
     prev_picked_node: Handle<UiNode>,
     captured_node: Handle<UiNode>,
     keyboard_focus_node: Handle<UiNode>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    keyboard_navigation: bool,
     cursor_position: Vector2<f32>,
     #[visit(skip)]
     #[reflect(hidden)]
@@ -654,6 +662,9 @@ pub struct UserInterface {
     #[reflect(hidden)]
     double_click_entries: FxHashMap<MouseButton, DoubleClickEntry>,
     pub double_click_time_slice: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    localization: Localization,
 }
 
 impl Clone for UserInterface {
@@ -677,6 +688,7 @@ impl Clone for UserInterface {
             prev_picked_node: self.prev_picked_node,
             captured_node: self.captured_node,
             keyboard_focus_node: self.keyboard_focus_node,
+            keyboard_navigation: self.keyboard_navigation,
             cursor_position: self.cursor_position,
             receiver,
             sender,
@@ -696,6 +708,7 @@ impl Clone for UserInterface {
             default_font: self.default_font.clone(),
             double_click_entries: self.double_click_entries.clone(),
             double_click_time_slice: self.double_click_time_slice,
+            localization: self.localization.clone(),
         }
     }
 }
@@ -824,6 +837,7 @@ impl UserInterface {
             picked_node: Handle::NONE,
             prev_picked_node: Handle::NONE,
             keyboard_focus_node: Handle::NONE,
+            keyboard_navigation: false,
             stack: Default::default(),
             picking_stack: Default::default(),
             bubble_queue: Default::default(),
@@ -840,6 +854,7 @@ impl UserInterface {
             default_font: BUILT_IN_FONT.clone(),
             double_click_entries: Default::default(),
             double_click_time_slice: 0.5, // 500 ms is standard in most operating systems.
+            localization: Default::default(),
         };
         ui.root_canvas = ui.add_node(UiNode::new(Canvas {
             widget: WidgetBuilder::new().build(),
@@ -1006,6 +1021,8 @@ impl UserInterface {
             entry.timer -= dt;
         }
 
+        self.update_localization();
+
         self.handle_layout_events();
 
         self.measure_node(self.root_canvas, screen_size);
@@ -1084,6 +1101,17 @@ impl UserInterface {
             }
         }
 
+        // Highlight focused widget, so the user will know where the focus is when navigating without a mouse.
+        if self.keyboard_navigation {
+            let focused = navigation::tab_stop_ancestor(self, self.keyboard_focus_node);
+            if let Some(focused) = self.nodes.try_borrow(focused) {
+                let bounds = focused.screen_bounds();
+                self.drawing_context.push_rect(&bounds, 1.0);
+                self.drawing_context
+                    .commit(bounds, BRUSH_BRIGHT_BLUE, CommandTexture::None, None);
+            }
+        }
+
         // Debug info rendered on top of other.
         if self.visual_debug {
             if self.picked_node.is_some() {
@@ -1895,6 +1923,14 @@ impl UserInterface {
                                 }
                             }
                         }
+                        // Keys that weren't handled by the focused widget could be used for navigation.
+                        &WidgetMessage::KeyDown(code)
+                            if !message.handled()
+                                && message.direction() == MessageDirection::FromWidget
+                                && message.destination() == self.keyboard_focus_node =>
+                        {
+                            self.handle_navigation_key(code);
+                        }
                         _ => {}
                     }
                 }
@@ -2007,6 +2043,116 @@ impl UserInterface {
         self.captured_node
     }
 
+    /// Returns a reference to the localization of the UI.
+    pub fn localization(&self) -> &Localization {
+        &self.localization
+    }
+
+    /// Returns a reference to the localization of the UI. Every widget with localized text will be updated
+    /// on next [`Self::update`] call if the active locale or the set of string tables is changed.
+    pub fn localization_mut(&mut self) -> &mut Localization {
+        &mut self.localization
+    }
+
+    /// Sets new localization of the UI. Every widget with localized text will be updated on next
+    /// [`Self::update`] call.
+    pub fn set_localization(&mut self, localization: Localization) {
+        self.localization = localization;
+        self.localization.invalidate();
+    }
+
+    fn update_localization(&mut self) {
+        if self.localization.take_changes() {
+            for node in self.nodes.iter_mut() {
+                node.on_locale_changed(&self.localization);
+            }
+        }
+    }
+
+    /// Returns a handle of the widget that currently has keyboard focus.
+    pub fn keyboard_focus(&self) -> Handle<UiNode> {
+        self.keyboard_focus_node
+    }
+
+    /// Returns `true` if the keyboard focus was moved by keyboard (or gamepad) navigation and wasn't
+    /// reset by the mouse since then. Focused widget is highlighted in this case.
+    pub fn is_keyboard_navigation_active(&self) -> bool {
+        self.keyboard_navigation
+    }
+
+    /// Moves keyboard focus in the given direction. `Tab`/`Shift+Tab` and arrow keys are handled automatically,
+    /// this method could be used to map any other input (for example, gamepad D-pad) to focus navigation.
+    /// Navigation is restricted to the current focus scope (a window, a popup or the root canvas). Returns `true`
+    /// if the focus was moved, `false` - otherwise.
+    pub fn navigate(&mut self, direction: NavigationDirection) -> bool {
+        let target = navigation::find_navigation_target(self, self.keyboard_focus_node, direction);
+        if target.is_none() {
+            return false;
+        }
+
+        self.keyboard_navigation = true;
+        self.request_focus(target);
+
+        // Make sure that the newly focused widget is visible in every scroll viewer it is in.
+        let mut parent = self.nodes[target].parent();
+        while let Some(node) = self.nodes.try_borrow(parent) {
+            if node.cast::<ScrollViewer>().is_some() {
+                self.send_message(ScrollViewerMessage::bring_into_view(
+                    parent,
+                    MessageDirection::ToWidget,
+                    target,
+                ));
+            }
+            parent = node.parent();
+        }
+
+        true
+    }
+
+    /// Activates currently focused widget (the closest tab stop of the focused widget, to be precise), by sending
+    /// [`WidgetMessage::Activate`] message to it. `Enter` and `Space` keys are handled automatically, this method
+    /// could be used to map any other input (for example, gamepad button) to activation. Returns `true` if there
+    /// was a widget to activate, `false` - otherwise.
+    pub fn activate_focused(&mut self) -> bool {
+        let focused = navigation::tab_stop_ancestor(self, self.keyboard_focus_node);
+        if focused.is_some() {
+            self.keyboard_navigation = true;
+            self.send_message(WidgetMessage::activate(
+                focused,
+                MessageDirection::FromWidget,
+            ));
+            true
+        } else {
+            false
+        }
+    }
+
+    fn handle_navigation_key(&mut self, code: KeyCode) {
+        let direction = match code {
+            KeyCode::Tab if self.keyboard_modifiers.shift => NavigationDirection::Previous,
+            KeyCode::Tab => NavigationDirection::Next,
+            KeyCode::ArrowUp => NavigationDirection::Up,
+            KeyCode::ArrowDown => NavigationDirection::Down,
+            KeyCode::ArrowLeft => NavigationDirection::Left,
+            KeyCode::ArrowRight => NavigationDirection::Right,
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space => {
+                self.activate_focused();
+                return;
+            }
+            _ => return,
+        };
+
+        // Arrow keys are often used for something else (camera movement, etc.) when nothing is
+        // focused, so spatial navigation could only be started from a focusable widget.
+        let is_tab = matches!(
+            direction,
+            NavigationDirection::Next | NavigationDirection::Previous
+        );
+        if is_tab || navigation::tab_stop_ancestor(self, self.keyboard_focus_node).is_some() {
+            self.navigate(direction);
+        }
+    }
+
     // Tries to set new picked node (a node under the cursor) and returns `true` if the node was
     // changed.
     fn try_set_picked_node(&mut self, node: Handle<UiNode>) -> bool {
@@ -2111,6 +2257,7 @@ impl UserInterface {
                             self.drag_context.click_pos = self.cursor_position;
                         }
 
+                        self.keyboard_navigation = false;
                         self.request_focus(self.picked_node);
 
                         if self.picked_node.is_some() {
@@ -3095,8 +3242,13 @@ mod test {
     use crate::message::{ButtonState, KeyCode};
     use crate::{
         border::BorderBuilder,
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
         core::algebra::{Rotation2, UnitComplex, Vector2},
-        message::MessageDirection,
+        grid::{Column, GridBuilder, Row},
+        message::{KeyboardModifiers, MessageDirection, UiMessage},
+        navigation::NavigationDirection,
+        text::TextBuilder,
         text_box::TextBoxBuilder,
         transform_size,
         widget::{WidgetBuilder, WidgetMessage},
@@ -3187,4 +3339,111 @@ mod test {
 
         assert!(ui.poll_message().is_none());
     }
+
+    fn press_key(ui: &mut UserInterface, button: KeyCode) -> Vec<UiMessage> {
+        ui.process_os_event(&OsEvent::KeyboardInput {
+            button,
+            state: ButtonState::Pressed,
+            text: Default::default(),
+        });
+        let mut messages = Vec::new();
+        while let Some(message) = ui.poll_message() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    #[test]
+    fn test_focus_navigation() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let ctx = &mut ui.build_ctx();
+        let mut make_button = |row, column, tab_index| {
+            ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .on_row(row)
+                    .on_column(column)
+                    .with_tab_index(tab_index),
+            )
+            .build(ctx)
+        };
+        let top_left = make_button(0, 0, None);
+        let top_right = make_button(0, 1, None);
+        let bottom_left = make_button(1, 0, None);
+        let bottom_right = make_button(1, 1, Some(0));
+        let check_box = CheckBoxBuilder::new(WidgetBuilder::new().on_row(2))
+            .checked(Some(false))
+            .build(ctx);
+        // Non-interactive widgets must be skipped.
+        let text = TextBuilder::new(WidgetBuilder::new().on_row(2).on_column(1)).build(ctx);
+        GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(top_left)
+                .with_child(top_right)
+                .with_child(bottom_left)
+                .with_child(bottom_right)
+                .with_child(check_box)
+                .with_child(text),
+        )
+        .add_rows(vec![Row::strict(50.0); 3])
+        .add_columns(vec![Column::strict(100.0); 2])
+        .build(ctx);
+
+        ui.update(screen_size, 0.0);
+        while ui.poll_message().is_some() {}
+
+        assert!(!ui.node(text).tab_stop());
+        assert!(ui.node(check_box).tab_stop());
+
+        // Widgets with explicit tab index goes first, then every other in tree order.
+        for expected in [bottom_right, top_left, top_right, bottom_left, check_box] {
+            press_key(&mut ui, KeyCode::Tab);
+            assert_eq!(ui.keyboard_focus(), expected);
+        }
+        assert!(ui.is_keyboard_navigation_active());
+
+        // Tab order wraps around.
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus(), bottom_right);
+
+        ui.process_os_event(&OsEvent::KeyboardModifiers(KeyboardModifiers {
+            shift: true,
+            ..Default::default()
+        }));
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus(), check_box);
+        ui.process_os_event(&OsEvent::KeyboardModifiers(Default::default()));
+
+        // Spatial navigation.
+        press_key(&mut ui, KeyCode::ArrowUp);
+        assert_eq!(ui.keyboard_focus(), bottom_left);
+        press_key(&mut ui, KeyCode::ArrowRight);
+        assert_eq!(ui.keyboard_focus(), bottom_right);
+        press_key(&mut ui, KeyCode::ArrowUp);
+        assert_eq!(ui.keyboard_focus(), top_right);
+        press_key(&mut ui, KeyCode::ArrowLeft);
+        assert_eq!(ui.keyboard_focus(), top_left);
+        // Nothing above, focus must stay.
+        press_key(&mut ui, KeyCode::ArrowUp);
+        assert_eq!(ui.keyboard_focus(), top_left);
+
+        // Activation.
+        let messages = press_key(&mut ui, KeyCode::Enter);
+        assert!(messages.contains(&ButtonMessage::click(
+            top_left,
+            MessageDirection::FromWidget
+        )));
+
+        ui.navigate(NavigationDirection::Previous);
+        assert_eq!(ui.keyboard_focus(), bottom_right);
+        ui.navigate(NavigationDirection::Previous);
+        assert_eq!(ui.keyboard_focus(), check_box);
+        let messages = press_key(&mut ui, KeyCode::Space);
+        assert!(messages.contains(&CheckBoxMessage::checked(
+            check_box,
+            MessageDirection::FromWidget,
+            Some(true)
+        )));
+    }
 }
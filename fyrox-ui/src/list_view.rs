@@ -11,7 +11,7 @@ use crate::{
     decorator::{Decorator, DecoratorMessage},
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
-    message::{MessageDirection, UiMessage},
+    message::{KeyCode, MessageDirection, UiMessage},
     scroll_viewer::{ScrollViewer, ScrollViewerBuilder, ScrollViewerMessage},
     stack_panel::StackPanelBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -447,6 +447,37 @@ impl Control for ListView {
                     }
                 }
             }
+        } else if let Some(&WidgetMessage::KeyDown(code)) = message.data::<WidgetMessage>() {
+            if !message.handled()
+                && message.direction() == MessageDirection::FromWidget
+                && (message.destination() == self.handle()
+                    || self.has_descendant(message.destination(), ui))
+            {
+                // Keys at the edges of the list are left unhandled, so keyboard navigation could
+                // move focus out of the list.
+                let new_selection = match (code, self.selected_index) {
+                    (KeyCode::ArrowDown, None) if !self.items.is_empty() => Some(0),
+                    (KeyCode::ArrowDown, Some(index)) if index + 1 < self.items.len() => {
+                        Some(index + 1)
+                    }
+                    (KeyCode::ArrowUp, Some(index)) if index > 0 => Some(index - 1),
+                    _ => None,
+                };
+
+                if let Some(new_selection) = new_selection {
+                    ui.send_message(ListViewMessage::selection(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        Some(new_selection),
+                    ));
+                    ui.send_message(ListViewMessage::bring_item_into_view(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        self.items[new_selection],
+                    ));
+                    message.set_handled(true);
+                }
+            }
         }
     }
 }
@@ -521,7 +552,11 @@ impl ListViewBuilder {
         ctx.link(scroll_viewer, back);
 
         let list_box = ListView {
-            widget: self.widget_builder.with_child(back).build(),
+            widget: self
+                .widget_builder
+                .with_default_tab_stop(true)
+                .with_child(back)
+                .build(),
             selected_index: None,
             item_containers,
             items: self.items,
@@ -0,0 +1,198 @@
+//! Localized string editor is used to edit a key of a [`LocalizedString`] and to show its text in the active locale.
+//! See [`LocalizedStringEditor`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{color::Color, pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    define_constructor, define_widget_deref,
+    grid::{Column, GridBuilder, Row},
+    localization::LocalizedString,
+    message::{MessageDirection, UiMessage},
+    text::{TextBuilder, TextMessage},
+    text_box::TextBoxBuilder,
+    widget::{Widget, WidgetBuilder},
+    BuildContext, Control, Thickness, UiNode, UserInterface, VerticalAlignment,
+};
+use fyrox_core::uuid_provider;
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+};
+
+/// A set of messages that is used to fetch or modify values of [`LocalizedStringEditor`] widgets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalizedStringEditorMessage {
+    /// Fetches or modifies a value of a [`LocalizedStringEditor`] widget.
+    Value(LocalizedString),
+}
+
+impl LocalizedStringEditorMessage {
+    define_constructor!(
+        /// Creates [`LocalizedStringEditorMessage::Value`] message.
+        LocalizedStringEditorMessage:Value => fn value(LocalizedString), layout: false
+    );
+}
+
+/// Localized string editor is used to edit a key of a [`LocalizedString`]. It also shows a preview of the text for
+/// the key in the active locale, the preview is updated automatically when the locale changes. It is widely used in
+/// [`crate::inspector::Inspector`] to show and edit localized strings.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     localization::{editor::LocalizedStringEditorBuilder, LocalizedString},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_localized_string_editor(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     LocalizedStringEditorBuilder::new(WidgetBuilder::new())
+///         .with_value(LocalizedString::new("menu.play"))
+///         .build(ctx)
+/// }
+/// ```
+#[derive(Default, Clone, Visit, Reflect, Debug)]
+pub struct LocalizedStringEditor {
+    widget: Widget,
+    value: LocalizedString,
+    key: Handle<UiNode>,
+    preview: Handle<UiNode>,
+}
+
+define_widget_deref!(LocalizedStringEditor);
+
+uuid_provider!(LocalizedStringEditor = "f3c0f8d1-5f5e-4a43-a7b5-e0f4f0b6c3a2");
+
+impl LocalizedStringEditor {
+    fn sync_preview(&self, ui: &UserInterface) {
+        // There's no point to show the key itself (it is already shown in the key field) if it is empty.
+        let message = if self.value.key().is_empty() {
+            TextMessage::text(self.preview, MessageDirection::ToWidget, Default::default())
+        } else {
+            TextMessage::localized_text(
+                self.preview,
+                MessageDirection::ToWidget,
+                self.value.clone(),
+            )
+        };
+        ui.send_message(message);
+    }
+}
+
+impl Control for LocalizedStringEditor {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if message.destination() == self.handle && message.direction() == MessageDirection::ToWidget
+        {
+            if let Some(LocalizedStringEditorMessage::Value(value)) = message.data() {
+                if &self.value != value {
+                    self.value = value.clone();
+                    ui.send_message(message.reverse());
+
+                    ui.send_message(TextMessage::text(
+                        self.key,
+                        MessageDirection::ToWidget,
+                        value.key().to_owned(),
+                    ));
+                    self.sync_preview(ui);
+                }
+            }
+        } else if message.destination() == self.key
+            && message.direction() == MessageDirection::FromWidget
+        {
+            if let Some(TextMessage::Text(text)) = message.data() {
+                if self.value.key() != text {
+                    ui.send_message(LocalizedStringEditorMessage::value(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        LocalizedString::new(text),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Creates [`LocalizedStringEditor`] widgets and add them to the user interface.
+pub struct LocalizedStringEditorBuilder {
+    widget_builder: WidgetBuilder,
+    value: LocalizedString,
+}
+
+impl LocalizedStringEditorBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            value: Default::default(),
+        }
+    }
+
+    /// Sets a desired value of the [`LocalizedStringEditor`].
+    pub fn with_value(mut self, value: LocalizedString) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Finishes widget building.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let key;
+        let preview;
+        let grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child({
+                    key = TextBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(0)
+                            .on_row(0)
+                            .with_margin(Thickness::uniform(1.0)),
+                    )
+                    .with_text(self.value.key())
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .build(ctx);
+                    key
+                })
+                .with_child({
+                    let builder = TextBuilder::new(
+                        WidgetBuilder::new()
+                            .on_column(0)
+                            .on_row(1)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_foreground(Brush::Solid(Color::opaque(160, 160, 160))),
+                    );
+                    preview = if self.value.key().is_empty() {
+                        builder
+                    } else {
+                        builder.with_localized_text(self.value.clone())
+                    }
+                    .build(ctx);
+                    preview
+                }),
+        )
+        .add_row(Row::auto())
+        .add_row(Row::auto())
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        let editor = LocalizedStringEditor {
+            widget: self.widget_builder.with_child(grid).build(),
+            value: self.value,
+            key,
+            preview,
+        };
+
+        ctx.add_node(UiNode::new(editor))
+    }
+}
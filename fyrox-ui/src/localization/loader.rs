@@ -0,0 +1,35 @@
+//! String table loader.
+
+use crate::{
+    core::{uuid::Uuid, TypeUuidProvider},
+    localization::StringTable,
+};
+use fyrox_resource::{
+    io::ResourceIo,
+    loader::{BoxedLoaderFuture, LoaderPayload, ResourceLoader},
+    state::LoadError,
+};
+use std::{path::PathBuf, sync::Arc};
+
+/// Default implementation for string table loading.
+#[derive(Default)]
+pub struct StringTableLoader;
+
+impl ResourceLoader for StringTableLoader {
+    fn extensions(&self) -> &[&str] {
+        &["csv"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        StringTable::type_uuid()
+    }
+
+    fn load(&self, path: PathBuf, io: Arc<dyn ResourceIo>) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let table = StringTable::from_file(&path, io.as_ref())
+                .await
+                .map_err(LoadError::new)?;
+            Ok(LoaderPayload::new(table))
+        })
+    }
+}
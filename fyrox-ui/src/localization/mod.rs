@@ -0,0 +1,598 @@
+//! Localization is used to show text in the language chosen by the user. See [`Localization`] docs for more info and
+//! usage examples.
+
+#![warn(missing_docs)]
+
+use crate::core::{
+    io::FileLoadError, log::Log, reflect::prelude::*, uuid::Uuid, uuid_provider,
+    visitor::prelude::*, TypeUuidProvider,
+};
+use fxhash::{FxHashMap, FxHashSet};
+use fyrox_resource::{io::ResourceIo, Resource, ResourceData};
+use std::{
+    any::Any,
+    cell::RefCell,
+    error::Error,
+    fmt::{Display, Formatter},
+    path::Path,
+    string::FromUtf8Error,
+};
+
+pub mod editor;
+pub mod loader;
+
+/// A key of a localized string. Its actual text depends on the active locale of a [`Localization`] and it is
+/// resolved every time when the locale changes. If there's no text for the key (in both the active and the
+/// default locale), the key itself is used as text.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Visit, Reflect)]
+pub struct LocalizedString {
+    /// A key in a string table.
+    pub key: String,
+}
+
+uuid_provider!(LocalizedString = "7a6a2ee5-8a3f-4a0e-9b8e-3a0c3c9d5b1e");
+
+impl LocalizedString {
+    /// Creates new localized string with the given key.
+    pub fn new<S: AsRef<str>>(key: S) -> Self {
+        Self {
+            key: key.as_ref().to_owned(),
+        }
+    }
+
+    /// Returns the key of the localized string.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl From<&str> for LocalizedString {
+    fn from(key: &str) -> Self {
+        Self::new(key)
+    }
+}
+
+impl From<String> for LocalizedString {
+    fn from(key: String) -> Self {
+        Self { key }
+    }
+}
+
+/// An error that may occur during string table loading.
+#[derive(Debug)]
+pub enum StringTableError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// The source is not a valid UTF-8 text.
+    Utf8(FromUtf8Error),
+
+    /// The source has invalid format.
+    Parse {
+        /// Line number (starting from 1) at which the error has occurred.
+        line: usize,
+        /// The reason of the error.
+        reason: String,
+    },
+}
+
+impl Display for StringTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StringTableError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            StringTableError::Utf8(v) => {
+                write!(f, "String table must be a valid UTF-8 text. {v}")
+            }
+            StringTableError::Parse { line, reason } => {
+                write!(f, "Invalid string table at line {line}: {reason}")
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for StringTableError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<FromUtf8Error> for StringTableError {
+    fn from(e: FromUtf8Error) -> Self {
+        Self::Utf8(e)
+    }
+}
+
+/// A record of a CSV file and the line at which it starts.
+struct CsvRecord {
+    line: usize,
+    fields: Vec<String>,
+}
+
+fn parse_error(line: usize, reason: &str) -> StringTableError {
+    StringTableError::Parse {
+        line,
+        reason: reason.to_owned(),
+    }
+}
+
+/// Splits CSV source (RFC 4180) into records. Quoted fields could contain commas, line breaks and
+/// escaped (doubled) quotes. Empty lines are ignored.
+fn parse_csv(source: &str) -> Result<Vec<CsvRecord>, StringTableError> {
+    fn finish_record(records: &mut Vec<CsvRecord>, line: usize, fields: Vec<String>) {
+        let is_empty = fields.len() == 1 && fields[0].is_empty();
+        if !is_empty {
+            records.push(CsvRecord { line, fields });
+        }
+    }
+
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                // Normalize line endings in multi-line values.
+                '\r' if chars.peek() == Some(&'\n') => (),
+                '\n' => {
+                    line += 1;
+                    field.push('\n');
+                }
+                _ => field.push(c),
+            }
+        } else {
+            match c {
+                '"' if field.is_empty() && !quoted => {
+                    in_quotes = true;
+                    quoted = true;
+                }
+                '"' => return Err(parse_error(line, "unexpected quote in unquoted field")),
+                ',' => {
+                    fields.push(std::mem::take(&mut field));
+                    quoted = false;
+                }
+                '\r' if chars.peek() == Some(&'\n') => (),
+                '\n' => {
+                    fields.push(std::mem::take(&mut field));
+                    quoted = false;
+                    finish_record(&mut records, record_line, std::mem::take(&mut fields));
+                    line += 1;
+                    record_line = line;
+                }
+                _ if quoted => {
+                    return Err(parse_error(
+                        line,
+                        "unexpected character after closing quote",
+                    ))
+                }
+                _ => field.push(c),
+            }
+        }
+    }
+
+    if in_quotes {
+        return Err(parse_error(record_line, "unterminated quoted field"));
+    }
+
+    if !field.is_empty() || !fields.is_empty() || quoted {
+        fields.push(field);
+        finish_record(&mut records, record_line, fields);
+    }
+
+    Ok(records)
+}
+
+/// String table is a set of localized strings for one or more locales. Every string is identified by a unique key.
+///
+/// ## Format
+///
+/// String tables are stored in CSV files. The first row of a table is a header, its first column is ignored (it
+/// is usually called `key`) and every other column defines a locale. Every other row defines a key and its
+/// translation for every locale. An empty cell means that there's no translation for the locale and the text from
+/// the default locale of [`Localization`] will be used instead.
+///
+/// ```text
+/// key,en,fr,de
+/// menu.play,Play,Jouer,Spielen
+/// menu.quit,Quit,Quitter,Beenden
+/// menu.greeting,"Hello, ""friend""!","Salut, l'ami !",
+/// ```
+#[derive(Default, Debug, Clone, Reflect, Visit)]
+#[reflect(hide_all)]
+pub struct StringTable {
+    #[visit(skip)]
+    locales: Vec<String>,
+    #[visit(skip)]
+    entries: FxHashMap<String, Vec<String>>,
+}
+
+uuid_provider!(StringTable = "bd4ad6c2-2bb1-4b5d-9dd5-7a5e1b1f4b0a");
+
+impl ResourceData for StringTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn save(&mut self, _path: &Path) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
+
+    fn can_be_saved(&self) -> bool {
+        false
+    }
+}
+
+/// Type alias for string table resources.
+pub type StringTableResource = Resource<StringTable>;
+
+impl StringTable {
+    /// Loads a string table from the given CSV file. See [`StringTable`] docs for the format description.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, StringTableError> {
+        let bytes = io.load_file(path).await?;
+        Self::from_csv(&String::from_utf8(bytes)?)
+    }
+
+    /// Creates a string table from CSV source. See [`StringTable`] docs for the format description.
+    pub fn from_csv(source: &str) -> Result<Self, StringTableError> {
+        let mut records = parse_csv(source)?.into_iter();
+
+        let header = records
+            .next()
+            .ok_or_else(|| parse_error(1, "string table has no header"))?;
+        let locales = header.fields[1..]
+            .iter()
+            .map(|locale| locale.trim().to_owned())
+            .collect::<Vec<_>>();
+        if locales.is_empty() {
+            return Err(parse_error(header.line, "string table has no locales"));
+        }
+        if locales.iter().any(|locale| locale.is_empty()) {
+            return Err(parse_error(header.line, "locale name cannot be empty"));
+        }
+
+        let mut entries = FxHashMap::default();
+        for record in records {
+            if record.fields.len() > locales.len() + 1 {
+                return Err(parse_error(
+                    record.line,
+                    "the record has more fields than locales in the header",
+                ));
+            }
+
+            let mut fields = record.fields.into_iter();
+            let key = fields.next().unwrap_or_default().trim().to_owned();
+            if key.is_empty() {
+                return Err(parse_error(record.line, "key cannot be empty"));
+            }
+
+            let mut values = fields.collect::<Vec<_>>();
+            values.resize(locales.len(), String::new());
+
+            if entries.insert(key, values).is_some() {
+                return Err(parse_error(record.line, "duplicate key"));
+            }
+        }
+
+        Ok(Self { locales, entries })
+    }
+
+    /// Returns a list of locales of the table.
+    pub fn locales(&self) -> &[String] {
+        &self.locales
+    }
+
+    /// Returns an iterator over every key of the table.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|key| key.as_str())
+    }
+
+    /// Returns the text for the given key in the given locale. Returns [`None`] if there's no such key or it
+    /// has no translation for the locale.
+    pub fn get(&self, locale: &str, key: &str) -> Option<&str> {
+        let index = self.locales.iter().position(|l| l == locale)?;
+        self.entries
+            .get(key)
+            .and_then(|values| values.get(index))
+            .map(|value| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Sets the text for the given key in the given locale. Adds the locale, if it does not exist.
+    pub fn set(&mut self, locale: &str, key: &str, text: &str) {
+        let index = match self.locales.iter().position(|l| l == locale) {
+            Some(index) => index,
+            None => {
+                self.locales.push(locale.to_owned());
+                for values in self.entries.values_mut() {
+                    values.push(String::new());
+                }
+                self.locales.len() - 1
+            }
+        };
+
+        let locales_count = self.locales.len();
+        let values = self
+            .entries
+            .entry(key.to_owned())
+            .or_insert_with(|| vec![String::new(); locales_count]);
+        values[index] = text.to_owned();
+    }
+}
+
+/// Localization is a set of string tables and the active locale. It is used to resolve [`LocalizedString`]s to the
+/// text in the active locale. Every user interface has its own localization (see
+/// [`crate::UserInterface::localization_mut`]), every widget that shows localized text will be updated
+/// automatically when the active locale changes or when a string table is loaded.
+///
+/// If a key is missing in the active locale, the text from the default locale is used and a warning is written to
+/// the log (only once per key and locale).
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_resource::manager::ResourceManager;
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     localization::{LocalizedString, StringTable},
+/// #     text::TextBuilder,
+/// #     widget::WidgetBuilder,
+/// #     UiNode, UserInterface,
+/// # };
+/// fn setup_localization(ui: &mut UserInterface, resource_manager: &ResourceManager) -> Handle<UiNode> {
+///     let localization = ui.localization_mut();
+///     localization.add_table(resource_manager.request::<StringTable>("data/strings.csv"));
+///     localization.set_default_locale("en");
+///     localization.set_locale("fr");
+///
+///     TextBuilder::new(WidgetBuilder::new())
+///         .with_localized_text(LocalizedString::new("menu.play"))
+///         .build(&mut ui.build_ctx())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Localization {
+    tables: Vec<StringTableResource>,
+    locale: String,
+    default_locale: String,
+    changed: bool,
+    loaded_tables: usize,
+    // Pairs of locale and key that were already reported as missing, to not spam the log every frame.
+    reported_keys: RefCell<FxHashSet<(String, String)>>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl Localization {
+    /// Creates new localization with the given default locale, which is also used as the active one.
+    pub fn new<S: AsRef<str>>(default_locale: S) -> Self {
+        Self {
+            tables: Default::default(),
+            locale: default_locale.as_ref().to_owned(),
+            default_locale: default_locale.as_ref().to_owned(),
+            changed: false,
+            loaded_tables: 0,
+            reported_keys: Default::default(),
+        }
+    }
+
+    /// Adds a new string table. Tables are searched in the order of addition.
+    pub fn add_table(&mut self, table: StringTableResource) {
+        self.tables.push(table);
+        self.changed = true;
+    }
+
+    /// Removes the given string table.
+    pub fn remove_table(&mut self, table: &StringTableResource) {
+        if let Some(position) = self.tables.iter().position(|t| t == table) {
+            self.tables.remove(position);
+            self.changed = true;
+        }
+    }
+
+    /// Returns a list of string tables.
+    pub fn tables(&self) -> &[StringTableResource] {
+        &self.tables
+    }
+
+    /// Sets the new active locale.
+    pub fn set_locale<S: AsRef<str>>(&mut self, locale: S) {
+        if self.locale != locale.as_ref() {
+            self.locale = locale.as_ref().to_owned();
+            self.changed = true;
+        }
+    }
+
+    /// Returns the active locale.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Sets the new default locale. It is used when a key is missing in the active locale.
+    pub fn set_default_locale<S: AsRef<str>>(&mut self, locale: S) {
+        if self.default_locale != locale.as_ref() {
+            self.default_locale = locale.as_ref().to_owned();
+            self.changed = true;
+        }
+    }
+
+    /// Returns the default locale.
+    pub fn default_locale(&self) -> &str {
+        &self.default_locale
+    }
+
+    fn find(&self, locale: &str, key: &str) -> Option<String> {
+        self.tables.iter().find_map(|table| {
+            let mut state = table.state();
+            state
+                .data()
+                .and_then(|table| table.get(locale, key).map(|text| text.to_owned()))
+        })
+    }
+
+    /// Searches for the text of the given key in the active locale, then in the default one. Returns [`None`] if
+    /// there's no such key in both locales.
+    pub fn lookup(&self, key: &str) -> Option<String> {
+        if let Some(text) = self.find(&self.locale, key) {
+            return Some(text);
+        }
+
+        let text = if self.locale != self.default_locale {
+            self.find(&self.default_locale, key)
+        } else {
+            None
+        };
+
+        // Keys are most likely missing because of pending tables, there's no need to spam the log.
+        if !self.tables.iter().any(|table| table.is_loading())
+            && self
+                .reported_keys
+                .borrow_mut()
+                .insert((self.locale.clone(), key.to_owned()))
+        {
+            if text.is_some() {
+                Log::warn(format!(
+                    "Localization key {key} is missing in {} locale, {} locale is used instead.",
+                    self.locale, self.default_locale
+                ));
+            } else {
+                Log::warn(format!(
+                    "Localization key {key} is missing in both {} and {} (default) locales.",
+                    self.locale, self.default_locale
+                ));
+            }
+        }
+
+        text
+    }
+
+    /// Returns the text of the given localized string in the active locale. See [`Self::lookup`] for more info.
+    /// The key itself is returned if there's no text for it.
+    pub fn resolve(&self, string: &LocalizedString) -> String {
+        self.lookup(&string.key)
+            .unwrap_or_else(|| string.key.clone())
+    }
+
+    /// Forces every localized string to be resolved again.
+    pub(crate) fn invalidate(&mut self) {
+        self.changed = true;
+    }
+
+    /// Returns `true` if the locale or the set of loaded tables was changed since the last call, which means
+    /// that every localized string must be resolved again.
+    pub(crate) fn take_changes(&mut self) -> bool {
+        let loaded_tables = self.tables.iter().filter(|table| table.is_ok()).count();
+        let changed = self.changed || loaded_tables != self.loaded_tables;
+        self.changed = false;
+        self.loaded_tables = loaded_tables;
+        changed
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        localization::{Localization, LocalizedString, StringTable, StringTableResource},
+        text::{Text, TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_core::algebra::Vector2;
+
+    const SOURCE: &str = "key,en,fr\r\n\
+        menu.play,Play,Jouer\r\n\
+        \r\n\
+        menu.greeting,\"Hello, \"\"friend\"\"!\",\"Salut,\nl'ami !\"\n\
+        menu.quit,Quit\n";
+
+    fn make_localization() -> Localization {
+        let mut localization = Localization::new("en");
+        localization.add_table(StringTableResource::new_ok(
+            Default::default(),
+            StringTable::from_csv(SOURCE).unwrap(),
+        ));
+        localization
+    }
+
+    #[test]
+    fn test_string_table_parsing() {
+        let table = StringTable::from_csv(SOURCE).unwrap();
+        assert_eq!(table.locales(), ["en", "fr"]);
+        assert_eq!(table.keys().count(), 3);
+        assert_eq!(table.get("fr", "menu.play"), Some("Jouer"));
+        assert_eq!(table.get("en", "menu.greeting"), Some("Hello, \"friend\"!"));
+        assert_eq!(table.get("fr", "menu.greeting"), Some("Salut,\nl'ami !"));
+        assert_eq!(table.get("fr", "menu.quit"), None);
+        assert_eq!(table.get("de", "menu.play"), None);
+
+        assert!(StringTable::from_csv("key,en\na,\"b").is_err());
+        assert!(StringTable::from_csv("key,en\na,b,c").is_err());
+        assert!(StringTable::from_csv("key,en\na,b\na,c").is_err());
+        assert!(StringTable::from_csv("key\na").is_err());
+    }
+
+    #[test]
+    fn test_localization_fallback() {
+        let mut localization = make_localization();
+        localization.set_locale("fr");
+        assert_eq!(localization.resolve(&"menu.play".into()), "Jouer");
+        // Missing in the active locale, the default one must be used.
+        assert_eq!(localization.resolve(&"menu.quit".into()), "Quit");
+        // Missing everywhere.
+        assert_eq!(localization.lookup("menu.missing"), None);
+        assert_eq!(localization.resolve(&"menu.missing".into()), "menu.missing");
+    }
+
+    #[test]
+    fn test_text_locale_switching() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        ui.set_localization(make_localization());
+
+        let text = TextBuilder::new(WidgetBuilder::new())
+            .with_localized_text(LocalizedString::new("menu.play"))
+            .build(&mut ui.build_ctx());
+        let text_of = |ui: &UserInterface| ui.node(text).cast::<Text>().unwrap().text();
+        assert_eq!(text_of(&ui), "Play");
+
+        ui.localization_mut().set_locale("fr");
+        ui.update(screen_size, 0.0);
+        assert_eq!(text_of(&ui), "Jouer");
+
+        // Explicitly set text must not be changed by localization.
+        ui.send_message(TextMessage::text(
+            text,
+            crate::message::MessageDirection::ToWidget,
+            "Literal".to_string(),
+        ));
+        while ui.poll_message().is_some() {}
+        ui.localization_mut().set_locale("en");
+        ui.update(screen_size, 0.0);
+        assert_eq!(text_of(&ui), "Literal");
+    }
+}
@@ -11,6 +11,7 @@ use crate::{
     decorator::DecoratorBuilder,
     define_constructor,
     grid::{Column, GridBuilder, Row},
+    localization::LocalizedString,
     message::{ButtonState, MessageDirection, OsEvent, UiMessage},
     popup::{Placement, Popup, PopupBuilder, PopupMessage},
     stack_panel::StackPanelBuilder,
@@ -650,6 +651,18 @@ pub enum MenuItemContent<'a, 'b> {
         /// Create an arrow or not.
         arrow: bool,
     },
+    /// The same as [`Self::Text`], but with localized text that will be updated automatically when the active locale
+    /// changes.
+    LocalizedText {
+        /// Localized text of the menu item.
+        text: LocalizedString,
+        /// Shortcut of the menu item.
+        shortcut: &'b str,
+        /// Icon of the menu item. Usually it is a [`crate::image::Image`] or [`crate::vector_image::VectorImage`] widget instance.
+        icon: Handle<UiNode>,
+        /// Create an arrow or not.
+        arrow: bool,
+    },
     /// Allows to put any node into menu item. It allows to customize menu item how needed - i.e. put image in it, or other user
     /// control.
     Node(Handle<UiNode>),
@@ -676,6 +689,16 @@ impl<'a, 'b> MenuItemContent<'a, 'b> {
         }
     }
 
+    /// Creates a menu item content with a localized text and an arrow (with no icon or shortcut).
+    pub fn localized_text(text: LocalizedString) -> Self {
+        MenuItemContent::LocalizedText {
+            text,
+            shortcut: "",
+            icon: Default::default(),
+            arrow: true,
+        }
+    }
+
     /// Creates a menu item content with a text only (with no icon, shortcut, arrow).
     pub fn text_no_arrow(text: &'a str) -> Self {
         MenuItemContent::Text {
@@ -687,6 +710,60 @@ impl<'a, 'b> MenuItemContent<'a, 'b> {
     }
 }
 
+fn make_text_content(
+    text: impl FnOnce(TextBuilder) -> TextBuilder,
+    shortcut: &str,
+    icon: Handle<UiNode>,
+    arrow: bool,
+    has_items: bool,
+    ctx: &mut BuildContext,
+) -> Handle<UiNode> {
+    GridBuilder::new(
+        WidgetBuilder::new()
+            .with_child(icon)
+            .with_child(
+                text(TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_vertical_alignment(VerticalAlignment::Center)
+                        .with_margin(Thickness::left(2.0))
+                        .on_column(1),
+                ))
+                .build(ctx),
+            )
+            .with_child(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_vertical_alignment(VerticalAlignment::Center)
+                        .with_horizontal_alignment(HorizontalAlignment::Right)
+                        .with_margin(Thickness::uniform(1.0))
+                        .on_column(2),
+                )
+                .with_text(shortcut)
+                .build(ctx),
+            )
+            .with_child(if arrow {
+                VectorImageBuilder::new(
+                    WidgetBuilder::new()
+                        .with_visibility(has_items)
+                        .on_column(3)
+                        .with_foreground(BRUSH_BRIGHT)
+                        .with_horizontal_alignment(HorizontalAlignment::Center)
+                        .with_vertical_alignment(VerticalAlignment::Center),
+                )
+                .with_primitives(make_arrow_primitives(ArrowDirection::Right, 8.0))
+                .build(ctx)
+            } else {
+                Handle::NONE
+            }),
+    )
+    .add_row(Row::auto())
+    .add_column(Column::auto())
+    .add_column(Column::stretch())
+    .add_column(Column::auto())
+    .add_column(Column::strict(10.0))
+    .build(ctx)
+}
+
 /// Menu builder creates [`MenuItem`] widgets and adds them to the user interface.
 pub struct MenuItemBuilder<'a, 'b> {
     widget_builder: WidgetBuilder,
@@ -742,51 +819,27 @@ impl<'a, 'b> MenuItemBuilder<'a, 'b> {
                 shortcut,
                 icon,
                 arrow,
-            }) => GridBuilder::new(
-                WidgetBuilder::new()
-                    .with_child(icon)
-                    .with_child(
-                        TextBuilder::new(
-                            WidgetBuilder::new()
-                                .with_vertical_alignment(VerticalAlignment::Center)
-                                .with_margin(Thickness::left(2.0))
-                                .on_column(1),
-                        )
-                        .with_text(text)
-                        .build(ctx),
-                    )
-                    .with_child(
-                        TextBuilder::new(
-                            WidgetBuilder::new()
-                                .with_vertical_alignment(VerticalAlignment::Center)
-                                .with_horizontal_alignment(HorizontalAlignment::Right)
-                                .with_margin(Thickness::uniform(1.0))
-                                .on_column(2),
-                        )
-                        .with_text(shortcut)
-                        .build(ctx),
-                    )
-                    .with_child(if arrow {
-                        VectorImageBuilder::new(
-                            WidgetBuilder::new()
-                                .with_visibility(!self.items.is_empty())
-                                .on_column(3)
-                                .with_foreground(BRUSH_BRIGHT)
-                                .with_horizontal_alignment(HorizontalAlignment::Center)
-                                .with_vertical_alignment(VerticalAlignment::Center),
-                        )
-                        .with_primitives(make_arrow_primitives(ArrowDirection::Right, 8.0))
-                        .build(ctx)
-                    } else {
-                        Handle::NONE
-                    }),
-            )
-            .add_row(Row::auto())
-            .add_column(Column::auto())
-            .add_column(Column::stretch())
-            .add_column(Column::auto())
-            .add_column(Column::strict(10.0))
-            .build(ctx),
+            }) => make_text_content(
+                |builder| builder.with_text(text),
+                shortcut,
+                icon,
+                arrow,
+                !self.items.is_empty(),
+                ctx,
+            ),
+            Some(MenuItemContent::LocalizedText {
+                text,
+                shortcut,
+                icon,
+                arrow,
+            }) => make_text_content(
+                |builder| builder.with_localized_text(text),
+                shortcut,
+                icon,
+                arrow,
+                !self.items.is_empty(),
+                ctx,
+            ),
             Some(MenuItemContent::Node(node)) => node,
         };
 
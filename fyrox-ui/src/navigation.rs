@@ -0,0 +1,173 @@
+//! Keyboard (and gamepad) focus navigation. It allows users to move keyboard focus between widgets using `Tab`/`Shift+Tab`
+//! (tab order) and arrow keys (or a D-pad, spatial navigation). See [`NavigationDirection`] and
+//! [`crate::UserInterface::navigate`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{algebra::Vector2, math::Rect, pool::Handle},
+    popup::Popup,
+    window::Window,
+    UiNode, UserInterface,
+};
+use std::cmp::Ordering;
+
+/// Defines where the keyboard focus should be moved to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NavigationDirection {
+    /// Next widget in the tab order of the current focus scope. Wraps around at the end of the scope.
+    Next,
+    /// Previous widget in the tab order of the current focus scope. Wraps around at the beginning of the scope.
+    Previous,
+    /// Closest widget above the currently focused one.
+    Up,
+    /// Closest widget below the currently focused one.
+    Down,
+    /// Closest widget on the left side of the currently focused one.
+    Left,
+    /// Closest widget on the right side of the currently focused one.
+    Right,
+}
+
+impl NavigationDirection {
+    fn axis(self) -> Option<Vector2<f32>> {
+        match self {
+            NavigationDirection::Next | NavigationDirection::Previous => None,
+            NavigationDirection::Up => Some(Vector2::new(0.0, -1.0)),
+            NavigationDirection::Down => Some(Vector2::new(0.0, 1.0)),
+            NavigationDirection::Left => Some(Vector2::new(-1.0, 0.0)),
+            NavigationDirection::Right => Some(Vector2::new(1.0, 0.0)),
+        }
+    }
+}
+
+/// Returns the closest widget (starting from the given one) that can receive keyboard focus during navigation.
+pub(crate) fn tab_stop_ancestor(ui: &UserInterface, node: Handle<UiNode>) -> Handle<UiNode> {
+    let mut handle = node;
+    while let Some(widget) = ui.try_get_node(handle) {
+        if widget.tab_stop() {
+            return handle;
+        }
+        handle = widget.parent();
+    }
+    Handle::NONE
+}
+
+/// Returns a root of the focus scope, that contains the given node. Focus scope restricts navigation, so focus can't
+/// leave a window or a popup. Modal windows and popups (anything that restricts picking) always define the scope,
+/// no matter where the focus currently is.
+pub(crate) fn focus_scope(ui: &UserInterface, node: Handle<UiNode>) -> Handle<UiNode> {
+    if let Some(restriction) = ui.top_picking_restriction() {
+        if ui.try_get_node(restriction.handle).is_some() {
+            return restriction.handle;
+        }
+    }
+
+    let mut handle = node;
+    while let Some(widget) = ui.try_get_node(handle) {
+        // Closed windows and popups are just hidden, so they can't be used as a scope.
+        if (widget.cast::<Window>().is_some() || widget.cast::<Popup>().is_some())
+            && widget.is_globally_visible()
+        {
+            return handle;
+        }
+        handle = widget.parent();
+    }
+
+    ui.root()
+}
+
+/// Collects every focusable widget of the scope in tab order. Widgets with explicit tab index goes first (in ascending
+/// order of their indices), then every other widget in tree order (depth-first). Invisible and disabled widgets (and
+/// their descendants) are ignored.
+pub(crate) fn collect_tab_stops(ui: &UserInterface, scope: Handle<UiNode>) -> Vec<Handle<UiNode>> {
+    let mut indexed = Vec::new();
+    let mut ordered = Vec::new();
+
+    let mut stack = vec![scope];
+    while let Some(handle) = stack.pop() {
+        let Some(widget) = ui.try_get_node(handle) else {
+            continue;
+        };
+
+        if !widget.is_globally_visible() || !widget.enabled() {
+            continue;
+        }
+
+        if widget.tab_stop() {
+            match widget.tab_index() {
+                Some(index) => indexed.push((index, handle)),
+                None => ordered.push(handle),
+            }
+        }
+
+        // Reverse order to keep depth-first traversal in the order of children.
+        stack.extend(widget.children().iter().rev());
+    }
+
+    // Stable sort keeps tree order for widgets with the same index.
+    indexed.sort_by_key(|(index, _)| *index);
+
+    indexed
+        .into_iter()
+        .map(|(_, handle)| handle)
+        .chain(ordered)
+        .collect()
+}
+
+fn spatial_score(current: &Rect<f32>, candidate: &Rect<f32>, axis: Vector2<f32>) -> Option<f32> {
+    let delta = candidate.center() - current.center();
+    let primary = delta.dot(&axis);
+    if primary <= f32::EPSILON {
+        return None;
+    }
+    let orthogonal = (delta - axis.scale(primary)).norm();
+    // Prefer the widgets that are aligned with the current one, otherwise the navigation will "jump" across the
+    // screen to the widgets that are close, but placed diagonally.
+    Some(primary + 2.0 * orthogonal)
+}
+
+/// Searches for a widget that should receive keyboard focus if it is moved from the `current` widget in the
+/// given direction. Returns [`Handle::NONE`] if there's no such widget.
+pub(crate) fn find_navigation_target(
+    ui: &UserInterface,
+    current: Handle<UiNode>,
+    direction: NavigationDirection,
+) -> Handle<UiNode> {
+    let current = tab_stop_ancestor(ui, current);
+    let scope = focus_scope(ui, current);
+    let tab_stops = collect_tab_stops(ui, scope);
+
+    let Some(position) = tab_stops.iter().position(|h| *h == current) else {
+        // Nothing is focused in the scope yet, start from the beginning (or the end) of the scope.
+        return match direction {
+            NavigationDirection::Previous => tab_stops.last().cloned().unwrap_or_default(),
+            _ => tab_stops.first().cloned().unwrap_or_default(),
+        };
+    };
+
+    match direction.axis() {
+        None => {
+            let count = tab_stops.len();
+            let index = if direction == NavigationDirection::Next {
+                (position + 1) % count
+            } else {
+                (position + count - 1) % count
+            };
+            tab_stops[index]
+        }
+        Some(axis) => {
+            let current_bounds = ui.node(current).screen_bounds();
+            tab_stops
+                .iter()
+                .filter(|h| **h != current)
+                .filter_map(|h| {
+                    spatial_score(&current_bounds, &ui.node(*h).screen_bounds(), axis)
+                        .map(|score| (score, *h))
+                })
+                .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                .map(|(_, h)| h)
+                .unwrap_or_default()
+        }
+    }
+}
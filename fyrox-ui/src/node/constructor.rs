@@ -20,6 +20,7 @@ use crate::{
     inspector::Inspector,
     key::{HotKeyEditor, KeyBindingEditor},
     list_view::{ListView, ListViewItem},
+    localization::editor::LocalizedStringEditor,
     menu::{Menu, MenuItem},
     messagebox::MessageBox,
     nine_patch::NinePatch,
@@ -120,6 +121,7 @@ impl WidgetConstructorContainer {
         container.add::<RectEditor<f32>>();
         container.add::<RectEditor<f64>>();
 
+        container.add::<LocalizedStringEditor>();
         container.add::<PathEditor>();
         container.add::<ProgressBar>();
         container.add::<ScrollBar>();
@@ -11,6 +11,7 @@ use crate::{
     draw::DrawingContext,
     font::FontResource,
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
+    localization::{Localization, LocalizedString},
     message::{MessageDirection, UiMessage},
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, HorizontalAlignment, UiNode, UserInterface, VerticalAlignment,
@@ -25,8 +26,11 @@ use std::{
 /// Possible messages that can be used to alternate [`Text`] widget state at runtime.
 #[derive(Debug, Clone, PartialEq)]
 pub enum TextMessage {
-    /// Used to set new text of the widget.
+    /// Used to set new text of the widget. The widget will no longer be localized, if it was.
     Text(String),
+    /// Used to set new localized text of the widget. The text will be updated automatically when the active
+    /// locale of the UI changes. See [Text](Text#localization) for usage examples.
+    LocalizedText(LocalizedString),
     /// Used to set new text wrapping mode of the widget. See [Text](Text#text-alignment-and-word-wrapping) for usage
     /// examples.
     Wrap(WrapMode),
@@ -56,6 +60,11 @@ impl TextMessage {
         TextMessage:Text => fn text(String), layout: false
     );
 
+    define_constructor!(
+        /// Creates new [`TextMessage::LocalizedText`] message.
+        TextMessage:LocalizedText => fn localized_text(LocalizedString), layout: false
+    );
+
     define_constructor!(
         /// Creates new [`TextMessage::Wrap`] message.
         TextMessage:Wrap => fn wrap(WrapMode), layout: false
@@ -273,6 +282,7 @@ impl TextMessage {
 /// `TextMessage::Text -> TextMessage::text(widget_handle, direction, text)`):
 ///
 /// - [`TextMessage::Text`] - sets new text for a `Text` widget.
+/// - [`TextMessage::LocalizedText`] - sets new [localized text](Text#localization) for a `Text` widget.
 /// - [`TextMessage::Wrap`] - sets new [wrapping mode](Text#text-alignment-and-word-wrapping).
 /// - [`TextMessage::Font`] - sets new [font](Text#fonts-and-colors)
 /// - [`TextMessage::VerticalAlignment`] and `TextMessage::HorizontalAlignment` sets
@@ -302,12 +312,33 @@ impl TextMessage {
 ///
 /// Please keep in mind, that like any other situation when you "changing" something via messages, you should remember
 /// that the change is **not** immediate.
+///
+/// ## Localization
+///
+/// Text widget could show a text from the string tables of the UI localization (see [`crate::localization::Localization`]).
+/// Such text will be changed automatically when the active locale changes:
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     localization::LocalizedString, text::TextBuilder, widget::WidgetBuilder, UiNode, UserInterface
+/// # };
+/// fn create_localized_text(ui: &mut UserInterface) -> Handle<UiNode> {
+///     TextBuilder::new(WidgetBuilder::new())
+///         .with_localized_text(LocalizedString::new("menu.play"))
+///         .build(&mut ui.build_ctx())
+/// }
+/// ```
 #[derive(Default, Clone, Visit, Reflect, Debug)]
 pub struct Text {
     /// Base widget of the Text widget.
     pub widget: Widget,
     /// [`FormattedText`] instance that is used to layout text and generate drawing commands.
     pub formatted_text: RefCell<FormattedText>,
+    /// Optional localized text of the widget. If set, the text of the widget will be updated automatically when
+    /// the active locale of the UI changes.
+    #[visit(optional)]
+    pub localized_text: Option<LocalizedString>,
 }
 
 crate::define_widget_deref!(Text);
@@ -342,6 +373,15 @@ impl Control for Text {
         );
     }
 
+    fn on_locale_changed(&mut self, localization: &Localization) {
+        if let Some(localized_text) = self.localized_text.as_ref() {
+            self.formatted_text
+                .borrow_mut()
+                .set_text(localization.resolve(localized_text));
+            self.invalidate_layout();
+        }
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
@@ -350,10 +390,17 @@ impl Control for Text {
                 let mut text_ref = self.formatted_text.borrow_mut();
                 match msg {
                     TextMessage::Text(text) => {
+                        self.localized_text = None;
                         text_ref.set_text(text);
                         drop(text_ref);
                         self.invalidate_layout();
                     }
+                    TextMessage::LocalizedText(localized_text) => {
+                        text_ref.set_text(ui.localization().resolve(localized_text));
+                        drop(text_ref);
+                        self.localized_text = Some(localized_text.clone());
+                        self.invalidate_layout();
+                    }
                     &TextMessage::Wrap(wrap) => {
                         if text_ref.wrap_mode() != wrap {
                             text_ref.set_wrap(wrap);
@@ -434,6 +481,11 @@ impl Text {
         self.formatted_text.borrow().text()
     }
 
+    /// Returns current localized text of the widget (if any).
+    pub fn localized_text(&self) -> Option<&LocalizedString> {
+        self.localized_text.as_ref()
+    }
+
     /// Returns current font of the widget.
     pub fn font(&self) -> FontResource {
         self.formatted_text.borrow().get_font()
@@ -454,6 +506,7 @@ impl Text {
 pub struct TextBuilder {
     widget_builder: WidgetBuilder,
     text: Option<String>,
+    localized_text: Option<LocalizedString>,
     font: Option<FontResource>,
     vertical_text_alignment: VerticalAlignment,
     horizontal_text_alignment: HorizontalAlignment,
@@ -471,6 +524,7 @@ impl TextBuilder {
         Self {
             widget_builder,
             text: None,
+            localized_text: None,
             font: None,
             vertical_text_alignment: VerticalAlignment::Top,
             horizontal_text_alignment: HorizontalAlignment::Left,
@@ -489,6 +543,13 @@ impl TextBuilder {
         self
     }
 
+    /// Sets the desired localized text of the widget. It takes precedence over the text set by
+    /// [`Self::with_text`].
+    pub fn with_localized_text(mut self, localized_text: LocalizedString) -> Self {
+        self.localized_text = Some(localized_text);
+        self
+    }
+
     /// Sets the desired font of the widget.
     pub fn with_font(mut self, font: FontResource) -> Self {
         self.font = Some(font);
@@ -562,11 +623,16 @@ impl TextBuilder {
             self.widget_builder.foreground = Some(Brush::Solid(Color::opaque(220, 220, 220)));
         }
 
+        let text = match self.localized_text.as_ref() {
+            Some(localized_text) => ui.localization().resolve(localized_text),
+            None => self.text.unwrap_or_default(),
+        };
+
         let text = Text {
             widget: self.widget_builder.build(),
             formatted_text: RefCell::new(
                 FormattedTextBuilder::new(font)
-                    .with_text(self.text.unwrap_or_default())
+                    .with_text(text)
                     .with_vertical_alignment(self.vertical_text_alignment)
                     .with_horizontal_alignment(self.horizontal_text_alignment)
                     .with_wrap(self.wrap)
@@ -577,6 +643,7 @@ impl TextBuilder {
                     .with_height(self.height)
                     .build(),
             ),
+            localized_text: self.localized_text,
         };
         ui.add_node(UiNode::new(text))
     }
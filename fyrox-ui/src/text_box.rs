@@ -1350,8 +1350,10 @@ impl Control for TextBox {
                         }
 
                         // TextBox "eats" all input by default, some of the keys are used for input control while
-                        // others are used directly to enter text.
-                        message.set_handled(true);
+                        // others are used directly to enter text. Tab is left for keyboard navigation.
+                        if *code != KeyCode::Tab {
+                            message.set_handled(true);
+                        }
                     }
                     WidgetMessage::Focus => {
                         if message.direction() == MessageDirection::FromWidget {
@@ -1455,6 +1457,15 @@ impl Control for TextBox {
                                 }
                             }
                         }
+                        TextMessage::LocalizedText(localized_text) => {
+                            // Text box content is editable, so it can't follow the active locale. The text is
+                            // resolved only once instead.
+                            ui.send_message(TextMessage::text(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                ui.localization().resolve(localized_text),
+                            ));
+                        }
                         TextMessage::Wrap(wrap_mode) => {
                             if text.wrap_mode() != *wrap_mode {
                                 text.set_wrap(*wrap_mode);
@@ -1743,7 +1754,7 @@ impl TextBoxBuilder {
         }
 
         let text_box = TextBox {
-            widget: self.widget_builder.build(),
+            widget: self.widget_builder.with_default_tab_stop(true).build(),
             caret_position: Position::default(),
             caret_visible: false,
             blink_timer: 0.0,
@@ -107,6 +107,13 @@ pub enum WidgetMessage {
     /// Direction: **From UI/To UI**.
     Unfocus,
 
+    /// Initiated when a keyboard-focused widget was activated by the user (by pressing `Enter` or `Space`
+    /// or by a gamepad button mapped to [`crate::UserInterface::activate_focused`]). Widgets that support
+    /// activation (buttons, check boxes, etc.) react to this message as if they were clicked.
+    ///
+    /// Direction: **From UI**.
+    Activate,
+
     /// Initiated when dragging of a widget has started.
     ///
     /// Direction: **From UI**.
@@ -550,6 +557,11 @@ impl WidgetMessage {
         WidgetMessage:Unfocus => fn unfocus(), layout: false
     );
 
+    define_constructor!(
+        /// Creates [`WidgetMessage::Activate`] message.
+        WidgetMessage:Activate => fn activate(), layout: false
+    );
+
     // Internal messages. Do not use.
     define_constructor!(
         /// Creates [`WidgetMessage::MouseDown`] message. This method is for internal use only, and should not
@@ -746,6 +758,13 @@ pub struct Widget {
     /// A flag, that defines whether the widget is enabled or not. Disabled widgets cannot be interacted by used and they're
     /// greyed out.
     pub enabled: bool,
+    /// A flag, that defines whether the widget can receive keyboard focus during keyboard (or gamepad) navigation.
+    #[visit(optional)]
+    pub tab_stop: bool,
+    /// Optional index of the widget in the tab order of its focus scope. Widgets with an explicit index are visited
+    /// first (in ascending order of their indices), then every other tab stop in tree order.
+    #[visit(optional)]
+    pub tab_index: Option<usize>,
     /// Optional cursor icon that will be used for mouse cursor when hovering over the widget.
     pub cursor: Option<CursorIcon>,
     /// Optional opacity of the widget. It should be in `[0.0..1.0]` range, where 0.0 - fully transparent, 1.0 - fully opaque.
@@ -1553,6 +1572,18 @@ impl Widget {
         self.enabled
     }
 
+    /// Returns `true` if the widget can receive keyboard focus during keyboard navigation, `false` - otherwise.
+    #[inline]
+    pub fn tab_stop(&self) -> bool {
+        self.tab_stop
+    }
+
+    /// Returns an explicit index of the widget in the tab order (if any).
+    #[inline]
+    pub fn tab_index(&self) -> Option<usize> {
+        self.tab_index
+    }
+
     /// Sets new cursor of the widget.
     #[inline]
     pub fn set_cursor(&mut self, cursor: Option<CursorIcon>) {
@@ -1700,6 +1731,11 @@ pub struct WidgetBuilder {
     pub draw_on_top: bool,
     /// Whether the widget is enabled or not.
     pub enabled: bool,
+    /// Whether the widget can be focused by keyboard navigation or not. [`None`] means that the widget
+    /// will use its default value (which is defined by the widget itself).
+    pub tab_stop: Option<bool>,
+    /// Index of the widget in the tab order.
+    pub tab_index: Option<usize>,
     /// Cursor of the widget.
     pub cursor: Option<CursorIcon>,
     /// Opacity of the widget.
@@ -1756,6 +1792,8 @@ impl WidgetBuilder {
             user_data: None,
             draw_on_top: false,
             enabled: true,
+            tab_stop: None,
+            tab_index: None,
             cursor: None,
             opacity: None,
             tooltip: Default::default(),
@@ -1958,6 +1996,25 @@ impl WidgetBuilder {
         self
     }
 
+    /// Defines whether the widget can be focused by keyboard (or gamepad) navigation or not.
+    pub fn with_tab_stop(mut self, tab_stop: bool) -> Self {
+        self.tab_stop = Some(tab_stop);
+        self
+    }
+
+    /// Sets an explicit index of the widget in the tab order of its focus scope.
+    pub fn with_tab_index(mut self, tab_index: Option<usize>) -> Self {
+        self.tab_index = tab_index;
+        self
+    }
+
+    /// Enables keyboard focus for the widget, unless it was explicitly disabled by [`Self::with_tab_stop`]. Intended
+    /// to be used by interactive widgets (buttons, check boxes, etc.) in their builders.
+    pub fn with_default_tab_stop(mut self, tab_stop: bool) -> Self {
+        self.tab_stop.get_or_insert(tab_stop);
+        self
+    }
+
     /// Sets the desired widget id.
     pub fn with_id(mut self, id: Uuid) -> Self {
         self.id = id;
@@ -2038,6 +2095,8 @@ impl WidgetBuilder {
             user_data: self.user_data.clone(),
             draw_on_top: self.draw_on_top,
             enabled: self.enabled,
+            tab_stop: self.tab_stop.unwrap_or_default(),
+            tab_index: self.tab_index,
             cursor: self.cursor,
             clip_bounds: Cell::new(Default::default()),
             opacity: self.opacity,
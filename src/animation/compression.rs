@@ -0,0 +1,575 @@
+//! Animation compression reduces memory footprint of animations by removing redundant data from their tracks. See
+//! [`AnimationCompressionOptions`] docs for more info.
+
+use crate::{
+    animation::{
+        container::{fetch_packed_rotation, PackedRotationKey},
+        track::Track,
+        value::{TrackValue, ValueBinding},
+        Animation,
+    },
+    core::{
+        curve::{Curve, CurveKey, CurveKeyKind},
+        reflect::prelude::*,
+        uuid_provider,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Points at which spans of curves are sampled when checking the error of reduced curves.
+const SPAN_SAMPLES: [f32; 3] = [0.25, 0.5, 0.75];
+
+/// A set of options for animation compression. Compression is lossy, but the error is bounded by the tolerances.
+/// It consists of the following steps:
+///
+/// 1) Collapsing of constant tracks - if a track does not change its value, every curve of the track is collapsed
+///    to a single key. The track itself is kept, because a missing track is not the same as the rest pose at
+///    runtime - it does not override the values of blended animations and of other animations of the node.
+/// 2) Error-bounded keyframe reduction - every key, that could be restored by interpolation of neighbour keys, is
+///    removed.
+/// 3) Optional quantization of rotations - rotation tracks are stored as packed quaternion keys (see
+///    [`PackedRotationKey`]) instead of three curves of Euler angles.
+///
+/// Only position, rotation and scale tracks are compressed, property tracks are left untouched.
+///
+/// Compression could be enabled on import using [`crate::resource::model::ModelImportOptions`], for example
+/// `foo.fbx.options` file could look like this:
+///
+/// ```text
+/// (
+///     animation_compression: Some((
+///         position_tolerance: 0.001,
+///         rotation_tolerance: 0.002,
+///     ))
+/// )
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct AnimationCompressionOptions {
+    /// Maximum allowed error (distance in local space of a node) of position tracks.
+    pub position_tolerance: f32,
+
+    /// Maximum allowed error (angle in radians) of rotation tracks. It cannot be lower than the error of
+    /// quantization (about `1e-4` radians), if rotations are quantized.
+    pub rotation_tolerance: f32,
+
+    /// Maximum allowed error of scale tracks.
+    pub scale_tolerance: f32,
+
+    /// Whether to collapse tracks, that do not change their values, to a single key per curve or not.
+    pub collapse_constant_tracks: bool,
+
+    /// Whether to quantize rotations or not. Quantized rotation tracks are stored as packed quaternion keys
+    /// (48 bits per key, see [`PackedRotationKey`]), that take about 6 times less memory than curves of Euler
+    /// angles, but they cannot be edited in the animation editor.
+    pub quantize_rotations: bool,
+}
+
+uuid_provider!(AnimationCompressionOptions = "8d5a1b9c-2f07-4a4e-9b1e-5c2b9f3e6a71");
+
+impl Default for AnimationCompressionOptions {
+    fn default() -> Self {
+        Self {
+            position_tolerance: 0.0005,
+            rotation_tolerance: 0.001,
+            scale_tolerance: 0.0005,
+            collapse_constant_tracks: true,
+            quantize_rotations: false,
+        }
+    }
+}
+
+impl AnimationCompressionOptions {
+    fn tolerance(&self, binding: &ValueBinding) -> Option<f32> {
+        match binding {
+            ValueBinding::Position => Some(self.position_tolerance),
+            ValueBinding::Rotation => Some(self.rotation_tolerance),
+            ValueBinding::Scale => Some(self.scale_tolerance),
+            ValueBinding::Property { .. } => None,
+        }
+    }
+}
+
+/// Compression report contains statistics of compression of one or more animations.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CompressionReport {
+    /// Total amount of keys before compression.
+    pub original_keys: usize,
+    /// Total amount of keys after compression.
+    pub compressed_keys: usize,
+    /// Amount of constant tracks, that were collapsed to a single key per curve.
+    pub collapsed_tracks: usize,
+    /// Approximate memory usage (in bytes) of the tracks before compression.
+    pub original_size: usize,
+    /// Approximate memory usage (in bytes) of the tracks after compression.
+    pub compressed_size: usize,
+    /// Maximum error of position tracks.
+    pub max_position_error: f32,
+    /// Maximum error (in radians) of rotation tracks.
+    pub max_rotation_error: f32,
+    /// Maximum error of scale tracks.
+    pub max_scale_error: f32,
+}
+
+impl CompressionReport {
+    /// Returns the amount of bytes saved by compression.
+    pub fn saved_bytes(&self) -> usize {
+        self.original_size.saturating_sub(self.compressed_size)
+    }
+
+    /// Returns maximum error among all kinds of tracks.
+    pub fn max_error(&self) -> f32 {
+        self.max_position_error
+            .max(self.max_rotation_error)
+            .max(self.max_scale_error)
+    }
+
+    /// Merges statistics of other report into this one.
+    pub fn merge(&mut self, other: &CompressionReport) {
+        self.original_keys += other.original_keys;
+        self.compressed_keys += other.compressed_keys;
+        self.collapsed_tracks += other.collapsed_tracks;
+        self.original_size += other.original_size;
+        self.compressed_size += other.compressed_size;
+        self.max_position_error = self.max_position_error.max(other.max_position_error);
+        self.max_rotation_error = self.max_rotation_error.max(other.max_rotation_error);
+        self.max_scale_error = self.max_scale_error.max(other.max_scale_error);
+    }
+
+    fn add_error(&mut self, binding: &ValueBinding, error: f32) {
+        let max_error = match binding {
+            ValueBinding::Position => &mut self.max_position_error,
+            ValueBinding::Rotation => &mut self.max_rotation_error,
+            ValueBinding::Scale => &mut self.max_scale_error,
+            ValueBinding::Property { .. } => return,
+        };
+        *max_error = max_error.max(error);
+    }
+}
+
+impl Display for CompressionReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "keys: {} -> {}, collapsed tracks: {}, memory: {} -> {} bytes (saved {} bytes), \
+            max error: position {}, rotation {} rad, scale {}",
+            self.original_keys,
+            self.compressed_keys,
+            self.collapsed_tracks,
+            self.original_size,
+            self.compressed_size,
+            self.saved_bytes(),
+            self.max_position_error,
+            self.max_rotation_error,
+            self.max_scale_error
+        )
+    }
+}
+
+fn track_size(track: &Track) -> usize {
+    let container = track.data_container();
+    std::mem::size_of::<Track>()
+        + std::mem::size_of_val(container.curves_ref())
+        + curve_keys_count(track) * std::mem::size_of::<CurveKey>()
+        + std::mem::size_of_val(container.packed_rotations())
+}
+
+fn curve_keys_count(track: &Track) -> usize {
+    track
+        .data_container()
+        .curves_ref()
+        .iter()
+        .map(|curve| curve.keys().len())
+        .sum()
+}
+
+fn keys_count(track: &Track) -> usize {
+    curve_keys_count(track) + track.data_container().packed_rotations().len()
+}
+
+// Returns a metric distance between two values of the same binding.
+fn distance(a: &TrackValue, b: &TrackValue) -> f32 {
+    match (a, b) {
+        (TrackValue::Real(a), TrackValue::Real(b)) => (a - b).abs(),
+        (TrackValue::Vector2(a), TrackValue::Vector2(b)) => (a - b).norm(),
+        (TrackValue::Vector3(a), TrackValue::Vector3(b)) => (a - b).norm(),
+        (TrackValue::Vector4(a), TrackValue::Vector4(b)) => (a - b).norm(),
+        (TrackValue::UnitQuaternion(a), TrackValue::UnitQuaternion(b)) => a.angle_to(b),
+        _ => f32::MAX,
+    }
+}
+
+// Returns sorted locations of keys of every curve of the track.
+fn key_locations(track: &Track) -> Vec<f32> {
+    let mut keys = track
+        .data_container()
+        .curves_ref()
+        .iter()
+        .flat_map(|curve| curve.keys().iter().map(|key| key.location()))
+        .collect::<Vec<_>>();
+    keys.sort_by(|a, b| a.total_cmp(b));
+    keys.dedup();
+    keys
+}
+
+// Returns sorted locations at which the track should be sampled to compare it with other track.
+fn sample_locations(track: &Track) -> Vec<f32> {
+    let keys = key_locations(track);
+    let mut locations = Vec::with_capacity(keys.len() * (SPAN_SAMPLES.len() + 1));
+    for pair in keys.windows(2) {
+        locations.push(pair[0]);
+        for t in SPAN_SAMPLES {
+            locations.push(pair[0] + (pair[1] - pair[0]) * t);
+        }
+    }
+    locations.extend(keys.last());
+    locations
+}
+
+// Replaces every curve of the track with a single key, that has the value of the curve at the given location.
+fn collapse_track(track: &mut Track, location: f32) {
+    for curve in track.data_container_mut().curves_mut() {
+        let mut collapsed = Curve::from(vec![CurveKey::new(
+            location,
+            curve.value_at(location),
+            CurveKeyKind::Constant,
+        )]);
+        collapsed.set_id(curve.id());
+        collapsed.set_name(curve.name());
+        *curve = collapsed;
+    }
+}
+
+// Returns `true` if the reduced span from `keys[from]` to `keys[to]` approximates original curve with the given
+// tolerance.
+fn is_span_valid(keys: &[CurveKey], from: usize, to: usize, tolerance: f32) -> bool {
+    let (a, b) = (&keys[from], &keys[to]);
+    let length = b.location() - a.location();
+    if length <= 0.0 {
+        return false;
+    }
+
+    let reduced_value = |location: f32| a.interpolate(b, (location - a.location()) / length);
+
+    for (i, pair) in keys[from..=to].windows(2).enumerate() {
+        let (left, right) = (&pair[0], &pair[1]);
+        if i > 0 && (reduced_value(left.location()) - left.value).abs() > tolerance {
+            return false;
+        }
+        let span = right.location() - left.location();
+        for t in SPAN_SAMPLES {
+            let location = left.location() + span * t;
+            if (reduced_value(location) - left.interpolate(right, t)).abs() > tolerance {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+// Removes every key that could be restored by interpolation of the kept neighbour keys with the given tolerance.
+// The first and the last keys are always kept, so the duration of the curve remains the same.
+fn reduce_keys(keys: &[CurveKey], tolerance: f32) -> Vec<CurveKey> {
+    if keys.len() <= 2 {
+        return keys.to_vec();
+    }
+
+    let mut reduced = vec![keys[0].clone()];
+    let mut anchor = 0;
+    while anchor < keys.len() - 1 {
+        let mut end = anchor + 1;
+        while end + 1 < keys.len() && is_span_valid(keys, anchor, end + 1, tolerance) {
+            end += 1;
+        }
+        reduced.push(keys[end].clone());
+        anchor = end;
+    }
+    reduced
+}
+
+fn compress_track(track: &mut Track, tolerance: f32) {
+    let is_rotation = *track.binding() == ValueBinding::Rotation;
+    let components = track.data_container().curves_ref().len().max(1) as f32;
+    // Errors of components accumulate: the angle of a rotation is bounded by the sum of errors of Euler angles and
+    // the distance between vectors is bounded by the length of the error vector.
+    let component_tolerance = if is_rotation {
+        tolerance / components
+    } else {
+        tolerance / components.sqrt()
+    };
+
+    for curve in track.data_container_mut().curves_mut() {
+        let mut reduced = Curve::from(reduce_keys(curve.keys(), component_tolerance));
+        reduced.set_id(curve.id());
+        reduced.set_name(curve.name());
+        *curve = reduced;
+    }
+}
+
+// Replaces curves of the rotation track with packed keys. Keys are reduced in the same way as keys of curves, but the
+// error is measured as an angle between original and decoded rotations, so it includes the error of quantization.
+fn quantize_rotation_track(track: &mut Track, samples: &[f32], tolerance: f32) {
+    let original = |location: f32| match track.fetch(location).map(|v| v.value) {
+        Some(TrackValue::UnitQuaternion(rotation)) => Some(rotation),
+        _ => None,
+    };
+
+    let Some(keys) = key_locations(track)
+        .into_iter()
+        .map(|location| Some(PackedRotationKey::new(location, original(location)?)))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return;
+    };
+    let Some(samples) = samples
+        .iter()
+        .map(|location| Some((*location, original(*location)?)))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return;
+    };
+
+    let is_span_valid = |from: usize, to: usize| {
+        let span = [keys[from].clone(), keys[to].clone()];
+        let first = samples.partition_point(|(location, _)| *location < span[0].location());
+        let last = samples.partition_point(|(location, _)| *location <= span[1].location());
+        samples[first..last].iter().all(|(location, rotation)| {
+            fetch_packed_rotation(&span, *location)
+                .is_some_and(|decoded| decoded.angle_to(rotation) <= tolerance)
+        })
+    };
+
+    let mut reduced = keys.first().cloned().into_iter().collect::<Vec<_>>();
+    let mut anchor = 0;
+    while anchor + 1 < keys.len() {
+        let mut end = anchor + 1;
+        while end + 1 < keys.len() && is_span_valid(anchor, end + 1) {
+            end += 1;
+        }
+        reduced.push(keys[end].clone());
+        anchor = end;
+    }
+
+    let container = track.data_container_mut();
+    for curve in container.curves_mut() {
+        let mut empty = Curve::default();
+        empty.set_id(curve.id());
+        empty.set_name(curve.name());
+        *curve = empty;
+    }
+    container.set_packed_rotations(reduced);
+}
+
+/// Compresses the given animation using the specified options. Returns a report with statistics of the compression.
+pub fn compress_animation(
+    animation: &mut Animation,
+    options: &AnimationCompressionOptions,
+) -> CompressionReport {
+    let mut report = CompressionReport::default();
+
+    for track in animation.tracks() {
+        report.original_keys += keys_count(track);
+        report.original_size += track_size(track);
+    }
+
+    for track in animation.tracks_mut() {
+        let Some(tolerance) = options.tolerance(track.binding()) else {
+            continue;
+        };
+
+        let locations = sample_locations(track);
+        let original = track.clone();
+
+        let is_constant = options.collapse_constant_tracks
+            && locations
+                .first()
+                .and_then(|first| original.fetch(*first))
+                .is_some_and(|first| {
+                    locations
+                        .iter()
+                        .filter_map(|location| original.fetch(*location))
+                        .all(|value| distance(&value.value, &first.value) <= tolerance)
+                });
+
+        if is_constant {
+            collapse_track(track, locations[0]);
+            report.collapsed_tracks += 1;
+        } else if options.quantize_rotations && *track.binding() == ValueBinding::Rotation {
+            quantize_rotation_track(track, &locations, tolerance);
+        } else {
+            compress_track(track, tolerance);
+        }
+
+        let max_error = locations
+            .iter()
+            .filter_map(|location| Some((original.fetch(*location)?, track.fetch(*location)?)))
+            .map(|(a, b)| distance(&a.value, &b.value))
+            .fold(0.0f32, f32::max);
+        report.add_error(track.binding(), max_error);
+    }
+
+    for track in animation.tracks() {
+        report.compressed_keys += keys_count(track);
+        report.compressed_size += track_size(track);
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            compression::{compress_animation, AnimationCompressionOptions},
+            container::PackedRotationKey,
+            track::{test::make_track, Track},
+            value::{TrackValue, ValueBinding},
+            Animation,
+        },
+        core::algebra::{UnitQuaternion, Vector3},
+        scene::{base::BaseBuilder, graph::Graph, pivot::PivotBuilder},
+    };
+
+    // Samples the function at 101 evenly distributed locations in `[0; 1]` range.
+    fn sample(func: impl Fn(f32) -> Vector3<f32>) -> impl Iterator<Item = (f32, Vector3<f32>)> {
+        (0..=100).map(move |i| {
+            let location = i as f32 / 100.0;
+            (location, func(location))
+        })
+    }
+
+    fn max_error(original: &Track, compressed: &Track) -> f32 {
+        (0..=1000)
+            .map(|i| {
+                let location = i as f32 / 1000.0;
+                match (
+                    original.fetch(location).unwrap().value,
+                    compressed.fetch(location).unwrap().value,
+                ) {
+                    (TrackValue::Vector3(a), TrackValue::Vector3(b)) => (a - b).norm(),
+                    (TrackValue::UnitQuaternion(a), TrackValue::UnitQuaternion(b)) => {
+                        a.angle_to(&b)
+                    }
+                    _ => unreachable!(),
+                }
+            })
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_keyframe_reduction() {
+        let node = PivotBuilder::new(BaseBuilder::new()).build(&mut Graph::new());
+
+        let position = make_track(
+            ValueBinding::Position,
+            sample(|t| {
+                // Linear part and a curved part.
+                Vector3::new(t * 2.0, (t * 6.0).sin(), 0.0)
+            }),
+        )
+        .with_target(node);
+        let rotation = make_track(
+            ValueBinding::Rotation,
+            sample(|t| Vector3::new(0.0, t * 2.0, 0.0)),
+        )
+        .with_target(node);
+
+        let mut animation = Animation::default();
+        animation.add_track(position.clone());
+        animation.add_track(rotation.clone());
+
+        let options = AnimationCompressionOptions::default();
+        let report = compress_animation(&mut animation, &options);
+
+        assert_eq!(report.original_keys, 606);
+        assert!(report.compressed_keys < report.original_keys / 3);
+        assert!(report.saved_bytes() > 0);
+        assert_eq!(report.collapsed_tracks, 0);
+
+        let position_error = max_error(&position, &animation.tracks()[0]);
+        let rotation_error = max_error(&rotation, &animation.tracks()[1]);
+        assert!(position_error <= options.position_tolerance * 1.01);
+        assert!(rotation_error <= options.rotation_tolerance * 1.01);
+        assert!(report.max_position_error <= options.position_tolerance);
+        assert!(report.max_rotation_error <= options.rotation_tolerance);
+
+        // Linear rotation could be represented by just two keys per curve.
+        assert_eq!(
+            animation.tracks()[1]
+                .data_container()
+                .curves_ref()
+                .iter()
+                .map(|c| c.keys().len())
+                .sum::<usize>(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_constant_tracks_and_quantization() {
+        let node = PivotBuilder::new(BaseBuilder::new()).build(&mut Graph::new());
+
+        // Constant tracks are collapsed, no matter if they match the rest pose or not.
+        let scale =
+            make_track(ValueBinding::Scale, sample(|_| Vector3::repeat(1.0))).with_target(node);
+        let position = make_track(
+            ValueBinding::Position,
+            sample(|_| Vector3::new(1.0, 2.0, 3.0)),
+        )
+        .with_target(node);
+        let rotation = make_track(
+            ValueBinding::Rotation,
+            sample(|t| Vector3::new((t * 3.0).cos(), t * 4.0, 0.25)),
+        )
+        .with_target(node);
+
+        let mut animation = Animation::default();
+        animation.add_track(scale.clone());
+        animation.add_track(position.clone());
+        animation.add_track(rotation.clone());
+
+        let mut curves_only = animation.clone();
+        let curves_report =
+            compress_animation(&mut curves_only, &AnimationCompressionOptions::default());
+
+        let options = AnimationCompressionOptions {
+            quantize_rotations: true,
+            ..Default::default()
+        };
+        let report = compress_animation(&mut animation, &options);
+
+        assert_eq!(report.collapsed_tracks, 2);
+        assert_eq!(animation.tracks().len(), 3);
+        for (original, compressed) in [scale, position].iter().zip(animation.tracks()) {
+            assert_eq!(max_error(original, compressed), 0.0);
+            assert!(compressed
+                .data_container()
+                .curves_ref()
+                .iter()
+                .all(|c| c.keys().len() == 1));
+        }
+
+        // Quantized rotations are stored as packed keys instead of curves.
+        let container = animation.tracks()[2].data_container();
+        assert!(container.curves_ref().iter().all(|c| c.keys().is_empty()));
+        assert!(container.packed_rotations().len() > 1);
+        assert!(report.compressed_size < curves_report.compressed_size);
+
+        // The error of quantization is included in the tolerance checks.
+        let rotation_error = max_error(&rotation, &animation.tracks()[2]);
+        assert!(rotation_error <= options.rotation_tolerance * 1.01);
+        assert!(report.max_rotation_error <= options.rotation_tolerance);
+    }
+
+    #[test]
+    fn test_packed_rotation_key() {
+        for (x, y, z) in [(0.0, 0.0, 0.0), (3.0, -1.0, 0.5), (-2.5, 1.5, 3.1)] {
+            let rotation = UnitQuaternion::from_euler_angles(x, y, z);
+            let key = PackedRotationKey::new(1.0, rotation);
+            assert_eq!(key.location(), 1.0);
+            assert!(key.rotation().angle_to(&rotation) < 2.0e-4);
+        }
+    }
+}
@@ -4,13 +4,14 @@
 use crate::{
     animation::value::TrackValue,
     core::{
-        algebra::{Vector2, Vector3, Vector4},
+        algebra::{Quaternion, UnitQuaternion, Vector2, Vector3, Vector4},
         curve::Curve,
         math::{quat_from_euler, RotationOrder},
         reflect::prelude::*,
         visitor::prelude::*,
     },
 };
+use std::f32::consts::{FRAC_1_SQRT_2, PI};
 
 /// The kind of track output value, the animation system works only with numeric properties and the number
 /// of variants is small.
@@ -57,6 +58,89 @@ impl Default for TrackValueKind {
     }
 }
 
+/// A rotation key, that is stored in a compact form: a quaternion is encoded using "smallest three" method - the
+/// largest component is dropped (it is restored from the other three) and the other components are stored as 15-bit
+/// integers, that gives 48 bits per rotation with an error of about `1e-4` radians. A key takes 10 bytes instead of
+/// three keys of Euler angles curves. See [`TrackDataContainer::set_packed_rotations`].
+#[derive(Visit, Reflect, Debug, Clone, Default, PartialEq)]
+pub struct PackedRotationKey {
+    location: f32,
+    data: [u16; 3],
+}
+
+const PACKED_COMPONENT_MAX: f32 = 0x7FFF as f32;
+
+impl PackedRotationKey {
+    /// Creates a new key at the given location.
+    pub fn new(location: f32, rotation: UnitQuaternion<f32>) -> Self {
+        let mut coords = rotation.into_inner().coords;
+        let largest = coords.iamax();
+        // `q` and `-q` are the same rotation, the dropped component is always positive.
+        if coords[largest] < 0.0 {
+            coords = -coords;
+        }
+
+        let mut data = [0u16; 3];
+        for (i, component) in (0..4).filter(|i| *i != largest).enumerate() {
+            let normalized = (coords[component] / FRAC_1_SQRT_2 * 0.5 + 0.5).clamp(0.0, 1.0);
+            data[i] = (normalized * PACKED_COMPONENT_MAX).round() as u16;
+        }
+        data[0] |= ((largest & 1) as u16) << 15;
+        data[1] |= ((largest >> 1) as u16) << 15;
+
+        Self { location, data }
+    }
+
+    /// Returns location of the key.
+    pub fn location(&self) -> f32 {
+        self.location
+    }
+
+    /// Decodes the rotation of the key.
+    pub fn rotation(&self) -> UnitQuaternion<f32> {
+        let largest = ((self.data[0] >> 15) | ((self.data[1] >> 15) << 1)) as usize;
+        let mut coords = Vector4::<f32>::zeros();
+        let mut sum = 0.0;
+        for (i, component) in (0..4).filter(|i| *i != largest).enumerate() {
+            let normalized = (self.data[i] & 0x7FFF) as f32 / PACKED_COMPONENT_MAX;
+            let value = (normalized * 2.0 - 1.0) * FRAC_1_SQRT_2;
+            coords[component] = value;
+            sum += value * value;
+        }
+        coords[largest] = (1.0 - sum).max(0.0).sqrt();
+        UnitQuaternion::from_quaternion(Quaternion::from(coords))
+    }
+}
+
+// Interpolates packed keys linearly (normalized lerp along the shortest path).
+pub(crate) fn fetch_packed_rotation(
+    keys: &[PackedRotationKey],
+    time: f32,
+) -> Option<UnitQuaternion<f32>> {
+    let right = keys.partition_point(|key| key.location < time);
+    match (
+        right.checked_sub(1).and_then(|i| keys.get(i)),
+        keys.get(right),
+    ) {
+        (Some(left), Some(right)) => {
+            let length = right.location - left.location;
+            let t = if length > 0.0 {
+                (time - left.location) / length
+            } else {
+                1.0
+            };
+            let a = left.rotation();
+            let mut b = right.rotation().into_inner();
+            if a.coords.dot(&b.coords) < 0.0 {
+                b = -b;
+            }
+            Some(UnitQuaternion::from_quaternion(a.into_inner().lerp(&b, t)))
+        }
+        (Some(key), None) | (None, Some(key)) => Some(key.rotation()),
+        (None, None) => None,
+    }
+}
+
 /// Container for a track data. Strictly speaking, it is just a set of parametric curves which can be
 /// fetched at a given time position simultaneously, producing a value of desired type. Which type of
 /// value is produced is defined by [`TrackValueKind`] enumeration. Usually a container contains up to
@@ -68,6 +152,9 @@ impl Default for TrackValueKind {
 pub struct TrackDataContainer {
     curves: Vec<Curve>,
     kind: TrackValueKind,
+    #[visit(optional)]
+    #[reflect(hidden)]
+    packed_rotations: Vec<PackedRotationKey>,
 }
 
 impl TrackDataContainer {
@@ -84,6 +171,7 @@ impl TrackDataContainer {
             curves: (0..kind.components_count())
                 .map(|_| Curve::default())
                 .collect(),
+            packed_rotations: Default::default(),
         }
     }
 
@@ -124,6 +212,19 @@ impl TrackDataContainer {
         self.kind
     }
 
+    /// Sets packed rotation keys, that are used instead of the curves when the kind of the container is
+    /// [`TrackValueKind::UnitQuaternion`]. The keys must be sorted by their locations and are interpolated linearly.
+    /// Packed keys take much less memory than curves, but they cannot be edited. Pass an empty vector to use the
+    /// curves again.
+    pub fn set_packed_rotations(&mut self, keys: Vec<PackedRotationKey>) {
+        self.packed_rotations = keys;
+    }
+
+    /// Returns packed rotation keys of the container. See [`Self::set_packed_rotations`] for more info.
+    pub fn packed_rotations(&self) -> &[PackedRotationKey] {
+        &self.packed_rotations
+    }
+
     /// Tries to get a value at a given time. The method could fail if the internal set of curves is malformed
     /// and cannot produce a desired value (for example, [`Vector3`] can be fetched only if the amount of curves
     /// is 3).
@@ -145,6 +246,9 @@ impl TrackDataContainer {
                 self.curves.get(2)?.value_at(time),
                 self.curves.get(3)?.value_at(time),
             ))),
+            TrackValueKind::UnitQuaternion if !self.packed_rotations.is_empty() => {
+                fetch_packed_rotation(&self.packed_rotations, time).map(TrackValue::UnitQuaternion)
+            }
             TrackValueKind::UnitQuaternion => {
                 // Convert Euler angles to quaternion
                 let x = self.curves.get(0)?.value_at(time);
@@ -169,6 +273,38 @@ impl TrackDataContainer {
                 length = max_location;
             }
         }
+        if let Some(last) = self.packed_rotations.last() {
+            length = length.max(last.location);
+        }
         length
     }
 }
+
+fn unwrap_angles(angles: Vector3<f32>, previous: Vector3<f32>) -> Vector3<f32> {
+    angles.zip_map(&previous, |angle, previous| {
+        angle + 2.0 * PI * ((previous - angle) / (2.0 * PI)).round()
+    })
+}
+
+/// Converts a quaternion to Euler angles (`XYZ` order) that are the closest to the previous ones. This
+/// is needed to prevent sudden "flips" when interpolating the angles.
+pub(crate) fn quat_to_continuous_euler(
+    rotation: UnitQuaternion<f32>,
+    previous: Option<Vector3<f32>>,
+) -> Vector3<f32> {
+    let (x, y, z) = rotation.euler_angles();
+    let angles = Vector3::new(x, y, z);
+    match previous {
+        None => angles,
+        Some(previous) => {
+            // Every rotation has two equivalent sets of Euler angles.
+            let a = unwrap_angles(angles, previous);
+            let b = unwrap_angles(Vector3::new(x + PI, PI - y, z + PI), previous);
+            if (a - previous).norm_squared() <= (b - previous).norm_squared() {
+                a
+            } else {
+                b
+            }
+        }
+    }
+}
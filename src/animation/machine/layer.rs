@@ -73,6 +73,17 @@ pub struct MachineLayer {
 
     mask: LayerMask,
 
+    #[visit(optional)] // Backward compatibility
+    blend_mode: LayerBlendMode,
+
+    #[visit(optional)] // Backward compatibility
+    #[reflect(hidden)]
+    reference_animation: Handle<Animation>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    reference_pose: AnimationPose,
+
     #[reflect(hidden)]
     nodes: Pool<PoseNode>,
 
@@ -91,6 +102,21 @@ pub struct MachineLayer {
     #[reflect(hidden)]
     active_transition: Handle<Transition>,
 
+    // Actual source and destination states of the active transition. They could differ from the states of the
+    // transition itself, if the transition is an any state transition, or it connects sub-machines.
+    #[visit(optional)]
+    #[reflect(hidden)]
+    transition_source: Handle<State>,
+
+    #[visit(optional)]
+    #[reflect(hidden)]
+    transition_dest: Handle<State>,
+
+    // A pose of an interrupted transition, new transition blends from this pose.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    interrupted_pose: Option<AnimationPose>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     final_pose: AnimationPose,
@@ -104,6 +130,19 @@ pub struct MachineLayer {
     debug: bool,
 }
 
+/// Defines how a layer is blended into the final pose of a state machine.
+#[derive(Default, Debug, Visit, Reflect, Clone, Copy, PartialEq, Eq)]
+pub enum LayerBlendMode {
+    /// The pose of the layer is interpolated with the poses of the previous layers using the weight of the layer.
+    #[default]
+    Override,
+    /// The difference between the pose of the layer and the reference pose (see
+    /// [`MachineLayer::set_reference_animation`]) is added on top of the poses of the previous layers. It is used for
+    /// overlays like breathing, recoil, leaning, etc. The layer affects only the nodes that are animated by the
+    /// previous layers.
+    Additive,
+}
+
 impl NameProvider for MachineLayer {
     fn name(&self) -> &str {
         &self.name
@@ -161,10 +200,16 @@ impl MachineLayer {
             active_state: Default::default(),
             entry_state: Default::default(),
             active_transition: Default::default(),
+            transition_source: Default::default(),
+            transition_dest: Default::default(),
+            interrupted_pose: None,
             weight: 1.0,
             events: FixedEventQueue::new(2048),
             debug: false,
             mask: Default::default(),
+            blend_mode: Default::default(),
+            reference_animation: Default::default(),
+            reference_pose: Default::default(),
         }
     }
 
@@ -187,13 +232,191 @@ impl MachineLayer {
     }
 
     /// Sets new entry state of the layer. Entry state will always be active on the first frame and will remain active
-    /// until some transition won't change it.
+    /// until some transition won't change it. If the entry state is a sub-machine, its entry state will be activated
+    /// (see [`Self::resolve_entry_state`]).
     #[inline]
     pub fn set_entry_state(&mut self, entry_state: Handle<State>) {
         self.active_state = entry_state;
         self.entry_state = entry_state;
     }
 
+    /// Returns the actual state, that will be activated when entering a given state. For sub-machines it is the entry
+    /// state of the nested graph (or the first state of the nested graph, if the entry state is not set), for other
+    /// states it is the state itself. Returns [`Handle::NONE`], if a sub-machine is empty.
+    pub fn resolve_entry_state(&self, mut state: Handle<State>) -> Handle<State> {
+        // The amount of iterations is limited to prevent infinite loops in malformed graphs.
+        for _ in 0..=self.states.alive_count() {
+            match self.states.try_borrow(state) {
+                Some(state_ref) if state_ref.sub_machine => {
+                    state = if self.states.is_valid_handle(state_ref.entry_state) {
+                        state_ref.entry_state
+                    } else {
+                        self.states
+                            .pair_iter()
+                            .find_map(|(h, s)| if s.parent == state { Some(h) } else { None })
+                            .unwrap_or_default()
+                    };
+                }
+                _ => return state,
+            }
+        }
+        Handle::NONE
+    }
+
+    /// Returns `true` if the given state is the `container` state itself or it belongs to the nested graph of the
+    /// `container` (directly or indirectly).
+    pub fn is_state_inside(&self, mut state: Handle<State>, container: Handle<State>) -> bool {
+        if container.is_none() {
+            return false;
+        }
+
+        for _ in 0..=self.states.alive_count() {
+            if state == container {
+                return true;
+            }
+            match self.states.try_borrow(state) {
+                Some(state_ref) => state = state_ref.parent,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Returns actual source and destination states of the active transition (if any). They could differ from the
+    /// states of the transition itself, if the transition is an any state transition, or it connects sub-machines.
+    pub fn active_transition_states(&self) -> Option<(Handle<State>, Handle<State>)> {
+        let transition = self.transitions.try_borrow(self.active_transition)?;
+        Some((
+            if self.transition_source.is_some() {
+                self.transition_source
+            } else {
+                transition.source
+            },
+            if self.transition_dest.is_some() {
+                self.transition_dest
+            } else {
+                self.resolve_entry_state(transition.dest)
+            },
+        ))
+    }
+
+    fn find_transition(
+        &self,
+        current_state: Handle<State>,
+        parameters: &ParameterContainer,
+        animations: &AnimationContainer,
+    ) -> Option<Handle<Transition>> {
+        let mut result: Option<(Handle<Transition>, i32)> = None;
+
+        for (handle, transition) in self.transitions.pair_iter() {
+            if handle == self.active_transition
+                || result.is_some_and(|(_, priority)| priority >= transition.priority)
+            {
+                continue;
+            }
+
+            // Exit transitions of sub-machines could be activated from any state of their nested graphs.
+            if !transition.any_state && !self.is_state_inside(current_state, transition.source) {
+                continue;
+            }
+
+            // Do not re-enter the state (or the sub-machine) we're already in.
+            if self.is_state_inside(current_state, transition.dest)
+                || self.resolve_entry_state(transition.dest).is_none()
+            {
+                continue;
+            }
+
+            if transition.condition.calculate_value(parameters, animations) {
+                result = Some((handle, transition.priority));
+            }
+        }
+
+        result.map(|(handle, _)| handle)
+    }
+
+    fn activate_transition(
+        &mut self,
+        handle: Handle<Transition>,
+        current_state: Handle<State>,
+        animations: &mut AnimationContainer,
+    ) {
+        if self.active_transition.is_some() {
+            // Interrupt active transition and remember its current pose, so the new transition will start from it
+            // without sudden jumps.
+            let mut interrupted_pose = AnimationPose::default();
+            self.blend_transition_pose(&mut interrupted_pose);
+            self.interrupted_pose = Some(interrupted_pose);
+
+            self.transitions[self.active_transition].reset();
+        }
+
+        let dest = self.resolve_entry_state(self.transitions[handle].dest);
+
+        if let Some(current_state_ref) = self.states.try_borrow(current_state) {
+            for action in current_state_ref.on_leave_actions.iter() {
+                action.apply(animations);
+            }
+        }
+
+        self.events.push(Event::StateLeave(current_state));
+        if self.debug {
+            Log::writeln(
+                MessageKind::Information,
+                format!("Leaving state: {}", self.states[current_state].name),
+            );
+        }
+
+        if let Some(dest_ref) = self.states.try_borrow(dest) {
+            for action in dest_ref.on_enter_actions.iter() {
+                action.apply(animations);
+            }
+        }
+
+        self.events.push(Event::StateEnter(dest));
+        if self.debug {
+            Log::writeln(
+                MessageKind::Information,
+                format!("Entering state: {}", self.states[dest].name),
+            );
+        }
+
+        self.active_state = Handle::NONE;
+        self.transition_source = current_state;
+        self.transition_dest = dest;
+
+        self.active_transition = handle;
+        self.events
+            .push(Event::ActiveTransitionChanged(self.active_transition));
+    }
+
+    fn blend_transition_pose(&self, pose: &mut AnimationPose) {
+        let (Some(transition), Some((source, dest))) = (
+            self.transitions.try_borrow(self.active_transition),
+            self.active_transition_states(),
+        ) else {
+            return;
+        };
+
+        // Blend between source and dest states.
+        if let Some(interrupted_pose) = self.interrupted_pose.as_ref() {
+            pose.blend_with(interrupted_pose, 1.0 - transition.blend_factor());
+        } else if let Some(source_pose) = self
+            .states
+            .try_borrow(source)
+            .and_then(|s| s.pose(&self.nodes))
+        {
+            pose.blend_with(&source_pose, 1.0 - transition.blend_factor());
+        }
+        if let Some(dest_pose) = self
+            .states
+            .try_borrow(dest)
+            .and_then(|s| s.pose(&self.nodes))
+        {
+            pose.blend_with(&dest_pose, transition.blend_factor());
+        }
+    }
+
     /// Returns a handle of current entry state.
     #[inline]
     pub fn entry_state(&self) -> Handle<State> {
@@ -275,6 +498,7 @@ impl MachineLayer {
         }
 
         self.active_state = self.entry_state;
+        self.interrupted_pose = None;
     }
 
     /// Fetches animation events from an active state (or a transition). It could be used to fetch animation events from a layer
@@ -306,11 +530,13 @@ impl MachineLayer {
                     strategy,
                 ),
             };
-        } else if let Some(transition) = self.transitions.try_borrow(self.active_transition) {
-            if let (Some(source_state), Some(dest_state)) = (
-                self.states.try_borrow(transition.source()),
-                self.states.try_borrow(transition.dest()),
-            ) {
+        } else if let (Some(transition), Some((source, dest))) = (
+            self.transitions.try_borrow(self.active_transition),
+            self.active_transition_states(),
+        ) {
+            if let (Some(source_state), Some(dest_state)) =
+                (self.states.try_borrow(source), self.states.try_borrow(dest))
+            {
                 let mut events = Vec::new();
                 match strategy {
                     AnimationEventCollectionStrategy::All => {
@@ -362,18 +588,10 @@ impl MachineLayer {
                 return LayerAnimationEventsCollection {
                     source: AnimationEventsSource::Transition {
                         handle: self.active_transition,
-                        source_state_handle: transition.source,
-                        dest_state_handle: transition.dest,
-                        source_state_name: self
-                            .states
-                            .try_borrow(transition.source)
-                            .map(|s| s.name.clone())
-                            .unwrap_or_default(),
-                        dest_state_name: self
-                            .states
-                            .try_borrow(transition.dest)
-                            .map(|s| s.name.clone())
-                            .unwrap_or_default(),
+                        source_state_handle: source,
+                        dest_state_handle: dest,
+                        source_state_name: source_state.name.clone(),
+                        dest_state_name: dest_state.name.clone(),
                     },
                     events,
                 };
@@ -530,6 +748,43 @@ impl MachineLayer {
         &self.mask
     }
 
+    /// Sets new blend mode of the layer. See [`LayerBlendMode`] docs for more info.
+    #[inline]
+    pub fn set_blend_mode(&mut self, blend_mode: LayerBlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    /// Returns current blend mode of the layer.
+    #[inline]
+    pub fn blend_mode(&self) -> LayerBlendMode {
+        self.blend_mode
+    }
+
+    /// Sets an animation, that will be used as a source of the reference pose for additive layers. The reference pose
+    /// is the first frame of the animation (the start of its time slice). The difference between the layer pose and the
+    /// reference pose is added on top of the previous layers. If the reference animation is not set, the layer pose
+    /// itself is treated as the difference.
+    #[inline]
+    pub fn set_reference_animation(&mut self, animation: Handle<Animation>) {
+        self.reference_animation = animation;
+    }
+
+    /// Returns a handle of the reference animation of the layer.
+    #[inline]
+    pub fn reference_animation(&self) -> Handle<Animation> {
+        self.reference_animation
+    }
+
+    /// Returns reference pose of the layer, it is available only for additive layers with the reference animation.
+    #[inline]
+    pub fn reference_pose(&self) -> Option<&AnimationPose> {
+        if self.blend_mode == LayerBlendMode::Additive && self.reference_animation.is_some() {
+            Some(&self.reference_pose)
+        } else {
+            None
+        }
+    }
+
     /// Returns final pose of the layer.
     #[inline]
     pub fn pose(&self) -> &AnimationPose {
@@ -576,74 +831,55 @@ impl MachineLayer {
     ) -> &AnimationPose {
         self.final_pose.reset();
 
+        // Active state could be a sub-machine (for example, when it is an entry state of the layer), in this case
+        // the actual state is the entry state of the nested graph.
+        if self.active_transition.is_none()
+            && self
+                .states
+                .try_borrow(self.active_state)
+                .is_some_and(|s| s.sub_machine)
+        {
+            self.active_state = self.resolve_entry_state(self.active_state);
+        }
+
         if self.active_state.is_some() || self.active_transition.is_some() {
             // Gather actual poses for each state.
             for state in self.states.iter_mut() {
                 state.update(&self.nodes, parameters, animations, dt);
             }
 
-            if self.active_transition.is_none() {
-                // Find transition.
-                for (handle, transition) in self.transitions.pair_iter_mut() {
-                    if transition.dest() == self.active_state
-                        || transition.source() != self.active_state
-                    {
-                        continue;
-                    }
-
-                    if transition.condition.calculate_value(parameters, animations) {
-                        if let Some(active_state) = self.states.try_borrow(self.active_state) {
-                            for action in active_state.on_leave_actions.iter() {
-                                action.apply(animations);
-                            }
-                        }
-
-                        self.events.push(Event::StateLeave(self.active_state));
-                        if self.debug {
-                            Log::writeln(
-                                MessageKind::Information,
-                                format!("Leaving state: {}", self.states[self.active_state].name),
-                            );
-                        }
-
-                        if let Some(source) = self.states.try_borrow(transition.dest()) {
-                            for action in source.on_enter_actions.iter() {
-                                action.apply(animations);
-                            }
-                        }
-
-                        self.events.push(Event::StateEnter(transition.dest()));
-                        if self.debug {
-                            Log::writeln(
-                                MessageKind::Information,
-                                format!("Entering state: {}", self.states[transition.dest()].name),
-                            );
-                        }
-
-                        self.active_state = Handle::NONE;
-
-                        self.active_transition = handle;
-                        self.events
-                            .push(Event::ActiveTransitionChanged(self.active_transition));
+            // Transitions could be activated either from the active state or from the destination state of the
+            // active transition, if the latter is interruptible.
+            let current_state = if let Some(active_transition) =
+                self.transitions.try_borrow(self.active_transition)
+            {
+                if active_transition.interruptible {
+                    self.active_transition_states()
+                        .map(|(_, dest)| dest)
+                        .unwrap_or_default()
+                } else {
+                    Handle::NONE
+                }
+            } else {
+                self.active_state
+            };
 
-                        break;
-                    }
+            if current_state.is_some() {
+                if let Some(handle) = self.find_transition(current_state, parameters, animations) {
+                    self.activate_transition(handle, current_state, animations);
                 }
             }
 
             // Double check for active transition because we can have empty machine.
             if self.active_transition.is_some() {
-                let transition = &mut self.transitions[self.active_transition];
+                let mut final_pose = std::mem::take(&mut self.final_pose);
+                self.blend_transition_pose(&mut final_pose);
+                self.final_pose = final_pose;
 
-                // Blend between source and dest states.
-                if let Some(source_pose) = self.states[transition.source()].pose(&self.nodes) {
-                    self.final_pose
-                        .blend_with(&source_pose, 1.0 - transition.blend_factor());
-                }
-                if let Some(dest_pose) = self.states[transition.dest()].pose(&self.nodes) {
-                    self.final_pose
-                        .blend_with(&dest_pose, transition.blend_factor());
-                }
+                let (transition_source, transition_dest) =
+                    self.active_transition_states().unwrap_or_default();
+
+                let transition = &mut self.transitions[self.active_transition];
 
                 transition.update(dt);
 
@@ -651,13 +887,16 @@ impl MachineLayer {
                     transition.reset();
 
                     self.active_transition = Handle::NONE;
+                    self.transition_source = Handle::NONE;
+                    self.transition_dest = Handle::NONE;
+                    self.interrupted_pose = None;
                     self.events
                         .push(Event::ActiveTransitionChanged(self.active_transition));
 
-                    self.active_state = transition.dest();
+                    self.active_state = transition_dest;
                     self.events.push(Event::ActiveStateChanged {
-                        prev: transition.source(),
-                        new: transition.dest(),
+                        prev: transition_source,
+                        new: transition_dest,
                     });
 
                     if self.debug {
@@ -673,7 +912,11 @@ impl MachineLayer {
             } else {
                 // We must have active state all the time when we do not have any active transition.
                 // Just get pose from active state.
-                if let Some(active_state_pose) = self.states[self.active_state].pose(&self.nodes) {
+                if let Some(active_state_pose) = self
+                    .states
+                    .try_borrow(self.active_state)
+                    .and_then(|s| s.pose(&self.nodes))
+                {
                     active_state_pose.clone_into(&mut self.final_pose);
                 }
             }
@@ -683,6 +926,129 @@ impl MachineLayer {
             .poses_mut()
             .retain(|h, _| self.mask.should_animate(*h));
 
+        if self.blend_mode == LayerBlendMode::Additive {
+            if let Some(reference_animation) = animations.try_get(self.reference_animation) {
+                reference_animation.sample_pose(
+                    reference_animation.time_slice().start,
+                    &mut self.reference_pose,
+                );
+            }
+        }
+
         &self.final_pose
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            machine::{MachineLayer, Parameter, ParameterContainer, State, Transition},
+            AnimationContainer,
+        },
+        core::pool::Handle,
+    };
+
+    fn set_rule(parameters: &mut ParameterContainer, name: &str, value: bool) {
+        *parameters.get_mut(name).unwrap() = Parameter::Rule(value);
+    }
+
+    #[test]
+    fn test_sub_machines_and_any_state_transitions() {
+        let mut layer = MachineLayer::new();
+
+        let idle = layer.add_state(State::new("Idle", Handle::NONE));
+        let locomotion = layer.add_state(State::new_sub_machine("Locomotion"));
+        let walk = layer.add_state(State::new("Walk", Handle::NONE).with_parent(locomotion));
+        let run = layer.add_state(State::new("Run", Handle::NONE).with_parent(locomotion));
+        let death = layer.add_state(State::new("Death", Handle::NONE));
+
+        assert_eq!(layer.resolve_entry_state(locomotion), walk);
+        layer.states_mut()[locomotion].entry_state = run;
+        assert_eq!(layer.resolve_entry_state(locomotion), run);
+        layer.states_mut()[locomotion].entry_state = Handle::NONE;
+        assert!(layer.is_state_inside(run, locomotion));
+        assert!(!layer.is_state_inside(idle, locomotion));
+
+        layer.add_transition(Transition::new(
+            "Idle->Locomotion",
+            idle,
+            locomotion,
+            1.0,
+            "Move",
+        ));
+        layer.add_transition(Transition::new("Walk->Run", walk, run, 1.0, "Run"));
+        // Exit transition.
+        layer.add_transition(Transition::new(
+            "Locomotion->Idle",
+            locomotion,
+            idle,
+            1.0,
+            "Stop",
+        ));
+        let mut to_death = Transition::new_any_state("Any->Death", death, 1.0, "Dead");
+        to_death.set_priority(1);
+        let to_death = layer.add_transition(to_death);
+
+        let mut parameters = ParameterContainer::default();
+        for name in ["Move", "Run", "Stop", "Dead"] {
+            parameters.add(name, Parameter::Rule(false));
+        }
+        let mut animations = AnimationContainer::new();
+
+        // Entering a sub-machine activates its entry state.
+        set_rule(&mut parameters, "Move", true);
+        layer.evaluate_pose(&mut animations, &parameters, 1.0);
+        assert_eq!(layer.active_state(), walk);
+
+        set_rule(&mut parameters, "Run", true);
+        layer.evaluate_pose(&mut animations, &parameters, 1.0);
+        assert_eq!(layer.active_state(), run);
+
+        // Exit transition could be activated from any state of the nested graph. Any state transition has higher
+        // priority, so it must be chosen.
+        set_rule(&mut parameters, "Stop", true);
+        set_rule(&mut parameters, "Dead", true);
+        layer.evaluate_pose(&mut animations, &parameters, 0.5);
+        assert_eq!(layer.active_transition(), to_death);
+        assert_eq!(layer.active_transition_states(), Some((run, death)));
+
+        layer.evaluate_pose(&mut animations, &parameters, 0.5);
+        assert_eq!(layer.active_state(), death);
+
+        // Any state transition must not re-enter its destination state.
+        layer.evaluate_pose(&mut animations, &parameters, 1.0);
+        assert_eq!(layer.active_state(), death);
+        assert!(layer.active_transition().is_none());
+    }
+
+    #[test]
+    fn test_transition_interruption() {
+        let mut layer = MachineLayer::new();
+
+        let a = layer.add_state(State::new("A", Handle::NONE));
+        let b = layer.add_state(State::new("B", Handle::NONE));
+        let c = layer.add_state(State::new("C", Handle::NONE));
+
+        let mut a_to_b = Transition::new("A->B", a, b, 1.0, "ToB");
+        a_to_b.set_interruptible(true);
+        let a_to_b = layer.add_transition(a_to_b);
+        let b_to_c = layer.add_transition(Transition::new("B->C", b, c, 1.0, "ToC"));
+
+        let mut parameters = ParameterContainer::default();
+        parameters.add("ToB", Parameter::Rule(true));
+        parameters.add("ToC", Parameter::Rule(false));
+        let mut animations = AnimationContainer::new();
+
+        layer.evaluate_pose(&mut animations, &parameters, 0.5);
+        assert_eq!(layer.active_transition(), a_to_b);
+
+        set_rule(&mut parameters, "ToC", true);
+        layer.evaluate_pose(&mut animations, &parameters, 0.5);
+        assert_eq!(layer.active_transition(), b_to_c);
+        assert_eq!(layer.active_transition_states(), Some((b, c)));
+
+        layer.evaluate_pose(&mut animations, &parameters, 1.0);
+        assert_eq!(layer.active_state(), c);
+    }
+}
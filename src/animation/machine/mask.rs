@@ -2,22 +2,43 @@
 //! for more info.
 
 use crate::{
-    core::{pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    core::{pool::Handle, reflect::prelude::*, uuid_provider, visitor::prelude::*},
     scene::{graph::Graph, node::Node},
 };
 
+/// A weight of a particular bone in a layer mask. See [`LayerMask::set_bone_weight`] docs for more info.
+#[derive(Default, Debug, Visit, Reflect, Clone, Copy, PartialEq)]
+pub struct BoneWeight {
+    /// A handle of a bone.
+    pub bone: Handle<Node>,
+    /// A weight of the bone in `[0; 1]` range.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub weight: f32,
+}
+
+uuid_provider!(BoneWeight = "d7d6f5c6-50d1-4c1a-a9f5-ee6bb8bd0475");
+
 /// Layer mask is a sort of blacklist that prevents layer from animating certain nodes. Its main use case is to
 /// disable animation on animation layers for specific body parts of humanoid (but not only) characters. The
 /// mask holds handles of nodes that **will not** be animated.
-#[derive(Default, Debug, Visit, Reflect, Clone, PartialEq, Eq)]
+///
+/// Additionally, the mask could hold per-bone weights (see [`Self::set_bone_weight`]), that allows you to animate
+/// some bones only partially. For example, an aiming layer could affect the spine only by 50%, while affecting
+/// the arms fully.
+#[derive(Default, Debug, Visit, Reflect, Clone, PartialEq)]
 pub struct LayerMask {
     excluded_bones: Vec<Handle<Node>>,
+    #[visit(optional)] // Backward compatibility
+    bone_weights: Vec<BoneWeight>,
 }
 
 impl From<Vec<Handle<Node>>> for LayerMask {
     fn from(mut excluded_bones: Vec<Handle<Node>>) -> Self {
         excluded_bones.sort_by_key(|h| h.index());
-        Self { excluded_bones }
+        Self {
+            excluded_bones,
+            bone_weights: Default::default(),
+        }
     }
 }
 
@@ -29,8 +50,12 @@ impl LayerMask {
         Self::from(graph.traverse_handle_iter(root).collect::<Vec<_>>())
     }
 
-    /// Merges a given layer mask in the current mask, handles will be automatically de-duplicated.
+    /// Merges a given layer mask in the current mask, handles will be automatically de-duplicated. Bone weights of
+    /// the other mask will overwrite respective bone weights of the current mask.
     pub fn merge(&mut self, other: LayerMask) {
+        for bone_weight in other.bone_weights.iter() {
+            self.set_bone_weight(bone_weight.bone, bone_weight.weight);
+        }
         for handle in other.into_inner() {
             if !self.contains(handle) {
                 self.add(handle);
@@ -112,6 +137,58 @@ impl LayerMask {
         !self.contains(node)
     }
 
+    /// Sets a weight of a given bone. The weight is used as a multiplier for the layer weight when the layer pose is
+    /// blended into the final pose. Bones without explicitly set weight have the weight of `1.0`, excluded bones
+    /// always have the weight of `0.0`.
+    ///
+    /// # Performance
+    ///
+    /// The method has O(log(n)) complexity, which means it is very fast for most use cases.
+    pub fn set_bone_weight(&mut self, bone: Handle<Node>, weight: f32) {
+        match self.bone_weight_index(bone) {
+            Ok(index) => self.bone_weights[index].weight = weight,
+            Err(index) => self.bone_weights.insert(index, BoneWeight { bone, weight }),
+        }
+    }
+
+    /// Removes an explicitly set weight of a given bone (if any), so the bone will have the weight of `1.0`.
+    pub fn reset_bone_weight(&mut self, bone: Handle<Node>) {
+        if let Ok(index) = self.bone_weight_index(bone) {
+            self.bone_weights.remove(index);
+        }
+    }
+
+    /// Returns a weight of a given bone. See [`Self::set_bone_weight`] for more info.
+    ///
+    /// # Performance
+    ///
+    /// The method has O(log(n)) complexity, which means it is very fast for most use cases.
+    #[inline]
+    pub fn bone_weight(&self, bone: Handle<Node>) -> f32 {
+        if self.contains(bone) {
+            0.0
+        } else if let Ok(index) = self.bone_weight_index(bone) {
+            self.bone_weights[index].weight
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns a reference to explicitly set bone weights, sorted by handles.
+    #[inline]
+    pub fn bone_weights(&self) -> &[BoneWeight] {
+        &self.bone_weights
+    }
+
+    fn bone_weight_index(&self, bone: Handle<Node>) -> Result<usize, usize> {
+        self.bone_weights.binary_search_by(|w| {
+            w.bone
+                .index()
+                .cmp(&bone.index())
+                .then(w.bone.generation().cmp(&bone.generation()))
+        })
+    }
+
     /// Return a reference to inner container. There's only non-mutable version because inner container must always
     /// be sorted.
     #[inline]
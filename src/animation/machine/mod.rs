@@ -15,10 +15,10 @@ use crate::{
 };
 
 pub use event::Event;
-pub use layer::MachineLayer;
-pub use mask::LayerMask;
+pub use layer::{LayerBlendMode, MachineLayer};
+pub use mask::{BoneWeight, LayerMask};
 pub use node::{
-    blend::{BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput},
+    blend::{BlendAdditive, BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput},
     play::PlayAnimation,
     AnimationPoseSource, PoseNode,
 };
@@ -294,10 +294,20 @@ impl Machine {
         self.final_pose.reset();
 
         for layer in self.layers.iter_mut() {
-            let weight = layer.weight();
-            let pose = layer.evaluate_pose(animations, &self.parameters, dt);
+            layer.evaluate_pose(animations, &self.parameters, dt);
 
-            self.final_pose.blend_with(pose, weight);
+            match layer.blend_mode() {
+                LayerBlendMode::Override => {
+                    self.final_pose
+                        .blend_with_mask(layer.pose(), layer.weight(), layer.mask())
+                }
+                LayerBlendMode::Additive => self.final_pose.add_difference_with_mask(
+                    layer.pose(),
+                    layer.reference_pose(),
+                    layer.weight(),
+                    layer.mask(),
+                ),
+            }
         }
 
         &self.final_pose
@@ -423,3 +423,152 @@ impl AnimationPoseSource for BlendAnimationsByIndex {
         Default::default()
     }
 }
+
+/// A node that adds a difference between an additive pose and a reference pose on top of a source pose. It is used
+/// for overlays, that must be applied on top of some other animation - breathing, recoil, leaning, etc. For example,
+/// a recoil animation could be authored as a standing pose with a recoil motion, and the standing pose could be used
+/// as the reference. In this case only the recoil motion will be added to any source pose (walking, running, etc.).
+///
+/// The node has three inputs: the source pose (index 0), the additive pose (index 1) and an optional reference pose
+/// (index 2). If the reference pose is not set, the additive pose is treated as the difference itself. Node poses,
+/// that are missing in the source pose, are not affected by the additive pose.
+#[derive(Default, Debug, Visit, Clone, Reflect, PartialEq)]
+pub struct BlendAdditive {
+    /// Base node.
+    pub base: BasePoseNode,
+
+    /// A source pose, on top of which the difference will be added.
+    #[reflect(hidden)]
+    pub source: Handle<PoseNode>,
+
+    /// An additive pose.
+    #[reflect(hidden)]
+    pub additive: Handle<PoseNode>,
+
+    /// An optional reference pose.
+    #[reflect(hidden)]
+    pub reference: Handle<PoseNode>,
+
+    /// Weight of the additive pose.
+    pub weight: PoseWeight,
+
+    /// Output pose of the node.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub output_pose: RefCell<AnimationPose>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    additive_pose: RefCell<AnimationPose>,
+}
+
+impl Deref for BlendAdditive {
+    type Target = BasePoseNode;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for BlendAdditive {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl BlendAdditive {
+    /// Creates new additive blend node.
+    pub fn new(
+        source: Handle<PoseNode>,
+        additive: Handle<PoseNode>,
+        reference: Handle<PoseNode>,
+        weight: PoseWeight,
+    ) -> Self {
+        Self {
+            base: Default::default(),
+            source,
+            additive,
+            reference,
+            weight,
+            output_pose: Default::default(),
+            additive_pose: Default::default(),
+        }
+    }
+
+    /// Return a set of handle of children nodes (source, additive and reference poses).
+    pub fn children(&self) -> Vec<Handle<PoseNode>> {
+        vec![self.source, self.additive, self.reference]
+    }
+
+    /// Returns a mutable reference to an input by its index (0 - source, 1 - additive, 2 - reference).
+    pub fn input_mut(&mut self, index: usize) -> Option<&mut Handle<PoseNode>> {
+        match index {
+            0 => Some(&mut self.source),
+            1 => Some(&mut self.additive),
+            2 => Some(&mut self.reference),
+            _ => None,
+        }
+    }
+}
+
+impl AnimationPoseSource for BlendAdditive {
+    fn eval_pose(
+        &self,
+        nodes: &Pool<PoseNode>,
+        params: &ParameterContainer,
+        animations: &AnimationContainer,
+        dt: f32,
+    ) -> Ref<'_, AnimationPose> {
+        self.output_pose.borrow_mut().reset();
+
+        if let Some(source) = nodes.try_borrow(self.source) {
+            source
+                .eval_pose(nodes, params, animations, dt)
+                .clone_into(&mut self.output_pose.borrow_mut());
+        }
+
+        if let Some(additive) = nodes.try_borrow(self.additive) {
+            // The additive pose is copied, because the reference pose could be evaluated using the same nodes.
+            additive
+                .eval_pose(nodes, params, animations, dt)
+                .clone_into(&mut self.additive_pose.borrow_mut());
+
+            let weight = self.weight.value(params).unwrap_or_default();
+            let additive_pose = self.additive_pose.borrow();
+            if let Some(reference) = nodes.try_borrow(self.reference) {
+                self.output_pose.borrow_mut().add_difference(
+                    &additive_pose,
+                    Some(&reference.eval_pose(nodes, params, animations, dt)),
+                    weight,
+                );
+            } else {
+                self.output_pose
+                    .borrow_mut()
+                    .add_difference(&additive_pose, None, weight);
+            }
+        }
+
+        self.output_pose.borrow()
+    }
+
+    fn pose(&self) -> Ref<'_, AnimationPose> {
+        self.output_pose.borrow()
+    }
+
+    fn collect_animation_events(
+        &self,
+        nodes: &Pool<PoseNode>,
+        params: &ParameterContainer,
+        animations: &AnimationContainer,
+        strategy: AnimationEventCollectionStrategy,
+    ) -> Vec<(Handle<Animation>, AnimationEvent)> {
+        let mut events = Vec::new();
+        // The reference pose is static, so it never emits events.
+        for input in [self.source, self.additive] {
+            if let Some(source) = nodes.try_borrow(input) {
+                events.extend(source.collect_animation_events(nodes, params, animations, strategy));
+            }
+        }
+        events
+    }
+}
@@ -5,8 +5,13 @@ use crate::animation::AnimationEvent;
 use crate::{
     animation::{
         machine::{
-            node::{blend::BlendAnimations, blendspace::BlendSpace, play::PlayAnimation},
-            BlendAnimationsByIndex, BlendPose, IndexedBlendInput, ParameterContainer, State,
+            node::{
+                blend::{BlendAdditive, BlendAnimations},
+                blendspace::BlendSpace,
+                play::PlayAnimation,
+            },
+            BlendAnimationsByIndex, BlendPose, IndexedBlendInput, ParameterContainer, PoseWeight,
+            State,
         },
         Animation, AnimationContainer, AnimationPose,
     },
@@ -51,6 +56,9 @@ pub enum PoseNode {
 
     /// See doc for [`BlendSpace`]
     BlendSpace(BlendSpace),
+
+    /// See docs for [`BlendAdditive`].
+    BlendAdditive(BlendAdditive),
 }
 
 impl Default for PoseNode {
@@ -79,6 +87,17 @@ impl PoseNode {
         Self::BlendAnimationsByIndex(BlendAnimationsByIndex::new(index_parameter, inputs))
     }
 
+    /// Creates new node that adds a difference between the additive and the reference poses on top of the source
+    /// pose.
+    pub fn make_blend_additive(
+        source: Handle<PoseNode>,
+        additive: Handle<PoseNode>,
+        reference: Handle<PoseNode>,
+        weight: PoseWeight,
+    ) -> Self {
+        Self::BlendAdditive(BlendAdditive::new(source, additive, reference, weight))
+    }
+
     /// Returns a set of handles to children pose nodes.
     pub fn children(&self) -> Vec<Handle<PoseNode>> {
         match self {
@@ -89,6 +108,7 @@ impl PoseNode {
             Self::BlendAnimations(blend_animations) => blend_animations.children(),
             Self::BlendAnimationsByIndex(blend_by_index) => blend_by_index.children(),
             Self::BlendSpace(blend_space) => blend_space.children(),
+            Self::BlendAdditive(blend_additive) => blend_additive.children(),
         }
     }
 }
@@ -100,6 +120,7 @@ macro_rules! static_dispatch {
             PoseNode::BlendAnimations(v) => v.$func($($args),*),
             PoseNode::BlendAnimationsByIndex(v) => v.$func($($args),*),
             PoseNode::BlendSpace(v) => v.$func($($args),*),
+            PoseNode::BlendAdditive(v) => v.$func($($args),*),
         }
     };
 }
@@ -102,6 +102,17 @@ impl StateAction {
 
 /// State is a final "container" for animation pose. It has backing pose node which provides a set of values.
 /// States can be connected with each other using _transitions_, states with transitions form a state graph.
+///
+/// # Nested state machines
+///
+/// A state could also be a sub-machine - a container for a nested state graph (see [`State::new_sub_machine`]).
+/// States of the nested graph have the sub-machine as their [`State::parent`]. A sub-machine does not have its own
+/// pose, instead:
+///
+/// - A transition to a sub-machine leads to its entry state (see [`State::entry_state`]). If the entry state is
+///   a sub-machine as well, the process is repeated.
+/// - A transition from a sub-machine is an exit transition - it could be activated from every state of the nested
+///   graph (including deeper nested graphs).
 #[derive(Default, Debug, Visit, Clone, Reflect, PartialEq)]
 pub struct State {
     /// Position of state on the canvas. It is editor-specific data.
@@ -118,9 +129,25 @@ pub struct State {
     #[visit(optional)]
     pub on_leave_actions: Vec<StateActionWrapper>,
 
-    /// Root node of the state that provides the state with animation data.
+    /// Root node of the state that provides the state with animation data. It is not used by sub-machines.
     #[reflect(read_only)]
     pub root: Handle<PoseNode>,
+
+    /// A sub-machine that contains the state. [`Handle::NONE`] means that the state belongs to the top-level graph
+    /// of a layer.
+    #[visit(optional)]
+    #[reflect(read_only)]
+    pub parent: Handle<State>,
+
+    /// Whether the state is a sub-machine (a container for a nested state graph) or not.
+    #[visit(optional)]
+    #[reflect(read_only)]
+    pub sub_machine: bool,
+
+    /// Entry state of a sub-machine. If not set, the first state of the nested graph will be used.
+    #[visit(optional)]
+    #[reflect(read_only)]
+    pub entry_state: Handle<State>,
 }
 
 impl NameProvider for State {
@@ -138,9 +165,26 @@ impl State {
             on_enter_actions: Default::default(),
             on_leave_actions: Default::default(),
             root,
+            parent: Default::default(),
+            sub_machine: false,
+            entry_state: Default::default(),
         }
     }
 
+    /// Creates new sub-machine state. See [`State`] docs for more info about nested state machines.
+    pub fn new_sub_machine(name: &str) -> Self {
+        Self {
+            sub_machine: true,
+            ..Self::new(name, Default::default())
+        }
+    }
+
+    /// Sets a sub-machine, that will contain the state.
+    pub fn with_parent(mut self, parent: Handle<State>) -> Self {
+        self.parent = parent;
+        self
+    }
+
     /// Returns a final pose of the state.
     pub fn pose<'a>(&self, nodes: &'a Pool<PoseNode>) -> Option<Ref<'a, AnimationPose>> {
         nodes.try_borrow(self.root).map(|root| root.pose())
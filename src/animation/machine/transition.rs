@@ -375,6 +375,23 @@ pub struct Transition {
 
     /// 0 - evaluates `src` pose, 1 - `dest`, 0..1 - blends `src` and `dest`
     pub(crate) blend_factor: f32,
+
+    #[reflect(
+        description = "Any state transition could be activated from every state of a layer, its source state is ignored."
+    )]
+    pub(crate) any_state: bool,
+
+    #[reflect(
+        description = "Transitions with higher priority are checked first, if there's multiple transitions \
+        that could be activated at the same time."
+    )]
+    pub(crate) priority: i32,
+
+    #[reflect(
+        description = "Whether the transition could be interrupted by transitions from its destination state \
+        (or any state transitions) while it is active."
+    )]
+    pub(crate) interruptible: bool,
 }
 
 impl Visit for Transition {
@@ -386,6 +403,9 @@ impl Visit for Transition {
         self.source.visit("Source", &mut guard)?;
         self.dest.visit("Dest", &mut guard)?;
         self.blend_factor.visit("BlendFactor", &mut guard)?;
+        let _ = self.any_state.visit("AnyState", &mut guard);
+        let _ = self.priority.visit("Priority", &mut guard);
+        let _ = self.interruptible.visit("Interruptible", &mut guard);
 
         if guard.is_reading() {
             if self.condition.visit("Condition", &mut guard).is_err() {
@@ -436,6 +456,18 @@ impl Transition {
             dest,
             blend_factor: 0.0,
             condition: LogicNode::Parameter(rule.to_owned()),
+            any_state: false,
+            priority: 0,
+            interruptible: false,
+        }
+    }
+
+    /// Creates a new named transition, that could be activated from every state of a layer. See [`Self::set_any_state`]
+    /// for more info.
+    pub fn new_any_state(name: &str, dest: Handle<State>, time: f32, rule: &str) -> Transition {
+        Self {
+            any_state: true,
+            ..Self::new(name, Handle::NONE, dest, time, rule)
         }
     }
 
@@ -473,6 +505,43 @@ impl Transition {
         &self.condition
     }
 
+    /// Makes the transition "any state" transition. Such transitions could be activated from every state of a layer
+    /// (including the states of nested state machines), the source state is ignored. It is useful to reduce the amount
+    /// of duplicated transitions, for example to switch to `Death` state from any other state.
+    pub fn set_any_state(&mut self, any_state: bool) {
+        self.any_state = any_state;
+    }
+
+    /// Returns `true` if the transition is "any state" transition, `false` - otherwise.
+    pub fn is_any_state(&self) -> bool {
+        self.any_state
+    }
+
+    /// Sets new priority of the transition. If multiple transitions could be activated at the same time, the one with
+    /// the highest priority will be activated. Transitions with the same priority are checked in the order of
+    /// creation. Default priority is zero.
+    pub fn set_priority(&mut self, priority: i32) {
+        self.priority = priority;
+    }
+
+    /// Returns the priority of the transition.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Defines whether the transition could be interrupted while it is active. Active transition could be interrupted
+    /// by transitions from its destination state or by any state transitions. The interrupting transition starts
+    /// blending from the current (blended) pose, so there are no sudden jumps. Transitions are not interruptible by
+    /// default.
+    pub fn set_interruptible(&mut self, interruptible: bool) {
+        self.interruptible = interruptible;
+    }
+
+    /// Returns `true` if the transition could be interrupted, `false` - otherwise.
+    pub fn is_interruptible(&self) -> bool {
+        self.interruptible
+    }
+
     /// Returns true if the transition from the source to the destination state was finished.
     #[inline]
     pub fn is_done(&self) -> bool {
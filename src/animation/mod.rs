@@ -30,9 +30,11 @@ use crate::animation::value::{TrackValue, ValueBinding};
 pub use pose::{AnimationPose, NodePose};
 pub use signal::{AnimationEvent, AnimationSignal};
 
+pub mod compression;
 pub mod container;
 pub mod machine;
 pub mod pose;
+pub mod retarget;
 pub mod signal;
 pub mod spritesheet;
 pub mod track;
@@ -782,6 +784,19 @@ impl Animation {
     pub fn pose(&self) -> &AnimationPose {
         &self.pose
     }
+
+    /// Samples every enabled track of the animation at the given time and writes the values into the given pose. Unlike
+    /// [`Self::pose`], it does not depend on the current time position of the animation.
+    pub fn sample_pose(&self, time: f32, pose: &mut AnimationPose) {
+        pose.reset();
+        for track in self.tracks.iter() {
+            if track.is_enabled() {
+                if let Some(bound_value) = track.fetch(time) {
+                    pose.add_to_node_pose(track.target(), bound_value);
+                }
+            }
+        }
+    }
 }
 
 impl Default for Animation {
@@ -1,7 +1,7 @@
 //! Pose is a set of property values of a node ([`NodePose`]) or a set of nodes ([`AnimationPose`]).
 
 use crate::{
-    animation::{value::BoundValue, value::BoundValueCollection, RootMotion},
+    animation::{machine::LayerMask, value::BoundValue, value::BoundValueCollection, RootMotion},
     core::{
         log::{Log, MessageKind},
         pool::Handle,
@@ -37,6 +37,13 @@ impl NodePose {
     pub fn blend_with(&mut self, other: &NodePose, weight: f32) {
         self.values.blend_with(&other.values, weight)
     }
+
+    /// Adds a difference between the other pose and the reference pose to the current pose. See
+    /// [`super::value::BoundValue::add_difference`] docs for more info.
+    pub fn add_difference(&mut self, other: &NodePose, reference: Option<&NodePose>, weight: f32) {
+        self.values
+            .add_difference(&other.values, reference.map(|r| &r.values), weight)
+    }
 }
 
 /// Animations pose is a set of node poses. See [`NodePose`] docs for more info.
@@ -83,6 +90,63 @@ impl AnimationPose {
             .blend_with(&other.root_motion.clone().unwrap_or_default(), weight);
     }
 
+    /// Does the same as [`Self::blend_with`], but the weight for each node pose is additionally multiplied by a
+    /// respective bone weight from the given layer mask. See [`LayerMask::bone_weight`] for more info.
+    pub fn blend_with_mask(&mut self, other: &AnimationPose, weight: f32, mask: &LayerMask) {
+        for (handle, other_pose) in other.poses.iter() {
+            if let Some(current_pose) = self.poses.get_mut(handle) {
+                current_pose.blend_with(other_pose, weight * mask.bone_weight(*handle));
+            } else {
+                self.add_node_pose(other_pose.clone());
+            }
+        }
+
+        self.root_motion
+            .get_or_insert_with(Default::default)
+            .blend_with(&other.root_motion.clone().unwrap_or_default(), weight);
+    }
+
+    /// Adds a difference between the other pose and the reference pose (scaled by the weight) on top of the current
+    /// pose. It is used for additive animations (breathing, recoil, leaning, etc.), that must be applied on top of
+    /// some base animation. If there's no reference pose, the other pose is treated as the difference itself. Node
+    /// poses, that are missing in the current pose, are ignored. Root motion is not affected.
+    pub fn add_difference(
+        &mut self,
+        other: &AnimationPose,
+        reference: Option<&AnimationPose>,
+        weight: f32,
+    ) {
+        for (handle, current_pose) in self.poses.iter_mut() {
+            if let Some(other_pose) = other.poses.get(handle) {
+                current_pose.add_difference(
+                    other_pose,
+                    reference.and_then(|r| r.poses.get(handle)),
+                    weight,
+                );
+            }
+        }
+    }
+
+    /// Does the same as [`Self::add_difference`], but the weight for each node pose is additionally multiplied by a
+    /// respective bone weight from the given layer mask. See [`LayerMask::bone_weight`] for more info.
+    pub fn add_difference_with_mask(
+        &mut self,
+        other: &AnimationPose,
+        reference: Option<&AnimationPose>,
+        weight: f32,
+        mask: &LayerMask,
+    ) {
+        for (handle, current_pose) in self.poses.iter_mut() {
+            if let Some(other_pose) = other.poses.get(handle) {
+                current_pose.add_difference(
+                    other_pose,
+                    reference.and_then(|r| r.poses.get(handle)),
+                    weight * mask.bone_weight(*handle),
+                );
+            }
+        }
+    }
+
     fn add_node_pose(&mut self, local_pose: NodePose) {
         self.poses.insert(local_pose.node, local_pose);
     }
@@ -154,3 +218,121 @@ impl AnimationPose {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            machine::LayerMask,
+            value::{BoundValue, TrackValue, ValueBinding},
+            AnimationPose,
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        scene::node::Node,
+    };
+
+    fn make_pose(node: Handle<Node>, binding: ValueBinding, value: TrackValue) -> AnimationPose {
+        let mut pose = AnimationPose::default();
+        pose.add_to_node_pose(node, BoundValue { binding, value });
+        pose
+    }
+
+    fn value(pose: &AnimationPose, node: Handle<Node>) -> &TrackValue {
+        &pose.poses()[&node].values.values[0].value
+    }
+
+    #[test]
+    fn test_add_difference() {
+        let node = Handle::new(1, 1);
+
+        let mut base = make_pose(
+            node,
+            ValueBinding::Position,
+            TrackValue::Vector3(Vector3::new(1.0, 0.0, 0.0)),
+        );
+        let additive = make_pose(
+            node,
+            ValueBinding::Position,
+            TrackValue::Vector3(Vector3::new(0.0, 2.0, 0.0)),
+        );
+        let reference = make_pose(
+            node,
+            ValueBinding::Position,
+            TrackValue::Vector3(Vector3::new(0.0, 1.0, 0.0)),
+        );
+        base.add_difference(&additive, Some(&reference), 0.5);
+        assert_eq!(
+            value(&base, node),
+            &TrackValue::Vector3(Vector3::new(1.0, 0.5, 0.0))
+        );
+
+        // Without a reference, the additive pose is the difference itself.
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 1.0);
+        let mut base = make_pose(
+            node,
+            ValueBinding::Rotation,
+            TrackValue::UnitQuaternion(rotation),
+        );
+        let additive = make_pose(
+            node,
+            ValueBinding::Rotation,
+            TrackValue::UnitQuaternion(rotation),
+        );
+        base.add_difference(&additive, None, 1.0);
+        let TrackValue::UnitQuaternion(result) = value(&base, node) else {
+            unreachable!()
+        };
+        assert!((result.angle() - 2.0).abs() < 0.001);
+
+        // Rest scale is one, not zero.
+        let mut base = make_pose(
+            node,
+            ValueBinding::Scale,
+            TrackValue::Vector3(Vector3::repeat(1.0)),
+        );
+        let additive = make_pose(
+            node,
+            ValueBinding::Scale,
+            TrackValue::Vector3(Vector3::repeat(2.0)),
+        );
+        base.add_difference(&additive, None, 1.0);
+        assert_eq!(
+            value(&base, node),
+            &TrackValue::Vector3(Vector3::repeat(2.0))
+        );
+    }
+
+    #[test]
+    fn test_bone_weights() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+
+        let mut mask = LayerMask::from(vec![b]);
+        mask.set_bone_weight(a, 0.25);
+        assert_eq!(mask.bone_weight(a), 0.25);
+        assert_eq!(mask.bone_weight(b), 0.0);
+        assert_eq!(mask.bone_weight(Handle::new(3, 1)), 1.0);
+
+        let mut base = make_pose(
+            a,
+            ValueBinding::Position,
+            TrackValue::Vector3(Vector3::zeros()),
+        );
+        let other = make_pose(
+            a,
+            ValueBinding::Position,
+            TrackValue::Vector3(Vector3::new(10.0, 0.0, 0.0)),
+        );
+        base.blend_with_mask(&other, 1.0, &mask);
+        assert_eq!(
+            value(&base, a),
+            &TrackValue::Vector3(Vector3::new(2.5, 0.0, 0.0))
+        );
+
+        mask.reset_bone_weight(a);
+        assert_eq!(mask.bone_weight(a), 1.0);
+    }
+}
@@ -0,0 +1,1146 @@
+//! Animation retargeting allows you to play an animation, that was made for one skeleton, on another skeleton with
+//! different bone count, bone lengths or rest pose. See [`AnimationRetargeter`] docs for more info.
+
+use crate::{
+    animation::{
+        container::{quat_to_continuous_euler, TrackDataContainer, TrackValueKind},
+        track::Track,
+        value::{TrackValue, ValueBinding},
+        Animation,
+    },
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector3},
+        curve::{CurveKey, CurveKeyKind},
+        log::{Log, MessageKind},
+        math::Matrix4Ext,
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use fxhash::FxHashMap;
+use std::f32::consts::PI;
+
+/// Rotations are stored as Euler angles, which are interpolated differently than quaternions. Spans with large
+/// rotations are subdivided, so every segment rotates at most by this angle (in radians).
+const MAX_ROTATION_STEP: f32 = 15.0 * PI / 180.0;
+
+/// Maximum amount of subdivisions of a single span of a rotation track.
+const MAX_SUBDIVISIONS: usize = 16;
+
+/// A side of a paired bone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// A bone of a standard humanoid skeleton. It is used to match bones of skeletons that have different naming
+/// conventions (Mixamo, Unreal Engine, 3ds Max Biped, etc.). See [`HumanoidBone::classify`] for more info.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HumanoidBone {
+    /// Pelvis, usually the root of the skeleton.
+    Hips,
+    /// The first (lowest) spine bone.
+    Spine,
+    /// The second spine bone.
+    Chest,
+    /// The third spine bone.
+    UpperChest,
+    /// Neck.
+    Neck,
+    /// Head.
+    Head,
+    /// Left clavicle.
+    LeftShoulder,
+    /// Left upper arm.
+    LeftUpperArm,
+    /// Left forearm.
+    LeftLowerArm,
+    /// Left hand.
+    LeftHand,
+    /// Right clavicle.
+    RightShoulder,
+    /// Right upper arm.
+    RightUpperArm,
+    /// Right forearm.
+    RightLowerArm,
+    /// Right hand.
+    RightHand,
+    /// Left thigh.
+    LeftUpperLeg,
+    /// Left calf.
+    LeftLowerLeg,
+    /// Left foot.
+    LeftFoot,
+    /// Left toes.
+    LeftToes,
+    /// Right thigh.
+    RightUpperLeg,
+    /// Right calf.
+    RightLowerLeg,
+    /// Right foot.
+    RightFoot,
+    /// Right toes.
+    RightToes,
+}
+
+impl HumanoidBone {
+    /// All humanoid bones.
+    pub const ALL: [HumanoidBone; 22] = [
+        Self::Hips,
+        Self::Spine,
+        Self::Chest,
+        Self::UpperChest,
+        Self::Neck,
+        Self::Head,
+        Self::LeftShoulder,
+        Self::LeftUpperArm,
+        Self::LeftLowerArm,
+        Self::LeftHand,
+        Self::RightShoulder,
+        Self::RightUpperArm,
+        Self::RightLowerArm,
+        Self::RightHand,
+        Self::LeftUpperLeg,
+        Self::LeftLowerLeg,
+        Self::LeftFoot,
+        Self::LeftToes,
+        Self::RightUpperLeg,
+        Self::RightLowerLeg,
+        Self::RightFoot,
+        Self::RightToes,
+    ];
+
+    // Returns normalized name patterns of the bone, `%` will be replaced with a full name of a side (`left` or
+    // `right`), `$` - with a short name of a side (`l` or `r`).
+    fn patterns(self) -> (Option<Side>, &'static [&'static str]) {
+        const SHOULDER: &[&str] = &[
+            "%shoulder",
+            "$shoulder",
+            "shoulder$",
+            "$clavicle",
+            "clavicle$",
+        ];
+        const UPPER_ARM: &[&str] = &["%arm", "%upperarm", "$upperarm", "upperarm$", "arm$"];
+        const LOWER_ARM: &[&str] = &["%forearm", "%lowerarm", "$forearm", "forearm$", "lowerarm$"];
+        const HAND: &[&str] = &["%hand", "$hand", "hand$"];
+        const UPPER_LEG: &[&str] = &["%upleg", "%upperleg", "$thigh", "thigh$", "upperleg$"];
+        const LOWER_LEG: &[&str] = &["%leg", "%lowerleg", "$calf", "calf$", "lowerleg$", "shin$"];
+        const FOOT: &[&str] = &["%foot", "$foot", "foot$"];
+        const TOES: &[&str] = &["%toebase", "%toes", "$toe0", "$toe", "toe$", "ball$"];
+
+        match self {
+            Self::Hips => (None, &["hips", "pelvis", "hip"]),
+            Self::Spine => (None, &["spine", "spine01"]),
+            Self::Chest => (None, &["chest", "spine1", "spine02"]),
+            Self::UpperChest => (None, &["upperchest", "spine2", "spine03"]),
+            Self::Neck => (None, &["neck", "neck01"]),
+            Self::Head => (None, &["head"]),
+            Self::LeftShoulder => (Some(Side::Left), SHOULDER),
+            Self::LeftUpperArm => (Some(Side::Left), UPPER_ARM),
+            Self::LeftLowerArm => (Some(Side::Left), LOWER_ARM),
+            Self::LeftHand => (Some(Side::Left), HAND),
+            Self::RightShoulder => (Some(Side::Right), SHOULDER),
+            Self::RightUpperArm => (Some(Side::Right), UPPER_ARM),
+            Self::RightLowerArm => (Some(Side::Right), LOWER_ARM),
+            Self::RightHand => (Some(Side::Right), HAND),
+            Self::LeftUpperLeg => (Some(Side::Left), UPPER_LEG),
+            Self::LeftLowerLeg => (Some(Side::Left), LOWER_LEG),
+            Self::LeftFoot => (Some(Side::Left), FOOT),
+            Self::LeftToes => (Some(Side::Left), TOES),
+            Self::RightUpperLeg => (Some(Side::Right), UPPER_LEG),
+            Self::RightLowerLeg => (Some(Side::Right), LOWER_LEG),
+            Self::RightFoot => (Some(Side::Right), FOOT),
+            Self::RightToes => (Some(Side::Right), TOES),
+        }
+    }
+
+    fn matches(self, normalized_name: &str) -> bool {
+        let (side, patterns) = self.patterns();
+        let (full, short) = match side {
+            None => ("", ""),
+            Some(Side::Left) => ("left", "l"),
+            Some(Side::Right) => ("right", "r"),
+        };
+        patterns
+            .iter()
+            .any(|pattern| pattern.replace('%', full).replace('$', short) == normalized_name)
+    }
+
+    /// Tries to find a humanoid bone that corresponds to the given bone name. The name is normalized before
+    /// matching - namespaces (`mixamorig:`), common prefixes (`Bip001`), separators and letter case are ignored.
+    /// For example `mixamorig:LeftUpLeg`, `thigh_l` and `Bip001 L Thigh` are all classified as
+    /// [`HumanoidBone::LeftUpperLeg`].
+    pub fn classify(name: &str) -> Option<Self> {
+        let normalized = normalize_bone_name(name);
+        Self::ALL
+            .iter()
+            .cloned()
+            .find(|bone| bone.matches(&normalized))
+    }
+}
+
+fn normalize_bone_name(name: &str) -> String {
+    // Strip namespace.
+    let name = name.rsplit(':').next().unwrap_or(name);
+
+    let mut normalized = name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect::<String>();
+
+    for prefix in ["mixamorig", "bip001", "bip01"] {
+        if let Some(stripped) = normalized.strip_prefix(prefix) {
+            normalized = stripped.to_string();
+            break;
+        }
+    }
+
+    normalized
+}
+
+/// An explicit mapping of a bone of a source skeleton to a bone of a target skeleton.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct BoneMappingOverride {
+    /// Name of a bone of a source skeleton.
+    pub source: String,
+    /// Name of a bone of a target skeleton.
+    pub target: String,
+}
+
+/// Bone mapping defines which bone of a source skeleton corresponds to a bone of a target skeleton. Bones are
+/// matched in the following order:
+///
+/// 1) Explicit overrides (see [`Self::set_override`]).
+/// 2) Exact names.
+/// 3) Humanoid template (if enabled), see [`HumanoidBone::classify`].
+///
+/// Source bones without a match are not retargeted.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct BoneMapping {
+    humanoid_template: bool,
+    overrides: Vec<BoneMappingOverride>,
+}
+
+impl BoneMapping {
+    /// Creates new bone mapping that matches bones only by their names.
+    pub fn by_name() -> Self {
+        Self::default()
+    }
+
+    /// Creates new bone mapping that matches bones by their names and by humanoid template. It is the best option for
+    /// humanoid characters with different naming conventions.
+    pub fn humanoid() -> Self {
+        Self {
+            humanoid_template: true,
+            overrides: Default::default(),
+        }
+    }
+
+    /// Adds new override (or replaces existing one) and returns self.
+    pub fn with_override(mut self, source: &str, target: &str) -> Self {
+        self.set_override(source, target);
+        self
+    }
+
+    /// Adds new override (or replaces existing one). Overrides have the highest priority when matching bones.
+    pub fn set_override(&mut self, source: &str, target: &str) {
+        if let Some(existing) = self.overrides.iter_mut().find(|o| o.source == source) {
+            existing.target = target.to_string();
+        } else {
+            self.overrides.push(BoneMappingOverride {
+                source: source.to_string(),
+                target: target.to_string(),
+            });
+        }
+    }
+
+    /// Removes an override of a source bone with the given name.
+    pub fn remove_override(&mut self, source: &str) -> Option<BoneMappingOverride> {
+        let index = self.overrides.iter().position(|o| o.source == source)?;
+        Some(self.overrides.remove(index))
+    }
+
+    /// Returns a reference to the overrides.
+    pub fn overrides(&self) -> &[BoneMappingOverride] {
+        &self.overrides
+    }
+
+    /// Enables or disables humanoid template.
+    pub fn set_humanoid_template(&mut self, enabled: bool) {
+        self.humanoid_template = enabled;
+    }
+
+    /// Returns `true` if the humanoid template is enabled, `false` - otherwise.
+    pub fn is_humanoid_template_enabled(&self) -> bool {
+        self.humanoid_template
+    }
+
+    /// Finds matching bones of two hierarchies and returns source-to-target mapping.
+    pub fn map(
+        &self,
+        source_graph: &Graph,
+        source_root: Handle<Node>,
+        target_graph: &Graph,
+        target_root: Handle<Node>,
+    ) -> FxHashMap<Handle<Node>, Handle<Node>> {
+        let mut target_by_name = FxHashMap::default();
+        let mut target_by_bone = FxHashMap::default();
+        for handle in target_graph.traverse_handle_iter(target_root) {
+            let name = target_graph[handle].name();
+            target_by_name.entry(name).or_insert(handle);
+            if self.humanoid_template {
+                if let Some(bone) = HumanoidBone::classify(name) {
+                    target_by_bone.entry(bone).or_insert(handle);
+                }
+            }
+        }
+
+        let mut mapping = FxHashMap::default();
+        for handle in source_graph.traverse_handle_iter(source_root) {
+            let name = source_graph[handle].name();
+
+            let target =
+                if let Some(mapping_override) = self.overrides.iter().find(|o| o.source == name) {
+                    let target = target_by_name
+                        .get(mapping_override.target.as_str())
+                        .cloned();
+                    if target.is_none() {
+                        Log::writeln(
+                            MessageKind::Warning,
+                            format!(
+                                "Unable to find target bone {} for source bone {}!",
+                                mapping_override.target, name
+                            ),
+                        );
+                    }
+                    target
+                } else if let Some(target) = target_by_name.get(name) {
+                    Some(*target)
+                } else if self.humanoid_template {
+                    HumanoidBone::classify(name).and_then(|bone| target_by_bone.get(&bone).cloned())
+                } else {
+                    None
+                };
+
+            if let Some(target) = target {
+                mapping.insert(handle, target);
+            }
+        }
+
+        mapping
+    }
+}
+
+/// Defines how the translation of the root bone (usually hips) is scaled.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub enum RootMotionScaling {
+    /// Translation of the root bone is not scaled.
+    None,
+    /// The scale is calculated as a ratio of the heights of the root bones of target and source skeletons in their
+    /// rest poses. The heights are measured in the units of the parent space of the root bones (the space of their
+    /// local translations), so scale of ancestors of the bones (for example, `0.01` scale of FBX armatures) is taken
+    /// into account. For example, a walk animation of a tall character will be scaled down for a short character,
+    /// so its feet won't slide.
+    #[default]
+    Auto,
+    /// Fixed scale.
+    Custom(f32),
+}
+
+/// Animation retargeter maps animations made for one skeleton to another skeleton with different bone count, bone
+/// lengths or rest pose.
+///
+/// # How it works
+///
+/// At first, bones of both skeletons are matched using [`BoneMapping`]. Then, for each pair of matching bones, the
+/// retargeter computes rest pose compensation - animated rotations are converted to the deltas relative to the rest
+/// pose of the source skeleton in the model space and these deltas are applied to the rest pose of the target
+/// skeleton. Translations of the bones are scaled to match bone lengths of the target skeleton, translation of the
+/// root bone (usually hips) is scaled using [`RootMotionScaling`].
+///
+/// Both skeletons should be in their rest (bind) pose when computing the mapping. Keys of rotation and
+/// translation tracks are resampled to linear keys, other tracks are copied as is.
+///
+/// # Usage
+///
+/// Retargeter could be used at import time (see `ModelResourceExtension::retarget_animations_with`), or at
+/// runtime:
+///
+/// ```rust
+/// use fyrox::{
+///     animation::{retarget::AnimationRetargeter, Animation},
+///     core::pool::Handle,
+///     scene::{graph::Graph, node::Node},
+/// };
+///
+/// fn retarget(
+///     animation: &Animation,
+///     source_graph: &Graph,
+///     source_root: Handle<Node>,
+///     target_graph: &Graph,
+///     target_root: Handle<Node>,
+/// ) -> Animation {
+///     AnimationRetargeter::humanoid()
+///         .build_map(source_graph, source_root, target_graph, target_root)
+///         .retarget_animation(animation)
+/// }
+/// ```
+///
+/// The map could be re-used to retarget multiple animations.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct AnimationRetargeter {
+    mapping: BoneMapping,
+    compensate_rest_pose: bool,
+    root_motion_scaling: RootMotionScaling,
+    root_bone: String,
+}
+
+impl Default for AnimationRetargeter {
+    fn default() -> Self {
+        Self {
+            mapping: Default::default(),
+            compensate_rest_pose: true,
+            root_motion_scaling: Default::default(),
+            root_bone: Default::default(),
+        }
+    }
+}
+
+impl AnimationRetargeter {
+    /// Creates new retargeter that matches bones by their names.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates new retargeter that matches bones by their names and by humanoid template.
+    pub fn humanoid() -> Self {
+        Self {
+            mapping: BoneMapping::humanoid(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets new bone mapping and returns self.
+    pub fn with_mapping(mut self, mapping: BoneMapping) -> Self {
+        self.mapping = mapping;
+        self
+    }
+
+    /// Defines whether rest pose compensation should be applied or not and returns self. Without the compensation,
+    /// rotations and translations are copied as is, which works only for skeletons with the same rest pose.
+    pub fn with_rest_pose_compensation(mut self, enabled: bool) -> Self {
+        self.compensate_rest_pose = enabled;
+        self
+    }
+
+    /// Sets new root motion scaling mode and returns self.
+    pub fn with_root_motion_scaling(mut self, scaling: RootMotionScaling) -> Self {
+        self.root_motion_scaling = scaling;
+        self
+    }
+
+    /// Sets the name of the root bone of the source skeleton and returns self. If the name is empty, the bone is
+    /// selected automatically: root motion node of an animation (if any), or a bone that is classified as
+    /// [`HumanoidBone::Hips`].
+    pub fn with_root_bone(mut self, name: &str) -> Self {
+        self.root_bone = name.to_string();
+        self
+    }
+
+    /// Returns a reference to the bone mapping.
+    pub fn mapping(&self) -> &BoneMapping {
+        &self.mapping
+    }
+
+    /// Returns a reference to the bone mapping.
+    pub fn mapping_mut(&mut self) -> &mut BoneMapping {
+        &mut self.mapping
+    }
+
+    /// Returns `true` if the rest pose compensation is enabled, `false` - otherwise.
+    pub fn is_rest_pose_compensation_enabled(&self) -> bool {
+        self.compensate_rest_pose
+    }
+
+    /// Returns current root motion scaling mode.
+    pub fn root_motion_scaling(&self) -> RootMotionScaling {
+        self.root_motion_scaling
+    }
+
+    /// Returns the name of the root bone of the source skeleton.
+    pub fn root_bone(&self) -> &str {
+        &self.root_bone
+    }
+
+    /// Matches bones of source and target skeletons and computes everything needed to retarget animations. The
+    /// resulting map could be used to retarget any number of animations of the source skeleton.
+    pub fn build_map(
+        &self,
+        source_graph: &Graph,
+        source_root: Handle<Node>,
+        target_graph: &Graph,
+        target_root: Handle<Node>,
+    ) -> RetargetMap {
+        let mapping = self
+            .mapping
+            .map(source_graph, source_root, target_graph, target_root);
+
+        let mut bones = FxHashMap::default();
+        for (source, target) in mapping {
+            let source_rest = RestPose::new(source_graph, source, source_root);
+            let target_rest = RestPose::new(target_graph, target, target_root);
+
+            let bone = if self.compensate_rest_pose {
+                // Effective local rotation of a node is `Pre * R * Post^-1`, `R` is what animation changes. Animated
+                // rotation is converted into a delta relative to the source rest pose (in model space), which is then
+                // applied to the target rest pose:
+                // R' = Pre'^-1 * Gp'^-1 * Gp * Pre * R * Post^-1 * G^-1 * G' * Post'
+                let parent_delta =
+                    target_rest.parent_rotation.inverse() * source_rest.parent_rotation;
+                BoneRetarget {
+                    target,
+                    pre_rotation: target_rest.pre_rotation.inverse()
+                        * parent_delta
+                        * source_rest.pre_rotation,
+                    post_rotation: source_rest.post_rotation.inverse()
+                        * source_rest.rotation.inverse()
+                        * target_rest.rotation
+                        * target_rest.post_rotation,
+                    parent_delta,
+                    source_position: source_rest.position,
+                    target_position: target_rest.position,
+                    length_ratio: length_ratio(
+                        target_rest.position.norm(),
+                        source_rest.position.norm(),
+                    ),
+                    height_ratio: length_ratio(target_rest.height, source_rest.height),
+                }
+            } else {
+                BoneRetarget {
+                    target,
+                    height_ratio: length_ratio(target_rest.height, source_rest.height),
+                    ..Default::default()
+                }
+            };
+
+            bones.insert(source, bone);
+        }
+
+        let root_bone = if self.root_bone.is_empty() {
+            source_graph
+                .traverse_handle_iter(source_root)
+                .find(|h| {
+                    HumanoidBone::classify(source_graph[*h].name()) == Some(HumanoidBone::Hips)
+                })
+                .unwrap_or_default()
+        } else {
+            source_graph
+                .find_by_name(source_root, &self.root_bone)
+                .map(|(h, _)| h)
+                .unwrap_or_default()
+        };
+
+        RetargetMap {
+            bones,
+            root_bone,
+            root_motion_scaling: self.root_motion_scaling,
+            compensate_rest_pose: self.compensate_rest_pose,
+        }
+    }
+
+    /// Retargets an animation of the source skeleton to the target skeleton. It is a shortcut for
+    /// [`Self::build_map`] + [`RetargetMap::retarget_animation`].
+    pub fn retarget(
+        &self,
+        animation: &Animation,
+        source_graph: &Graph,
+        source_root: Handle<Node>,
+        target_graph: &Graph,
+        target_root: Handle<Node>,
+    ) -> Animation {
+        self.build_map(source_graph, source_root, target_graph, target_root)
+            .retarget_animation(animation)
+    }
+}
+
+fn length_ratio(target: f32, source: f32) -> f32 {
+    if source.abs() > f32::EPSILON && target.abs() > f32::EPSILON {
+        target / source
+    } else {
+        1.0
+    }
+}
+
+struct RestPose {
+    position: Vector3<f32>,
+    pre_rotation: UnitQuaternion<f32>,
+    post_rotation: UnitQuaternion<f32>,
+    // Rotation of the node in the space of the root node of the hierarchy.
+    rotation: UnitQuaternion<f32>,
+    // Rotation of the parent node in the space of the root node of the hierarchy.
+    parent_rotation: UnitQuaternion<f32>,
+    // Height of the node in the space of the root node of the hierarchy, measured in the units of the parent space
+    // of the node, so it could be compared with local translations of the node.
+    height: f32,
+}
+
+impl RestPose {
+    fn new(graph: &Graph, node: Handle<Node>, root: Handle<Node>) -> Self {
+        fn effective_rotation(node: &Node) -> UnitQuaternion<f32> {
+            let transform = node.local_transform();
+            **transform.pre_rotation()
+                * **transform.rotation()
+                * transform.post_rotation().inverse()
+        }
+
+        let mut parent_rotation = UnitQuaternion::identity();
+        let mut matrix = Matrix4::identity();
+        if node != root {
+            let mut parent = graph[node].parent();
+            while parent.is_some() && parent != root {
+                let parent_ref = &graph[parent];
+                parent_rotation = effective_rotation(parent_ref) * parent_rotation;
+                matrix = parent_ref.local_transform().matrix() * matrix;
+                parent = parent_ref.parent();
+            }
+        }
+
+        // Average scale of the parent space, it converts model space distances into local ones.
+        let parent_scale = matrix.fixed_view::<3, 3>(0, 0).determinant().abs().cbrt();
+
+        let node_ref = &graph[node];
+        let transform = node_ref.local_transform();
+        let height = (matrix * transform.matrix()).position().y;
+        Self {
+            position: **transform.position(),
+            pre_rotation: **transform.pre_rotation(),
+            post_rotation: **transform.post_rotation(),
+            rotation: parent_rotation * effective_rotation(node_ref),
+            parent_rotation,
+            height: if parent_scale > f32::EPSILON {
+                height / parent_scale
+            } else {
+                height
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct BoneRetarget {
+    target: Handle<Node>,
+    pre_rotation: UnitQuaternion<f32>,
+    post_rotation: UnitQuaternion<f32>,
+    parent_delta: UnitQuaternion<f32>,
+    source_position: Vector3<f32>,
+    target_position: Vector3<f32>,
+    length_ratio: f32,
+    height_ratio: f32,
+}
+
+impl Default for BoneRetarget {
+    fn default() -> Self {
+        Self {
+            target: Default::default(),
+            pre_rotation: UnitQuaternion::identity(),
+            post_rotation: UnitQuaternion::identity(),
+            parent_delta: UnitQuaternion::identity(),
+            source_position: Default::default(),
+            target_position: Default::default(),
+            length_ratio: 1.0,
+            height_ratio: 1.0,
+        }
+    }
+}
+
+impl BoneRetarget {
+    fn rotation(&self, rotation: UnitQuaternion<f32>) -> UnitQuaternion<f32> {
+        self.pre_rotation * rotation * self.post_rotation
+    }
+
+    fn position(&self, position: Vector3<f32>, root_scale: Option<f32>) -> Vector3<f32> {
+        match root_scale {
+            // Root bone keeps its motion, but in the space of the target skeleton.
+            Some(scale) => self.parent_delta * position.scale(scale),
+            // Other bones keep the proportions of the target skeleton.
+            None => {
+                self.target_position
+                    + self.parent_delta * (position - self.source_position).scale(self.length_ratio)
+            }
+        }
+    }
+}
+
+/// Retarget map contains everything needed to retarget animations from one skeleton to another. Use
+/// [`AnimationRetargeter::build_map`] to create it.
+#[derive(Clone, Debug, Default)]
+pub struct RetargetMap {
+    bones: FxHashMap<Handle<Node>, BoneRetarget>,
+    root_bone: Handle<Node>,
+    root_motion_scaling: RootMotionScaling,
+    compensate_rest_pose: bool,
+}
+
+impl RetargetMap {
+    /// Returns a bone of the target skeleton, that corresponds to the given bone of the source skeleton.
+    pub fn target_of(&self, source: Handle<Node>) -> Option<Handle<Node>> {
+        self.bones.get(&source).map(|bone| bone.target)
+    }
+
+    /// Returns an iterator over source-to-target pairs of matched bones.
+    pub fn pairs(&self) -> impl Iterator<Item = (Handle<Node>, Handle<Node>)> + '_ {
+        self.bones
+            .iter()
+            .map(|(source, bone)| (*source, bone.target))
+    }
+
+    /// Retargets the given animation. Tracks of the bones without a match in the target skeleton are removed.
+    pub fn retarget_animation(&self, animation: &Animation) -> Animation {
+        let mut result = animation.clone();
+
+        let root_bone = animation
+            .root_motion_settings_ref()
+            .map(|settings| settings.node)
+            .filter(|node| self.bones.contains_key(node))
+            .unwrap_or(self.root_bone);
+
+        let root_scale = self
+            .bones
+            .get(&root_bone)
+            .map(|bone| match self.root_motion_scaling {
+                RootMotionScaling::None => 1.0,
+                RootMotionScaling::Auto => bone.height_ratio,
+                RootMotionScaling::Custom(scale) => scale,
+            });
+
+        result.retain_tracks(|track| {
+            if self.bones.contains_key(&track.target()) {
+                true
+            } else {
+                Log::writeln(
+                    MessageKind::Warning,
+                    format!(
+                        "Track {:?} of animation {} was removed, because its node has no match in the target \
+                        skeleton.",
+                        track.binding(),
+                        animation.name()
+                    ),
+                );
+                false
+            }
+        });
+
+        for track in result.tracks_mut() {
+            let bone = &self.bones[&track.target()];
+            let source = track.target();
+            track.set_target(bone.target);
+
+            match track.binding().clone() {
+                ValueBinding::Rotation if self.compensate_rest_pose => {
+                    retarget_rotation_track(track, |rotation| bone.rotation(rotation));
+                }
+                ValueBinding::Position if self.compensate_rest_pose || source == root_bone => {
+                    let is_root = source == root_bone;
+                    let scale = if is_root { root_scale } else { None };
+                    if self.compensate_rest_pose {
+                        retarget_position_track(track, |position| bone.position(position, scale));
+                    } else {
+                        let scale = scale.unwrap_or(1.0);
+                        retarget_position_track(track, |position| position.scale(scale));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if let Some(settings) = result.root_motion_settings_mut() {
+            settings.node = self.target_of(settings.node).unwrap_or_default();
+        }
+
+        result
+    }
+}
+
+// Returns sorted key locations of all curves (and packed rotations) of the container.
+fn key_locations(container: &TrackDataContainer) -> Vec<f32> {
+    let mut locations = container
+        .curves_ref()
+        .iter()
+        .flat_map(|curve| curve.keys().iter().map(|key| key.location()))
+        .chain(
+            container
+                .packed_rotations()
+                .iter()
+                .map(|key| key.location()),
+        )
+        .collect::<Vec<_>>();
+    locations.sort_by(|a, b| a.total_cmp(b));
+    locations.dedup_by(|a, b| (*a - *b).abs() <= f32::EPSILON);
+    locations
+}
+
+// Keys stay constant only if every curve has a constant key at the location. Packed rotations are always linear.
+fn key_kind(container: &TrackDataContainer, location: f32) -> CurveKeyKind {
+    let is_constant = container.packed_rotations().is_empty()
+        && container.curves_ref().iter().all(|curve| {
+            curve
+                .keys()
+                .iter()
+                .filter(|key| (key.location() - location).abs() <= f32::EPSILON)
+                .all(|key| key.kind == CurveKeyKind::Constant)
+        });
+
+    if is_constant {
+        CurveKeyKind::Constant
+    } else {
+        CurveKeyKind::Linear
+    }
+}
+
+fn fill_container(
+    container: &TrackDataContainer,
+    kind: TrackValueKind,
+    frames: Vec<(f32, Vector3<f32>, CurveKeyKind)>,
+) -> TrackDataContainer {
+    let mut new_container = TrackDataContainer::new(kind);
+    for (new_curve, curve) in new_container
+        .curves_mut()
+        .iter_mut()
+        .zip(container.curves_ref())
+    {
+        new_curve.set_name(curve.name());
+    }
+    for (location, value, kind) in frames {
+        for (curve, component) in new_container.curves_mut().iter_mut().zip(value.iter()) {
+            curve.add_key(CurveKey::new(location, *component, kind.clone()));
+        }
+    }
+    new_container
+}
+
+fn retarget_rotation_track<F>(track: &mut Track, func: F)
+where
+    F: Fn(UnitQuaternion<f32>) -> UnitQuaternion<f32>,
+{
+    let container = track.data_container();
+    let fetch = |location: f32| match container.fetch(location) {
+        Some(TrackValue::UnitQuaternion(rotation)) => Some(rotation),
+        _ => None,
+    };
+
+    let locations = key_locations(container);
+    let mut frames = Vec::new();
+    let mut previous = None;
+    for (i, location) in locations.iter().enumerate() {
+        let kind = key_kind(container, *location);
+
+        // Subdivide large rotations to preserve original interpolation as close as possible.
+        let subdivisions = match (kind.clone(), locations.get(i + 1)) {
+            (CurveKeyKind::Linear, Some(next)) => match (fetch(*location), fetch(*next)) {
+                (Some(a), Some(b)) => ((a.angle_to(&b) / MAX_ROTATION_STEP).ceil() as usize)
+                    .clamp(1, MAX_SUBDIVISIONS),
+                _ => 1,
+            },
+            _ => 1,
+        };
+
+        for k in 0..subdivisions {
+            let location = match locations.get(i + 1) {
+                Some(next) => *location + (*next - *location) * k as f32 / subdivisions as f32,
+                None => *location,
+            };
+            let Some(rotation) = fetch(location) else {
+                return;
+            };
+            let angles = quat_to_continuous_euler(func(rotation), previous);
+            previous = Some(angles);
+            frames.push((location, angles, kind.clone()));
+        }
+    }
+
+    *track.data_container_mut() = fill_container(
+        track.data_container(),
+        TrackValueKind::UnitQuaternion,
+        frames,
+    );
+}
+
+fn retarget_position_track<F>(track: &mut Track, func: F)
+where
+    F: Fn(Vector3<f32>) -> Vector3<f32>,
+{
+    let container = track.data_container();
+    let mut frames = Vec::new();
+    for location in key_locations(container) {
+        let Some(TrackValue::Vector3(position)) = container.fetch(location) else {
+            return;
+        };
+        frames.push((location, func(position), key_kind(container, location)));
+    }
+
+    *track.data_container_mut() =
+        fill_container(track.data_container(), TrackValueKind::Vector3, frames);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            retarget::{AnimationRetargeter, HumanoidBone},
+            track::test::make_track,
+            value::{TrackValue, ValueBinding},
+            Animation,
+        },
+        core::{
+            algebra::{UnitQuaternion, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder, graph::Graph, node::Node, pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn make_skeleton(
+        graph: &mut Graph,
+        names: [&str; 2],
+        hips_height: f32,
+        spine_length: f32,
+        spine_rotation: UnitQuaternion<f32>,
+    ) -> (Handle<Node>, Handle<Node>) {
+        let spine = PivotBuilder::new(
+            BaseBuilder::new().with_name(names[1]).with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, spine_length, 0.0))
+                    .with_local_rotation(spine_rotation)
+                    .build(),
+            ),
+        )
+        .build(graph);
+        let hips = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_name(names[0])
+                .with_children(&[spine])
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, hips_height, 0.0))
+                        .build(),
+                ),
+        )
+        .build(graph);
+        (hips, spine)
+    }
+
+    fn fetch(
+        animation: &Animation,
+        target: Handle<Node>,
+        binding: ValueBinding,
+        time: f32,
+    ) -> TrackValue {
+        animation
+            .tracks()
+            .iter()
+            .find(|t| t.target() == target && t.binding() == &binding)
+            .unwrap()
+            .fetch(time)
+            .unwrap()
+            .value
+    }
+
+    #[test]
+    fn test_humanoid_bone_classification() {
+        assert_eq!(
+            HumanoidBone::classify("mixamorig:Hips"),
+            Some(HumanoidBone::Hips)
+        );
+        assert_eq!(HumanoidBone::classify("pelvis"), Some(HumanoidBone::Hips));
+        assert_eq!(
+            HumanoidBone::classify("mixamorig:LeftUpLeg"),
+            Some(HumanoidBone::LeftUpperLeg)
+        );
+        assert_eq!(
+            HumanoidBone::classify("thigh_l"),
+            Some(HumanoidBone::LeftUpperLeg)
+        );
+        assert_eq!(
+            HumanoidBone::classify("Bip001 R Forearm"),
+            Some(HumanoidBone::RightLowerArm)
+        );
+        assert_eq!(
+            HumanoidBone::classify("spine_02"),
+            Some(HumanoidBone::Chest)
+        );
+        assert_eq!(HumanoidBone::classify("Weapon"), None);
+    }
+
+    #[test]
+    fn test_retarget_different_proportions() {
+        let mut source_graph = Graph::new();
+        let (source_hips, source_spine) = make_skeleton(
+            &mut source_graph,
+            ["mixamorig:Hips", "mixamorig:Spine"],
+            1.0,
+            0.5,
+            UnitQuaternion::identity(),
+        );
+        let source_root = source_graph.get_root();
+
+        // Target skeleton is twice as tall, has different names and different rest pose of the spine.
+        let target_rest_rotation = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 0.5);
+        let mut target_graph = Graph::new();
+        let (target_hips, target_spine) = make_skeleton(
+            &mut target_graph,
+            ["pelvis", "spine_01"],
+            2.0,
+            1.0,
+            target_rest_rotation,
+        );
+
+        let mut animation = Animation::default();
+        animation.add_track(
+            make_track(
+                ValueBinding::Position,
+                [
+                    (0.0, Vector3::new(0.0, 1.0, 0.0)),
+                    (1.0, Vector3::new(0.0, 1.0, 1.0)),
+                ],
+            )
+            .with_target(source_hips),
+        );
+        animation.add_track(
+            make_track(
+                ValueBinding::Rotation,
+                [
+                    (0.0, Vector3::default()),
+                    (1.0, Vector3::new(0.3, 0.0, 0.0)),
+                ],
+            )
+            .with_target(source_spine),
+        );
+        animation.add_track(
+            make_track(
+                ValueBinding::Position,
+                [
+                    (0.0, Vector3::new(0.0, 0.5, 0.0)),
+                    (1.0, Vector3::new(0.0, 0.5, 0.0)),
+                ],
+            )
+            .with_target(source_spine),
+        );
+        // A bone without a match must be removed.
+        animation.add_track(
+            make_track(ValueBinding::Scale, [(0.0, Vector3::repeat(1.0))]).with_target(source_root),
+        );
+
+        let retargeted = AnimationRetargeter::humanoid().retarget(
+            &animation,
+            &source_graph,
+            source_hips,
+            &target_graph,
+            target_hips,
+        );
+        assert_eq!(retargeted.tracks().len(), 3);
+        assert!(retargeted
+            .tracks()
+            .iter()
+            .all(|t| t.target() == target_hips || t.target() == target_spine));
+
+        // Root motion is scaled by the ratio of hips heights.
+        let TrackValue::Vector3(hips_position) =
+            fetch(&retargeted, target_hips, ValueBinding::Position, 1.0)
+        else {
+            unreachable!()
+        };
+        assert!((hips_position - Vector3::new(0.0, 2.0, 2.0)).norm() < 1e-4);
+
+        // Other bones keep the proportions of the target skeleton.
+        let TrackValue::Vector3(spine_position) =
+            fetch(&retargeted, target_spine, ValueBinding::Position, 1.0)
+        else {
+            unreachable!()
+        };
+        assert!((spine_position - Vector3::new(0.0, 1.0, 0.0)).norm() < 1e-4);
+
+        // Rest pose of the source maps to the rest pose of the target, animated delta is preserved.
+        let TrackValue::UnitQuaternion(rest_rotation) =
+            fetch(&retargeted, target_spine, ValueBinding::Rotation, 0.0)
+        else {
+            unreachable!()
+        };
+        assert!(rest_rotation.angle_to(&target_rest_rotation) < 1e-4);
+
+        let TrackValue::UnitQuaternion(rotation) =
+            fetch(&retargeted, target_spine, ValueBinding::Rotation, 1.0)
+        else {
+            unreachable!()
+        };
+        let expected =
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3) * target_rest_rotation;
+        assert!(rotation.angle_to(&expected) < 1e-4);
+    }
+
+    #[test]
+    fn test_root_motion_scaling_accounts_for_ancestor_scale() {
+        let mut source_graph = Graph::new();
+        let (source_hips, _) = make_skeleton(
+            &mut source_graph,
+            ["Hips", "Spine"],
+            1.0,
+            0.5,
+            UnitQuaternion::identity(),
+        );
+        let source_root = source_graph.get_root();
+
+        // Target skeleton has the same height, but its bones are in centimeters.
+        let mut target_graph = Graph::new();
+        let (target_hips, _) = make_skeleton(
+            &mut target_graph,
+            ["Hips", "Spine"],
+            100.0,
+            50.0,
+            UnitQuaternion::identity(),
+        );
+        let armature = PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_scale(Vector3::repeat(0.01))
+                    .build(),
+            ),
+        )
+        .build(&mut target_graph);
+        target_graph.link_nodes(target_hips, armature);
+        let target_root = target_graph.get_root();
+
+        let mut animation = Animation::default();
+        animation.add_track(
+            make_track(
+                ValueBinding::Position,
+                [
+                    (0.0, Vector3::new(0.0, 1.0, 0.0)),
+                    (1.0, Vector3::new(0.0, 1.0, 1.0)),
+                ],
+            )
+            .with_target(source_hips),
+        );
+
+        let retargeted = AnimationRetargeter::humanoid().retarget(
+            &animation,
+            &source_graph,
+            source_root,
+            &target_graph,
+            target_root,
+        );
+
+        // Local translations of the target hips are in centimeters.
+        let TrackValue::Vector3(hips_position) =
+            fetch(&retargeted, target_hips, ValueBinding::Position, 1.0)
+        else {
+            unreachable!()
+        };
+        assert!((hips_position - Vector3::new(0.0, 100.0, 100.0)).norm() < 1e-2);
+    }
+}
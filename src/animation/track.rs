@@ -161,3 +161,38 @@ impl Track {
         self.id
     }
 }
+
+#[cfg(test)]
+pub(crate) mod test {
+    use crate::{
+        animation::{
+            container::{TrackDataContainer, TrackValueKind},
+            track::Track,
+            value::ValueBinding,
+        },
+        core::{
+            algebra::Vector3,
+            curve::{CurveKey, CurveKeyKind},
+        },
+    };
+
+    /// Creates a position, rotation or scale track with linear keys at the given locations. Values of rotation
+    /// tracks are Euler angles.
+    pub(crate) fn make_track(
+        binding: ValueBinding,
+        keys: impl IntoIterator<Item = (f32, Vector3<f32>)>,
+    ) -> Track {
+        let kind = if binding == ValueBinding::Rotation {
+            TrackValueKind::UnitQuaternion
+        } else {
+            TrackValueKind::Vector3
+        };
+        let mut container = TrackDataContainer::new(kind);
+        for (location, value) in keys {
+            for (curve, component) in container.curves_mut().iter_mut().zip(value.iter()) {
+                curve.add_key(CurveKey::new(location, *component, CurveKeyKind::Linear));
+            }
+        }
+        Track::new(container, binding)
+    }
+}
@@ -156,6 +156,24 @@ impl TrackValue {
         }
     }
 
+    /// Adds a difference between `value` and `reference` scaled by the given weight to the current value. Rotations
+    /// are combined multiplicatively, the difference is applied in the local space of the current rotation. This is
+    /// used for additive blending, when an animation is applied on top of some other animation. Does nothing if the
+    /// types are different.
+    pub fn add_difference(&mut self, value: &Self, reference: &Self, weight: f32) {
+        match (self, value, reference) {
+            (Self::Real(a), Self::Real(b), Self::Real(r)) => *a += (*b - *r) * weight,
+            (Self::Vector2(a), Self::Vector2(b), Self::Vector2(r)) => *a += (b - r).scale(weight),
+            (Self::Vector3(a), Self::Vector3(b), Self::Vector3(r)) => *a += (b - r).scale(weight),
+            (Self::Vector4(a), Self::Vector4(b), Self::Vector4(r)) => *a += (b - r).scale(weight),
+            (Self::UnitQuaternion(a), Self::UnitQuaternion(b), Self::UnitQuaternion(r)) => {
+                let difference = r.inverse() * b;
+                *a *= UnitQuaternion::identity().nlerp(&difference, weight);
+            }
+            _ => (),
+        }
+    }
+
     /// Tries to perform a numeric type casting of the current value to some other and returns a boxed value, that can
     /// be used to set the value using reflection.
     pub fn numeric_type_cast(&self, value_type: ValueType) -> Option<Box<dyn Reflect>> {
@@ -308,6 +326,38 @@ impl BoundValue {
         assert_eq!(self.binding, other.binding);
         self.value.blend_with(&other.value, weight);
     }
+
+    /// Adds a difference between the `value` and the `reference` to the current value. If there's no reference, the
+    /// `value` itself is treated as the difference (in other words, the reference is the "rest" value - zero for
+    /// real numbers and vectors, identity for rotations and unit scale). See [`TrackValue::add_difference`] for more
+    /// info.
+    pub fn add_difference(&mut self, value: &Self, reference: Option<&Self>, weight: f32) {
+        assert_eq!(self.binding, value.binding);
+        match reference {
+            Some(reference) => self
+                .value
+                .add_difference(&value.value, &reference.value, weight),
+            None => self
+                .value
+                .add_difference(&value.value, &value.rest_value(), weight),
+        }
+    }
+
+    fn rest_value(&self) -> TrackValue {
+        match self.value {
+            TrackValue::Real(_) => TrackValue::Real(0.0),
+            TrackValue::Vector2(_) => TrackValue::Vector2(Default::default()),
+            TrackValue::Vector3(_) => {
+                if self.binding == ValueBinding::Scale {
+                    TrackValue::Vector3(Vector3::repeat(1.0))
+                } else {
+                    TrackValue::Vector3(Default::default())
+                }
+            }
+            TrackValue::Vector4(_) => TrackValue::Vector4(Default::default()),
+            TrackValue::UnitQuaternion(_) => TrackValue::UnitQuaternion(Default::default()),
+        }
+    }
 }
 
 /// A collection of values that are bounds to some properties.
@@ -328,6 +378,19 @@ impl BoundValueCollection {
         }
     }
 
+    /// Tries to add a difference between each value of the other collection and a respective (by binding) value of
+    /// the reference collection to a respective value of the current collection. See [`BoundValue::add_difference`]
+    /// docs for more info.
+    pub fn add_difference(&mut self, other: &Self, reference: Option<&Self>, weight: f32) {
+        for value in self.values.iter_mut() {
+            if let Some(other_value) = other.values.iter().find(|v| v.binding == value.binding) {
+                let reference_value =
+                    reference.and_then(|r| r.values.iter().find(|v| v.binding == value.binding));
+                value.add_difference(other_value, reference_value, weight);
+            }
+        }
+    }
+
     /// Tries to set each value from the collection to the respective property (by binding) of the given scene node.
     pub fn apply(&self, node_ref: &mut Node) {
         for bound_value in self.values.iter() {
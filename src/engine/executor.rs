@@ -7,6 +7,8 @@ use crate::{
         log::{Log, MessageKind},
     },
     engine::{
+        error::EngineError,
+        replay::{Recorder, Recording, Replayer},
         Engine, EngineInitParams, GraphicsContext, GraphicsContextParams, SerializationContext,
     },
     event::{Event, WindowEvent},
@@ -17,7 +19,10 @@ use crate::{
 };
 use clap::Parser;
 use std::{
+    cell::Cell,
     ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+    rc::Rc,
     sync::Arc,
 };
 
@@ -26,6 +31,60 @@ use std::{
 struct Args {
     #[clap(short, long, default_value = "")]
     override_scene: String,
+    #[clap(long, default_value = "")]
+    record: String,
+    #[clap(long, default_value_t = DEFAULT_SNAPSHOT_INTERVAL)]
+    snapshot_interval: usize,
+    #[clap(long, default_value = "")]
+    replay: String,
+}
+
+/// Defines whether the executor records the game session or replays a recorded one. See
+/// [`crate::engine::replay`] module docs for more info. The mode can also be set from the command line by
+/// `--record <path>` (with optional `--snapshot-interval <ticks>`) and `--replay <path>` arguments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// The game runs normally.
+    #[default]
+    Off,
+    /// The game session is recorded and saved to a file periodically and when the game is closed.
+    Record {
+        /// Path of the recording file.
+        path: PathBuf,
+        /// Scenes are captured on every `snapshot_interval` tick (`0` disables snapshots).
+        snapshot_interval: usize,
+    },
+    /// The recorded session is replayed as fast as possible, ignoring real input and real time. The game
+    /// exits when the replay is finished or diverges from the recording. In the latter case, the process exits
+    /// with non-zero exit code, so replays can be used as regression tests. Usually, replays are run in headless
+    /// mode (see [`Executor::set_headless`]).
+    Replay {
+        /// Path of the recording file.
+        path: PathBuf,
+    },
+}
+
+// Amount of ticks between snapshots of scenes of a recording, that is started from the command line. Snapshots
+// are relatively expensive, so they're not taken on every tick.
+const DEFAULT_SNAPSHOT_INTERVAL: usize = 60;
+
+// Amount of ticks between saves of a recording, so the recording is not lost if the game crashes.
+const RECORDING_SAVE_INTERVAL: usize = 600;
+
+enum ReplayState {
+    Off,
+    Recording { recorder: Recorder, path: PathBuf },
+    Replaying(Replayer),
+}
+
+fn save_recording(recorder: &mut Recorder, path: &Path) {
+    match recorder.recording_mut().save(path) {
+        Ok(_) => Log::info(format!("Recording saved to {}.", path.display())),
+        Err(e) => Log::err(format!(
+            "Unable to save recording to {}. Reason: {e:?}",
+            path.display()
+        )),
+    }
 }
 
 /// Executor is a small wrapper that manages plugins and scripts for your game.
@@ -34,6 +93,7 @@ pub struct Executor {
     engine: Engine,
     desired_update_rate: f32,
     headless: bool,
+    replay_mode: ReplayMode,
 }
 
 impl Deref for Executor {
@@ -79,6 +139,7 @@ impl Executor {
             engine,
             desired_update_rate: Self::DEFAULT_UPDATE_RATE,
             headless: false,
+            replay_mode: Default::default(),
         }
     }
 
@@ -100,7 +161,8 @@ impl Executor {
 
     /// Defines whether the executor should initialize graphics context or not. Headless mode could
     /// be useful for game servers, where you don't need to have a window, renderer, sound, etc.
-    /// By default, headless mode is off.
+    /// Game logic is still updated in headless mode (see [`Engine::set_headless`]). By default, headless
+    /// mode is off.
     pub fn set_headless(&mut self, headless: bool) {
         self.headless = headless;
     }
@@ -110,6 +172,16 @@ impl Executor {
         self.headless
     }
 
+    /// Sets a new replay mode. See [`ReplayMode`] docs for more info.
+    pub fn set_replay_mode(&mut self, replay_mode: ReplayMode) {
+        self.replay_mode = replay_mode;
+    }
+
+    /// Returns current replay mode.
+    pub fn replay_mode(&self) -> &ReplayMode {
+        &self.replay_mode
+    }
+
     /// Sets the desired update rate in frames per second.
     pub fn set_desired_update_rate(&mut self, update_rate: f32) {
         self.desired_update_rate = update_rate.abs();
@@ -128,14 +200,70 @@ impl Executor {
         self.engine.add_plugin_constructor(plugin)
     }
 
+    /// Adds new dynamic plugin to the executor, the plugin will be enabled only on [`Executor::run`]. See
+    /// [`Engine::add_dynamic_plugin`] for more info.
+    ///
+    /// # Safety
+    ///
+    /// See [`Engine::add_dynamic_plugin`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub unsafe fn add_dynamic_plugin<P: AsRef<std::path::Path>>(
+        &mut self,
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<(), EngineError> {
+        self.engine.add_dynamic_plugin(path, reload_when_changed)
+    }
+
     /// Runs the executor - starts your game.
     pub fn run(self) {
         let mut engine = self.engine;
         let event_loop = self.event_loop;
         let headless = self.headless;
+        let mut replay_mode = self.replay_mode;
+
+        engine.set_headless(headless);
 
         let args = Args::parse();
 
+        if !args.record.is_empty() {
+            replay_mode = ReplayMode::Record {
+                path: args.record.into(),
+                snapshot_interval: args.snapshot_interval,
+            };
+        } else if !args.replay.is_empty() {
+            replay_mode = ReplayMode::Replay {
+                path: args.replay.into(),
+            };
+        }
+
+        // The seed must be set before plugins are initialized, because they could use the random number generator.
+        let mut replay_state = match replay_mode {
+            ReplayMode::Off => ReplayState::Off,
+            ReplayMode::Record {
+                path,
+                snapshot_interval,
+            } => ReplayState::Recording {
+                recorder: Recorder::new(engine.random.seed(), snapshot_interval, &mut engine.input),
+                path,
+            },
+            ReplayMode::Replay { path } => {
+                let recording = Recording::load(&path).unwrap_or_else(|e| {
+                    panic!("Unable to load recording {}. Reason: {e:?}", path.display())
+                });
+                let replayer = Replayer::new(recording, &mut engine.input);
+                engine.random.reseed(replayer.seed());
+                ReplayState::Replaying(replayer)
+            }
+        };
+        let replay_failed = Rc::new(Cell::new(false));
+        let replay_failed_flag = replay_failed.clone();
+
+        // Games must not write anything to their data directory, so only existing metadata is used. The scan
+        // runs in the background, resources requested by their ids will wait until it is finished.
+        #[cfg(not(target_arch = "wasm32"))]
+        engine.resource_manager.spawn_registry_update("./", false);
+
         engine.enable_plugins(
             if args.override_scene.is_empty() {
                 None
@@ -153,7 +281,20 @@ impl Executor {
         run_executor(event_loop, move |event, window_target| {
             window_target.set_control_flow(ControlFlow::Wait);
 
-            engine.handle_os_event_by_plugins(&event, fixed_time_step, window_target, &mut lag);
+            engine.input.handle_os_event(&event);
+
+            if let ReplayState::Recording { recorder, .. } = &mut replay_state {
+                recorder.record_os_event(&event);
+            }
+
+            // Real input must not affect replayed session, recorded input is passed to plugins and scripts
+            // instead (see below).
+            let forward_event = !matches!(replay_state, ReplayState::Replaying(_))
+                || !matches!(event, Event::WindowEvent { .. } | Event::DeviceEvent { .. });
+
+            if forward_event {
+                engine.handle_os_event_by_plugins(&event, fixed_time_step, window_target, &mut lag);
+            }
 
             let scenes = engine
                 .scenes
@@ -166,7 +307,9 @@ impl Executor {
                     engine.register_scripted_scene(scene_handle);
                 }
 
-                engine.handle_os_event_by_scripts(&event, scene_handle, fixed_time_step);
+                if forward_event {
+                    engine.handle_os_event_by_scripts(&event, scene_handle, fixed_time_step);
+                }
             }
 
             match event {
@@ -193,19 +336,99 @@ impl Executor {
                     );
                 }
                 Event::AboutToWait => {
-                    let elapsed = previous.elapsed();
-                    previous = Instant::now();
-                    lag += elapsed.as_secs_f32();
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if engine.has_changed_dynamic_plugins() {
+                        Log::verify(engine.reload_dynamic_plugins(Some(window_target)));
+                    }
+
+                    if let ReplayState::Replaying(replayer) = &mut replay_state {
+                        // Replay runs as fast as possible, one recorded tick per iteration.
+                        window_target.set_control_flow(ControlFlow::Poll);
+
+                        // Recorded ticks must not be skipped, so the replay waits until the engine is
+                        // able to update (until the graphics context is created, for example).
+                        if engine.is_updating() {
+                            if let Some(dt) = replayer.begin_tick(&mut engine.input) {
+                                for os_event in replayer.os_events() {
+                                    engine.handle_os_event_by_plugins(
+                                        &os_event,
+                                        dt,
+                                        window_target,
+                                        &mut lag,
+                                    );
+                                    for &scene_handle in scenes.iter() {
+                                        engine.handle_os_event_by_scripts(
+                                            &os_event,
+                                            scene_handle,
+                                            dt,
+                                        );
+                                    }
+                                }
+
+                                engine.update(dt, window_target, &mut lag, Default::default());
+                                match replayer.end_tick(&mut engine.scenes) {
+                                    Ok(None) => (),
+                                    Ok(Some(divergence)) => {
+                                        Log::err(divergence.to_string());
+                                        replay_failed_flag.set(true);
+                                        window_target.exit();
+                                    }
+                                    Err(e) => {
+                                        Log::err(format!(
+                                            "Unable to compare snapshots. Reason: {e:?}"
+                                        ));
+                                        replay_failed_flag.set(true);
+                                        window_target.exit();
+                                    }
+                                }
+                            } else {
+                                Log::info(format!(
+                                    "Replay of {} ticks finished without divergences.",
+                                    replayer.position()
+                                ));
+                                window_target.exit();
+                            }
+                        }
+                    } else {
+                        let elapsed = previous.elapsed();
+                        previous = Instant::now();
+                        lag += elapsed.as_secs_f32();
 
-                    while lag >= fixed_time_step {
-                        engine.update(fixed_time_step, window_target, &mut lag, Default::default());
-                        lag -= fixed_time_step;
+                        while lag >= fixed_time_step {
+                            engine.update(
+                                fixed_time_step,
+                                window_target,
+                                &mut lag,
+                                Default::default(),
+                            );
+                            if let ReplayState::Recording { recorder, path } = &mut replay_state {
+                                // A tick, that was not performed, must not be recorded.
+                                if engine.is_updating() {
+                                    Log::verify(recorder.record_tick(
+                                        fixed_time_step,
+                                        &mut engine.input,
+                                        &mut engine.scenes,
+                                    ));
+                                    if recorder.recording().ticks.len() % RECORDING_SAVE_INTERVAL
+                                        == 0
+                                    {
+                                        save_recording(recorder, path);
+                                    }
+                                }
+                            }
+                            lag -= fixed_time_step;
+                        }
                     }
 
                     if let GraphicsContext::Initialized(ref ctx) = engine.graphics_context {
                         ctx.window.request_redraw();
                     }
                 }
+                Event::LoopExiting => {
+                    if let ReplayState::Recording { recorder, path } = &mut replay_state {
+                        save_recording(recorder, path);
+                    }
+                }
                 Event::WindowEvent { event, .. } => {
                     match event {
                         WindowEvent::CloseRequested => window_target.exit(),
@@ -235,7 +458,11 @@ impl Executor {
                 }
                 _ => (),
             }
-        })
+        });
+
+        if replay_failed.get() {
+            std::process::exit(1);
+        }
     }
 }
 
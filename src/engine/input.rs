@@ -0,0 +1,1181 @@
+//! Input subsystem maps raw input from keyboard, mouse and gamepads to named actions and axes, so game code
+//! does not need to deal with raw OS events. See [`Input`] docs for more info.
+
+use crate::{
+    core::{
+        algebra::Vector2,
+        parking_lot::Mutex,
+        reflect::prelude::*,
+        visitor::{prelude::*, Visitor},
+    },
+    event::{DeviceEvent, ElementState, Event, MouseScrollDelta, WindowEvent},
+    gui::message::{KeyCode, MouseButton},
+    keyboard::PhysicalKey,
+    utils::{translate_button, translate_key_to_ui},
+};
+use fxhash::{FxHashMap, FxHashSet};
+use std::sync::Arc;
+
+/// Unique identifier of a gamepad. It is assigned by an input backend when a gamepad is connected.
+pub type GamepadId = usize;
+
+/// A button of a gamepad. Names of the face buttons are based on their position, for example [`Self::South`]
+/// is `A` on Xbox controllers and `Cross` on PlayStation controllers.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy, Visit, Reflect)]
+#[allow(missing_docs)]
+pub enum GamepadButton {
+    #[default]
+    South,
+    East,
+    North,
+    West,
+    LeftTrigger,
+    LeftTrigger2,
+    RightTrigger,
+    RightTrigger2,
+    Select,
+    Start,
+    Mode,
+    LeftThumb,
+    RightThumb,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+/// An analog axis of a gamepad. Values of the axes are in `[-1.0; 1.0]` range.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy, Visit, Reflect)]
+#[allow(missing_docs)]
+pub enum GamepadAxis {
+    #[default]
+    LeftStickX,
+    LeftStickY,
+    LeftZ,
+    RightStickX,
+    RightStickY,
+    RightZ,
+}
+
+/// Relative axis of a mouse. Values of the axes are not normalized, they contain the amount of movement
+/// (in device-specific units) that was made since the previous update of the input.
+#[derive(Debug, Default, Hash, PartialEq, Eq, Clone, Copy, Visit, Reflect)]
+pub enum MouseAxis {
+    /// Horizontal movement of the mouse.
+    #[default]
+    X,
+    /// Vertical movement of the mouse.
+    Y,
+    /// Vertical scrolling of the mouse wheel.
+    Wheel,
+}
+
+/// A physical input, that can be bound to an action or an axis.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy, Visit, Reflect)]
+pub enum InputSource {
+    /// A key of a keyboard.
+    Key(KeyCode),
+    /// A button of a mouse.
+    MouseButton(MouseButton),
+    /// A relative axis of a mouse.
+    MouseAxis(MouseAxis),
+    /// A button of any connected gamepad.
+    GamepadButton(GamepadButton),
+    /// An axis of any connected gamepad.
+    GamepadAxis(GamepadAxis),
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        Self::Key(KeyCode::Unknown)
+    }
+}
+
+impl InputSource {
+    /// Returns `true` if the source can only be either pressed or released.
+    pub fn is_digital(&self) -> bool {
+        matches!(self, Self::Key(_) | Self::MouseButton(_))
+    }
+}
+
+/// A binding of an input source to an action or an axis.
+#[derive(Debug, PartialEq, Clone, Visit, Reflect)]
+pub struct InputBinding {
+    /// An input source.
+    pub source: InputSource,
+    /// A multiplier for the value of the source. Negative scale can be used to bind an input source to the
+    /// negative direction of an axis (for example, `A` key to the left movement) or to invert an axis.
+    pub scale: f32,
+}
+
+impl Default for InputBinding {
+    fn default() -> Self {
+        Self {
+            source: Default::default(),
+            scale: 1.0,
+        }
+    }
+}
+
+impl From<InputSource> for InputBinding {
+    fn from(source: InputSource) -> Self {
+        Self::new(source)
+    }
+}
+
+impl InputBinding {
+    /// Creates a new binding of the given source with the scale of `1.0`.
+    pub fn new(source: InputSource) -> Self {
+        Self { source, scale: 1.0 }
+    }
+
+    /// Sets a new scale of the binding.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+}
+
+/// A named action, that can be either active or inactive. For example, "Jump", "Fire", "Pause" and so on.
+#[derive(Debug, PartialEq, Clone, Visit, Reflect)]
+pub struct InputAction {
+    /// Name of the action.
+    pub name: String,
+    /// A set of bindings of the action. The action is active if any of its bindings is active.
+    pub bindings: Vec<InputBinding>,
+    /// Minimal scaled value of an analog input source (gamepad axis, trigger, etc.) at which the action
+    /// becomes active. Default is `0.5`.
+    pub threshold: f32,
+}
+
+impl Default for InputAction {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            bindings: Default::default(),
+            threshold: 0.5,
+        }
+    }
+}
+
+impl InputAction {
+    /// Creates a new action without any bindings.
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a new binding to the action.
+    pub fn with_binding<B: Into<InputBinding>>(mut self, binding: B) -> Self {
+        self.bindings.push(binding.into());
+        self
+    }
+
+    /// Sets a new activation threshold of the action.
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+}
+
+/// A named axis, that has a value in `[-1.0; 1.0]` range (mouse axes are not bound to the range). For
+/// example, "MoveForward", "Turn" and so on.
+#[derive(Debug, PartialEq, Clone, Visit, Reflect)]
+pub struct InputAxis {
+    /// Name of the axis.
+    pub name: String,
+    /// A set of bindings of the axis. Value of the axis is the sum of scaled values of its bindings.
+    pub bindings: Vec<InputBinding>,
+    /// Values of gamepad axes with magnitude less than the dead zone are treated as zero. It is used to
+    /// suppress noise of sticks near their rest position. Default is `0.15`.
+    pub dead_zone: f32,
+}
+
+impl Default for InputAxis {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            bindings: Default::default(),
+            dead_zone: 0.15,
+        }
+    }
+}
+
+impl InputAxis {
+    /// Creates a new axis without any bindings.
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a new binding to the axis.
+    pub fn with_binding<B: Into<InputBinding>>(mut self, binding: B) -> Self {
+        self.bindings.push(binding.into());
+        self
+    }
+
+    /// Sets a new dead zone of the axis.
+    pub fn with_dead_zone(mut self, dead_zone: f32) -> Self {
+        self.dead_zone = dead_zone;
+        self
+    }
+}
+
+/// A named set of actions and axes, that are active only when the context is on the stack of active contexts
+/// of [`Input`]. For example, a game could have "Gameplay", "Vehicle" and "Menu" contexts.
+#[derive(Debug, Default, PartialEq, Clone, Visit, Reflect)]
+pub struct InputContext {
+    /// Name of the context.
+    pub name: String,
+    /// A set of actions of the context.
+    pub actions: Vec<InputAction>,
+    /// A set of axes of the context.
+    pub axes: Vec<InputAxis>,
+    /// If `true`, then actions and axes of the contexts below this one on the stack of active contexts are
+    /// disabled. It could be used, for example, to prevent a character from moving while a menu is opened.
+    pub blocking: bool,
+}
+
+impl InputContext {
+    /// Creates a new empty context.
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a new action to the context.
+    pub fn with_action(mut self, action: InputAction) -> Self {
+        self.actions.push(action);
+        self
+    }
+
+    /// Adds a new axis to the context.
+    pub fn with_axis(mut self, axis: InputAxis) -> Self {
+        self.axes.push(axis);
+        self
+    }
+
+    /// Defines whether the context blocks the contexts below it or not.
+    pub fn with_blocking(mut self, blocking: bool) -> Self {
+        self.blocking = blocking;
+        self
+    }
+
+    /// Returns a reference to an action with the given name.
+    pub fn action(&self, name: &str) -> Option<&InputAction> {
+        self.actions.iter().find(|a| a.name == name)
+    }
+
+    /// Returns a reference to an action with the given name.
+    pub fn action_mut(&mut self, name: &str) -> Option<&mut InputAction> {
+        self.actions.iter_mut().find(|a| a.name == name)
+    }
+
+    /// Returns a reference to an axis with the given name.
+    pub fn axis(&self, name: &str) -> Option<&InputAxis> {
+        self.axes.iter().find(|a| a.name == name)
+    }
+
+    /// Returns a reference to an axis with the given name.
+    pub fn axis_mut(&mut self, name: &str) -> Option<&mut InputAxis> {
+        self.axes.iter_mut().find(|a| a.name == name)
+    }
+}
+
+/// A set of all input contexts of a game. The map can be modified at runtime (for example, to let a player
+/// rebind controls) and saved using [`Visit`] trait.
+#[derive(Debug, Default, PartialEq, Clone, Visit, Reflect)]
+pub struct InputMap {
+    /// A set of input contexts.
+    pub contexts: Vec<InputContext>,
+}
+
+impl InputMap {
+    /// Adds a new context to the map. If there is a context with the same name, it will be replaced.
+    pub fn add_context(&mut self, context: InputContext) {
+        if let Some(existing) = self.context_mut(&context.name) {
+            *existing = context;
+        } else {
+            self.contexts.push(context);
+        }
+    }
+
+    /// Returns a reference to a context with the given name.
+    pub fn context(&self, name: &str) -> Option<&InputContext> {
+        self.contexts.iter().find(|c| c.name == name)
+    }
+
+    /// Returns a reference to a context with the given name.
+    pub fn context_mut(&mut self, name: &str) -> Option<&mut InputContext> {
+        self.contexts.iter_mut().find(|c| c.name == name)
+    }
+
+    /// Saves the map into a memory buffer, which then can be written to a file.
+    pub fn save_to_memory(&mut self) -> Result<Vec<u8>, VisitError> {
+        let mut visitor = Visitor::new();
+        self.visit("InputMap", &mut visitor)?;
+        visitor.save_binary_to_vec()
+    }
+
+    /// Loads the map from a memory buffer, that was created by [`Self::save_to_memory`].
+    pub fn load_from_memory(data: &[u8]) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_from_memory(data)?;
+        let mut map = Self::default();
+        map.visit("InputMap", &mut visitor)?;
+        Ok(map)
+    }
+}
+
+/// A raw input event, that is used to update the state of [`Input`].
+#[derive(Debug, PartialEq, Clone, Visit)]
+pub enum InputEvent {
+    /// A key was pressed or released.
+    Key {
+        /// Code of the key.
+        key: KeyCode,
+        /// New state of the key.
+        pressed: bool,
+    },
+    /// A mouse button was pressed or released.
+    MouseButton {
+        /// The mouse button.
+        button: MouseButton,
+        /// New state of the button.
+        pressed: bool,
+    },
+    /// The mouse was moved by the given amount.
+    MouseMotion {
+        /// Relative movement of the mouse.
+        delta: Vector2<f32>,
+    },
+    /// The mouse wheel was scrolled by the given amount of lines.
+    MouseWheel {
+        /// Amount of scrolling.
+        delta: f32,
+    },
+    /// A gamepad was connected.
+    GamepadConnected(GamepadId),
+    /// A gamepad was disconnected.
+    GamepadDisconnected(GamepadId),
+    /// A value of a gamepad button has changed. Digital buttons have either `0.0` or `1.0` values, analog
+    /// buttons (triggers) have values in `[0.0; 1.0]` range.
+    GamepadButton {
+        /// Id of the gamepad.
+        gamepad: GamepadId,
+        /// The button.
+        button: GamepadButton,
+        /// New value of the button.
+        value: f32,
+    },
+    /// A value of a gamepad axis has changed.
+    GamepadAxis {
+        /// Id of the gamepad.
+        gamepad: GamepadId,
+        /// The axis.
+        axis: GamepadAxis,
+        /// New value of the axis in `[-1.0; 1.0]` range.
+        value: f32,
+    },
+}
+
+impl Default for InputEvent {
+    fn default() -> Self {
+        // An event without any effect.
+        Self::MouseWheel { delta: 0.0 }
+    }
+}
+
+/// Input backend is a source of input events, that are not passed to the engine via the OS events (for
+/// example, gamepad events). The engine polls the backend on every update.
+pub trait InputBackend {
+    /// Moves all pending events of the backend to the given vector.
+    fn poll(&mut self, events: &mut Vec<InputEvent>);
+}
+
+/// An input backend, that produces only events, that were pushed into it manually. It could be used in tests
+/// or to simulate input (for example, to replay recorded input). Clones of the backend share the same event
+/// queue, so one clone can be passed to [`Input::set_backend`] and another one can be used to push events.
+#[derive(Default, Clone)]
+pub struct FakeInputBackend {
+    events: Arc<Mutex<Vec<InputEvent>>>,
+}
+
+impl FakeInputBackend {
+    /// Adds a new event to the queue of the backend.
+    pub fn push(&self, event: InputEvent) {
+        self.events.lock().push(event);
+    }
+}
+
+impl InputBackend for FakeInputBackend {
+    fn poll(&mut self, events: &mut Vec<InputEvent>) {
+        events.append(&mut self.events.lock());
+    }
+}
+
+/// An input backend, that uses `gilrs` crate to receive events from gamepads.
+#[cfg(feature = "gilrs")]
+pub struct GilrsInputBackend {
+    gilrs: gilrs::Gilrs,
+}
+
+#[cfg(feature = "gilrs")]
+impl GilrsInputBackend {
+    /// Creates a new backend. It could fail if the current platform is not supported by `gilrs`.
+    pub fn new() -> Result<Self, Box<gilrs::Error>> {
+        Ok(Self {
+            gilrs: gilrs::Gilrs::new().map_err(Box::new)?,
+        })
+    }
+
+    fn translate_button(button: gilrs::Button) -> Option<GamepadButton> {
+        Some(match button {
+            gilrs::Button::South => GamepadButton::South,
+            gilrs::Button::East => GamepadButton::East,
+            gilrs::Button::North => GamepadButton::North,
+            gilrs::Button::West => GamepadButton::West,
+            gilrs::Button::LeftTrigger => GamepadButton::LeftTrigger,
+            gilrs::Button::LeftTrigger2 => GamepadButton::LeftTrigger2,
+            gilrs::Button::RightTrigger => GamepadButton::RightTrigger,
+            gilrs::Button::RightTrigger2 => GamepadButton::RightTrigger2,
+            gilrs::Button::Select => GamepadButton::Select,
+            gilrs::Button::Start => GamepadButton::Start,
+            gilrs::Button::Mode => GamepadButton::Mode,
+            gilrs::Button::LeftThumb => GamepadButton::LeftThumb,
+            gilrs::Button::RightThumb => GamepadButton::RightThumb,
+            gilrs::Button::DPadUp => GamepadButton::DPadUp,
+            gilrs::Button::DPadDown => GamepadButton::DPadDown,
+            gilrs::Button::DPadLeft => GamepadButton::DPadLeft,
+            gilrs::Button::DPadRight => GamepadButton::DPadRight,
+            _ => return None,
+        })
+    }
+
+    fn translate_axis(axis: gilrs::Axis) -> Option<GamepadAxis> {
+        Some(match axis {
+            gilrs::Axis::LeftStickX => GamepadAxis::LeftStickX,
+            gilrs::Axis::LeftStickY => GamepadAxis::LeftStickY,
+            gilrs::Axis::LeftZ => GamepadAxis::LeftZ,
+            gilrs::Axis::RightStickX => GamepadAxis::RightStickX,
+            gilrs::Axis::RightStickY => GamepadAxis::RightStickY,
+            gilrs::Axis::RightZ => GamepadAxis::RightZ,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(feature = "gilrs")]
+impl InputBackend for GilrsInputBackend {
+    fn poll(&mut self, events: &mut Vec<InputEvent>) {
+        while let Some(gilrs::Event { id, event, .. }) = self.gilrs.next_event() {
+            let gamepad = usize::from(id);
+            match event {
+                gilrs::EventType::ButtonPressed(button, _) => {
+                    if let Some(button) = Self::translate_button(button) {
+                        events.push(InputEvent::GamepadButton {
+                            gamepad,
+                            button,
+                            value: 1.0,
+                        });
+                    }
+                }
+                gilrs::EventType::ButtonReleased(button, _) => {
+                    if let Some(button) = Self::translate_button(button) {
+                        events.push(InputEvent::GamepadButton {
+                            gamepad,
+                            button,
+                            value: 0.0,
+                        });
+                    }
+                }
+                gilrs::EventType::ButtonChanged(button, value, _) => {
+                    if let Some(button) = Self::translate_button(button) {
+                        events.push(InputEvent::GamepadButton {
+                            gamepad,
+                            button,
+                            value,
+                        });
+                    }
+                }
+                gilrs::EventType::AxisChanged(axis, value, _) => {
+                    if let Some(axis) = Self::translate_axis(axis) {
+                        events.push(InputEvent::GamepadAxis {
+                            gamepad,
+                            axis,
+                            value,
+                        });
+                    }
+                }
+                gilrs::EventType::Connected => events.push(InputEvent::GamepadConnected(gamepad)),
+                gilrs::EventType::Disconnected => {
+                    events.push(InputEvent::GamepadDisconnected(gamepad))
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+/// State of a single gamepad.
+#[derive(Debug, Default, Clone)]
+pub struct GamepadState {
+    buttons: FxHashMap<GamepadButton, f32>,
+    axes: FxHashMap<GamepadAxis, f32>,
+}
+
+impl GamepadState {
+    /// Returns a value of the given button in `[0.0; 1.0]` range.
+    pub fn button(&self, button: GamepadButton) -> f32 {
+        self.buttons.get(&button).cloned().unwrap_or_default()
+    }
+
+    /// Returns a raw value (without dead zone applied) of the given axis in `[-1.0; 1.0]` range.
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes.get(&axis).cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct ActionState {
+    value: f32,
+    pressed: bool,
+    was_pressed: bool,
+}
+
+/// Input is a layer between raw input (keyboard, mouse, gamepads) and game logic. Instead of checking for
+/// particular keys, game logic checks for named actions ("Jump") and axes ("MoveForward"), which are defined
+/// in [`InputMap`] and can be bound to any input source and rebound at runtime.
+///
+/// ## Contexts
+///
+/// Actions and axes are grouped in [`InputContext`]s. Only the contexts on the stack of active contexts are
+/// processed (see [`Self::push_context`] and [`Self::pop_context`]). When an action or an axis is defined in
+/// multiple active contexts, the topmost context wins. A blocking context disables all contexts below it.
+///
+/// ## Updating
+///
+/// The engine updates the input right before updating plugins and scripts, so the state of actions and axes
+/// stays the same during an update tick. OS events are passed to the input by the executor, if you're using
+/// your own game loop, you must call [`Self::handle_os_event`] for each event.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     engine::input::{Input, InputAction, InputAxis, InputBinding, InputContext, InputSource, GamepadAxis, GamepadButton},
+/// #     gui::message::KeyCode,
+/// # };
+/// fn setup(input: &mut Input) {
+///     input.map.add_context(
+///         InputContext::new("Gameplay")
+///             .with_action(
+///                 InputAction::new("Jump")
+///                     .with_binding(InputSource::Key(KeyCode::Space))
+///                     .with_binding(InputSource::GamepadButton(GamepadButton::South)),
+///             )
+///             .with_axis(
+///                 InputAxis::new("MoveRight")
+///                     .with_binding(InputBinding::new(InputSource::Key(KeyCode::KeyA)).with_scale(-1.0))
+///                     .with_binding(InputSource::Key(KeyCode::KeyD))
+///                     .with_binding(InputSource::GamepadAxis(GamepadAxis::LeftStickX)),
+///             ),
+///     );
+///     input.push_context("Gameplay");
+/// }
+///
+/// fn update(input: &Input) {
+///     if input.is_action_just_pressed("Jump") {
+///         // Jump.
+///     }
+///     let _speed = input.axis_value("MoveRight");
+/// }
+/// ```
+#[derive(Default)]
+pub struct Input {
+    /// A set of all input contexts. It can be modified at any time, changes will be applied on the next
+    /// update.
+    pub map: InputMap,
+    active_contexts: Vec<String>,
+    backend: Option<Box<dyn InputBackend>>,
+    pending_events: Vec<InputEvent>,
+    recorded_events: Option<Vec<InputEvent>>,
+    external_input_blocked: bool,
+    keys: FxHashSet<KeyCode>,
+    mouse_buttons: FxHashSet<MouseButton>,
+    gamepads: FxHashMap<GamepadId, GamepadState>,
+    // Digital sources, that were pressed since the last update. They're treated as active during the next
+    // update even if they were released already, so short presses are not lost.
+    pressed_since_update: FxHashSet<InputSource>,
+    mouse_delta_accumulator: Vector2<f32>,
+    wheel_accumulator: f32,
+    last_pressed_accumulator: Option<InputSource>,
+    mouse_delta: Vector2<f32>,
+    wheel: f32,
+    last_pressed: Option<InputSource>,
+    actions: FxHashMap<String, ActionState>,
+    axes: FxHashMap<String, f32>,
+}
+
+impl Input {
+    /// Sets a new input backend, that will be used as a source of additional events (gamepads, etc.).
+    pub fn set_backend(&mut self, backend: Box<dyn InputBackend>) {
+        self.backend = Some(backend);
+    }
+
+    /// Pushes a context with the given name on top of the stack of active contexts. The context must be
+    /// defined in [`Self::map`] (it could be added later as well).
+    pub fn push_context<S: AsRef<str>>(&mut self, name: S) {
+        self.active_contexts.push(name.as_ref().to_owned());
+    }
+
+    /// Removes the topmost context from the stack of active contexts and returns its name.
+    pub fn pop_context(&mut self) -> Option<String> {
+        self.active_contexts.pop()
+    }
+
+    /// Returns the stack of active contexts. The last context is the topmost one.
+    pub fn active_contexts(&self) -> &[String] {
+        &self.active_contexts
+    }
+
+    /// Enables or disables recording of input events. When enabled, every event, that was applied to the input,
+    /// is stored and can be retrieved by [`Self::take_recorded_events`].
+    pub fn set_recording(&mut self, enabled: bool) {
+        self.recorded_events = if enabled { Some(Vec::new()) } else { None };
+    }
+
+    /// Returns all the events, that were recorded since the previous call of the method.
+    pub fn take_recorded_events(&mut self) -> Vec<InputEvent> {
+        self.recorded_events
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    /// Blocks or unblocks OS events and events of the backend. When blocked, only the events passed to
+    /// [`Self::process_event`] directly are applied. It is used to replay recorded input.
+    pub fn set_external_input_blocked(&mut self, blocked: bool) {
+        self.external_input_blocked = blocked;
+    }
+
+    /// Returns `true` if the given action is active.
+    pub fn is_action_pressed(&self, name: &str) -> bool {
+        self.actions.get(name).is_some_and(|a| a.pressed)
+    }
+
+    /// Returns `true` if the given action became active during the last update.
+    pub fn is_action_just_pressed(&self, name: &str) -> bool {
+        self.actions
+            .get(name)
+            .is_some_and(|a| a.pressed && !a.was_pressed)
+    }
+
+    /// Returns `true` if the given action became inactive during the last update.
+    pub fn is_action_just_released(&self, name: &str) -> bool {
+        self.actions
+            .get(name)
+            .is_some_and(|a| !a.pressed && a.was_pressed)
+    }
+
+    /// Returns the strongest scaled value of the bindings of the given action. It could be used for analog
+    /// actions, such as acceleration bound to a trigger.
+    pub fn action_value(&self, name: &str) -> f32 {
+        self.actions.get(name).map(|a| a.value).unwrap_or_default()
+    }
+
+    /// Returns a value of the given axis. It is in `[-1.0; 1.0]` range, unless the axis is bound to a mouse
+    /// axis.
+    pub fn axis_value(&self, name: &str) -> f32 {
+        self.axes.get(name).cloned().unwrap_or_default()
+    }
+
+    /// Returns `true` if the given key is held down.
+    pub fn is_key_down(&self, key: KeyCode) -> bool {
+        self.keys.contains(&key)
+    }
+
+    /// Returns `true` if the given mouse button is held down.
+    pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.mouse_buttons.contains(&button)
+    }
+
+    /// Returns the movement of the mouse during the last update.
+    pub fn mouse_delta(&self) -> Vector2<f32> {
+        self.mouse_delta
+    }
+
+    /// Returns an iterator over all connected gamepads.
+    pub fn gamepads(&self) -> impl Iterator<Item = (GamepadId, &GamepadState)> {
+        self.gamepads.iter().map(|(id, state)| (*id, state))
+    }
+
+    /// Returns the first input source, that was pressed during the last update. It is useful to implement
+    /// "press a key to bind" logic in control settings menus.
+    pub fn last_pressed_source(&self) -> Option<InputSource> {
+        self.last_pressed
+    }
+
+    /// Translates an OS event into input events. It is called by the executor automatically.
+    pub fn handle_os_event(&mut self, event: &Event<()>) {
+        if self.external_input_blocked {
+            return;
+        }
+
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::KeyboardInput { event, .. } => {
+                    if let PhysicalKey::Code(key) = event.physical_key {
+                        self.process_event(InputEvent::Key {
+                            key: translate_key_to_ui(key),
+                            pressed: event.state == ElementState::Pressed,
+                        });
+                    }
+                }
+                WindowEvent::MouseInput { state, button, .. } => {
+                    self.process_event(InputEvent::MouseButton {
+                        button: translate_button(*button),
+                        pressed: *state == ElementState::Pressed,
+                    })
+                }
+                WindowEvent::MouseWheel { delta, .. } => {
+                    let delta = match delta {
+                        MouseScrollDelta::LineDelta(_, y) => *y,
+                        MouseScrollDelta::PixelDelta(pos) => pos.y as f32,
+                    };
+                    self.process_event(InputEvent::MouseWheel { delta })
+                }
+                WindowEvent::Focused(false) => {
+                    // Release events won't be received when the window is not focused.
+                    let keys = self.keys.drain().collect::<Vec<_>>();
+                    for key in keys {
+                        self.process_event(InputEvent::Key {
+                            key,
+                            pressed: false,
+                        });
+                    }
+                    let buttons = self.mouse_buttons.drain().collect::<Vec<_>>();
+                    for button in buttons {
+                        self.process_event(InputEvent::MouseButton {
+                            button,
+                            pressed: false,
+                        });
+                    }
+                }
+                _ => (),
+            },
+            Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => self.process_event(InputEvent::MouseMotion {
+                delta: Vector2::new(delta.0 as f32, delta.1 as f32),
+            }),
+            _ => (),
+        }
+    }
+
+    /// Applies the given event to the raw state of the input. Keep in mind, that the state of actions and axes
+    /// is changed only in [`Self::update`].
+    pub fn process_event(&mut self, event: InputEvent) {
+        if let Some(recorded_events) = self.recorded_events.as_mut() {
+            recorded_events.push(event.clone());
+        }
+
+        let mut pressed_source = None;
+
+        match event {
+            InputEvent::Key { key, pressed } => {
+                if pressed {
+                    if self.keys.insert(key) {
+                        pressed_source = Some(InputSource::Key(key));
+                    }
+                } else {
+                    self.keys.remove(&key);
+                }
+            }
+            InputEvent::MouseButton { button, pressed } => {
+                if pressed {
+                    if self.mouse_buttons.insert(button) {
+                        pressed_source = Some(InputSource::MouseButton(button));
+                    }
+                } else {
+                    self.mouse_buttons.remove(&button);
+                }
+            }
+            InputEvent::MouseMotion { delta } => self.mouse_delta_accumulator += delta,
+            InputEvent::MouseWheel { delta } => self.wheel_accumulator += delta,
+            InputEvent::GamepadConnected(gamepad) => {
+                self.gamepads.entry(gamepad).or_default();
+            }
+            InputEvent::GamepadDisconnected(gamepad) => {
+                self.gamepads.remove(&gamepad);
+            }
+            InputEvent::GamepadButton {
+                gamepad,
+                button,
+                value,
+            } => {
+                let state = self.gamepads.entry(gamepad).or_default();
+                let previous = state.buttons.insert(button, value).unwrap_or_default();
+                if previous < 0.5 && value >= 0.5 {
+                    pressed_source = Some(InputSource::GamepadButton(button));
+                }
+            }
+            InputEvent::GamepadAxis {
+                gamepad,
+                axis,
+                value,
+            } => {
+                let state = self.gamepads.entry(gamepad).or_default();
+                let previous = state.axes.insert(axis, value).unwrap_or_default();
+                if previous.abs() < 0.5 && value.abs() >= 0.5 {
+                    self.last_pressed_accumulator
+                        .get_or_insert(InputSource::GamepadAxis(axis));
+                }
+            }
+        }
+
+        if let Some(source) = pressed_source {
+            self.pressed_since_update.insert(source);
+            self.last_pressed_accumulator.get_or_insert(source);
+        }
+    }
+
+    /// Polls the backend and updates the state of actions and axes. It is called by the engine automatically
+    /// on each update tick.
+    pub fn update(&mut self) {
+        if let Some(backend) = self.backend.as_mut() {
+            backend.poll(&mut self.pending_events);
+            if self.external_input_blocked {
+                self.pending_events.clear();
+            }
+        }
+        for event in std::mem::take(&mut self.pending_events) {
+            self.process_event(event);
+        }
+
+        self.mouse_delta = std::mem::take(&mut self.mouse_delta_accumulator);
+        self.wheel = std::mem::take(&mut self.wheel_accumulator);
+        self.last_pressed = self.last_pressed_accumulator.take();
+
+        for action in self.actions.values_mut() {
+            action.was_pressed = action.pressed;
+            action.pressed = false;
+            action.value = 0.0;
+        }
+        self.axes.clear();
+
+        let mut resolved_actions = FxHashSet::default();
+        for context_name in self.active_contexts.iter().rev() {
+            let Some(context) = self.map.context(context_name) else {
+                continue;
+            };
+
+            for action in context.actions.iter() {
+                if !resolved_actions.insert(action.name.as_str()) {
+                    // Already defined by a context above.
+                    continue;
+                }
+                let mut pressed = false;
+                let mut value = 0.0f32;
+                for binding in action.bindings.iter() {
+                    let binding_value = self.source_value(&binding.source, 0.0) * binding.scale;
+                    if binding_value.abs() > value.abs() {
+                        value = binding_value;
+                    }
+                    if binding.source.is_digital() {
+                        pressed |= binding_value > 0.0;
+                    } else {
+                        pressed |= binding_value >= action.threshold;
+                    }
+                }
+                let state = self.actions.entry(action.name.clone()).or_default();
+                state.pressed = pressed;
+                state.value = value;
+            }
+
+            for axis in context.axes.iter() {
+                if self.axes.contains_key(&axis.name) {
+                    continue;
+                }
+                let mut bounded = 0.0;
+                let mut unbounded = 0.0;
+                for binding in axis.bindings.iter() {
+                    let value = self.source_value(&binding.source, axis.dead_zone) * binding.scale;
+                    if matches!(binding.source, InputSource::MouseAxis(_)) {
+                        unbounded += value;
+                    } else {
+                        bounded += value;
+                    }
+                }
+                self.axes
+                    .insert(axis.name.clone(), bounded.clamp(-1.0, 1.0) + unbounded);
+            }
+
+            if context.blocking {
+                break;
+            }
+        }
+
+        self.actions
+            .retain(|_, state| state.pressed || state.was_pressed);
+        self.pressed_since_update.clear();
+    }
+
+    fn source_value(&self, source: &InputSource, dead_zone: f32) -> f32 {
+        let digital = |down: bool| {
+            if down || self.pressed_since_update.contains(source) {
+                1.0
+            } else {
+                0.0
+            }
+        };
+
+        match *source {
+            InputSource::Key(key) => digital(self.keys.contains(&key)),
+            InputSource::MouseButton(button) => digital(self.mouse_buttons.contains(&button)),
+            InputSource::MouseAxis(axis) => match axis {
+                MouseAxis::X => self.mouse_delta.x,
+                MouseAxis::Y => self.mouse_delta.y,
+                MouseAxis::Wheel => self.wheel,
+            },
+            InputSource::GamepadButton(button) => {
+                let value = self
+                    .gamepads
+                    .values()
+                    .map(|g| g.button(button))
+                    .fold(0.0, f32::max);
+                if self.pressed_since_update.contains(source) {
+                    value.max(1.0)
+                } else {
+                    value
+                }
+            }
+            InputSource::GamepadAxis(axis) => {
+                let value = self
+                    .gamepads
+                    .values()
+                    .map(|g| g.axis(axis))
+                    .fold(0.0f32, |a, v| if v.abs() > a.abs() { v } else { a });
+                apply_dead_zone(value, dead_zone)
+            }
+        }
+    }
+}
+
+// Zeroes values within the dead zone and rescales the rest, so the output is continuous and still reaches 1.0.
+fn apply_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    let magnitude = value.abs();
+    if magnitude <= dead_zone {
+        0.0
+    } else if dead_zone >= 1.0 {
+        value.signum()
+    } else {
+        value.signum() * ((magnitude - dead_zone) / (1.0 - dead_zone)).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        engine::input::{
+            FakeInputBackend, GamepadAxis, GamepadButton, Input, InputAction, InputAxis,
+            InputBinding, InputContext, InputEvent, InputMap, InputSource, MouseAxis,
+        },
+        gui::message::KeyCode,
+    };
+
+    fn gameplay_context() -> InputContext {
+        InputContext::new("Gameplay")
+            .with_action(
+                InputAction::new("Jump")
+                    .with_binding(InputSource::Key(KeyCode::Space))
+                    .with_binding(InputSource::GamepadButton(GamepadButton::South)),
+            )
+            .with_axis(
+                InputAxis::new("MoveRight")
+                    .with_binding(
+                        InputBinding::new(InputSource::Key(KeyCode::KeyA)).with_scale(-1.0),
+                    )
+                    .with_binding(InputSource::Key(KeyCode::KeyD))
+                    .with_binding(InputSource::GamepadAxis(GamepadAxis::LeftStickX))
+                    .with_dead_zone(0.2),
+            )
+            .with_axis(InputAxis::new("Look").with_binding(
+                InputBinding::new(InputSource::MouseAxis(MouseAxis::X)).with_scale(0.5),
+            ))
+    }
+
+    fn make_input() -> (Input, FakeInputBackend) {
+        let backend = FakeInputBackend::default();
+        let mut input = Input::default();
+        input.set_backend(Box::new(backend.clone()));
+        input.map.add_context(gameplay_context());
+        input.push_context("Gameplay");
+        (input, backend)
+    }
+
+    #[test]
+    fn test_actions() {
+        let (mut input, backend) = make_input();
+
+        input.update();
+        assert!(!input.is_action_pressed("Jump"));
+
+        backend.push(InputEvent::Key {
+            key: KeyCode::Space,
+            pressed: true,
+        });
+        input.update();
+        assert!(input.is_action_pressed("Jump"));
+        assert!(input.is_action_just_pressed("Jump"));
+
+        input.update();
+        assert!(input.is_action_pressed("Jump"));
+        assert!(!input.is_action_just_pressed("Jump"));
+
+        backend.push(InputEvent::Key {
+            key: KeyCode::Space,
+            pressed: false,
+        });
+        input.update();
+        assert!(!input.is_action_pressed("Jump"));
+        assert!(input.is_action_just_released("Jump"));
+
+        // A press and a release between two updates must not be lost.
+        backend.push(InputEvent::GamepadButton {
+            gamepad: 0,
+            button: GamepadButton::South,
+            value: 1.0,
+        });
+        backend.push(InputEvent::GamepadButton {
+            gamepad: 0,
+            button: GamepadButton::South,
+            value: 0.0,
+        });
+        input.update();
+        assert!(input.is_action_just_pressed("Jump"));
+        assert_eq!(
+            input.last_pressed_source(),
+            Some(InputSource::GamepadButton(GamepadButton::South))
+        );
+        input.update();
+        assert!(input.is_action_just_released("Jump"));
+        assert_eq!(input.last_pressed_source(), None);
+    }
+
+    #[test]
+    fn test_axes() {
+        let (mut input, backend) = make_input();
+
+        backend.push(InputEvent::Key {
+            key: KeyCode::KeyA,
+            pressed: true,
+        });
+        input.update();
+        assert_eq!(input.axis_value("MoveRight"), -1.0);
+
+        backend.push(InputEvent::Key {
+            key: KeyCode::KeyD,
+            pressed: true,
+        });
+        input.update();
+        assert_eq!(input.axis_value("MoveRight"), 0.0);
+
+        backend.push(InputEvent::Key {
+            key: KeyCode::KeyA,
+            pressed: false,
+        });
+        backend.push(InputEvent::Key {
+            key: KeyCode::KeyD,
+            pressed: false,
+        });
+        backend.push(InputEvent::GamepadAxis {
+            gamepad: 0,
+            axis: GamepadAxis::LeftStickX,
+            value: 0.1,
+        });
+        input.update();
+        assert_eq!(input.axis_value("MoveRight"), 0.0);
+
+        backend.push(InputEvent::GamepadAxis {
+            gamepad: 0,
+            axis: GamepadAxis::LeftStickX,
+            value: 0.6,
+        });
+        input.update();
+        assert!((input.axis_value("MoveRight") - 0.5).abs() < 1.0e-5);
+
+        backend.push(InputEvent::MouseMotion {
+            delta: Vector2::new(10.0, 3.0),
+        });
+        backend.push(InputEvent::MouseMotion {
+            delta: Vector2::new(4.0, 0.0),
+        });
+        input.update();
+        assert_eq!(input.axis_value("Look"), 7.0);
+        input.update();
+        assert_eq!(input.axis_value("Look"), 0.0);
+    }
+
+    #[test]
+    fn test_contexts() {
+        let (mut input, backend) = make_input();
+        input.map.add_context(
+            InputContext::new("Menu")
+                .with_action(
+                    InputAction::new("Confirm").with_binding(InputSource::Key(KeyCode::Space)),
+                )
+                .with_blocking(true),
+        );
+
+        backend.push(InputEvent::Key {
+            key: KeyCode::Space,
+            pressed: true,
+        });
+        input.update();
+        assert!(input.is_action_pressed("Jump"));
+        assert!(!input.is_action_pressed("Confirm"));
+
+        input.push_context("Menu");
+        input.update();
+        assert!(input.is_action_just_released("Jump"));
+        assert!(input.is_action_just_pressed("Confirm"));
+
+        assert_eq!(input.pop_context().as_deref(), Some("Menu"));
+        input.update();
+        assert!(input.is_action_just_pressed("Jump"));
+        assert!(!input.is_action_pressed("Confirm"));
+    }
+
+    #[test]
+    fn test_rebinding_persistence() {
+        let (mut input, backend) = make_input();
+
+        input
+            .map
+            .context_mut("Gameplay")
+            .unwrap()
+            .action_mut("Jump")
+            .unwrap()
+            .bindings[0] = InputSource::Key(KeyCode::KeyJ).into();
+
+        let data = input.map.save_to_memory().unwrap();
+        let map = InputMap::load_from_memory(&data).unwrap();
+        assert_eq!(map, input.map);
+
+        input.map = map;
+        backend.push(InputEvent::Key {
+            key: KeyCode::KeyJ,
+            pressed: true,
+        });
+        input.update();
+        assert!(input.is_action_pressed("Jump"));
+    }
+}
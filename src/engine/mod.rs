@@ -5,6 +5,11 @@
 
 pub mod error;
 pub mod executor;
+pub mod input;
+pub mod random;
+pub mod replay;
+pub mod savegame;
+pub mod task;
 
 use crate::{
     asset::{
@@ -13,9 +18,15 @@ use crate::{
     },
     core::{
         algebra::Vector2, futures::executor::block_on, instant, log::Log, pool::Handle,
-        reflect::Reflect, variable::try_inherit_properties, visitor::VisitError,
+        reflect::Reflect, task::AsyncTaskResult, variable::try_inherit_properties,
+        visitor::VisitError,
+    },
+    engine::{
+        error::EngineError,
+        input::Input,
+        random::GameRandom,
+        task::{ScriptTaskHandler, TaskPoolHandler},
     },
-    engine::error::EngineError,
     event::Event,
     gui::UserInterface,
     material::{
@@ -33,18 +44,25 @@ use crate::{
     scene::{
         base::NodeScriptMessage,
         camera::SkyBoxKind,
+        dim2,
         graph::{GraphUpdateSwitches, NodePool},
         node::{constructor::NodeConstructorContainer, Node},
+        rigidbody::RigidBody,
         sound::SoundEngine,
         Scene, SceneContainer, SceneLoader,
     },
     script::{
-        constructor::ScriptConstructorContainer, RoutingStrategy, Script, ScriptContext,
-        ScriptDeinitContext, ScriptMessage, ScriptMessageContext, ScriptMessageKind,
-        ScriptMessageSender,
+        constructor::ScriptConstructorContainer, Collision, CollisionContacts, RoutingStrategy,
+        Script, ScriptContext, ScriptDeinitContext, ScriptMessage, ScriptMessageContext,
+        ScriptMessageKind, ScriptMessageSender,
     },
     window::{Window, WindowBuilder},
 };
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{
+    core::{uuid::Uuid, visitor::Visitor},
+    plugin::dynamic::{self, DynamicPlugin},
+};
 use fxhash::{FxHashMap, FxHashSet};
 use fyrox_resource::untyped::{ResourceKind, UntypedResource};
 use fyrox_sound::{
@@ -71,7 +89,11 @@ use std::{ffi::CString, num::NonZeroU32};
 
 use fyrox_ui::font::BUILT_IN_FONT;
 use fyrox_ui::loader::UserInterfaceLoader;
-use fyrox_ui::{font::loader::FontLoader, font::Font};
+use fyrox_ui::{
+    font::loader::FontLoader,
+    font::Font,
+    localization::{loader::StringTableLoader, StringTable},
+};
 use std::{
     any::TypeId,
     collections::{HashSet, VecDeque},
@@ -431,15 +453,33 @@ pub struct Engine {
 
     plugins_enabled: bool,
 
+    // A set of plugins loaded from shared libraries, their constructors are stored in `plugin_constructors`.
+    #[cfg(not(target_arch = "wasm32"))]
+    dynamic_plugins: Vec<DynamicPlugin>,
+
     // Amount of time (in seconds) that passed from creation of the engine.
     elapsed_time: f32,
 
+    // Defines whether game logic is updated without a graphics context.
+    headless: bool,
+
     /// A special container that is able to create nodes by their type UUID. Use a copy of this
     /// value whenever you need it as a parameter in other parts of the engine.
     pub serialization_context: Arc<SerializationContext>,
 
     /// Script processor is used to run script methods in a strict order.
     pub script_processor: ScriptProcessor,
+
+    /// Task pool handler is used to run asynchronous tasks, results of which are passed to plugins and scripts.
+    /// See [`TaskPoolHandler`] docs for more info.
+    pub task_pool: TaskPoolHandler,
+
+    /// Input subsystem, that maps raw input to named actions and axes. See [`Input`] docs for more info.
+    pub input: Input,
+
+    /// Random number generator, that should be used by game logic to make runs reproducible. See [`GameRandom`]
+    /// docs for more info.
+    pub random: GameRandom,
 }
 
 /// Performs dispatch of script messages.
@@ -457,7 +497,7 @@ impl ScriptMessageDispatcher {
     }
 
     /// Subscribes a node to receive any message of the given type `T`. Subscription is automatically removed
-    /// if the node dies.
+    /// if the node dies. If the node has multiple scripts, every script of the node receives the message.
     pub fn subscribe_to<T: 'static>(&mut self, receiver: Handle<Node>) {
         self.type_groups
             .entry(TypeId::of::<T>())
@@ -481,6 +521,11 @@ impl ScriptMessageDispatcher {
         }
     }
 
+    // Removes every queued message without dispatching it. Returns amount of removed messages.
+    fn discard_messages(&self) -> usize {
+        self.message_receiver.try_iter().count()
+    }
+
     fn dispatch_messages(
         &self,
         scene: &mut Scene,
@@ -501,6 +546,7 @@ impl ScriptMessageDispatcher {
                                 elapsed_time,
                                 plugins,
                                 handle: target,
+                                script_index: 0,
                                 scene,
                                 resource_manager,
                                 message_sender,
@@ -522,6 +568,7 @@ impl ScriptMessageDispatcher {
                                     elapsed_time,
                                     plugins,
                                     handle: node,
+                                    script_index: 0,
                                     scene,
                                     resource_manager,
                                     message_sender,
@@ -543,6 +590,7 @@ impl ScriptMessageDispatcher {
                                     elapsed_time,
                                     plugins,
                                     handle: node,
+                                    script_index: 0,
                                     scene,
                                     resource_manager,
                                     message_sender,
@@ -563,6 +611,7 @@ impl ScriptMessageDispatcher {
                                 elapsed_time,
                                 plugins,
                                 handle: node,
+                                script_index: 0,
                                 scene,
                                 resource_manager,
                                 message_sender,
@@ -620,11 +669,70 @@ impl ScriptProcessor {
             .push(resource_manager.state().get_wait_context());
     }
 
+    // Passes a result of an asynchronous task to a script, that has spawned the task.
+    fn handle_script_task(
+        &mut self,
+        handler: ScriptTaskHandler,
+        payload: Box<dyn AsyncTaskResult>,
+        scenes: &mut SceneContainer,
+        plugins: &mut [Box<dyn Plugin>],
+        resource_manager: &ResourceManager,
+        task_pool: &mut TaskPoolHandler,
+        input: &mut Input,
+        random: &mut GameRandom,
+        dt: f32,
+        elapsed_time: f32,
+    ) {
+        let Some(scripted_scene) = self
+            .scripted_scenes
+            .iter_mut()
+            .find(|s| s.handle == handler.scene)
+        else {
+            return;
+        };
+
+        let Some(scene) = scenes.try_get_mut(handler.scene) else {
+            return;
+        };
+
+        let mut context = ScriptContext {
+            dt,
+            elapsed_time,
+            plugins,
+            handle: handler.node,
+            script_index: 0,
+            scene,
+            resource_manager,
+            message_sender: &scripted_scene.message_sender,
+            message_dispatcher: &mut scripted_scene.message_dispatcher,
+            scene_handle: handler.scene,
+            task_pool,
+            input,
+            random,
+        };
+
+        let mut task = Some((handler.closure, payload));
+        let mut ordinal = 0;
+        process_node(&mut context, &mut |script, context| {
+            if script.as_any_ref().type_id() == handler.script_type {
+                if ordinal == handler.script_ordinal {
+                    if let Some((closure, payload)) = task.take() {
+                        closure(payload, script, context);
+                    }
+                }
+                ordinal += 1;
+            }
+        });
+    }
+
     fn handle_scripts(
         &mut self,
         scenes: &mut SceneContainer,
         plugins: &mut Vec<Box<dyn Plugin>>,
         resource_manager: &ResourceManager,
+        task_pool: &mut TaskPoolHandler,
+        input: &mut Input,
+        random: &mut GameRandom,
         dt: f32,
         elapsed_time: f32,
     ) {
@@ -650,25 +758,30 @@ impl ScriptProcessor {
             let mut update_queue = VecDeque::new();
             let mut start_queue = VecDeque::new();
             for (handle, node) in scene.graph.pair_iter() {
-                if let Some(script) = node.script.as_ref() {
-                    if node.is_globally_enabled() {
-                        if script.initialized {
-                            if script.started {
-                                update_queue.push_back(handle);
-                            } else {
-                                start_queue.push_back(handle);
-                            }
-                        } else {
-                            scene
-                                .graph
-                                .script_message_sender
-                                .send(NodeScriptMessage::InitializeScript { handle })
-                                .unwrap();
-                        }
-                    }
+                if !node.is_globally_enabled() {
+                    continue;
+                }
+
+                if node.scripts().any(|script| !script.initialized) {
+                    scene
+                        .graph
+                        .script_message_sender
+                        .send(NodeScriptMessage::InitializeScript { handle })
+                        .unwrap();
+                } else if node.scripts().any(|script| !script.started) {
+                    start_queue.push_back(handle);
+                } else if node.has_scripts_assigned() {
+                    update_queue.push_back(handle);
                 }
             }
 
+            // Collisions of the last physics step are passed to scripts before the update.
+            let mut collisions = collect_collisions(scene);
+
+            // A node could be queued for update multiple times (for example, when a script was added to
+            // already updated node), but its scripts must be updated only once per frame.
+            let mut updated_nodes = FxHashSet::default();
+
             // We'll gather all scripts queued for destruction and destroy them all at once at the
             // end of the frame.
             let mut destruction_queue = VecDeque::new();
@@ -681,10 +794,15 @@ impl ScriptProcessor {
                     elapsed_time,
                     plugins,
                     handle: Default::default(),
+                    script_index: 0,
                     scene,
                     resource_manager,
                     message_sender: &scripted_scene.message_sender,
                     message_dispatcher: &mut scripted_scene.message_dispatcher,
+                    scene_handle: scripted_scene.handle,
+                    task_pool,
+                    input,
+                    random,
                 };
 
                 'init_loop: for init_loop_iteration in 0..max_iterations {
@@ -700,10 +818,10 @@ impl ScriptProcessor {
                                         script.on_init(context);
                                         script.initialized = true;
                                     }
-
-                                    // `on_start` must be called even if the script was initialized.
-                                    start_queue.push_back(handle);
                                 });
+
+                                // `on_start` must be called even if the scripts were initialized.
+                                start_queue.push_back(handle);
                             }
                             NodeScriptMessage::DestroyScript { handle, script } => {
                                 // Destruction is delayed to the end of the frame.
@@ -722,14 +840,18 @@ impl ScriptProcessor {
                         while let Some(node) = start_queue.pop_front() {
                             context.handle = node;
 
+                            let mut started = false;
                             process_node(&mut context, &mut |script, context| {
-                                if !script.started {
+                                if script.initialized && !script.started {
                                     script.on_start(context);
                                     script.started = true;
-
-                                    update_queue.push_back(node);
+                                    started = true;
                                 }
                             });
+
+                            if started {
+                                update_queue.push_back(node);
+                            }
                         }
                     }
 
@@ -741,15 +863,38 @@ impl ScriptProcessor {
                     }
                 }
 
+                // Pass collisions to scripts of the involved nodes.
+                for node_collision in collisions.drain(..) {
+                    context.handle = node_collision.node;
+
+                    process_node(&mut context, &mut |script, context| {
+                        if script.initialized && script.started {
+                            let collision = &node_collision.collision;
+                            match (node_collision.started, node_collision.sensor) {
+                                (true, false) => script.on_collision_started(collision, context),
+                                (false, false) => script.on_collision_stopped(collision, context),
+                                (true, true) => script.on_trigger_entered(collision, context),
+                                (false, true) => script.on_trigger_exited(collision, context),
+                            }
+                        }
+                    });
+                }
+
                 // Update all initialized and started scripts until there is something to initialize.
                 if update_queue.is_empty() {
                     break 'update_loop;
                 } else {
                     while let Some(handle) = update_queue.pop_front() {
+                        if !updated_nodes.insert(handle) {
+                            continue;
+                        }
+
                         context.handle = handle;
 
                         process_node(&mut context, &mut |script, context| {
-                            script.on_update(context);
+                            if script.initialized && script.started {
+                                script.on_update(context);
+                            }
                         });
                     }
 
@@ -786,8 +931,15 @@ impl ScriptProcessor {
             while let Some((handle, mut script)) = destruction_queue.pop_front() {
                 context.node_handle = handle;
 
-                // Unregister self in message dispatcher.
-                scripted_scene.message_dispatcher.unsubscribe(handle);
+                // Unregister the node in message dispatcher, if it does not have any other scripts.
+                if !context
+                    .scene
+                    .graph
+                    .try_get(handle)
+                    .is_some_and(|node| node.has_scripts_assigned())
+                {
+                    scripted_scene.message_dispatcher.unsubscribe(handle);
+                }
 
                 // `on_deinit` could also spawn new nodes, but we won't take those into account on
                 // this frame. They'll be correctly handled on next frame.
@@ -811,12 +963,14 @@ impl ScriptProcessor {
                 for node_index in 0..context.scene.graph.capacity() {
                     context.node_handle = context.scene.graph.handle_from_index(node_index);
 
-                    if let Some(mut script) = context
+                    let scripts = context
                         .scene
                         .graph
                         .try_get_mut(context.node_handle)
-                        .and_then(|node| node.script.take())
-                    {
+                        .map(|node| std::mem::take(&mut node.scripts))
+                        .unwrap_or_default();
+
+                    for mut script in scripts.into_iter().filter_map(|record| record.script) {
                         // A script could not be initialized in case if we added a scene, and then immediately
                         // removed it. Calling `on_deinit` in this case would be a violation of API contract.
                         if script.initialized {
@@ -937,39 +1091,164 @@ pub struct EngineInitParams {
     pub resource_manager: ResourceManager,
 }
 
+struct NodeCollision {
+    node: Handle<Node>,
+    started: bool,
+    sensor: bool,
+    collision: Collision,
+}
+
+// Converts collision events of both 3D and 2D physics of the scene into a list of collisions for every
+// involved node. Each collision is passed to the collider nodes and to their parent rigid bodies.
+fn collect_collisions(scene: &Scene) -> Vec<NodeCollision> {
+    let graph = &scene.graph;
+
+    let mut collisions = Vec::new();
+    let mut add = |collider: Handle<Node>,
+                   other_collider: Handle<Node>,
+                   started: bool,
+                   sensor: bool,
+                   contacts: CollisionContacts| {
+        let Some(collider_ref) = graph.try_get(collider) else {
+            return;
+        };
+
+        let mut receivers = vec![collider];
+        let parent = collider_ref.parent();
+        if graph.try_get(parent).is_some_and(|parent| {
+            parent.cast::<RigidBody>().is_some()
+                || parent.cast::<dim2::rigidbody::RigidBody>().is_some()
+        }) {
+            receivers.push(parent);
+        }
+
+        for node in receivers {
+            collisions.push(NodeCollision {
+                node,
+                started,
+                sensor,
+                collision: Collision {
+                    collider,
+                    other_collider,
+                    contacts: contacts.clone(),
+                },
+            });
+        }
+    };
+
+    for event in graph.physics.collision_events() {
+        let contacts = event
+            .contact_pair
+            .clone()
+            .map_or(CollisionContacts::None, CollisionContacts::Contacts3D);
+        add(
+            event.collider1,
+            event.collider2,
+            event.started,
+            event.sensor,
+            contacts.clone(),
+        );
+        add(
+            event.collider2,
+            event.collider1,
+            event.started,
+            event.sensor,
+            contacts,
+        );
+    }
+
+    for event in graph.physics2d.collision_events() {
+        let contacts = event
+            .contact_pair
+            .clone()
+            .map_or(CollisionContacts::None, CollisionContacts::Contacts2D);
+        add(
+            event.collider1,
+            event.collider2,
+            event.started,
+            event.sensor,
+            contacts.clone(),
+        );
+        add(
+            event.collider2,
+            event.collider1,
+            event.started,
+            event.sensor,
+            contacts,
+        );
+    }
+
+    collisions
+}
+
 macro_rules! define_process_node {
     ($name:ident, $ctx_type:ty) => {
         fn $name<T>(context: &mut $ctx_type, func: &mut T)
         where
             T: FnMut(&mut Script, &mut $ctx_type),
         {
-            // Take a script from node. We're temporarily taking ownership over script
-            // instance.
-            let mut script = match context.scene.graph.try_get_mut(context.handle) {
-                Some(node) => {
-                    if !node.is_globally_enabled() {
+            // Scripts are executed in the order of the list. A script can add or remove other scripts
+            // of the node (including itself), so the list is checked again on every iteration.
+            context.script_index = 0;
+            loop {
+                // Take a script from node. We're temporarily taking ownership over script
+                // instance.
+                let mut script = match context.scene.graph.try_get_mut(context.handle) {
+                    Some(node) => {
+                        if !node.is_globally_enabled() {
+                            return;
+                        }
+
+                        match node.scripts.get_mut(context.script_index) {
+                            Some(record) => {
+                                if let Some(script) = record.script.take() {
+                                    record.in_use = true;
+                                    script
+                                } else {
+                                    // Empty record.
+                                    context.script_index += 1;
+                                    continue;
+                                }
+                            }
+                            None => {
+                                // No more scripts.
+                                return;
+                            }
+                        }
+                    }
+                    None => {
+                        // Invalid handle.
                         return;
                     }
+                };
 
-                    if let Some(script) = node.script.take() {
-                        script
-                    } else {
-                        // No script.
-                        return;
+                func(&mut script, context);
+
+                // Put the script back to the node. We must do a checked borrow, because it is possible
+                // that the node is already destroyed by script logic. The record could be moved too, if
+                // the script has removed some other scripts of the node.
+                if let Some(node) = context.scene.graph.try_get_mut(context.handle) {
+                    if let Some(index) = node.scripts.iter().position(|record| record.in_use) {
+                        let record = &mut node.scripts[index];
+                        record.in_use = false;
+                        if record.should_be_deleted {
+                            node.scripts.remove(index);
+                            context.script_index = index;
+                        } else {
+                            record.script = Some(script);
+                            context.script_index = index + 1;
+                            continue;
+                        }
                     }
                 }
-                None => {
-                    // Invalid handle.
-                    return;
-                }
-            };
 
-            func(&mut script, context);
-
-            // Put the script back to the node. We must do a checked borrow, because it is possible
-            // that the node is already destroyed by script logic.
-            if let Some(node) = context.scene.graph.try_get_mut(context.handle) {
-                node.script = Some(script);
+                // The script was removed (or its node was destroyed) while it was executing.
+                Log::verify(context.scene.graph.script_message_sender.send(
+                    NodeScriptMessage::DestroyScript {
+                        script,
+                        handle: context.handle,
+                    },
+                ));
             }
         }
     };
@@ -980,8 +1259,12 @@ define_process_node!(process_node_message, ScriptMessageContext);
 
 pub(crate) fn process_scripts<T>(
     scene: &mut Scene,
+    scene_handle: Handle<Scene>,
     plugins: &mut [Box<dyn Plugin>],
     resource_manager: &ResourceManager,
+    task_pool: &mut TaskPoolHandler,
+    input: &mut Input,
+    random: &mut GameRandom,
     message_sender: &ScriptMessageSender,
     message_dispatcher: &mut ScriptMessageDispatcher,
     dt: f32,
@@ -995,10 +1278,15 @@ pub(crate) fn process_scripts<T>(
         elapsed_time,
         plugins,
         handle: Default::default(),
+        script_index: 0,
         scene,
         resource_manager,
         message_sender,
         message_dispatcher,
+        scene_handle,
+        task_pool,
+        input,
+        random,
     };
 
     for node_index in 0..context.scene.graph.capacity() {
@@ -1046,6 +1334,7 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<HrirSphereResourceData>();
     state.constructors_container.add::<Material>();
     state.constructors_container.add::<Font>();
+    state.constructors_container.add::<StringTable>();
     state.constructors_container.add::<UserInterface>();
 
     let loaders = &mut state.loaders;
@@ -1063,6 +1352,7 @@ pub(crate) fn initialize_resource_manager_loaders(
         resource_manager: resource_manager.clone(),
     });
     loaders.set(FontLoader::default());
+    loaders.set(StringTableLoader);
     loaders.set(UserInterfaceLoader {
         resource_manager: resource_manager.clone(),
     });
@@ -1119,9 +1409,21 @@ impl Engine {
 
         let sound_engine = SoundEngine::without_device();
 
+        #[allow(unused_mut)]
+        let mut input = Input::default();
+        #[cfg(feature = "gilrs")]
+        match input::GilrsInputBackend::new() {
+            Ok(backend) => input.set_backend(Box::new(backend)),
+            Err(e) => Log::err(format!("Unable to initialize gamepad input. Reason: {e}")),
+        }
+
         Ok(Self {
             graphics_context: GraphicsContext::Uninitialized(graphics_context_params),
             model_events_receiver: tx,
+            task_pool: TaskPoolHandler::new(resource_manager.task_pool()),
+            input,
+            random: Default::default(),
+            headless: false,
             async_scene_loader: AsyncSceneLoader::new(
                 resource_manager.clone(),
                 serialization_context.clone(),
@@ -1136,6 +1438,8 @@ impl Engine {
             script_processor: Default::default(),
             plugins_enabled: false,
             plugin_constructors: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            dynamic_plugins: Default::default(),
             elapsed_time: 0.0,
         })
     }
@@ -1483,6 +1787,9 @@ impl Engine {
                             elapsed_time: self.elapsed_time,
                             script_processor: &self.script_processor,
                             async_scene_loader: &mut self.async_scene_loader,
+                            task_pool: &mut self.task_pool,
+                            input: &mut self.input,
+                            random: &mut self.random,
                             window_target: Some(window_target),
                         };
 
@@ -1514,6 +1821,9 @@ impl Engine {
                     elapsed_time: self.elapsed_time,
                     script_processor: &self.script_processor,
                     async_scene_loader: &mut self.async_scene_loader,
+                    task_pool: &mut self.task_pool,
+                    input: &mut self.input,
+                    random: &mut self.random,
                     window_target: Some(window_target),
                 };
 
@@ -1627,38 +1937,45 @@ impl Engine {
         lag: &mut f32,
         switches: FxHashMap<Handle<Scene>, GraphUpdateSwitches>,
     ) {
-        if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
+        let window_size = if let GraphicsContext::Initialized(ctx) = &mut self.graphics_context {
             let inner_size = ctx.window.inner_size();
-            let window_size = Vector2::new(inner_size.width as f32, inner_size.height as f32);
-
-            self.resource_manager.state().update(dt);
             ctx.renderer.update_caches(dt);
-            self.handle_model_events();
-
-            for (handle, scene) in self.scenes.pair_iter_mut().filter(|(_, s)| *s.enabled) {
-                let frame_size =
-                    scene
-                        .rendering_options
-                        .render_target
-                        .as_ref()
-                        .map_or(window_size, |rt| {
-                            if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
-                                Vector2::new(width as f32, height as f32)
-                            } else {
-                                panic!("only rectangle textures can be used as render target!");
-                            }
-                        });
-
-                scene.update(
-                    frame_size,
-                    dt,
-                    switches.get(&handle).cloned().unwrap_or_default(),
-                );
-            }
+            Vector2::new(inner_size.width as f32, inner_size.height as f32)
+        } else if self.headless {
+            // There's no window, but the game logic must still run.
+            Vector2::new(1.0, 1.0)
+        } else {
+            return;
+        };
+
+        self.resource_manager.state().update(dt);
+        self.handle_model_events();
+
+        for (handle, scene) in self.scenes.pair_iter_mut().filter(|(_, s)| *s.enabled) {
+            let frame_size =
+                scene
+                    .rendering_options
+                    .render_target
+                    .as_ref()
+                    .map_or(window_size, |rt| {
+                        if let TextureKind::Rectangle { width, height } = rt.data_ref().kind() {
+                            Vector2::new(width as f32, height as f32)
+                        } else {
+                            panic!("only rectangle textures can be used as render target!");
+                        }
+                    });
 
-            self.update_plugins(dt, window_target, lag);
-            self.handle_scripts(dt);
+            scene.update(
+                frame_size,
+                dt,
+                switches.get(&handle).cloned().unwrap_or_default(),
+            );
         }
+
+        self.input.update();
+        self.handle_async_tasks(dt, window_target, lag);
+        self.update_plugins(dt, window_target, lag);
+        self.handle_scripts(dt);
     }
 
     /// Performs post update for the engine.
@@ -1673,8 +1990,26 @@ impl Engine {
             let time = instant::Instant::now();
             self.user_interface.update(window_size, dt);
             self.performance_statistics.ui_time = instant::Instant::now() - time;
-            self.elapsed_time += dt;
         }
+        self.elapsed_time += dt;
+    }
+
+    /// Enables or disables headless mode. In headless mode scenes, plugins and scripts are updated even if there's
+    /// no graphics context (with 1x1 frame size), otherwise [`Self::update`] does nothing until the graphics
+    /// context is created. By default, headless mode is off.
+    pub fn set_headless(&mut self, headless: bool) {
+        self.headless = headless;
+    }
+
+    /// Returns `true` if the headless mode is turned on, `false` - otherwise.
+    pub fn is_headless(&self) -> bool {
+        self.headless
+    }
+
+    /// Returns `true` if [`Self::update`] updates game logic at the moment, which happens when the graphics
+    /// context is initialized or when the engine is in headless mode.
+    pub fn is_updating(&self) -> bool {
+        self.headless || matches!(self.graphics_context, GraphicsContext::Initialized(_))
     }
 
     /// Returns true if the scene is registered for script processing.
@@ -1694,12 +2029,63 @@ impl Engine {
             &mut self.scenes,
             &mut self.plugins,
             &self.resource_manager,
+            &mut self.task_pool,
+            &mut self.input,
+            &mut self.random,
             dt,
             self.elapsed_time,
         );
         self.performance_statistics.scripts_time = instant::Instant::now() - time;
     }
 
+    fn handle_async_tasks(
+        &mut self,
+        dt: f32,
+        window_target: &EventLoopWindowTarget<()>,
+        lag: &mut f32,
+    ) {
+        while let Some(result) = self.task_pool.inner().next_task_result() {
+            if let Some(plugin_task_handler) = self.task_pool.pop_plugin_task_handler(result.id) {
+                (plugin_task_handler)(
+                    result.payload,
+                    &mut self.plugins,
+                    &mut PluginContext {
+                        scenes: &mut self.scenes,
+                        resource_manager: &self.resource_manager,
+                        graphics_context: &mut self.graphics_context,
+                        dt,
+                        lag,
+                        user_interface: &mut self.user_interface,
+                        serialization_context: &self.serialization_context,
+                        performance_statistics: &self.performance_statistics,
+                        elapsed_time: self.elapsed_time,
+                        script_processor: &self.script_processor,
+                        async_scene_loader: &mut self.async_scene_loader,
+                        task_pool: &mut self.task_pool,
+                        input: &mut self.input,
+                        random: &mut self.random,
+                        window_target: Some(window_target),
+                    },
+                );
+            } else if let Some(script_task_handler) =
+                self.task_pool.pop_script_task_handler(result.id)
+            {
+                self.script_processor.handle_script_task(
+                    script_task_handler,
+                    result.payload,
+                    &mut self.scenes,
+                    &mut self.plugins,
+                    &self.resource_manager,
+                    &mut self.task_pool,
+                    &mut self.input,
+                    &mut self.random,
+                    dt,
+                    self.elapsed_time,
+                );
+            }
+        }
+    }
+
     fn update_plugins(
         &mut self,
         dt: f32,
@@ -1721,6 +2107,9 @@ impl Engine {
                 elapsed_time: self.elapsed_time,
                 script_processor: &self.script_processor,
                 async_scene_loader: &mut self.async_scene_loader,
+                task_pool: &mut self.task_pool,
+                input: &mut self.input,
+                random: &mut self.random,
                 window_target: Some(window_target),
             };
 
@@ -1741,6 +2130,9 @@ impl Engine {
                     elapsed_time: self.elapsed_time,
                     script_processor: &self.script_processor,
                     async_scene_loader: &mut self.async_scene_loader,
+                    task_pool: &mut self.task_pool,
+                    input: &mut self.input,
+                    random: &mut self.random,
                     window_target: Some(window_target),
                 };
 
@@ -1776,6 +2168,9 @@ impl Engine {
                         elapsed_time: self.elapsed_time,
                         script_processor: &self.script_processor,
                         async_scene_loader: &mut self.async_scene_loader,
+                        task_pool: &mut self.task_pool,
+                        input: &mut self.input,
+                        random: &mut self.random,
                         window_target: Some(window_target),
                     },
                 );
@@ -1803,6 +2198,9 @@ impl Engine {
                     elapsed_time: self.elapsed_time,
                     script_processor: &self.script_processor,
                     async_scene_loader: &mut self.async_scene_loader,
+                    task_pool: &mut self.task_pool,
+                    input: &mut self.input,
+                    random: &mut self.random,
                     window_target: Some(window_target),
                 });
             }
@@ -1829,6 +2227,9 @@ impl Engine {
                     elapsed_time: self.elapsed_time,
                     script_processor: &self.script_processor,
                     async_scene_loader: &mut self.async_scene_loader,
+                    task_pool: &mut self.task_pool,
+                    input: &mut self.input,
+                    random: &mut self.random,
                     window_target: Some(window_target),
                 });
             }
@@ -1855,6 +2256,9 @@ impl Engine {
                     elapsed_time: self.elapsed_time,
                     script_processor: &self.script_processor,
                     async_scene_loader: &mut self.async_scene_loader,
+                    task_pool: &mut self.task_pool,
+                    input: &mut self.input,
+                    random: &mut self.random,
                     window_target: Some(window_target),
                 });
             }
@@ -1880,12 +2284,17 @@ impl Engine {
             .iter_mut()
             .find(|s| s.handle == scene)
         {
+            let scene_handle = scene;
             let scene = &mut self.scenes[scene];
             if *scene.enabled {
                 process_scripts(
                     scene,
+                    scene_handle,
                     &mut self.plugins,
                     &self.resource_manager,
+                    &mut self.task_pool,
+                    &mut self.input,
+                    &mut self.random,
                     &scripted_scene.message_sender,
                     &mut scripted_scene.message_dispatcher,
                     dt,
@@ -1986,6 +2395,9 @@ impl Engine {
                             elapsed_time: self.elapsed_time,
                             script_processor: &self.script_processor,
                             async_scene_loader: &mut self.async_scene_loader,
+                            task_pool: &mut self.task_pool,
+                            input: &mut self.input,
+                            random: &mut self.random,
                             window_target,
                         },
                     ));
@@ -2007,6 +2419,9 @@ impl Engine {
                         elapsed_time: self.elapsed_time,
                         script_processor: &self.script_processor,
                         async_scene_loader: &mut self.async_scene_loader,
+                        task_pool: &mut self.task_pool,
+                        input: &mut self.input,
+                        random: &mut self.random,
                         window_target,
                     });
                 }
@@ -2026,6 +2441,337 @@ impl Engine {
 
         self.plugin_constructors.push(Box::new(constructor));
     }
+
+    // Registers the constructor and returns type uuids of scripts and nodes, that were registered by it.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn register_dynamic_plugin_constructor(
+        &self,
+        constructor: &dyn PluginConstructor,
+    ) -> (FxHashSet<Uuid>, FxHashSet<Uuid>) {
+        let script_constructors = &self.serialization_context.script_constructors;
+        let node_constructors = &self.serialization_context.node_constructors;
+        let scripts_before = script_constructors
+            .map()
+            .keys()
+            .cloned()
+            .collect::<FxHashSet<_>>();
+        let nodes_before = node_constructors
+            .map()
+            .keys()
+            .cloned()
+            .collect::<FxHashSet<_>>();
+
+        constructor.register(PluginRegistrationContext {
+            serialization_context: &self.serialization_context,
+            resource_manager: &self.resource_manager,
+        });
+
+        let script_types = script_constructors
+            .map()
+            .keys()
+            .filter(|uuid| !scripts_before.contains(uuid))
+            .cloned()
+            .collect();
+        let node_types = node_constructors
+            .map()
+            .keys()
+            .filter(|uuid| !nodes_before.contains(uuid))
+            .cloned()
+            .collect();
+        (script_types, node_types)
+    }
+
+    /// Loads a plugin from a shared library at the given path. If `reload_when_changed` is `true`, the plugin
+    /// will be reloaded (see [`Self::reload_dynamic_plugins`]) every time when the library is rebuilt. See
+    /// [`DynamicPlugin`] docs for more info.
+    ///
+    /// # Safety
+    ///
+    /// The library must be built by the same compiler and with the same version of the engine as the host
+    /// application, and it must export [`crate::plugin::dynamic::PLUGIN_CONSTRUCTOR_ENTRY_POINT`] function.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub unsafe fn add_dynamic_plugin<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        reload_when_changed: bool,
+    ) -> Result<(), EngineError> {
+        let (mut plugin, constructor) = DynamicPlugin::load(
+            path.as_ref(),
+            reload_when_changed,
+            self.plugin_constructors.len(),
+        )
+        .map_err(EngineError::Custom)?;
+
+        let (script_types, node_types) = self.register_dynamic_plugin_constructor(&*constructor);
+        plugin.script_types = script_types;
+        plugin.node_types = node_types;
+
+        self.plugin_constructors.push(constructor);
+        self.dynamic_plugins.push(plugin);
+
+        Ok(())
+    }
+
+    /// Returns `true` if there's at least one dynamic plugin, that should be reloaded because its library was
+    /// rebuilt.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn has_changed_dynamic_plugins(&self) -> bool {
+        self.dynamic_plugins
+            .iter()
+            .any(|plugin| plugin.reload_when_changed && plugin.is_changed())
+    }
+
+    /// Reloads every dynamic plugin, library of which was rebuilt. State of the plugin instance (if plugins are
+    /// enabled) is preserved using [`Plugin::visit_hot_reload_state`] and state of every script of the plugin
+    /// (in every scene and every loaded model resource) is preserved using its [`Visit`] implementation. Restored
+    /// scripts will receive [`crate::script::ScriptTrait::on_start`] call again, but not `on_init`.
+    ///
+    /// # Limitations
+    ///
+    /// - The plugin cannot be reloaded if there's at least one scene node of a type, that was registered by the
+    ///   plugin. An error is returned in this case and the plugin stays as is. A plugin, that failed to reload,
+    ///   is not reloaded again until its library is rebuilt once more.
+    /// - Results of every pending task (see [`TaskPoolHandler`]) are discarded, because the engine cannot tell
+    ///   which tasks were spawned by the plugin.
+    /// - Pending script messages (see [`ScriptMessageSender`]) are discarded for the same reason.
+    /// - Resource loaders registered by the plugin must not be used after reloading.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn reload_dynamic_plugins(
+        &mut self,
+        window_target: Option<&EventLoopWindowTarget<()>>,
+    ) -> Result<(), EngineError> {
+        for plugin_index in 0..self.dynamic_plugins.len() {
+            let plugin = &self.dynamic_plugins[plugin_index];
+            if plugin.reload_when_changed && plugin.is_changed() {
+                self.reload_dynamic_plugin(plugin_index, window_target)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_dynamic_plugin(
+        &mut self,
+        plugin_index: usize,
+        window_target: Option<&EventLoopWindowTarget<()>>,
+    ) -> Result<(), EngineError> {
+        let dynamic_plugin = &mut self.dynamic_plugins[plugin_index];
+
+        // The attempt is remembered even if it fails, the plugin will be reloaded again only when its library
+        // is rebuilt once more.
+        dynamic_plugin.mark_reload_attempted();
+
+        Log::info(format!(
+            "Reloading dynamic plugin {}...",
+            dynamic_plugin.source_path().display()
+        ));
+
+        let models = self
+            .resource_manager
+            .state()
+            .iter()
+            .filter_map(|resource| resource.try_cast::<Model>())
+            .filter(|model| model.is_ok())
+            .collect::<Vec<_>>();
+
+        for scene in self.scenes.iter() {
+            if let Some((handle, _)) = scene
+                .graph
+                .pair_iter()
+                .find(|(_, node)| dynamic_plugin.node_types.contains(&node.id()))
+            {
+                return Err(EngineError::Custom(format!(
+                    "Unable to reload dynamic plugin {}, because node {} has a type from the plugin!",
+                    dynamic_plugin.source_path().display(),
+                    handle
+                )));
+            }
+        }
+        for model in models.iter() {
+            if let Some((handle, _)) = model
+                .data_ref()
+                .scene
+                .graph
+                .pair_iter()
+                .find(|(_, node)| dynamic_plugin.node_types.contains(&node.id()))
+            {
+                return Err(EngineError::Custom(format!(
+                    "Unable to reload dynamic plugin {}, because node {} of model {} has a type from the plugin!",
+                    dynamic_plugin.source_path().display(),
+                    handle,
+                    model.kind()
+                )));
+            }
+        }
+
+        let (library, loaded_path, constructor) =
+            dynamic_plugin.begin_reload().map_err(EngineError::Custom)?;
+        let constructor_index = dynamic_plugin.constructor_index;
+
+        // Destroy every object created by the old library, saving their state first. Scripts, that are waiting
+        // for destruction, are destroyed right away, since they cannot outlive the library.
+        for scripted_scene in self.script_processor.scripted_scenes.iter() {
+            let discarded_messages = scripted_scene.message_dispatcher.discard_messages();
+            if discarded_messages > 0 {
+                Log::warn(format!(
+                    "{discarded_messages} pending script messages were discarded because of dynamic plugin reloading."
+                ));
+            }
+
+            let Some(scene) = self.scenes.try_get_mut(scripted_scene.handle) else {
+                continue;
+            };
+            let destroyed =
+                dynamic::take_destroyed_scripts(&mut scene.graph, &dynamic_plugin.script_types);
+            let mut context = ScriptDeinitContext {
+                elapsed_time: self.elapsed_time,
+                plugins: &mut self.plugins,
+                resource_manager: &self.resource_manager,
+                scene,
+                node_handle: Default::default(),
+                message_sender: &scripted_scene.message_sender,
+            };
+            for (handle, mut script) in destroyed {
+                context.node_handle = handle;
+                script.on_deinit(&mut context);
+            }
+        }
+        for scene in self.scenes.iter_mut() {
+            // Scenes without scripts processing could have such scripts too, they're just dropped.
+            dynamic::take_destroyed_scripts(&mut scene.graph, &dynamic_plugin.script_types);
+        }
+        for model in models.iter() {
+            dynamic::take_destroyed_scripts(
+                &mut model.data_ref().scene.graph,
+                &dynamic_plugin.script_types,
+            );
+        }
+
+        let mut scene_scripts = Vec::new();
+        for (handle, scene) in self.scenes.pair_iter_mut() {
+            scene_scripts.push((
+                handle,
+                dynamic::take_scripts(&mut scene.graph, &dynamic_plugin.script_types),
+            ));
+        }
+
+        let mut model_scripts = Vec::new();
+        for model in models {
+            let scripts = dynamic::take_scripts(
+                &mut model.data_ref().scene.graph,
+                &dynamic_plugin.script_types,
+            );
+            model_scripts.push((model, scripts));
+        }
+
+        let mut plugin_state = None;
+        if self.plugins_enabled {
+            let mut plugin = self.plugins.remove(constructor_index);
+            let mut visitor = Visitor::new();
+            match plugin
+                .visit_hot_reload_state(&mut visitor)
+                .and_then(|_| visitor.save_binary_to_vec())
+            {
+                Ok(data) => plugin_state = Some(data),
+                Err(err) => Log::err(format!(
+                    "Unable to save state of dynamic plugin, it will be reset. Reason: {:?}",
+                    err
+                )),
+            }
+        }
+
+        let discarded_tasks = self.task_pool.clear_handlers();
+        if discarded_tasks > 0 {
+            Log::warn(format!(
+                "{discarded_tasks} pending tasks were discarded because of dynamic plugin reloading."
+            ));
+        }
+
+        for type_uuid in dynamic_plugin.script_types.drain() {
+            self.serialization_context
+                .script_constructors
+                .remove(type_uuid);
+        }
+        for type_uuid in dynamic_plugin.node_types.drain() {
+            self.serialization_context
+                .node_constructors
+                .remove(type_uuid);
+        }
+
+        // Every object of the old library is destroyed at this point, it is safe to unload it.
+        drop(std::mem::replace(
+            &mut self.plugin_constructors[constructor_index],
+            constructor,
+        ));
+        dynamic_plugin.finish_reload(library, loaded_path);
+
+        let (script_types, node_types) =
+            self.register_dynamic_plugin_constructor(&*self.plugin_constructors[constructor_index]);
+        let dynamic_plugin = &mut self.dynamic_plugins[plugin_index];
+        dynamic_plugin.script_types = script_types;
+        dynamic_plugin.node_types = node_types;
+
+        if self.plugins_enabled {
+            let mut plugin = self.plugin_constructors[constructor_index].create_reloaded_instance(
+                PluginContext {
+                    scenes: &mut self.scenes,
+                    resource_manager: &self.resource_manager,
+                    graphics_context: &mut self.graphics_context,
+                    dt: 0.0,
+                    lag: &mut 0.0,
+                    user_interface: &mut self.user_interface,
+                    serialization_context: &self.serialization_context,
+                    performance_statistics: &self.performance_statistics,
+                    elapsed_time: self.elapsed_time,
+                    script_processor: &self.script_processor,
+                    async_scene_loader: &mut self.async_scene_loader,
+                    task_pool: &mut self.task_pool,
+                    input: &mut self.input,
+                    random: &mut self.random,
+                    window_target,
+                },
+            );
+
+            if let Some(plugin_state) = plugin_state {
+                if let Err(err) = Visitor::load_from_memory(&plugin_state)
+                    .and_then(|mut visitor| plugin.visit_hot_reload_state(&mut visitor))
+                {
+                    Log::err(format!(
+                        "Unable to restore state of dynamic plugin. Reason: {:?}",
+                        err
+                    ));
+                }
+            }
+
+            self.plugins.insert(constructor_index, plugin);
+        }
+
+        for (handle, scripts) in scene_scripts {
+            if let Some(scene) = self.scenes.try_get_mut(handle) {
+                dynamic::restore_scripts(
+                    &mut scene.graph,
+                    scripts,
+                    &self.serialization_context,
+                    &self.resource_manager,
+                );
+            }
+        }
+        for (model, scripts) in model_scripts {
+            dynamic::restore_scripts(
+                &mut model.data_ref().scene.graph,
+                scripts,
+                &self.serialization_context,
+                &self.resource_manager,
+            );
+        }
+
+        Log::info(format!(
+            "Dynamic plugin {} was reloaded successfully!",
+            self.dynamic_plugins[plugin_index].source_path().display()
+        ));
+
+        Ok(())
+    }
 }
 
 impl Drop for Engine {
@@ -2052,13 +2798,23 @@ impl Drop for Engine {
 mod test {
     use crate::{
         asset::manager::ResourceManager,
-        core::{pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
-        engine::ScriptProcessor,
+        core::{
+            algebra::Vector3, pool::Handle, reflect::prelude::*, uuid::Uuid, visitor::prelude::*,
+        },
+        engine::{input::Input, random::GameRandom, task::TaskPoolHandler, ScriptProcessor},
         impl_component_provider,
-        scene::{base::BaseBuilder, node::Node, pivot::PivotBuilder, Scene, SceneContainer},
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            node::Node,
+            pivot::PivotBuilder,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            transform::TransformBuilder,
+            Scene, SceneContainer,
+        },
         script::{
-            Script, ScriptContext, ScriptDeinitContext, ScriptMessageContext, ScriptMessagePayload,
-            ScriptTrait,
+            Collision, CollisionContacts, Script, ScriptContext, ScriptDeinitContext,
+            ScriptMessageContext, ScriptMessagePayload, ScriptTrait,
         },
     };
 
@@ -2165,6 +2921,9 @@ mod test {
     #[test]
     fn test_order() {
         let resource_manager = ResourceManager::new();
+        let mut task_pool = TaskPoolHandler::new(resource_manager.task_pool());
+        let mut input = Input::default();
+        let mut random = GameRandom::default();
         let mut scene = Scene::new();
 
         let (tx, rx) = mpsc::channel();
@@ -2194,6 +2953,9 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &mut task_pool,
+                &mut input,
+                &mut random,
                 0.0,
                 0.0,
             );
@@ -2327,6 +3089,9 @@ mod test {
     #[test]
     fn test_messages() {
         let resource_manager = ResourceManager::new();
+        let mut task_pool = TaskPoolHandler::new(resource_manager.task_pool());
+        let mut input = Input::default();
+        let mut random = GameRandom::default();
         let mut scene = Scene::new();
 
         let (tx, rx) = mpsc::channel();
@@ -2357,6 +3122,9 @@ mod test {
                 &mut scene_container,
                 &mut Default::default(),
                 &resource_manager,
+                &mut task_pool,
+                &mut input,
+                &mut random,
                 0.0,
                 0.0,
             );
@@ -2374,4 +3142,473 @@ mod test {
             }
         }
     }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct ScriptPiece {
+        id: u32,
+        frames: u32,
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(u32, &'static str)>,
+    }
+
+    impl_component_provider!(ScriptPiece);
+
+    impl ScriptTrait for ScriptPiece {
+        fn on_init(&mut self, _ctx: &mut ScriptContext) {
+            self.sender.send((self.id, "init")).unwrap();
+        }
+
+        fn on_start(&mut self, _ctx: &mut ScriptContext) {
+            self.sender.send((self.id, "start")).unwrap();
+        }
+
+        fn on_deinit(&mut self, _ctx: &mut ScriptDeinitContext) {
+            self.sender.send((self.id, "deinit")).unwrap();
+        }
+
+        fn on_update(&mut self, ctx: &mut ScriptContext) {
+            self.sender.send((self.id, "update")).unwrap();
+            self.frames += 1;
+
+            if self.frames == 2 {
+                let node = &mut ctx.scene.graph[ctx.handle];
+                match self.id {
+                    // Add a new script to the node.
+                    0 => node.add_script(Script::new(ScriptPiece {
+                        id: 2,
+                        frames: 0,
+                        sender: self.sender.clone(),
+                    })),
+                    // Remove self.
+                    1 => node.remove_script(ctx.script_index),
+                    // Copy the node while the script is running.
+                    3 => {
+                        ctx.scene
+                            .graph
+                            .copy_node_inplace(ctx.handle, &mut |_, _| true);
+                    }
+                    _ => (),
+                }
+            }
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_copy_node_with_running_script() {
+        let resource_manager = ResourceManager::new();
+        let mut task_pool = TaskPoolHandler::new(resource_manager.task_pool());
+        let mut input = Input::default();
+        let mut random = GameRandom::default();
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let piece = |id| {
+            Script::new(ScriptPiece {
+                id,
+                frames: 0,
+                sender: tx.clone(),
+            })
+        };
+
+        PivotBuilder::new(
+            BaseBuilder::new()
+                .with_script(piece(3))
+                .with_script(piece(4)),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(scene_handle, &resource_manager);
+
+        for _ in 0..4 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &mut task_pool,
+                &mut input,
+                &mut random,
+                0.0,
+                0.0,
+            );
+        }
+
+        let graph = &scene_container[scene_handle].graph;
+        let copies = graph
+            .linear_iter()
+            .filter(|node| node.script_count() > 0)
+            .collect::<Vec<_>>();
+        assert_eq!(copies.len(), 2);
+        for node in copies {
+            assert!(node.scripts.iter().all(|record| !record.in_use));
+            // The copy has an empty record, because the script was taken out of the original record at
+            // the moment of copying.
+            assert!(node.try_get_scripts::<ScriptPiece>().any(|s| s.id == 4));
+        }
+
+        // Scripts of the copy must still be updated every frame.
+        let updates = rx
+            .try_iter()
+            .filter(|(id, event)| *id == 4 && *event == "update")
+            .count();
+        assert_eq!(updates, 4 + 3);
+    }
+
+    #[test]
+    fn test_multiple_scripts() {
+        let resource_manager = ResourceManager::new();
+        let mut task_pool = TaskPoolHandler::new(resource_manager.task_pool());
+        let mut input = Input::default();
+        let mut random = GameRandom::default();
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let piece = |id| {
+            Script::new(ScriptPiece {
+                id,
+                frames: 0,
+                sender: tx.clone(),
+            })
+        };
+
+        let node_handle = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_script(piece(0))
+                .with_script(piece(1)),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(scene_handle, &resource_manager);
+
+        let mut expected: Vec<&[(u32, &str)]> = vec![
+            &[
+                (0, "init"),
+                (1, "init"),
+                (0, "start"),
+                (1, "start"),
+                (0, "update"),
+                (1, "update"),
+            ],
+            // Scripts added on a frame are updated from the next frame.
+            &[
+                (0, "update"),
+                (1, "update"),
+                (2, "init"),
+                (2, "start"),
+                (1, "deinit"),
+            ],
+            &[(0, "update"), (2, "update")],
+        ];
+        expected.reverse();
+
+        for _ in 0..3 {
+            script_processor.handle_scripts(
+                &mut scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &mut task_pool,
+                &mut input,
+                &mut random,
+                0.0,
+                0.0,
+            );
+
+            assert_eq!(rx.try_iter().collect::<Vec<_>>(), expected.pop().unwrap());
+        }
+
+        let graph = &mut scene_container[scene_handle].graph;
+        let node = &mut graph[node_handle];
+        assert_eq!(node.script_count(), 2);
+        assert_eq!(node.try_get_script::<ScriptPiece>().unwrap().id, 0);
+        assert_eq!(
+            node.try_get_scripts::<ScriptPiece>()
+                .map(|s| s.id)
+                .collect::<Vec<_>>(),
+            [0, 2]
+        );
+
+        // Change execution order.
+        node.move_script(1, 0);
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            &mut task_pool,
+            &mut input,
+            &mut random,
+            0.0,
+            0.0,
+        );
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            [(2, "update"), (0, "update")]
+        );
+
+        scene_container[scene_handle].graph.remove_node(node_handle);
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            &mut task_pool,
+            &mut input,
+            &mut random,
+            0.0,
+            0.0,
+        );
+        assert_eq!(
+            rx.try_iter().collect::<Vec<_>>(),
+            [(2, "deinit"), (0, "deinit")]
+        );
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct CollisionTracker {
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(&'static str, Handle<Node>, Handle<Node>, bool)>,
+    }
+
+    impl CollisionTracker {
+        fn send(&self, kind: &'static str, collision: &Collision) {
+            self.sender
+                .send((
+                    kind,
+                    collision.collider,
+                    collision.other_collider,
+                    collision.contacts != CollisionContacts::None,
+                ))
+                .unwrap();
+        }
+    }
+
+    impl_component_provider!(CollisionTracker);
+
+    impl ScriptTrait for CollisionTracker {
+        fn on_collision_started(&mut self, collision: &Collision, _ctx: &mut ScriptContext) {
+            self.send("collision_started", collision);
+        }
+
+        fn on_collision_stopped(&mut self, collision: &Collision, _ctx: &mut ScriptContext) {
+            self.send("collision_stopped", collision);
+        }
+
+        fn on_trigger_entered(&mut self, collision: &Collision, _ctx: &mut ScriptContext) {
+            self.send("trigger_entered", collision);
+        }
+
+        fn on_trigger_exited(&mut self, collision: &Collision, _ctx: &mut ScriptContext) {
+            self.send("trigger_exited", collision);
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_collision_callbacks() {
+        let resource_manager = ResourceManager::new();
+        let mut task_pool = TaskPoolHandler::new(resource_manager.task_pool());
+        let mut input = Input::default();
+        let mut random = GameRandom::default();
+        let mut scene = Scene::new();
+        let graph = &mut scene.graph;
+
+        let (tx, rx) = mpsc::channel();
+
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(5.0, 0.1, 5.0))
+            .build(graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[ground_collider]))
+            .with_body_type(RigidBodyType::Static)
+            .build(graph);
+
+        let sensor = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(1.0, 1.0, 1.0))
+            .with_sensor(true)
+            .build(graph);
+        RigidBodyBuilder::new(BaseBuilder::new().with_children(&[sensor]))
+            .with_body_type(RigidBodyType::Static)
+            .build(graph);
+
+        // The ball is already touching the ground and it is inside the sensor.
+        let ball_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::ball(0.5))
+            .build(graph);
+        let ball = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.55, 0.0))
+                        .build(),
+                )
+                .with_children(&[ball_collider])
+                .with_script(Script::new(CollisionTracker { sender: tx })),
+        )
+        .build(graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(scene_handle, &resource_manager);
+
+        let mut update = |scene_container: &mut SceneContainer| {
+            scene_container[scene_handle].graph.update(
+                Default::default(),
+                1.0 / 60.0,
+                Default::default(),
+            );
+            script_processor.handle_scripts(
+                scene_container,
+                &mut Default::default(),
+                &resource_manager,
+                &mut task_pool,
+                &mut input,
+                &mut random,
+                1.0 / 60.0,
+                0.0,
+            );
+            let mut events = rx.try_iter().collect::<Vec<_>>();
+            events.sort_by_key(|e| e.0);
+            events
+        };
+
+        // Native colliders are created on the first frame, so collisions are reported from the next one.
+        assert!(update(&mut scene_container).is_empty());
+        let events = update(&mut scene_container);
+        assert_eq!(
+            events,
+            [
+                ("collision_started", ball_collider, ground_collider, true),
+                ("trigger_entered", ball_collider, sensor, false),
+            ]
+        );
+
+        assert!(update(&mut scene_container).is_empty());
+
+        // Move the ball away.
+        scene_container[scene_handle].graph[ball]
+            .local_transform_mut()
+            .set_position(Vector3::new(0.0, 10.0, 0.0));
+        let events = update(&mut scene_container);
+        assert_eq!(
+            events,
+            [
+                ("collision_stopped", ball_collider, ground_collider, false),
+                ("trigger_exited", ball_collider, sensor, false),
+            ]
+        );
+    }
+
+    #[derive(Debug, Clone, Reflect, Visit)]
+    struct TaskSpawner {
+        id: u32,
+        #[reflect(hidden)]
+        #[visit(skip)]
+        sender: Sender<(u32, u32)>,
+    }
+
+    impl_component_provider!(TaskSpawner);
+
+    impl ScriptTrait for TaskSpawner {
+        fn on_start(&mut self, ctx: &mut ScriptContext) {
+            let id = self.id;
+            ctx.spawn_task(
+                async move { id * 10 },
+                |result: u32, script: &mut TaskSpawner, _ctx: &mut ScriptContext| {
+                    script.sender.send((script.id, result)).unwrap();
+                },
+            );
+        }
+
+        fn id(&self) -> Uuid {
+            Uuid::new_v4()
+        }
+    }
+
+    #[test]
+    fn test_script_tasks() {
+        let resource_manager = ResourceManager::new();
+        let mut task_pool = TaskPoolHandler::new(resource_manager.task_pool());
+        let mut input = Input::default();
+        let mut random = GameRandom::default();
+        let mut scene = Scene::new();
+
+        let (tx, rx) = mpsc::channel();
+
+        let node = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_script(Script::new(TaskSpawner {
+                    id: 1,
+                    sender: tx.clone(),
+                }))
+                .with_script(Script::new(TaskSpawner { id: 2, sender: tx })),
+        )
+        .build(&mut scene.graph);
+
+        let mut scene_container = SceneContainer::new(Default::default());
+        let scene_handle = scene_container.add(scene);
+        let mut script_processor = ScriptProcessor::default();
+        script_processor.register_scripted_scene(scene_handle, &resource_manager);
+
+        script_processor.handle_scripts(
+            &mut scene_container,
+            &mut Default::default(),
+            &resource_manager,
+            &mut task_pool,
+            &mut input,
+            &mut random,
+            0.0,
+            0.0,
+        );
+
+        // Indices of the scripts are changed while the tasks are running.
+        let (piece_tx, _piece_rx) = mpsc::channel();
+        let node = &mut scene_container[scene_handle].graph[node];
+        node.add_script(Script::new(ScriptPiece {
+            id: 0,
+            frames: 0,
+            sender: piece_tx,
+        }));
+        node.move_script(2, 0);
+
+        let mut completed = 0;
+        while completed < 2 {
+            let Some(result) = task_pool.inner().next_task_result() else {
+                std::thread::sleep(std::time::Duration::from_millis(1));
+                continue;
+            };
+            let handler = task_pool.pop_script_task_handler(result.id).unwrap();
+            script_processor.handle_script_task(
+                handler,
+                result.payload,
+                &mut scene_container,
+                &mut [],
+                &resource_manager,
+                &mut task_pool,
+                &mut input,
+                &mut random,
+                0.0,
+                0.0,
+            );
+            completed += 1;
+        }
+
+        // Each result is passed to the script, that has spawned the task.
+        let mut results = rx.try_iter().collect::<Vec<_>>();
+        results.sort();
+        assert_eq!(results, [(1, 10), (2, 20)]);
+    }
 }
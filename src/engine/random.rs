@@ -0,0 +1,87 @@
+//! Seeded random number generator for game logic. See [`GameRandom`] docs for more info.
+
+use crate::core::rand::{self, rngs::StdRng, Error, RngCore, SeedableRng};
+
+/// Random number generator of the engine. Game logic should use it instead of `thread_rng` or other sources of
+/// randomness, so a run of the game could be reproduced by setting the same seed. It is used by the executor to
+/// make recorded sessions replayable (see [`crate::engine::executor::ReplayMode`]).
+///
+/// The generator implements [`RngCore`], so every method of [`rand::Rng`] is available:
+///
+/// ```rust
+/// # use fyrox::{core::rand::Rng, engine::random::GameRandom};
+/// let mut random = GameRandom::new(123);
+/// let damage = random.gen_range(10..20);
+/// # assert!((10..20).contains(&damage));
+/// ```
+pub struct GameRandom {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Default for GameRandom {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl GameRandom {
+    /// Creates a new generator with the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the seed, that was used to create the generator.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Resets the generator to the initial state with the given seed.
+    pub fn reseed(&mut self, seed: u64) {
+        *self = Self::new(seed);
+    }
+}
+
+impl RngCore for GameRandom {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{core::rand::Rng, engine::random::GameRandom};
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = GameRandom::new(42);
+        let mut b = GameRandom::new(42);
+        let sequence = (0..16).map(|_| a.gen::<u32>()).collect::<Vec<_>>();
+        assert_eq!(
+            sequence,
+            (0..16).map(|_| b.gen::<u32>()).collect::<Vec<_>>()
+        );
+
+        a.reseed(42);
+        assert_eq!(a.seed(), 42);
+        assert_eq!(
+            sequence,
+            (0..16).map(|_| a.gen::<u32>()).collect::<Vec<_>>()
+        );
+    }
+}
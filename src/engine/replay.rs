@@ -0,0 +1,730 @@
+//! Recording and replaying of game sessions. A recording contains the seed of [`GameRandom`], time step and input
+//! events of every update tick, and checksums of scene nodes, that are used to detect the first tick at which a
+//! replay diverges from the recorded session. See [`Recorder`] and [`Replayer`] docs for more info.
+//!
+//! A session can be replayed precisely only if game logic is deterministic: it should use [`Input`] to read input,
+//! [`GameRandom`] to generate random numbers and [`crate::plugin::PluginContext::dt`] instead of real time. Raw OS
+//! events are recorded as well and passed to plugins and scripts during replay (see [`RecordedOsEvent`]). Keep in
+//! mind, that window keyboard events are replayed as [`DeviceEvent::Key`] events, because window keyboard events
+//! cannot be created outside of the event loop.
+//!
+//! [`GameRandom`]: crate::engine::random::GameRandom
+
+use crate::{
+    core::{
+        algebra::Vector2,
+        pool::Handle,
+        uuid::Uuid,
+        visitor::{prelude::*, Visitor},
+    },
+    dpi::{PhysicalPosition, PhysicalSize},
+    engine::input::{Input, InputEvent},
+    event::{
+        DeviceEvent, DeviceId, ElementState, Event, MouseScrollDelta, RawKeyEvent, TouchPhase,
+        WindowEvent,
+    },
+    gui::message::{KeyCode, MouseButton},
+    keyboard::PhysicalKey,
+    scene::{base::InstanceId, node::Node, Scene, SceneContainer},
+    utils::{
+        translate_button, translate_button_from_ui, translate_key_from_ui, translate_key_to_ui,
+    },
+    window::WindowId,
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+/// Checksum of a scene node. It is calculated from serialized data of the node, so any change of the node (its
+/// transform, properties, scripts, etc.) changes the checksum. Instance id of the node is ignored, because it is
+/// random for the nodes created at runtime.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Visit)]
+pub struct NodeChecksum {
+    /// Handle of the node.
+    pub handle: Handle<Node>,
+    /// Name of the node. It is used only for reporting.
+    pub name: String,
+    /// Checksum of the node.
+    pub checksum: u64,
+}
+
+/// A snapshot of a scene, that contains checksums of every node of the scene.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Visit)]
+pub struct SceneSnapshot {
+    /// Handle of the scene.
+    pub handle: Handle<Scene>,
+    /// Checksums of every node of the scene.
+    pub nodes: Vec<NodeChecksum>,
+}
+
+impl SceneSnapshot {
+    /// Creates a snapshot of the given scene.
+    pub fn take(handle: Handle<Scene>, scene: &mut Scene) -> Result<Self, VisitError> {
+        let mut nodes = Vec::new();
+        for (node_handle, node) in scene.graph.pair_iter_mut() {
+            let instance_id = node.instance_id();
+            node.set_instance_id(InstanceId(Uuid::nil()));
+            let mut visitor = Visitor::new();
+            let result = node.visit("Node", &mut visitor);
+            node.set_instance_id(instance_id);
+            result?;
+
+            nodes.push(NodeChecksum {
+                handle: node_handle,
+                name: node.name_owned(),
+                checksum: fxhash::hash64(&visitor.save_binary_to_vec()?),
+            });
+        }
+        Ok(Self { handle, nodes })
+    }
+}
+
+/// Creates snapshots of every scene in the given container.
+pub fn take_snapshots(scenes: &mut SceneContainer) -> Result<Vec<SceneSnapshot>, VisitError> {
+    scenes
+        .pair_iter_mut()
+        .map(|(handle, scene)| SceneSnapshot::take(handle, scene))
+        .collect()
+}
+
+/// A raw OS event, that was received during a recorded tick. Only the events, that could affect game logic, are
+/// recorded: keyboard, mouse, cursor, focus and resize events of the window and keyboard and mouse events of input
+/// devices.
+#[derive(Debug, Clone, PartialEq, Visit)]
+pub enum RecordedOsEvent {
+    /// [`WindowEvent::KeyboardInput`] event.
+    WindowKey {
+        /// Code of the key.
+        key: KeyCode,
+        /// New state of the key.
+        pressed: bool,
+        /// Whether the event is a key repeat event.
+        repeat: bool,
+    },
+    /// [`WindowEvent::MouseInput`] event.
+    MouseButton {
+        /// The mouse button.
+        button: MouseButton,
+        /// New state of the button.
+        pressed: bool,
+    },
+    /// [`WindowEvent::MouseWheel`] event.
+    MouseWheel {
+        /// Amount of scrolling.
+        delta: Vector2<f64>,
+        /// Whether the amount is in pixels or in lines.
+        pixels: bool,
+    },
+    /// [`WindowEvent::CursorMoved`] event.
+    CursorMoved {
+        /// New position of the cursor in physical pixels.
+        position: Vector2<f64>,
+    },
+    /// [`WindowEvent::Focused`] event.
+    Focused(bool),
+    /// [`WindowEvent::Resized`] event.
+    Resized(Vector2<u32>),
+    /// [`DeviceEvent::Key`] event.
+    DeviceKey {
+        /// Code of the key.
+        key: KeyCode,
+        /// New state of the key.
+        pressed: bool,
+    },
+    /// [`DeviceEvent::MouseMotion`] event.
+    MouseMotion {
+        /// Relative movement of the mouse.
+        delta: Vector2<f64>,
+    },
+}
+
+impl Default for RecordedOsEvent {
+    fn default() -> Self {
+        // An event without any effect.
+        Self::MouseMotion {
+            delta: Vector2::default(),
+        }
+    }
+}
+
+impl RecordedOsEvent {
+    /// Creates a recorded event from the given OS event. Returns `None` if the event is not recorded.
+    pub fn from_os_event(event: &Event<()>) -> Option<Self> {
+        match event {
+            Event::WindowEvent { event, .. } => match event {
+                WindowEvent::KeyboardInput { event, .. } => match event.physical_key {
+                    PhysicalKey::Code(key) => Some(Self::WindowKey {
+                        key: translate_key_to_ui(key),
+                        pressed: event.state == ElementState::Pressed,
+                        repeat: event.repeat,
+                    }),
+                    PhysicalKey::Unidentified(_) => None,
+                },
+                WindowEvent::MouseInput { state, button, .. } => Some(Self::MouseButton {
+                    button: translate_button(*button),
+                    pressed: *state == ElementState::Pressed,
+                }),
+                WindowEvent::MouseWheel { delta, .. } => Some(match delta {
+                    MouseScrollDelta::LineDelta(x, y) => Self::MouseWheel {
+                        delta: Vector2::new(*x as f64, *y as f64),
+                        pixels: false,
+                    },
+                    MouseScrollDelta::PixelDelta(delta) => Self::MouseWheel {
+                        delta: Vector2::new(delta.x, delta.y),
+                        pixels: true,
+                    },
+                }),
+                WindowEvent::CursorMoved { position, .. } => Some(Self::CursorMoved {
+                    position: Vector2::new(position.x, position.y),
+                }),
+                WindowEvent::Focused(focused) => Some(Self::Focused(*focused)),
+                WindowEvent::Resized(size) => {
+                    Some(Self::Resized(Vector2::new(size.width, size.height)))
+                }
+                _ => None,
+            },
+            Event::DeviceEvent { event, .. } => match event {
+                DeviceEvent::Key(RawKeyEvent {
+                    physical_key: PhysicalKey::Code(key),
+                    state,
+                }) => Some(Self::DeviceKey {
+                    key: translate_key_to_ui(*key),
+                    pressed: *state == ElementState::Pressed,
+                }),
+                DeviceEvent::MouseMotion { delta } => Some(Self::MouseMotion {
+                    delta: Vector2::new(delta.0, delta.1),
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Converts the recorded event back into an OS event of a dummy window and a dummy device, that is passed to
+    /// plugins and scripts during replay. Window keyboard events are converted into [`DeviceEvent::Key`], because
+    /// window keyboard events cannot be created outside of the event loop. Use [`Input`] to read keyboard state
+    /// in game logic, that should be replayed precisely.
+    pub fn to_os_event(&self) -> Event<()> {
+        let state = |pressed: bool| {
+            if pressed {
+                ElementState::Pressed
+            } else {
+                ElementState::Released
+            }
+        };
+        // SAFETY: Dummy ids are just placeholders, they're never passed to the OS.
+        let (device_id, window_id) = unsafe { (DeviceId::dummy(), WindowId::dummy()) };
+        let window_event = |event| Event::WindowEvent { window_id, event };
+        let device_event = |event| Event::DeviceEvent { device_id, event };
+
+        match self {
+            Self::WindowKey { key, pressed, .. } | Self::DeviceKey { key, pressed } => {
+                device_event(DeviceEvent::Key(RawKeyEvent {
+                    physical_key: PhysicalKey::Code(translate_key_from_ui(*key)),
+                    state: state(*pressed),
+                }))
+            }
+            Self::MouseButton { button, pressed } => window_event(WindowEvent::MouseInput {
+                device_id,
+                state: state(*pressed),
+                button: translate_button_from_ui(*button),
+            }),
+            Self::MouseWheel { delta, pixels } => window_event(WindowEvent::MouseWheel {
+                device_id,
+                delta: if *pixels {
+                    MouseScrollDelta::PixelDelta(PhysicalPosition::new(delta.x, delta.y))
+                } else {
+                    MouseScrollDelta::LineDelta(delta.x as f32, delta.y as f32)
+                },
+                phase: TouchPhase::Moved,
+            }),
+            Self::CursorMoved { position } => window_event(WindowEvent::CursorMoved {
+                device_id,
+                position: PhysicalPosition::new(position.x, position.y),
+            }),
+            Self::Focused(focused) => window_event(WindowEvent::Focused(*focused)),
+            Self::Resized(size) => {
+                window_event(WindowEvent::Resized(PhysicalSize::new(size.x, size.y)))
+            }
+            Self::MouseMotion { delta } => device_event(DeviceEvent::MouseMotion {
+                delta: (delta.x, delta.y),
+            }),
+        }
+    }
+}
+
+/// Recorded data of a single update tick.
+#[derive(Debug, Default, Clone, PartialEq, Visit)]
+pub struct RecordedTick {
+    /// Time step of the tick.
+    pub dt: f32,
+    /// Input events, that were applied to the input before or during the tick.
+    pub events: Vec<InputEvent>,
+    /// Raw OS events, that were received before or during the tick.
+    pub os_events: Vec<RecordedOsEvent>,
+    /// Snapshots of the scenes at the end of the tick. Snapshots could be made not on every tick, see
+    /// [`Recorder::new`].
+    pub snapshots: Option<Vec<SceneSnapshot>>,
+}
+
+/// A recorded session of a game.
+#[derive(Debug, Default, Clone, PartialEq, Visit)]
+pub struct Recording {
+    /// Seed of the random number generator of the engine.
+    pub seed: u64,
+    /// Recorded ticks.
+    pub ticks: Vec<RecordedTick>,
+}
+
+impl Recording {
+    /// Saves the recording to a memory buffer.
+    pub fn save_to_memory(&mut self) -> Result<Vec<u8>, VisitError> {
+        let mut visitor = Visitor::new();
+        self.visit("Recording", &mut visitor)?;
+        visitor.save_binary_to_vec()
+    }
+
+    /// Loads a recording from a memory buffer, that was created by [`Self::save_to_memory`].
+    pub fn load_from_memory(data: &[u8]) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_from_memory(data)?;
+        let mut recording = Self::default();
+        recording.visit("Recording", &mut visitor)?;
+        Ok(recording)
+    }
+
+    /// Saves the recording to a file.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<(), VisitError> {
+        std::fs::write(path, self.save_to_memory()?)?;
+        Ok(())
+    }
+
+    /// Loads a recording from a file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
+        Self::load_from_memory(&std::fs::read(path)?)
+    }
+}
+
+/// A reason of a divergence of a replay from a recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The scene exists in the recording, but not in the replay.
+    MissingScene,
+    /// The scene exists in the replay, but not in the recording.
+    UnexpectedScene,
+    /// The node exists in the recording, but not in the replay.
+    MissingNode,
+    /// The node exists in the replay, but not in the recording.
+    UnexpectedNode,
+    /// The node has a different state.
+    NodeChanged,
+}
+
+/// The first difference between a recording and its replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    /// Index of the tick at which the difference was found.
+    pub tick: usize,
+    /// Handle of the scene, that has the difference.
+    pub scene: Handle<Scene>,
+    /// Handle of the node, that has the difference. It is [`Handle::NONE`] if the whole scene is different.
+    pub node: Handle<Node>,
+    /// Name of the node, that has the difference.
+    pub node_name: String,
+    /// A reason of the difference.
+    pub kind: DivergenceKind,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let what = match self.kind {
+            DivergenceKind::MissingScene => "the scene is missing",
+            DivergenceKind::UnexpectedScene => "the scene was not recorded",
+            DivergenceKind::MissingNode => "the node is missing",
+            DivergenceKind::UnexpectedNode => "the node was not recorded",
+            DivergenceKind::NodeChanged => "the node has a different state",
+        };
+        write!(
+            f,
+            "Replay diverged at tick {}: {what} (scene {}, node {} {:?}).",
+            self.tick, self.scene, self.node, self.node_name
+        )
+    }
+}
+
+/// Compares recorded snapshots with the actual ones and returns the first difference (if any).
+pub fn compare_snapshots(
+    tick: usize,
+    expected: &[SceneSnapshot],
+    actual: &[SceneSnapshot],
+) -> Option<Divergence> {
+    let scene_divergence = |scene, kind| Divergence {
+        tick,
+        scene,
+        node: Handle::NONE,
+        node_name: Default::default(),
+        kind,
+    };
+
+    for expected_scene in expected {
+        let Some(actual_scene) = actual.iter().find(|s| s.handle == expected_scene.handle) else {
+            return Some(scene_divergence(
+                expected_scene.handle,
+                DivergenceKind::MissingScene,
+            ));
+        };
+
+        for expected_node in expected_scene.nodes.iter() {
+            let kind = match actual_scene
+                .nodes
+                .iter()
+                .find(|n| n.handle == expected_node.handle)
+            {
+                None => DivergenceKind::MissingNode,
+                Some(actual_node) if actual_node != expected_node => DivergenceKind::NodeChanged,
+                _ => continue,
+            };
+            return Some(Divergence {
+                tick,
+                scene: expected_scene.handle,
+                node: expected_node.handle,
+                node_name: expected_node.name.clone(),
+                kind,
+            });
+        }
+
+        if let Some(unexpected_node) = actual_scene.nodes.iter().find(|n| {
+            !expected_scene
+                .nodes
+                .iter()
+                .any(|expected_node| expected_node.handle == n.handle)
+        }) {
+            return Some(Divergence {
+                tick,
+                scene: expected_scene.handle,
+                node: unexpected_node.handle,
+                node_name: unexpected_node.name.clone(),
+                kind: DivergenceKind::UnexpectedNode,
+            });
+        }
+    }
+
+    actual
+        .iter()
+        .find(|s| !expected.iter().any(|e| e.handle == s.handle))
+        .map(|s| scene_divergence(s.handle, DivergenceKind::UnexpectedScene))
+}
+
+/// Recorder collects input events and snapshots of scenes on every update tick. Call [`Self::record_tick`] right
+/// after each update of the engine.
+pub struct Recorder {
+    recording: Recording,
+    snapshot_interval: usize,
+    os_events: Vec<RecordedOsEvent>,
+}
+
+impl Recorder {
+    /// Creates a new recorder and enables recording of input events. `seed` must be the seed of the random number
+    /// generator of the engine. Scenes are captured on every `snapshot_interval` tick (`0` disables snapshots), it
+    /// could be increased if capturing takes too much time.
+    pub fn new(seed: u64, snapshot_interval: usize, input: &mut Input) -> Self {
+        input.set_recording(true);
+        Self {
+            recording: Recording {
+                seed,
+                ticks: Default::default(),
+            },
+            snapshot_interval,
+            os_events: Default::default(),
+        }
+    }
+
+    /// Records the given OS event, if it could affect game logic (see [`RecordedOsEvent`]). The event is added to
+    /// the next recorded tick.
+    pub fn record_os_event(&mut self, event: &Event<()>) {
+        self.os_events.extend(RecordedOsEvent::from_os_event(event));
+    }
+
+    /// Records a tick, that was just performed with the given time step.
+    pub fn record_tick(
+        &mut self,
+        dt: f32,
+        input: &mut Input,
+        scenes: &mut SceneContainer,
+    ) -> Result<(), VisitError> {
+        let tick = self.recording.ticks.len();
+        let snapshots = if self.snapshot_interval != 0 && tick % self.snapshot_interval == 0 {
+            Some(take_snapshots(scenes)?)
+        } else {
+            None
+        };
+        self.recording.ticks.push(RecordedTick {
+            dt,
+            events: input.take_recorded_events(),
+            os_events: std::mem::take(&mut self.os_events),
+            snapshots,
+        });
+        Ok(())
+    }
+
+    /// Returns a reference to the recording.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Returns a reference to the recording.
+    pub fn recording_mut(&mut self) -> &mut Recording {
+        &mut self.recording
+    }
+
+    /// Stops recording of input events and returns the recording.
+    pub fn finish(self, input: &mut Input) -> Recording {
+        input.set_recording(false);
+        self.recording
+    }
+}
+
+/// Replayer feeds recorded input events to the engine and compares the state of scenes with recorded snapshots.
+/// On each tick call [`Self::begin_tick`] and update the engine with the returned time step, then call
+/// [`Self::end_tick`]. The engine's random number generator must be seeded with [`Self::seed`] before the game
+/// is initialized.
+pub struct Replayer {
+    recording: Recording,
+    position: usize,
+    divergence: Option<Divergence>,
+}
+
+impl Replayer {
+    /// Creates a new replayer and blocks external input, so only the recorded events are applied to the input.
+    pub fn new(recording: Recording, input: &mut Input) -> Self {
+        input.set_external_input_blocked(true);
+        Self {
+            recording,
+            position: 0,
+            divergence: None,
+        }
+    }
+
+    /// Returns the seed of the random number generator, that was used in the recorded session.
+    pub fn seed(&self) -> u64 {
+        self.recording.seed
+    }
+
+    /// Returns `true` if every recorded tick was replayed.
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.recording.ticks.len()
+    }
+
+    /// Returns an index of the next tick.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the first divergence of the replay from the recording (if any).
+    pub fn divergence(&self) -> Option<&Divergence> {
+        self.divergence.as_ref()
+    }
+
+    /// Returns recorded OS events of the next tick. They should be passed to plugins and scripts instead of real
+    /// OS events, see [`RecordedOsEvent::to_os_event`] for more info.
+    pub fn os_events(&self) -> Vec<Event<()>> {
+        self.recording
+            .ticks
+            .get(self.position)
+            .map(|tick| tick.os_events.iter().map(|e| e.to_os_event()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Applies recorded input events of the next tick to the input and returns the time step of the tick.
+    /// Returns `None` if there are no more ticks.
+    pub fn begin_tick(&mut self, input: &mut Input) -> Option<f32> {
+        let tick = self.recording.ticks.get(self.position)?;
+        for event in tick.events.iter() {
+            input.process_event(event.clone());
+        }
+        Some(tick.dt)
+    }
+
+    /// Compares the state of scenes with the recorded snapshot of the current tick (if any) and advances to the
+    /// next tick. Returns the first divergence, that was found so far.
+    pub fn end_tick(
+        &mut self,
+        scenes: &mut SceneContainer,
+    ) -> Result<Option<&Divergence>, VisitError> {
+        if let Some(expected) = self
+            .recording
+            .ticks
+            .get(self.position)
+            .and_then(|t| t.snapshots.as_ref())
+        {
+            if self.divergence.is_none() {
+                let actual = take_snapshots(scenes)?;
+                self.divergence = compare_snapshots(self.position, expected, &actual);
+            }
+        }
+        self.position += 1;
+        Ok(self.divergence.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        dpi::PhysicalPosition,
+        engine::{
+            input::{Input, InputAxis, InputContext, InputEvent, InputSource},
+            replay::{DivergenceKind, Recorder, Recording, Replayer},
+        },
+        event::{
+            DeviceEvent, DeviceId, ElementState, Event, MouseButton, RawKeyEvent, WindowEvent,
+        },
+        gui::message::KeyCode,
+        keyboard::{self, PhysicalKey},
+        scene::sound::SoundEngine,
+        scene::{base::BaseBuilder, pivot::PivotBuilder, Scene, SceneContainer},
+        window::WindowId,
+    };
+
+    fn make_world() -> (Input, SceneContainer) {
+        let mut input = Input::default();
+        input.map.add_context(
+            InputContext::new("Gameplay").with_axis(
+                InputAxis::new("MoveRight").with_binding(InputSource::Key(KeyCode::KeyD)),
+            ),
+        );
+        input.push_context("Gameplay");
+
+        let mut scene = Scene::new();
+        PivotBuilder::new(BaseBuilder::new().with_name("Player")).build(&mut scene.graph);
+        let mut scenes = SceneContainer::new(SoundEngine::without_device());
+        scenes.add(scene);
+
+        (input, scenes)
+    }
+
+    // A tiny "game": the player moves to the right while the axis is active.
+    fn simulate(input: &mut Input, scenes: &mut SceneContainer, dt: f32, cheat: bool) {
+        input.update();
+        let speed = input.axis_value("MoveRight") + if cheat { 1.0 } else { 0.0 };
+        for scene in scenes.iter_mut() {
+            let player = scene.graph.find_by_name_from_root("Player").unwrap().0;
+            scene.graph[player]
+                .local_transform_mut()
+                .offset(Vector3::new(speed * dt, 0.0, 0.0));
+        }
+    }
+
+    fn record() -> Recording {
+        let (mut input, mut scenes) = make_world();
+        let mut recorder = Recorder::new(123, 1, &mut input);
+        for tick in 0..5 {
+            if tick == 1 {
+                input.process_event(InputEvent::Key {
+                    key: KeyCode::KeyD,
+                    pressed: true,
+                });
+            } else if tick == 3 {
+                input.process_event(InputEvent::Key {
+                    key: KeyCode::KeyD,
+                    pressed: false,
+                });
+            }
+            simulate(&mut input, &mut scenes, 0.1, false);
+            recorder.record_tick(0.1, &mut input, &mut scenes).unwrap();
+        }
+        recorder.finish(&mut input)
+    }
+
+    fn replay(recording: Recording, cheat_at: Option<usize>) -> Replayer {
+        let (mut input, mut scenes) = make_world();
+        let mut replayer = Replayer::new(recording, &mut input);
+        while let Some(dt) = replayer.begin_tick(&mut input) {
+            let cheat = cheat_at == Some(replayer.position());
+            simulate(&mut input, &mut scenes, dt, cheat);
+            replayer.end_tick(&mut scenes).unwrap();
+        }
+        replayer
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut recording = record();
+        assert_eq!(recording.seed, 123);
+        assert_eq!(recording.ticks.len(), 5);
+        assert_eq!(recording.ticks[1].events.len(), 1);
+
+        let data = recording.save_to_memory().unwrap();
+        let recording = Recording::load_from_memory(&data).unwrap();
+
+        let replayer = replay(recording.clone(), None);
+        assert!(replayer.is_finished());
+        assert_eq!(replayer.divergence(), None);
+
+        let replayer = replay(recording, Some(2));
+        let divergence = replayer.divergence().unwrap();
+        assert_eq!(divergence.tick, 2);
+        assert_eq!(divergence.node_name, "Player");
+        assert_eq!(divergence.kind, DivergenceKind::NodeChanged);
+    }
+
+    #[test]
+    fn test_recorded_os_events_are_replayed() {
+        // SAFETY: Dummy ids are just placeholders, they're never passed to the OS.
+        let (device_id, window_id) = unsafe { (DeviceId::dummy(), WindowId::dummy()) };
+        let os_events = [
+            Event::DeviceEvent {
+                device_id,
+                event: DeviceEvent::Key(RawKeyEvent {
+                    physical_key: PhysicalKey::Code(keyboard::KeyCode::KeyD),
+                    state: ElementState::Pressed,
+                }),
+            },
+            Event::WindowEvent {
+                window_id,
+                event: WindowEvent::CursorMoved {
+                    device_id,
+                    position: PhysicalPosition::new(10.0, 20.0),
+                },
+            },
+            Event::WindowEvent {
+                window_id,
+                event: WindowEvent::MouseInput {
+                    device_id,
+                    state: ElementState::Released,
+                    button: MouseButton::Right,
+                },
+            },
+            Event::DeviceEvent {
+                device_id,
+                event: DeviceEvent::MouseMotion { delta: (1.0, -2.0) },
+            },
+        ];
+
+        let (mut input, mut scenes) = make_world();
+        let mut recorder = Recorder::new(123, 0, &mut input);
+        for tick in 0..3 {
+            if tick == 1 {
+                for event in os_events.iter() {
+                    recorder.record_os_event(event);
+                }
+                // Events, that don't affect game logic, are not recorded.
+                recorder.record_os_event(&Event::AboutToWait);
+            }
+            simulate(&mut input, &mut scenes, 0.1, false);
+            recorder.record_tick(0.1, &mut input, &mut scenes).unwrap();
+        }
+        let data = recorder.finish(&mut input).save_to_memory().unwrap();
+        let recording = Recording::load_from_memory(&data).unwrap();
+
+        let (mut input, _) = make_world();
+        let mut replayer = Replayer::new(recording, &mut input);
+        let mut replayed = Vec::new();
+        while replayer.begin_tick(&mut input).is_some() {
+            replayed.push(replayer.os_events());
+            replayer.end_tick(&mut scenes).unwrap();
+        }
+
+        assert_eq!(replayed, [vec![], os_events.to_vec(), vec![]]);
+    }
+}
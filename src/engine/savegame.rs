@@ -0,0 +1,324 @@
+//! Save games contain only the changes made to scenes after they were loaded from their source files (see
+//! [`Scene::save_delta`]) and the data of plugins (see [`Plugin::visit_save_data`]). See [`Engine::save_game`]
+//! and [`Engine::load_game`] docs for more info.
+
+use crate::{
+    asset::manager::ResourceManager,
+    core::{
+        log::Log,
+        pool::Handle,
+        visitor::{Visit, VisitError, Visitor},
+    },
+    engine::{Engine, SerializationContext},
+    plugin::Plugin,
+    scene::{Scene, SceneLoader},
+};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+use std::{
+    fmt::{Display, Formatter},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Magic bytes at the beginning of every save file.
+pub const SAVE_GAME_MAGIC: [u8; 4] = *b"FSAV";
+
+/// Current version of save files. Save files of newer versions cannot be loaded.
+pub const SAVE_GAME_VERSION: u32 = 1;
+
+const COMPRESSED_FLAG: u32 = 1;
+
+/// An error, that may occur during saving or loading a game.
+#[derive(Debug)]
+pub enum SaveGameError {
+    /// An IO error.
+    Io(std::io::Error),
+    /// Serialization error.
+    Visit(VisitError),
+    /// The data does not start with [`SAVE_GAME_MAGIC`].
+    InvalidHeader,
+    /// The data was saved by a newer version of the engine.
+    UnsupportedVersion(u32),
+}
+
+impl Display for SaveGameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveGameError::Io(v) => Display::fmt(v, f),
+            SaveGameError::Visit(v) => Display::fmt(v, f),
+            SaveGameError::InvalidHeader => write!(f, "The data is not a save file!"),
+            SaveGameError::UnsupportedVersion(v) => {
+                write!(f, "Unsupported save file version {v}!")
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for SaveGameError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SaveGameError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Header of a save file. It is stored uncompressed at the beginning of the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveGameHeader {
+    /// Version of the save file.
+    pub version: u32,
+    /// Whether the rest of the file is compressed or not.
+    pub compressed: bool,
+}
+
+impl SaveGameHeader {
+    const SIZE: usize = 12;
+
+    fn write(&self, dest: &mut Vec<u8>) {
+        dest.extend_from_slice(&SAVE_GAME_MAGIC);
+        dest.extend_from_slice(&self.version.to_le_bytes());
+        let flags = if self.compressed { COMPRESSED_FLAG } else { 0 };
+        dest.extend_from_slice(&flags.to_le_bytes());
+    }
+
+    /// Reads the header from the beginning of the given data.
+    pub fn read(data: &[u8]) -> Result<Self, SaveGameError> {
+        if data.len() < Self::SIZE || data[0..4] != SAVE_GAME_MAGIC {
+            return Err(SaveGameError::InvalidHeader);
+        }
+        let version = u32::from_le_bytes(data[4..8].try_into().unwrap());
+        let flags = u32::from_le_bytes(data[8..12].try_into().unwrap());
+        Ok(Self {
+            version,
+            compressed: flags & COMPRESSED_FLAG != 0,
+        })
+    }
+}
+
+/// Writes the content of the given visitor as a save file, optionally compressing it.
+pub fn write_save_game(visitor: &mut Visitor, compress: bool) -> Result<Vec<u8>, SaveGameError> {
+    let mut data = Vec::new();
+    SaveGameHeader {
+        version: SAVE_GAME_VERSION,
+        compressed: compress,
+    }
+    .write(&mut data);
+
+    let payload = visitor.save_binary_to_vec()?;
+    if compress {
+        let mut encoder = ZlibEncoder::new(data, Compression::default());
+        encoder.write_all(&payload)?;
+        Ok(encoder.finish()?)
+    } else {
+        data.extend_from_slice(&payload);
+        Ok(data)
+    }
+}
+
+/// Reads a save file, that was written by [`write_save_game`], and returns its header and a visitor with its
+/// content.
+pub fn read_save_game(data: &[u8]) -> Result<(SaveGameHeader, Visitor), SaveGameError> {
+    let header = SaveGameHeader::read(data)?;
+    if header.version > SAVE_GAME_VERSION {
+        return Err(SaveGameError::UnsupportedVersion(header.version));
+    }
+
+    let payload = &data[SaveGameHeader::SIZE..];
+    let visitor = if header.compressed {
+        let mut decompressed = Vec::new();
+        ZlibDecoder::new(payload).read_to_end(&mut decompressed)?;
+        Visitor::load_from_memory(&decompressed)?
+    } else {
+        Visitor::load_from_memory(payload)?
+    };
+
+    Ok((header, visitor))
+}
+
+/// Options of [`Engine::save_game`].
+#[derive(Debug, Clone)]
+pub struct SaveGameOptions {
+    /// Scenes to save with paths to their source files. Only the changes made to the scenes after they were
+    /// loaded from the source files are saved.
+    pub scenes: Vec<(Handle<Scene>, PathBuf)>,
+    /// Whether the save file should be compressed or not. Default is `true`.
+    pub compress: bool,
+}
+
+impl Default for SaveGameOptions {
+    fn default() -> Self {
+        Self {
+            scenes: Default::default(),
+            compress: true,
+        }
+    }
+}
+
+async fn load_original_scene(
+    path: &Path,
+    serialization_context: Arc<SerializationContext>,
+    resource_manager: ResourceManager,
+) -> Result<Scene, SaveGameError> {
+    let io = resource_manager.resource_io();
+    let (loader, _) = SceneLoader::from_file(
+        path,
+        io.as_ref(),
+        serialization_context,
+        resource_manager.clone(),
+    )
+    .await?;
+    Ok(loader.finish(&resource_manager).await)
+}
+
+impl Engine {
+    /// Saves the game. The save file contains only the changes made to the given scenes after they were loaded
+    /// from their source files (see [`Scene::save_delta`] for more info) and the data of every plugin (see
+    /// [`Plugin::visit_save_data`]). The original scenes are loaded from the source files to calculate the
+    /// changes, so the source files must not be changed between saving and loading.
+    pub async fn save_game(&mut self, options: SaveGameOptions) -> Result<Vec<u8>, SaveGameError> {
+        let mut originals = Vec::new();
+        for (_, path) in options.scenes.iter() {
+            originals.push(
+                load_original_scene(
+                    path,
+                    self.serialization_context.clone(),
+                    self.resource_manager.clone(),
+                )
+                .await?,
+            );
+        }
+
+        let mut visitor = Visitor::new();
+
+        let mut count = options.scenes.len() as u32;
+        count.visit("SceneCount", &mut visitor)?;
+        for (i, ((handle, path), original)) in options.scenes.iter().zip(originals).enumerate() {
+            let scene = self.scenes.try_get_mut(*handle).ok_or_else(|| {
+                SaveGameError::Visit(VisitError::User(format!("Invalid scene handle {handle}!")))
+            })?;
+
+            let mut region = visitor.enter_region(&format!("Scene{i}"))?;
+            let mut saved_handle = *handle;
+            saved_handle.visit("Handle", &mut region)?;
+            path.clone().visit("Path", &mut region)?;
+            scene.save_delta(
+                &original,
+                "Delta",
+                self.serialization_context.clone(),
+                self.resource_manager.clone(),
+                &mut region,
+            )?;
+        }
+
+        visit_plugins_data(&mut self.plugins, &mut visitor)?;
+
+        write_save_game(&mut visitor, options.compress)
+    }
+
+    /// Loads a game, that was saved by [`Self::save_game`]. Every scene from the save file is loaded from its
+    /// source file, the changes from the save file are applied to it and the scene is added to the engine. Scenes
+    /// are restored at their previous handles if possible, so it is recommended to remove every scene from the
+    /// engine before loading. Returns handles of the loaded scenes in the order in which they were saved.
+    pub async fn load_game(&mut self, data: &[u8]) -> Result<Vec<Handle<Scene>>, SaveGameError> {
+        let (_, mut visitor) = read_save_game(data)?;
+        visitor
+            .blackboard
+            .register(self.serialization_context.clone());
+        visitor
+            .blackboard
+            .register(Arc::new(self.resource_manager.clone()));
+
+        let mut count = 0u32;
+        count.visit("SceneCount", &mut visitor)?;
+
+        let mut entries = Vec::new();
+        for i in 0..count {
+            let mut region = visitor.enter_region(&format!("Scene{i}"))?;
+            let mut handle = Handle::<Scene>::NONE;
+            handle.visit("Handle", &mut region)?;
+            let mut path = PathBuf::default();
+            path.visit("Path", &mut region)?;
+            entries.push((handle, path));
+        }
+
+        let mut handles = Vec::new();
+        for (i, (handle, path)) in entries.into_iter().enumerate() {
+            let mut scene = load_original_scene(
+                &path,
+                self.serialization_context.clone(),
+                self.resource_manager.clone(),
+            )
+            .await?;
+
+            let mut region = visitor.enter_region(&format!("Scene{i}"))?;
+            scene.load_delta(
+                "Delta",
+                self.serialization_context.clone(),
+                self.resource_manager.clone(),
+                &mut region,
+            )?;
+            drop(region);
+
+            let new_handle = self.scenes.add_at_handle(handle, scene);
+            if new_handle != handle {
+                Log::warn(format!(
+                    "Unable to restore scene {} at {handle}, because the handle is occupied.",
+                    path.display()
+                ));
+            }
+            handles.push(new_handle);
+        }
+
+        visit_plugins_data(&mut self.plugins, &mut visitor)?;
+
+        Ok(handles)
+    }
+}
+
+fn visit_plugins_data(
+    plugins: &mut [Box<dyn Plugin>],
+    visitor: &mut Visitor,
+) -> Result<(), VisitError> {
+    let mut region = visitor.enter_region("Plugins")?;
+    for (i, plugin) in plugins.iter_mut().enumerate() {
+        let mut plugin_region = region.enter_region(&format!("Plugin{i}"))?;
+        plugin.visit_save_data(&mut plugin_region)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::visitor::{Visit, Visitor},
+        engine::savegame::{read_save_game, write_save_game, SaveGameError, SAVE_GAME_VERSION},
+    };
+
+    #[test]
+    fn test_save_game_header() {
+        for compress in [false, true] {
+            let mut visitor = Visitor::new();
+            let mut value = String::from("Foobar").repeat(100);
+            value.visit("Value", &mut visitor).unwrap();
+
+            let data = write_save_game(&mut visitor, compress).unwrap();
+            let (header, mut visitor) = read_save_game(&data).unwrap();
+            assert_eq!(header.version, SAVE_GAME_VERSION);
+            assert_eq!(header.compressed, compress);
+
+            let mut loaded = String::new();
+            loaded.visit("Value", &mut visitor).unwrap();
+            assert_eq!(loaded, value);
+        }
+
+        assert!(matches!(
+            read_save_game(b"RGS"),
+            Err(SaveGameError::InvalidHeader)
+        ));
+    }
+}
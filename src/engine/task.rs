@@ -0,0 +1,136 @@
+//! Task pool handler allows plugins and scripts to run heavy work off the main thread and receive results of the
+//! work on the main thread. See [`TaskPoolHandler`] docs for more info.
+
+use crate::{
+    core::{
+        pool::Handle,
+        task::{AsyncTask, AsyncTaskResult, TaskPool},
+        uuid::Uuid,
+    },
+    plugin::{Plugin, PluginContext},
+    scene::{node::Node, Scene},
+    script::{Script, ScriptContext, ScriptTrait},
+};
+use fxhash::FxHashMap;
+use std::{any::TypeId, sync::Arc};
+
+pub(crate) type PluginTaskHandler =
+    Box<dyn FnOnce(Box<dyn AsyncTaskResult>, &mut [Box<dyn Plugin>], &mut PluginContext)>;
+
+pub(crate) type ScriptTaskClosure =
+    Box<dyn FnOnce(Box<dyn AsyncTaskResult>, &mut Script, &mut ScriptContext)>;
+
+pub(crate) struct ScriptTaskHandler {
+    pub(crate) scene: Handle<Scene>,
+    pub(crate) node: Handle<Node>,
+    // Type of the script and its ordinal among the scripts of the same type are used to find the script, because
+    // its index could change while the task is running (if some scripts are added, removed or reordered).
+    pub(crate) script_type: TypeId,
+    pub(crate) script_ordinal: usize,
+    pub(crate) closure: ScriptTaskClosure,
+}
+
+/// Task pool handler allows you to spawn asynchronous tasks (procedural generation, path finding, file IO, etc.)
+/// and handle their results on the main thread with mutable access to a plugin or a script, and to the rest of
+/// the engine. On PC the tasks are executed on a thread pool, on WebAssembly they're executed as JS micro-tasks.
+///
+/// In most cases you should use [`PluginContext::spawn_task`] and [`ScriptContext::spawn_task`] methods, which
+/// use the handler internally.
+pub struct TaskPoolHandler {
+    task_pool: Arc<TaskPool>,
+    plugin_task_handlers: FxHashMap<Uuid, PluginTaskHandler>,
+    script_task_handlers: FxHashMap<Uuid, ScriptTaskHandler>,
+}
+
+impl TaskPoolHandler {
+    pub(crate) fn new(task_pool: Arc<TaskPool>) -> Self {
+        Self {
+            task_pool,
+            plugin_task_handlers: Default::default(),
+            script_task_handlers: Default::default(),
+        }
+    }
+
+    /// Spawns a new task, result of which will be passed to the plugin of the given type on the main thread. The
+    /// result will be discarded if there's no such plugin.
+    pub fn spawn_plugin_task<F, T, P, C>(&mut self, future: F, on_complete: C)
+    where
+        F: AsyncTask<T>,
+        T: AsyncTaskResult,
+        P: Plugin,
+        C: FnOnce(T, &mut P, &mut PluginContext) + 'static,
+    {
+        let id = self.task_pool.spawn_with_result(future);
+        self.plugin_task_handlers.insert(
+            id,
+            Box::new(move |result, plugins, context| {
+                let Some(plugin) = plugins.iter_mut().find_map(|p| p.cast_mut::<P>()) else {
+                    return;
+                };
+                if let Ok(result) = result.downcast::<T>() {
+                    on_complete(*result, plugin, context);
+                }
+            }),
+        );
+    }
+
+    /// Spawns a new task, result of which will be passed to a script of type `S` of a node on the main thread.
+    /// `script_ordinal` is the index of the script among the scripts of type `S` of the node (`0` is the first
+    /// script of this type), so the result will be passed to the same script even if scripts of other types are
+    /// added, removed or reordered. The result will be discarded if the node (or the script) does not exist or if
+    /// the node is disabled at the moment of completion.
+    pub fn spawn_script_task<F, T, S, C>(
+        &mut self,
+        scene: Handle<Scene>,
+        node: Handle<Node>,
+        script_ordinal: usize,
+        future: F,
+        on_complete: C,
+    ) where
+        F: AsyncTask<T>,
+        T: AsyncTaskResult,
+        S: ScriptTrait,
+        C: FnOnce(T, &mut S, &mut ScriptContext) + 'static,
+    {
+        let id = self.task_pool.spawn_with_result(future);
+        self.script_task_handlers.insert(
+            id,
+            ScriptTaskHandler {
+                scene,
+                node,
+                script_type: TypeId::of::<S>(),
+                script_ordinal,
+                closure: Box::new(move |result, script, context| {
+                    let Some(script) = script.cast_mut::<S>() else {
+                        return;
+                    };
+                    if let Ok(result) = result.downcast::<T>() {
+                        on_complete(*result, script, context);
+                    }
+                }),
+            },
+        );
+    }
+
+    /// Returns a reference to the underlying task pool. It could be used to spawn tasks, results of which should
+    /// not be passed to plugins or scripts.
+    pub fn inner(&self) -> &Arc<TaskPool> {
+        &self.task_pool
+    }
+
+    pub(crate) fn pop_plugin_task_handler(&mut self, id: Uuid) -> Option<PluginTaskHandler> {
+        self.plugin_task_handlers.remove(&id)
+    }
+
+    pub(crate) fn pop_script_task_handler(&mut self, id: Uuid) -> Option<ScriptTaskHandler> {
+        self.script_task_handlers.remove(&id)
+    }
+
+    // Removes every pending handler, results of the tasks will be discarded. Returns amount of removed handlers.
+    pub(crate) fn clear_handlers(&mut self) -> usize {
+        let count = self.plugin_task_handlers.len() + self.script_task_handlers.len();
+        self.plugin_task_handlers.clear();
+        self.script_task_handlers.clear();
+        count
+    }
+}
@@ -0,0 +1,423 @@
+//! Dynamic plugins are loaded from shared libraries (`.dll`, `.so`, `.dylib`) and they can be reloaded when the
+//! library is rebuilt, without restarting the engine (or the editor). See [`DynamicPlugin`] docs for more info.
+
+use crate::{
+    asset::manager::ResourceManager,
+    core::{
+        log::Log,
+        pool::Handle,
+        uuid::Uuid,
+        visitor::{Visit, Visitor},
+    },
+    engine::SerializationContext,
+    plugin::PluginConstructor,
+    scene::{
+        base::{NodeScriptMessage, ScriptRecord},
+        graph::Graph,
+        node::Node,
+    },
+    script::Script,
+};
+use fxhash::FxHashSet;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+/// Name of the function, that must be exported from a shared library of a dynamic plugin. The function must have
+/// the [`PluginConstructorEntryPoint`] signature:
+///
+/// ```rust
+/// # use fyrox::plugin::{Plugin, PluginConstructor, PluginContext};
+/// # struct GameConstructor;
+/// # impl PluginConstructor for GameConstructor {
+/// #     fn create_instance(&self, _: Option<&str>, _: PluginContext) -> Box<dyn Plugin> {
+/// #         todo!()
+/// #     }
+/// # }
+/// #[no_mangle]
+/// pub fn fyrox_plugin_constructor() -> Box<dyn PluginConstructor> {
+///     Box::new(GameConstructor)
+/// }
+/// ```
+pub const PLUGIN_CONSTRUCTOR_ENTRY_POINT: &str = "fyrox_plugin_constructor";
+
+/// Signature of the entry point of a dynamic plugin. See [`PLUGIN_CONSTRUCTOR_ENTRY_POINT`] docs for more info.
+pub type PluginConstructorEntryPoint = fn() -> Box<dyn PluginConstructor>;
+
+// A library is considered rebuilt only when it wasn't modified for some time, otherwise it could be loaded
+// while a compiler is still writing it.
+const MODIFICATION_SETTLE_TIME: Duration = Duration::from_millis(500);
+
+/// Dynamic plugin holds a shared library with a plugin constructor in it. The library is copied to a temporary
+/// file before loading, so the original file could be overwritten by a compiler while the plugin is loaded.
+///
+/// # Important
+///
+/// Rust does not have stable ABI, so the library must be built by the same compiler and with the same version of
+/// the engine as the host application. Every object created by the library (scripts, plugins, closures, etc.) must
+/// be destroyed before the library is unloaded, the engine does that for plugins and scripts by serializing their
+/// state and restoring it from the reloaded library (see [`crate::engine::Engine::reload_dynamic_plugins`]).
+pub struct DynamicPlugin {
+    source_path: PathBuf,
+    loaded_path: PathBuf,
+    modification_time: SystemTime,
+    generation: u32,
+    // Must be dropped after every object created by the library.
+    library: Option<libloading::Library>,
+    /// Defines whether the plugin should be reloaded when its library is changed.
+    pub reload_when_changed: bool,
+    pub(crate) constructor_index: usize,
+    pub(crate) script_types: FxHashSet<Uuid>,
+    pub(crate) node_types: FxHashSet<Uuid>,
+}
+
+fn modification_time(path: &Path) -> Result<SystemTime, String> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|err| {
+            format!(
+                "Unable to get modification time of {}. Reason: {:?}",
+                path.display(),
+                err
+            )
+        })
+}
+
+fn load_library(
+    source_path: &Path,
+    generation: u32,
+) -> Result<(libloading::Library, PathBuf, Box<dyn PluginConstructor>), String> {
+    let mut file_name = source_path
+        .file_name()
+        .ok_or_else(|| format!("{} is not a file!", source_path.display()))?
+        .to_os_string();
+    file_name.push(format!(".hot{generation}"));
+    let loaded_path = source_path.with_file_name(file_name);
+
+    std::fs::copy(source_path, &loaded_path).map_err(|err| {
+        format!(
+            "Unable to copy {} to {}. Reason: {:?}",
+            source_path.display(),
+            loaded_path.display(),
+            err
+        )
+    })?;
+
+    // SAFETY: Caller of `Engine::add_dynamic_plugin` guarantees that the library is compatible with the host.
+    unsafe {
+        let library = libloading::Library::new(&loaded_path).map_err(|err| {
+            format!(
+                "Unable to load dynamic plugin {}. Reason: {:?}",
+                source_path.display(),
+                err
+            )
+        })?;
+
+        let constructor = library
+            .get::<PluginConstructorEntryPoint>(PLUGIN_CONSTRUCTOR_ENTRY_POINT.as_bytes())
+            .map(|entry_point| entry_point())
+            .map_err(|err| {
+                format!(
+                    "Dynamic plugin {} does not export {} function. Reason: {:?}",
+                    source_path.display(),
+                    PLUGIN_CONSTRUCTOR_ENTRY_POINT,
+                    err
+                )
+            })?;
+
+        Ok((library, loaded_path, constructor))
+    }
+}
+
+impl DynamicPlugin {
+    /// Loads a shared library at the given path and creates a plugin constructor from it.
+    ///
+    /// # Safety
+    ///
+    /// The library must be built by the same compiler and with the same version of the engine as the host
+    /// application, and it must export [`PLUGIN_CONSTRUCTOR_ENTRY_POINT`] function.
+    pub(crate) unsafe fn load(
+        source_path: &Path,
+        reload_when_changed: bool,
+        constructor_index: usize,
+    ) -> Result<(Self, Box<dyn PluginConstructor>), String> {
+        let modification_time = modification_time(source_path)?;
+        let (library, loaded_path, constructor) = load_library(source_path, 0)?;
+        Ok((
+            Self {
+                source_path: source_path.to_path_buf(),
+                loaded_path,
+                modification_time,
+                generation: 0,
+                library: Some(library),
+                reload_when_changed,
+                constructor_index,
+                script_types: Default::default(),
+                node_types: Default::default(),
+            },
+            constructor,
+        ))
+    }
+
+    /// Returns a path of the library of the plugin.
+    pub fn source_path(&self) -> &Path {
+        &self.source_path
+    }
+
+    /// Returns `true` if the library of the plugin was rebuilt after it was loaded.
+    pub fn is_changed(&self) -> bool {
+        modification_time(&self.source_path).is_ok_and(|time| {
+            time != self.modification_time
+                && time
+                    .elapsed()
+                    .is_ok_and(|elapsed| elapsed >= MODIFICATION_SETTLE_TIME)
+        })
+    }
+
+    // Remembers current modification time of the library, so the plugin is not considered changed until the
+    // library is rebuilt again. Must be called on every reload attempt, even a failed one, otherwise the failed
+    // reload will be retried (and reported) every frame.
+    pub(crate) fn mark_reload_attempted(&mut self) {
+        if let Ok(time) = modification_time(&self.source_path) {
+            self.modification_time = time;
+        }
+    }
+
+    /// Loads a new version of the library. The old version stays loaded, it must be unloaded by [`Self::finish_reload`]
+    /// when every object created by it is destroyed.
+    pub(crate) fn begin_reload(
+        &mut self,
+    ) -> Result<(libloading::Library, PathBuf, Box<dyn PluginConstructor>), String> {
+        let new = load_library(&self.source_path, self.generation + 1)?;
+        self.generation += 1;
+        Ok(new)
+    }
+
+    /// Unloads the old version of the library and replaces it with the new one.
+    pub(crate) fn finish_reload(&mut self, library: libloading::Library, loaded_path: PathBuf) {
+        if let Some(old_library) = self.library.replace(library) {
+            Log::verify(old_library.close());
+        }
+        Log::verify(std::fs::remove_file(std::mem::replace(
+            &mut self.loaded_path,
+            loaded_path,
+        )));
+    }
+}
+
+impl Drop for DynamicPlugin {
+    fn drop(&mut self) {
+        // Objects created by the library could outlive the plugin (serialization context is shared, for example),
+        // so the library is never unloaded here. It will be unloaded by the OS when the process exits.
+        std::mem::forget(self.library.take());
+        let _ = std::fs::remove_file(&self.loaded_path);
+    }
+}
+
+// Serialized state of a script, that was taken out of a scene graph for the time of reloading.
+pub(crate) struct ScriptState {
+    node: Handle<Node>,
+    index: usize,
+    type_uuid: Uuid,
+    data: Vec<u8>,
+}
+
+/// Takes every script of the given types out of the graph and serializes their state. Records of the scripts
+/// stay in place (empty), so indices of other scripts are preserved.
+pub(crate) fn take_scripts(graph: &mut Graph, types: &FxHashSet<Uuid>) -> Vec<ScriptState> {
+    let mut states = Vec::new();
+    for (handle, node) in graph.pair_iter_mut() {
+        for (index, record) in node.scripts.iter_mut().enumerate() {
+            if !record
+                .script
+                .as_ref()
+                .is_some_and(|script| types.contains(&script.id()))
+            {
+                continue;
+            }
+
+            let Some(mut script) = record.script.take() else {
+                continue;
+            };
+
+            let type_uuid = script.id();
+            let mut visitor = Visitor::new();
+            let data = match script
+                .visit("Script", &mut visitor)
+                .and_then(|_| visitor.save_binary_to_vec())
+            {
+                Ok(data) => data,
+                Err(err) => {
+                    Log::err(format!(
+                        "Unable to save state of script {} of node {}, it will be reset. Reason: {:?}",
+                        type_uuid, handle, err
+                    ));
+                    Vec::new()
+                }
+            };
+
+            states.push(ScriptState {
+                node: handle,
+                index,
+                type_uuid,
+                data,
+            });
+        }
+    }
+    states
+}
+
+/// Takes every script of the given types, that waits for destruction in the script message queue of the graph.
+/// Other messages are put back to the queue.
+pub(crate) fn take_destroyed_scripts(
+    graph: &mut Graph,
+    types: &FxHashSet<Uuid>,
+) -> Vec<(Handle<Node>, Script)> {
+    let mut destroyed = Vec::new();
+    let mut rest = Vec::new();
+    while let Ok(message) = graph.script_message_receiver.try_recv() {
+        match message {
+            NodeScriptMessage::DestroyScript { handle, script } if types.contains(&script.id()) => {
+                destroyed.push((handle, script))
+            }
+            message => rest.push(message),
+        }
+    }
+    for message in rest {
+        Log::verify(graph.script_message_sender.send(message));
+    }
+    destroyed
+}
+
+/// Creates scripts using (possibly reloaded) script constructors, restores their state and puts them back to
+/// the graph. Scripts, that cannot be restored, are created in their default state.
+pub(crate) fn restore_scripts(
+    graph: &mut Graph,
+    states: Vec<ScriptState>,
+    serialization_context: &Arc<SerializationContext>,
+    resource_manager: &ResourceManager,
+) {
+    for state in states {
+        let Some(mut script) = serialization_context
+            .script_constructors
+            .try_create(&state.type_uuid)
+        else {
+            Log::err(format!(
+                "Script {} of node {} was removed from the reloaded plugin, the script is dropped.",
+                state.type_uuid, state.node
+            ));
+            continue;
+        };
+
+        let restored = Visitor::load_from_memory(&state.data).and_then(|mut visitor| {
+            visitor.blackboard.register(serialization_context.clone());
+            visitor
+                .blackboard
+                .register(Arc::new(resource_manager.clone()));
+            script.visit("Script", &mut visitor)
+        });
+
+        if let Err(err) = restored {
+            Log::err(format!(
+                "Unable to restore state of script {} of node {}, it is reset. Reason: {:?}",
+                state.type_uuid, state.node, err
+            ));
+            script = serialization_context
+                .script_constructors
+                .try_create(&state.type_uuid)
+                .unwrap();
+        }
+
+        if let Some(record) = graph
+            .try_get_mut(state.node)
+            .and_then(|node| node.scripts.get_mut(state.index))
+        {
+            *record = ScriptRecord::new(script);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{
+            reflect::prelude::*, uuid::Uuid, uuid_provider, visitor::prelude::*, TypeUuidProvider,
+        },
+        engine::SerializationContext,
+        impl_component_provider,
+        plugin::dynamic::{restore_scripts, take_destroyed_scripts, take_scripts},
+        scene::{
+            base::{BaseBuilder, NodeScriptMessage},
+            graph::Graph,
+            pivot::PivotBuilder,
+        },
+        script::{Script, ScriptTrait},
+    };
+    use std::sync::Arc;
+
+    #[derive(Reflect, Visit, Debug, Clone, Default)]
+    struct MyScript {
+        value: u32,
+    }
+
+    uuid_provider!(MyScript = "0e0b3a44-4fe4-4a5a-9a4d-6a6f8d4ad0d1");
+
+    impl_component_provider!(MyScript);
+
+    impl ScriptTrait for MyScript {
+        fn id(&self) -> Uuid {
+            Self::type_uuid()
+        }
+    }
+
+    #[test]
+    fn test_script_state_is_preserved() {
+        let serialization_context = Arc::new(SerializationContext::new());
+        serialization_context
+            .script_constructors
+            .add::<MyScript>("MyScript");
+
+        let mut graph = Graph::new();
+        let node =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(MyScript { value: 123 })))
+                .build(&mut graph);
+
+        let types = [MyScript::type_uuid()].into_iter().collect();
+        let states = take_scripts(&mut graph, &types);
+        assert_eq!(states.len(), 1);
+        assert_eq!(graph[node].script_count(), 1);
+        assert!(graph[node].try_get_script::<MyScript>().is_none());
+
+        restore_scripts(
+            &mut graph,
+            states,
+            &serialization_context,
+            &ResourceManager::new(),
+        );
+        assert_eq!(graph[node].try_get_script::<MyScript>().unwrap().value, 123);
+    }
+
+    #[test]
+    fn test_destroyed_scripts_are_taken_from_queue() {
+        let mut graph = Graph::new();
+        let node =
+            PivotBuilder::new(BaseBuilder::new().with_script(Script::new(MyScript { value: 123 })))
+                .build(&mut graph);
+        graph[node].remove_script(0);
+
+        let types = [MyScript::type_uuid()].into_iter().collect();
+        let destroyed = take_destroyed_scripts(&mut graph, &types);
+        assert_eq!(destroyed.len(), 1);
+        assert_eq!(destroyed[0].0, node);
+
+        // Other messages must stay in the queue.
+        assert!(matches!(
+            graph.script_message_receiver.try_recv(),
+            Ok(NodeScriptMessage::InitializeScript { handle }) if handle == node
+        ));
+        assert!(graph.script_message_receiver.try_recv().is_err());
+    }
+}
@@ -2,18 +2,24 @@
 
 #![warn(missing_docs)]
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod dynamic;
+
 use crate::{
     asset::manager::ResourceManager,
-    core::pool::Handle,
+    core::{
+        pool::Handle,
+        task::{AsyncTask, AsyncTaskResult},
+    },
     engine::{
-        AsyncSceneLoader, GraphicsContext, PerformanceStatistics, ScriptProcessor,
-        SerializationContext,
+        input::Input, random::GameRandom, task::TaskPoolHandler, AsyncSceneLoader, GraphicsContext,
+        PerformanceStatistics, ScriptProcessor, SerializationContext,
     },
     event::Event,
     gui::{message::UiMessage, UserInterface},
     scene::{Scene, SceneContainer},
 };
-use fyrox_core::visitor::VisitError;
+use fyrox_core::visitor::{VisitError, VisitResult, Visitor};
 use std::{any::Any, path::Path, sync::Arc};
 use winit::event_loop::EventLoopWindowTarget;
 
@@ -39,6 +45,13 @@ pub trait PluginConstructor {
     /// by the editor, to run your game with a scene you have current opened in the editor. Typical
     /// usage would be: `scene_path.unwrap_or("a/path/to/my/default/scene.rgs")`
     fn create_instance(&self, scene_path: Option<&str>, context: PluginContext) -> Box<dyn Plugin>;
+
+    /// The method is called when a dynamic plugin was reloaded, it must create a plugin instance without any side
+    /// effects (loading scenes, for example), because the state of the instance will be restored right after
+    /// using [`Plugin::visit_hot_reload_state`]. Default implementation calls [`Self::create_instance`].
+    fn create_reloaded_instance(&self, context: PluginContext) -> Box<dyn Plugin> {
+        self.create_instance(None, context)
+    }
 }
 
 /// Contains plugin environment for the registration stage.
@@ -102,6 +115,54 @@ pub struct PluginContext<'a, 'b> {
     /// Special field that associates main application event loop (not game loop) with OS-specific
     /// windows. It also can be used to alternate control flow of the application.
     pub window_target: Option<&'b EventLoopWindowTarget<()>>,
+
+    /// Task pool handler, that allows you to run asynchronous tasks. See [`PluginContext::spawn_task`] for
+    /// more info.
+    pub task_pool: &'a mut TaskPoolHandler,
+
+    /// Input subsystem, that maps raw input to named actions and axes. See [`Input`] docs for more info.
+    pub input: &'a mut Input,
+
+    /// Random number generator, that should be used by game logic to make runs reproducible. See [`GameRandom`]
+    /// docs for more info.
+    pub random: &'a mut GameRandom,
+}
+
+impl<'a, 'b> PluginContext<'a, 'b> {
+    /// Spawns an asynchronous task, that will be executed off the main thread (on PC) or as a JS micro-task (on
+    /// WebAssembly). When the task is completed, `on_complete` closure will be called on the main thread with the
+    /// result of the task, the plugin of the given type and the plugin context.
+    ///
+    /// ```rust
+    /// use fyrox::plugin::{Plugin, PluginContext};
+    ///
+    /// struct MyGame {
+    ///     level: Option<Vec<u8>>,
+    /// }
+    ///
+    /// impl Plugin for MyGame {}
+    ///
+    /// fn generate_level(context: &mut PluginContext) {
+    ///     context.spawn_task(
+    ///         async move {
+    ///             // Some heavy procedural generation.
+    ///             vec![0u8; 1024]
+    ///         },
+    ///         |level, game: &mut MyGame, _context| {
+    ///             game.level = Some(level);
+    ///         },
+    ///     );
+    /// }
+    /// ```
+    pub fn spawn_task<F, T, P, C>(&mut self, future: F, on_complete: C)
+    where
+        F: AsyncTask<T>,
+        T: AsyncTaskResult,
+        P: Plugin,
+        C: FnOnce(T, &mut P, &mut PluginContext) + 'static,
+    {
+        self.task_pool.spawn_plugin_task(future, on_complete)
+    }
 }
 
 /// Base plugin automatically implements type casting for plugins.
@@ -154,6 +215,11 @@ impl dyn Plugin {
 /// `#[repr(C)]` attribute which is not always easy and even possible (because some structures could
 /// be re-exported from dependencies). These are the main reasons why the engine uses static plugins.
 ///
+/// However, it is still possible to use dynamic plugins for faster iteration during development, see
+/// [`dynamic::DynamicPlugin`] docs for more info. In this case a plugin must be built with exactly the same
+/// compiler and engine version as the host application, and its state must be preserved across reloads using
+/// [`Plugin::visit_hot_reload_state`]. Release builds of your game should still use static plugins.
+///
 /// # Example
 ///
 /// ```rust
@@ -259,4 +325,22 @@ pub trait Plugin: BasePlugin {
         #[allow(unused_variables)] context: &mut PluginContext,
     ) {
     }
+
+    /// This method is called when the game is saved (with a writing visitor) or loaded (with a reading visitor),
+    /// see [`crate::engine::Engine::save_game`] for more info. It should be used to save and load the state of the
+    /// plugin, that is not stored in scenes. Default implementation does nothing.
+    fn visit_save_data(&mut self, #[allow(unused_variables)] visitor: &mut Visitor) -> VisitResult {
+        Ok(())
+    }
+
+    /// This method is called when a dynamic plugin is about to be reloaded (with a writing visitor) and right
+    /// after it was reloaded (with a reading visitor). It should be used to preserve the state of the plugin
+    /// across reloads, for example handles of scenes, that were created by the plugin. Default implementation
+    /// does nothing, which means that the plugin will be re-created in its initial state.
+    fn visit_hot_reload_state(
+        &mut self,
+        #[allow(unused_variables)] visitor: &mut Visitor,
+    ) -> VisitResult {
+        Ok(())
+    }
 }
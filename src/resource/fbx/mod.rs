@@ -35,7 +35,7 @@ use crate::{
                 FbxComponent, FbxMapping, FbxScene,
             },
         },
-        model::{MaterialSearchOptions, ModelImportOptions},
+        model::ModelImportOptions,
         texture::Texture,
     },
     scene::{
@@ -314,44 +314,10 @@ async fn create_surfaces(
                 let path = texture.get_file_path();
 
                 if let Some(filename) = path.file_name() {
-                    let texture_path = match model_import_options.material_search_options {
-                        MaterialSearchOptions::MaterialsDirectory(ref directory) => {
-                            Some(directory.join(filename))
-                        }
-                        MaterialSearchOptions::RecursiveUp => {
-                            let mut texture_path = None;
-                            let mut path = model_path.to_owned();
-                            while let Some(parent) = path.parent() {
-                                let candidate = parent.join(filename);
-                                if io.exists(&candidate).await {
-                                    texture_path = Some(candidate);
-                                    break;
-                                }
-                                path.pop();
-                            }
-                            texture_path
-                        }
-                        MaterialSearchOptions::WorkingDirectory => {
-                            let mut texture_path = None;
-
-                            let path = Path::new(".");
-
-                            if let Ok(iter) = io.walk_directory(path).await {
-                                for dir in iter {
-                                    if io.is_dir(&dir).await {
-                                        let candidate = dir.join(filename);
-                                        if candidate.exists() {
-                                            texture_path = Some(candidate);
-                                            break;
-                                        }
-                                    }
-                                }
-                            }
-
-                            texture_path
-                        }
-                        MaterialSearchOptions::UsePathDirectly => Some(path.clone()),
-                    };
+                    let texture_path = model_import_options
+                        .material_search_options
+                        .resolve_path(path, model_path, &*io)
+                        .await;
 
                     if let Some(texture_path) = texture_path {
                         let texture = resource_manager.request::<Texture>(texture_path.as_path());
@@ -0,0 +1,329 @@
+//! Animation conversion from glTF channels to engine animation tracks.
+
+use crate::{
+    animation::{
+        container::{quat_to_continuous_euler, TrackDataContainer, TrackValueKind},
+        track::Track,
+        value::{ValueBinding, ValueType},
+        Animation,
+    },
+    core::{
+        algebra::{Quaternion, UnitQuaternion},
+        curve::{CurveKey, CurveKeyKind},
+        log::Log,
+        math::lerpf,
+        pool::Handle,
+    },
+    resource::gltf::ImportContext,
+    scene::node::Node,
+};
+use fxhash::FxHashMap;
+use gltf::animation::{util::ReadOutputs, Interpolation, Property};
+use std::f32::consts::PI;
+
+/// Engine curves cannot represent glTF cubic splines exactly (tangents are defined differently), so
+/// such curves are resampled using this amount of linear segments per each span.
+const CUBIC_SPLINE_SUBDIVISIONS: usize = 4;
+
+/// Rotations are stored as Euler angles in the engine, which are interpolated differently than
+/// quaternions. Spans with large rotations are subdivided so every segment rotates at most by this
+/// angle (in radians), which keeps the difference between two interpolation methods negligible.
+const MAX_ROTATION_STEP: f32 = 15.0 * PI / 180.0;
+
+struct Key {
+    time: f32,
+    value: Vec<f32>,
+    in_tangent: Vec<f32>,
+    out_tangent: Vec<f32>,
+}
+
+fn to_quat(v: &[f32]) -> UnitQuaternion<f32> {
+    // glTF stores quaternions in (x, y, z, w) order.
+    UnitQuaternion::new_normalize(Quaternion::new(v[3], v[0], v[1], v[2]))
+}
+
+fn rotation_subdivisions(a: &Key, b: &Key) -> usize {
+    let angle = to_quat(&a.value).angle_to(&to_quat(&b.value));
+    ((angle / MAX_ROTATION_STEP).ceil() as usize).max(1)
+}
+
+fn interpolate(
+    a: &Key,
+    b: &Key,
+    t: f32,
+    interpolation: Interpolation,
+    is_rotation: bool,
+) -> Vec<f32> {
+    match interpolation {
+        Interpolation::Step => a.value.clone(),
+        Interpolation::Linear => {
+            if is_rotation {
+                let qa = to_quat(&a.value);
+                let mut qb = to_quat(&b.value);
+                // Take the shortest path.
+                if qa.coords.dot(&qb.coords) < 0.0 {
+                    qb = UnitQuaternion::new_unchecked(-qb.into_inner());
+                }
+                let q = qa.slerp(&qb, t);
+                vec![q.i, q.j, q.k, q.w]
+            } else {
+                a.value
+                    .iter()
+                    .zip(b.value.iter())
+                    .map(|(a, b)| lerpf(*a, *b, t))
+                    .collect()
+            }
+        }
+        Interpolation::CubicSpline => {
+            let dt = b.time - a.time;
+            let t2 = t * t;
+            let t3 = t2 * t;
+            let value = (0..a.value.len())
+                .map(|i| {
+                    (2.0 * t3 - 3.0 * t2 + 1.0) * a.value[i]
+                        + (t3 - 2.0 * t2 + t) * dt * a.out_tangent[i]
+                        + (-2.0 * t3 + 3.0 * t2) * b.value[i]
+                        + (t3 - t2) * dt * b.in_tangent[i]
+                })
+                .collect::<Vec<_>>();
+            if is_rotation {
+                let q = to_quat(&value);
+                vec![q.i, q.j, q.k, q.w]
+            } else {
+                value
+            }
+        }
+    }
+}
+
+/// Resamples the keys (if needed) to a form that can be represented by engine curves.
+fn resample(keys: &[Key], interpolation: Interpolation, is_rotation: bool) -> Vec<(f32, Vec<f32>)> {
+    let mut frames = Vec::new();
+
+    for pair in keys.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+
+        let subdivisions = match interpolation {
+            Interpolation::Step => 1,
+            Interpolation::Linear => {
+                if is_rotation {
+                    rotation_subdivisions(a, b)
+                } else {
+                    1
+                }
+            }
+            Interpolation::CubicSpline => {
+                if is_rotation {
+                    rotation_subdivisions(a, b).max(CUBIC_SPLINE_SUBDIVISIONS)
+                } else {
+                    CUBIC_SPLINE_SUBDIVISIONS
+                }
+            }
+        };
+
+        for k in 0..subdivisions {
+            let t = k as f32 / subdivisions as f32;
+            frames.push((
+                lerpf(a.time, b.time, t),
+                interpolate(a, b, t, interpolation, is_rotation),
+            ));
+        }
+    }
+
+    if let Some(last) = keys.last() {
+        frames.push((last.time, last.value.clone()));
+    }
+
+    frames
+}
+
+fn key_kind(interpolation: Interpolation) -> CurveKeyKind {
+    match interpolation {
+        Interpolation::Step => CurveKeyKind::Constant,
+        Interpolation::Linear | Interpolation::CubicSpline => CurveKeyKind::Linear,
+    }
+}
+
+fn fill_track(track: &mut Track, frames: &[(f32, Vec<f32>)], kind: CurveKeyKind) {
+    let curves = track.data_container_mut().curves_mut();
+    for (time, value) in frames {
+        for (curve, component) in curves.iter_mut().zip(value.iter()) {
+            curve.add_key(CurveKey::new(*time, *component, kind.clone()));
+        }
+    }
+}
+
+fn make_keys(
+    times: &[f32],
+    values: &[f32],
+    width: usize,
+    interpolation: Interpolation,
+) -> Vec<Key> {
+    let stride = if interpolation == Interpolation::CubicSpline {
+        width * 3
+    } else {
+        width
+    };
+
+    times
+        .iter()
+        .zip(values.chunks_exact(stride))
+        .map(|(time, chunk)| {
+            if interpolation == Interpolation::CubicSpline {
+                Key {
+                    time: *time,
+                    in_tangent: chunk[0..width].to_vec(),
+                    value: chunk[width..2 * width].to_vec(),
+                    out_tangent: chunk[2 * width..3 * width].to_vec(),
+                }
+            } else {
+                Key {
+                    time: *time,
+                    value: chunk.to_vec(),
+                    in_tangent: Default::default(),
+                    out_tangent: Default::default(),
+                }
+            }
+        })
+        .collect()
+}
+
+fn convert_channel(
+    ctx: &ImportContext,
+    channel: gltf::animation::Channel,
+    node_map: &FxHashMap<usize, Handle<Node>>,
+) -> Vec<Track> {
+    let target = channel.target();
+    let Some(&node) = node_map.get(&target.node().index()) else {
+        return Default::default();
+    };
+
+    let reader = channel.reader(|buffer| ctx.buffer_data(buffer));
+    let (Some(inputs), Some(outputs)) = (reader.read_inputs(), reader.read_outputs()) else {
+        Log::warn(format!(
+            "Animation channel {} has no data and will be ignored.",
+            channel.index()
+        ));
+        return Default::default();
+    };
+    let times = inputs.collect::<Vec<_>>();
+    let interpolation = channel.sampler().interpolation();
+    let kind = key_kind(interpolation);
+
+    match outputs {
+        ReadOutputs::Translations(translations) => {
+            let values = translations.flatten().collect::<Vec<_>>();
+            let frames = resample(
+                &make_keys(&times, &values, 3, interpolation),
+                interpolation,
+                false,
+            );
+            let mut track = Track::new_position().with_target(node);
+            fill_track(&mut track, &frames, kind);
+            vec![track]
+        }
+        ReadOutputs::Scales(scales) => {
+            let values = scales.flatten().collect::<Vec<_>>();
+            let frames = resample(
+                &make_keys(&times, &values, 3, interpolation),
+                interpolation,
+                false,
+            );
+            let mut track = Track::new_scale().with_target(node);
+            fill_track(&mut track, &frames, kind);
+            vec![track]
+        }
+        ReadOutputs::Rotations(rotations) => {
+            let values = rotations.into_f32().flatten().collect::<Vec<_>>();
+            let frames = resample(
+                &make_keys(&times, &values, 4, interpolation),
+                interpolation,
+                true,
+            );
+            let mut previous = None;
+            let euler_frames = frames
+                .iter()
+                .map(|(time, value)| {
+                    let angles = quat_to_continuous_euler(to_quat(value), previous);
+                    previous = Some(angles);
+                    (*time, angles.as_slice().to_vec())
+                })
+                .collect::<Vec<_>>();
+            let mut track = Track::new_rotation().with_target(node);
+            fill_track(&mut track, &euler_frames, kind);
+            vec![track]
+        }
+        ReadOutputs::MorphTargetWeights(weights) => {
+            let values = weights.into_f32().collect::<Vec<_>>();
+            let stride = if interpolation == Interpolation::CubicSpline {
+                3
+            } else {
+                1
+            };
+            if times.is_empty() {
+                return Default::default();
+            }
+            let targets_count = values.len() / (times.len() * stride);
+            let frames = resample(
+                &make_keys(&times, &values, targets_count, interpolation),
+                interpolation,
+                false,
+            );
+            (0..targets_count)
+                .map(|target_index| {
+                    let mut track = Track::new(
+                        TrackDataContainer::new(TrackValueKind::Real),
+                        ValueBinding::Property {
+                            name: format!("blend_shapes[{target_index}].weight"),
+                            value_type: ValueType::F32,
+                        },
+                    )
+                    .with_target(node);
+                    let curve = &mut track.data_container_mut().curves_mut()[0];
+                    for (time, value) in frames.iter() {
+                        // Engine blend shape weights are in [0; 100] range.
+                        curve.add_key(CurveKey::new(
+                            *time,
+                            value[target_index] * 100.0,
+                            kind.clone(),
+                        ));
+                    }
+                    track
+                })
+                .collect()
+        }
+    }
+}
+
+/// Converts every animation of the document to engine animation. Every animation track will be
+/// bound to an appropriate node using the given map of glTF node indices to engine node handles.
+pub(super) fn convert_animations(
+    ctx: &ImportContext,
+    node_map: &FxHashMap<usize, Handle<Node>>,
+) -> Vec<Animation> {
+    ctx.document
+        .animations()
+        .map(|gltf_animation| {
+            let mut animation = Animation::default();
+            animation.set_name(
+                gltf_animation
+                    .name()
+                    .map(|name| name.to_owned())
+                    .unwrap_or_else(|| format!("Animation{}", gltf_animation.index())),
+            );
+            for channel in gltf_animation.channels() {
+                // Morph target weights can only be applied to mesh nodes.
+                if channel.target().property() == Property::MorphTargetWeights
+                    && channel.target().node().mesh().is_none()
+                {
+                    continue;
+                }
+                for track in convert_channel(ctx, channel, node_map) {
+                    animation.add_track(track);
+                }
+            }
+            animation.fit_length_to_content();
+            animation
+        })
+        .collect()
+}
@@ -0,0 +1,92 @@
+//! Contains all possible errors that can occur during glTF parsing and conversion.
+
+use crate::core::io::FileLoadError;
+use std::fmt::{Display, Formatter};
+
+/// See module docs.
+#[derive(Debug)]
+pub enum GltfError {
+    /// The document is malformed or it uses unsupported features.
+    Gltf(gltf::Error),
+
+    /// Unable to load a file referenced by the document (an external buffer for example).
+    FileLoadError(FileLoadError),
+
+    /// A binary glTF document references its binary chunk, but the chunk is missing.
+    MissingBlob,
+
+    /// A buffer has less bytes than declared in the document.
+    BufferTooShort {
+        /// Index of the buffer in the document.
+        index: usize,
+        /// Declared length of the buffer.
+        expected: usize,
+        /// Actual length of the buffer.
+        actual: usize,
+    },
+
+    /// Data URI is malformed or uses unsupported encoding.
+    InvalidDataUri(String),
+
+    /// A primitive uses more than 256 joints, the engine supports only 8-bit bone indices.
+    TooManyJoints(usize),
+
+    /// Arbitrary error that can have any meaning.
+    Custom(Box<String>),
+}
+
+impl Display for GltfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GltfError::Gltf(v) => {
+                write!(f, "glTF: Malformed or unsupported document. Reason: {v}")
+            }
+            GltfError::FileLoadError(v) => {
+                write!(f, "glTF: File load error {v:?}.")
+            }
+            GltfError::MissingBlob => {
+                write!(f, "glTF: Binary chunk is missing.")
+            }
+            GltfError::BufferTooShort {
+                index,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "glTF: Buffer {index} is too short. Expected {expected} bytes, got {actual}."
+                )
+            }
+            GltfError::InvalidDataUri(v) => {
+                write!(f, "glTF: Invalid data URI {v}.")
+            }
+            GltfError::TooManyJoints(v) => {
+                write!(
+                    f,
+                    "glTF: Skin has {v} joints, but only 256 joints per mesh are supported."
+                )
+            }
+            GltfError::Custom(v) => {
+                write!(f, "glTF: {v}")
+            }
+        }
+    }
+}
+
+impl From<gltf::Error> for GltfError {
+    fn from(err: gltf::Error) -> Self {
+        GltfError::Gltf(err)
+    }
+}
+
+impl From<FileLoadError> for GltfError {
+    fn from(err: FileLoadError) -> Self {
+        GltfError::FileLoadError(err)
+    }
+}
+
+impl From<String> for GltfError {
+    fn from(err: String) -> Self {
+        GltfError::Custom(Box::new(err))
+    }
+}
@@ -0,0 +1,407 @@
+//! Material conversion from glTF PBR metallic-roughness model to the standard shader.
+
+use crate::{
+    asset::untyped::ResourceKind,
+    core::{
+        algebra::{Vector3, Vector4},
+        color::Color,
+        log::Log,
+        sstorage::ImmutableString,
+    },
+    material::{shader::SamplerFallback, Material, MaterialResource, PropertyValue},
+    resource::{
+        gltf::{decode_data_uri, error::GltfError, percent_decode, ImportContext},
+        texture::{
+            Texture, TextureImportOptions, TextureKind, TexturePixelKind, TextureResource,
+            TextureResourceExtension, TextureWrapMode,
+        },
+    },
+};
+use fxhash::FxHashMap;
+use gltf::texture::WrappingMode;
+use std::path::PathBuf;
+
+/// Location of image data.
+enum ImageSource {
+    /// An image is stored in a separate file.
+    External(PathBuf),
+    /// An image is stored inside the document (either in a buffer or in a data URI).
+    Embedded(Vec<u8>),
+}
+
+/// Converted textures, shared across all materials of a document.
+#[derive(Default)]
+pub(super) struct TextureCache {
+    textures: FxHashMap<usize, Option<TextureResource>>,
+    // glTF packs metalness and roughness into blue and green channels of a single texture, while
+    // the standard shader expects them in separate textures.
+    metallic_roughness: FxHashMap<(usize, u32, u32), Option<(TextureResource, TextureResource)>>,
+}
+
+fn convert_wrap_mode(mode: WrappingMode) -> TextureWrapMode {
+    match mode {
+        WrappingMode::ClampToEdge => TextureWrapMode::ClampToEdge,
+        WrappingMode::MirroredRepeat => TextureWrapMode::MirroredRepeat,
+        WrappingMode::Repeat => TextureWrapMode::Repeat,
+    }
+}
+
+fn image_source(ctx: &ImportContext, image: gltf::Image) -> Result<ImageSource, GltfError> {
+    match image.source() {
+        gltf::image::Source::View { view, .. } => {
+            let buffer = ctx
+                .buffers
+                .get(view.buffer().index())
+                .ok_or(GltfError::MissingBlob)?;
+            let data = buffer
+                .get(view.offset()..view.offset() + view.length())
+                .ok_or(GltfError::BufferTooShort {
+                    index: view.buffer().index(),
+                    expected: view.offset() + view.length(),
+                    actual: buffer.len(),
+                })?;
+            Ok(ImageSource::Embedded(data.to_vec()))
+        }
+        gltf::image::Source::Uri { uri, .. } => {
+            if let Some(data) = uri.strip_prefix("data:") {
+                Ok(ImageSource::Embedded(decode_data_uri(data)?))
+            } else {
+                Ok(ImageSource::External(
+                    ctx.model_path
+                        .parent()
+                        .unwrap_or(ctx.model_path)
+                        .join(percent_decode(uri)),
+                ))
+            }
+        }
+    }
+}
+
+fn single_channel_texture(
+    width: u32,
+    height: u32,
+    bytes: Vec<u8>,
+    s_wrap_mode: TextureWrapMode,
+    t_wrap_mode: TextureWrapMode,
+) -> Option<TextureResource> {
+    let mut texture = Texture::from_bytes(
+        TextureKind::Rectangle { width, height },
+        TexturePixelKind::R8,
+        bytes,
+    )?;
+    texture.set_s_wrap_mode(s_wrap_mode);
+    texture.set_t_wrap_mode(t_wrap_mode);
+    Some(TextureResource::new_ok(ResourceKind::Embedded, texture))
+}
+
+fn constant_texture(value: f32) -> Option<TextureResource> {
+    single_channel_texture(
+        1,
+        1,
+        vec![(value.clamp(0.0, 1.0) * 255.0) as u8],
+        TextureWrapMode::Repeat,
+        TextureWrapMode::Repeat,
+    )
+}
+
+impl TextureCache {
+    async fn texture(
+        &mut self,
+        ctx: &ImportContext<'_>,
+        texture: gltf::Texture<'_>,
+    ) -> Option<TextureResource> {
+        let image = texture.source();
+        if let Some(resource) = self.textures.get(&image.index()) {
+            return resource.clone();
+        }
+
+        let resource = match image_source(ctx, image.clone()) {
+            Ok(ImageSource::External(path)) => {
+                let resource_manager = &ctx.resource_manager;
+                let io = resource_manager.resource_io();
+                match ctx
+                    .model_import_options
+                    .material_search_options
+                    .resolve_path(&path, ctx.model_path, &*io)
+                    .await
+                {
+                    Some(path) => Some(resource_manager.request::<Texture>(path)),
+                    None => {
+                        Log::warn(format!(
+                            "Unable to find a texture {:?} for 3D model {:?} using {:?} option!",
+                            path, ctx.model_path, ctx.model_import_options
+                        ));
+                        None
+                    }
+                }
+            }
+            Ok(ImageSource::Embedded(data)) => {
+                let sampler = texture.sampler();
+                let import_options = TextureImportOptions::default()
+                    .with_s_wrap_mode(convert_wrap_mode(sampler.wrap_s()))
+                    .with_t_wrap_mode(convert_wrap_mode(sampler.wrap_t()));
+                match TextureResource::load_from_memory(
+                    ResourceKind::Embedded,
+                    &data,
+                    import_options,
+                ) {
+                    Ok(resource) => Some(resource),
+                    Err(e) => {
+                        Log::err(format!(
+                            "Unable to load embedded image {} of 3D model {:?}. Reason: {:?}",
+                            image.index(),
+                            ctx.model_path,
+                            e
+                        ));
+                        None
+                    }
+                }
+            }
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to load image {} of 3D model {:?}. Reason: {}",
+                    image.index(),
+                    ctx.model_path,
+                    e
+                ));
+                None
+            }
+        };
+
+        self.textures.insert(image.index(), resource.clone());
+
+        resource
+    }
+
+    /// Splits packed metallic-roughness texture in two separate textures (metallic and roughness
+    /// respectively) and bakes the factors in them.
+    async fn metallic_roughness(
+        &mut self,
+        ctx: &ImportContext<'_>,
+        texture: gltf::Texture<'_>,
+        metallic_factor: f32,
+        roughness_factor: f32,
+    ) -> Option<(TextureResource, TextureResource)> {
+        let image = texture.source();
+        let key = (
+            image.index(),
+            metallic_factor.to_bits(),
+            roughness_factor.to_bits(),
+        );
+        if let Some(resources) = self.metallic_roughness.get(&key) {
+            return resources.clone();
+        }
+
+        let data = match image_source(ctx, image.clone()) {
+            Ok(ImageSource::External(path)) => {
+                let io = ctx.resource_manager.resource_io();
+                match ctx
+                    .model_import_options
+                    .material_search_options
+                    .resolve_path(&path, ctx.model_path, &*io)
+                    .await
+                {
+                    Some(path) => match io.load_file(&path).await {
+                        Ok(data) => Some(data),
+                        Err(e) => {
+                            Log::err(format!(
+                                "Unable to load metallic-roughness texture {:?}. Reason: {:?}",
+                                path, e
+                            ));
+                            None
+                        }
+                    },
+                    None => {
+                        Log::warn(format!(
+                            "Unable to find a texture {:?} for 3D model {:?} using {:?} option!",
+                            path, ctx.model_path, ctx.model_import_options
+                        ));
+                        None
+                    }
+                }
+            }
+            Ok(ImageSource::Embedded(data)) => Some(data),
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to load image {} of 3D model {:?}. Reason: {}",
+                    image.index(),
+                    ctx.model_path,
+                    e
+                ));
+                None
+            }
+        };
+
+        let resources = data.and_then(|data| match image::load_from_memory(&data) {
+            Ok(decoded) => {
+                let rgba = decoded.to_rgba8();
+                let (width, height) = rgba.dimensions();
+                let mut metallic = Vec::with_capacity((width * height) as usize);
+                let mut roughness = Vec::with_capacity((width * height) as usize);
+                for pixel in rgba.pixels() {
+                    metallic.push((pixel[2] as f32 * metallic_factor).min(255.0) as u8);
+                    roughness.push((pixel[1] as f32 * roughness_factor).min(255.0) as u8);
+                }
+                let sampler = texture.sampler();
+                let s_wrap_mode = convert_wrap_mode(sampler.wrap_s());
+                let t_wrap_mode = convert_wrap_mode(sampler.wrap_t());
+                Some((
+                    single_channel_texture(width, height, metallic, s_wrap_mode, t_wrap_mode)?,
+                    single_channel_texture(width, height, roughness, s_wrap_mode, t_wrap_mode)?,
+                ))
+            }
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to decode metallic-roughness image {} of 3D model {:?}. Reason: {:?}",
+                    image.index(),
+                    ctx.model_path,
+                    e
+                ));
+                None
+            }
+        });
+
+        self.metallic_roughness.insert(key, resources.clone());
+
+        resources
+    }
+}
+
+fn set_property(material: &mut Material, name: &str, value: PropertyValue) {
+    if let Err(e) = material.set_property(&ImmutableString::new(name), value) {
+        Log::err(format!(
+            "Unable to set material property {} for glTF material! Reason: {:?}",
+            name, e
+        ));
+    }
+}
+
+fn set_texture(
+    material: &mut Material,
+    name: &str,
+    texture: Option<TextureResource>,
+    fallback: SamplerFallback,
+) {
+    if texture.is_some() {
+        set_property(
+            material,
+            name,
+            PropertyValue::Sampler {
+                value: texture,
+                fallback,
+            },
+        );
+    }
+}
+
+/// Converts glTF material to a material that uses the standard shader. Only the first set of texture
+/// coordinates is supported.
+pub(super) async fn convert_material(
+    ctx: &ImportContext<'_>,
+    cache: &mut TextureCache,
+    gltf_material: gltf::Material<'_>,
+) -> MaterialResource {
+    let mut material = if gltf_material.double_sided() {
+        Material::standard_two_sides()
+    } else {
+        Material::standard()
+    };
+
+    let pbr = gltf_material.pbr_metallic_roughness();
+
+    set_property(
+        &mut material,
+        "diffuseColor",
+        PropertyValue::Color(Color::from(Vector4::from(pbr.base_color_factor()))),
+    );
+
+    if let Some(info) = pbr.base_color_texture() {
+        let texture = cache.texture(ctx, info.texture()).await;
+        set_texture(
+            &mut material,
+            "diffuseTexture",
+            texture,
+            SamplerFallback::White,
+        );
+    }
+
+    if let Some(info) = gltf_material.normal_texture() {
+        let texture = cache.texture(ctx, info.texture()).await;
+        set_texture(
+            &mut material,
+            "normalTexture",
+            texture,
+            SamplerFallback::Normal,
+        );
+    }
+
+    if let Some(info) = gltf_material.occlusion_texture() {
+        // Occlusion is stored in red channel, exactly as the standard shader expects.
+        let texture = cache.texture(ctx, info.texture()).await;
+        set_texture(&mut material, "aoTexture", texture, SamplerFallback::White);
+    }
+
+    let metallic_factor = pbr.metallic_factor();
+    let roughness_factor = pbr.roughness_factor();
+    let (metallic, roughness) = match pbr.metallic_roughness_texture() {
+        Some(info) => cache
+            .metallic_roughness(ctx, info.texture(), metallic_factor, roughness_factor)
+            .await
+            .map_or((None, None), |(m, r)| (Some(m), Some(r))),
+        // Default fallback values of the standard shader are zero metalness and full roughness,
+        // create textures only when the factors differ from them.
+        None => (
+            if metallic_factor > 0.0 {
+                constant_texture(metallic_factor)
+            } else {
+                None
+            },
+            if roughness_factor < 1.0 {
+                constant_texture(roughness_factor)
+            } else {
+                None
+            },
+        ),
+    };
+    set_texture(
+        &mut material,
+        "metallicTexture",
+        metallic,
+        SamplerFallback::Black,
+    );
+    set_texture(
+        &mut material,
+        "roughnessTexture",
+        roughness,
+        SamplerFallback::White,
+    );
+
+    let emissive_factor = Vector3::from(gltf_material.emissive_factor());
+    if emissive_factor != Vector3::default() {
+        let emission = match gltf_material.emissive_texture() {
+            Some(info) => cache.texture(ctx, info.texture()).await,
+            None => TextureResource::from_bytes(
+                TextureKind::Rectangle {
+                    width: 1,
+                    height: 1,
+                },
+                TexturePixelKind::RGBA8,
+                vec![255; 4],
+                ResourceKind::Embedded,
+            ),
+        };
+        set_texture(
+            &mut material,
+            "emissionTexture",
+            emission,
+            SamplerFallback::Black,
+        );
+        set_property(
+            &mut material,
+            "emissionStrength",
+            PropertyValue::Vector3(emissive_factor),
+        );
+    }
+
+    MaterialResource::new_ok(ResourceKind::Embedded, material)
+}
@@ -0,0 +1,284 @@
+//! Mesh conversion from glTF primitives to engine surfaces.
+
+use crate::{
+    core::{
+        algebra::{Vector2, Vector3, Vector4},
+        log::Log,
+        math::TriangleDefinition,
+    },
+    material::MaterialResource,
+    resource::gltf::{error::GltfError, ImportContext},
+    scene::mesh::{
+        buffer::{TriangleBuffer, VertexBuffer},
+        surface::{
+            BlendShape, BlendShapesContainer, InputBlendShapeData, Surface, SurfaceData,
+            SurfaceSharedData,
+        },
+        vertex::{AnimatedVertex, StaticVertex},
+    },
+    utils,
+};
+use gltf::mesh::Mode;
+
+/// Converts a list of indices to a list of triangles using primitive topology. Returns [`None`] for
+/// topologies that cannot form triangles (points and lines).
+fn make_triangles(mode: Mode, indices: &[u32]) -> Option<Vec<TriangleDefinition>> {
+    match mode {
+        Mode::Triangles => Some(
+            indices
+                .chunks_exact(3)
+                .map(|t| TriangleDefinition([t[0], t[1], t[2]]))
+                .collect(),
+        ),
+        Mode::TriangleStrip => Some(
+            indices
+                .windows(3)
+                .enumerate()
+                .map(|(i, t)| {
+                    // Every odd triangle of a strip has reversed winding, fix it to keep faces
+                    // oriented consistently.
+                    if i % 2 == 0 {
+                        TriangleDefinition([t[0], t[1], t[2]])
+                    } else {
+                        TriangleDefinition([t[1], t[0], t[2]])
+                    }
+                })
+                .collect(),
+        ),
+        Mode::TriangleFan => indices.split_first().map(|(&first, rest)| {
+            rest.windows(2)
+                .map(|t| TriangleDefinition([first, t[0], t[1]]))
+                .collect()
+        }),
+        Mode::Points | Mode::Lines | Mode::LineLoop | Mode::LineStrip => None,
+    }
+}
+
+/// Result of mesh conversion.
+pub(super) struct ConvertedMesh {
+    pub surfaces: Vec<Surface>,
+    pub blend_shapes: Vec<BlendShape>,
+}
+
+/// Converts every primitive of the given mesh to a separate surface. Skinned surfaces will have
+/// their bone indices and weights filled, but an actual list of bones must be assigned later, when
+/// every node of the document is converted.
+pub(super) fn convert_mesh(
+    ctx: &ImportContext,
+    mesh: gltf::Mesh,
+    is_skinned: bool,
+    weights: Option<&[f32]>,
+    materials: &[MaterialResource],
+) -> Result<ConvertedMesh, GltfError> {
+    let mut surfaces = Vec::new();
+    let mut blend_shapes_count = 0;
+
+    for primitive in mesh.primitives() {
+        let reader = primitive.reader(|buffer| ctx.buffer_data(buffer));
+
+        let positions = match reader.read_positions() {
+            Some(positions) => positions.map(Vector3::from).collect::<Vec<_>>(),
+            None => {
+                Log::warn(format!(
+                    "Primitive {} of mesh {} does not have positions and will be ignored.",
+                    primitive.index(),
+                    mesh.index()
+                ));
+                continue;
+            }
+        };
+        let vertex_count = positions.len();
+
+        let indices = match reader.read_indices() {
+            Some(indices) => indices.into_u32().collect::<Vec<_>>(),
+            None => (0..vertex_count as u32).collect(),
+        };
+
+        let triangles = match make_triangles(primitive.mode(), &indices) {
+            Some(triangles) => triangles,
+            None => {
+                Log::warn(format!(
+                    "Primitive {} of mesh {} has unsupported topology {:?} and will be ignored.",
+                    primitive.index(),
+                    mesh.index(),
+                    primitive.mode()
+                ));
+                continue;
+            }
+        };
+
+        if triangles
+            .iter()
+            .any(|t| t.0.iter().any(|&i| i as usize >= vertex_count))
+        {
+            return Err(GltfError::from(format!(
+                "Primitive {} of mesh {} references non-existing vertices.",
+                primitive.index(),
+                mesh.index()
+            )));
+        }
+
+        let normals = reader
+            .read_normals()
+            .map(|normals| normals.map(Vector3::from).collect::<Vec<_>>());
+        let tangents = reader
+            .read_tangents()
+            .map(|tangents| tangents.map(Vector4::from).collect::<Vec<_>>());
+        let tex_coords = reader
+            .read_tex_coords(0)
+            .map(|tex_coords| tex_coords.into_f32().map(Vector2::from).collect::<Vec<_>>());
+
+        let normal = |i: usize| {
+            normals
+                .as_ref()
+                .and_then(|n| n.get(i).cloned())
+                .unwrap_or_else(Vector3::y)
+        };
+        let tangent = |i: usize| {
+            tangents
+                .as_ref()
+                .and_then(|t| t.get(i).cloned())
+                .unwrap_or_else(|| Vector4::new(1.0, 0.0, 0.0, 1.0))
+        };
+        let tex_coord = |i: usize| {
+            tex_coords
+                .as_ref()
+                .and_then(|t| t.get(i).cloned())
+                .unwrap_or_default()
+        };
+
+        let joints = reader
+            .read_joints(0)
+            .map(|joints| joints.into_u16().collect::<Vec<_>>());
+        let bone_weights = reader
+            .read_weights(0)
+            .map(|weights| weights.into_f32().collect::<Vec<_>>());
+
+        let vertex_buffer = match (is_skinned, joints, bone_weights) {
+            (true, Some(joints), Some(bone_weights)) => {
+                let mut vertices = Vec::with_capacity(vertex_count);
+                for (i, position) in positions.iter().enumerate() {
+                    let joint = joints.get(i).cloned().unwrap_or_default();
+                    let mut bone_indices = [0u8; 4];
+                    for (index, &joint) in bone_indices.iter_mut().zip(joint.iter()) {
+                        *index = u8::try_from(joint)
+                            .map_err(|_| GltfError::TooManyJoints(joint as usize + 1))?;
+                    }
+                    vertices.push(AnimatedVertex {
+                        position: *position,
+                        tex_coord: tex_coord(i),
+                        normal: normal(i),
+                        tangent: tangent(i),
+                        bone_weights: bone_weights.get(i).cloned().unwrap_or_default(),
+                        bone_indices,
+                    });
+                }
+                VertexBuffer::new(vertex_count, vertices)
+            }
+            _ => {
+                let vertices = positions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, position)| StaticVertex {
+                        position: *position,
+                        tex_coord: tex_coord(i),
+                        normal: normal(i),
+                        tangent: tangent(i),
+                    })
+                    .collect::<Vec<_>>();
+                VertexBuffer::new(vertex_count, vertices)
+            }
+        }
+        .map_err(|e| GltfError::from(format!("Unable to create vertex buffer. Reason: {e:?}")))?;
+
+        let mut data = SurfaceData::new(vertex_buffer, TriangleBuffer::new(triangles), false);
+
+        if normals.is_none() {
+            // The specification requires flat normals in this case.
+            if let Err(e) = data.calculate_normals() {
+                Log::err(format!("Unable to calculate normals. Reason: {e:?}"));
+            }
+        }
+        if tangents.is_none() && tex_coords.is_some() {
+            if let Err(e) = data.calculate_tangents() {
+                Log::err(format!("Unable to calculate tangents. Reason: {e:?}"));
+            }
+        }
+
+        let blend_shapes = reader
+            .read_morph_targets()
+            .enumerate()
+            .map(|(target_index, (positions, normals, tangents))| {
+                let mut blend_shape = InputBlendShapeData {
+                    default_weight: weights
+                        .and_then(|w| w.get(target_index).cloned())
+                        .unwrap_or_default()
+                        * 100.0,
+                    name: blend_shape_name(target_index),
+                    positions: Default::default(),
+                    normals: Default::default(),
+                    tangents: Default::default(),
+                };
+                // glTF stores displacements for every vertex, but most of them are usually zero,
+                // so store only the ones that actually change something.
+                let fill = |dest: &mut fxhash::FxHashMap<_, _>, src: Vec<[f32; 3]>| {
+                    for (i, offset) in src.into_iter().enumerate() {
+                        let offset = Vector3::from(offset);
+                        if offset != Vector3::default() {
+                            dest.insert(i as u32, utils::vec3_f16_from_f32(offset));
+                        }
+                    }
+                };
+                if let Some(positions) = positions {
+                    fill(&mut blend_shape.positions, positions.collect());
+                }
+                if let Some(normals) = normals {
+                    fill(&mut blend_shape.normals, normals.collect());
+                }
+                if let Some(tangents) = tangents {
+                    fill(&mut blend_shape.tangents, tangents.collect());
+                }
+                blend_shape
+            })
+            .collect::<Vec<_>>();
+
+        if !blend_shapes.is_empty() {
+            blend_shapes_count = blend_shapes_count.max(blend_shapes.len());
+            data.blend_shapes_container = Some(BlendShapesContainer::from_lists(
+                &data.vertex_buffer,
+                &blend_shapes,
+            ));
+        }
+
+        let mut surface = Surface::new(SurfaceSharedData::new(data));
+        if let Some(material) = primitive
+            .material()
+            .index()
+            .and_then(|index| materials.get(index))
+        {
+            surface.set_material(material.clone());
+        }
+        surfaces.push(surface);
+    }
+
+    let blend_shapes = (0..blend_shapes_count)
+        .map(|target_index| BlendShape {
+            weight: weights
+                .and_then(|w| w.get(target_index).cloned())
+                .unwrap_or_default()
+                * 100.0,
+            name: blend_shape_name(target_index),
+        })
+        .collect();
+
+    Ok(ConvertedMesh {
+        surfaces,
+        blend_shapes,
+    })
+}
+
+/// glTF does not have names for morph targets (there's only unofficial `targetNames` extra data),
+/// so generate them from indices.
+pub(super) fn blend_shape_name(target_index: usize) -> String {
+    format!("MorphTarget{target_index}")
+}
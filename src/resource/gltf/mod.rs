@@ -0,0 +1,485 @@
+//! Contains all methods to load and convert glTF 2.0 model format (both `.gltf` and `.glb`).
+//!
+//! glTF is an open format for 3D scenes and models, that is supported by pretty much every 3D modelling
+//! software (including Blender). It supports node hierarchies, meshes with PBR materials, skinning,
+//! morph targets and keyframe animation.
+//!
+//! Normally you should never use methods from this module directly, use resource manager to load
+//! models and create their instances.
+
+mod animation;
+pub mod error;
+mod material;
+mod mesh;
+
+use crate::{
+    animation::AnimationContainer,
+    asset::manager::ResourceManager,
+    core::{
+        algebra::{Matrix4, Quaternion, UnitQuaternion, Vector3},
+        instant::Instant,
+        log::{Log, MessageKind},
+        pool::Handle,
+        uuid::Uuid,
+    },
+    material::MaterialResource,
+    resource::{
+        gltf::{error::GltfError, material::TextureCache},
+        model::ModelImportOptions,
+    },
+    scene::{
+        animation::AnimationPlayerBuilder,
+        base::{BaseBuilder, InstanceId},
+        graph::Graph,
+        mesh::{Mesh, MeshBuilder},
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+        Scene,
+    },
+};
+use base64::Engine;
+use fxhash::{FxHashMap, FxHashSet};
+use fyrox_resource::io::ResourceIo;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+
+/// Shared state of the conversion process.
+struct ImportContext<'a> {
+    document: &'a gltf::Document,
+    buffers: Vec<Vec<u8>>,
+    resource_manager: ResourceManager,
+    model_path: &'a Path,
+    model_import_options: &'a ModelImportOptions,
+}
+
+impl<'a> ImportContext<'a> {
+    fn buffer_data(&self, buffer: gltf::Buffer) -> Option<&[u8]> {
+        self.buffers.get(buffer.index()).map(|data| data.as_slice())
+    }
+}
+
+/// Decodes `%XX` sequences of a relative URI.
+fn percent_decode(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(byte) = uri
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Decodes the content of a data URI (everything after `data:` prefix).
+fn decode_data_uri(data: &str) -> Result<Vec<u8>, GltfError> {
+    let (header, payload) = data
+        .split_once(',')
+        .ok_or_else(|| GltfError::InvalidDataUri(data.chars().take(32).collect()))?;
+
+    if header.ends_with(";base64") {
+        base64::engine::general_purpose::STANDARD
+            .decode(payload)
+            .map_err(|e| GltfError::InvalidDataUri(format!("{header}: {e}")))
+    } else {
+        Ok(percent_decode(payload).into_bytes())
+    }
+}
+
+async fn load_buffers(
+    gltf: &mut gltf::Gltf,
+    model_path: &Path,
+    io: &dyn ResourceIo,
+) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut blob = gltf.blob.take();
+    let mut buffers = Vec::new();
+
+    for buffer in gltf.document.buffers() {
+        let data = match buffer.source() {
+            gltf::buffer::Source::Bin => blob.take().ok_or(GltfError::MissingBlob)?,
+            gltf::buffer::Source::Uri(uri) => {
+                if let Some(data) = uri.strip_prefix("data:") {
+                    decode_data_uri(data)?
+                } else {
+                    let path = model_path
+                        .parent()
+                        .unwrap_or(model_path)
+                        .join(percent_decode(uri));
+                    io.load_file(&path).await?
+                }
+            }
+        };
+
+        if data.len() < buffer.length() {
+            return Err(GltfError::BufferTooShort {
+                index: buffer.index(),
+                expected: buffer.length(),
+                actual: data.len(),
+            });
+        }
+
+        buffers.push(data);
+    }
+
+    Ok(buffers)
+}
+
+fn convert_node(
+    ctx: &ImportContext,
+    gltf_node: &gltf::Node,
+    materials: &[MaterialResource],
+    graph: &mut Graph,
+) -> Result<Handle<Node>, GltfError> {
+    let name = gltf_node
+        .name()
+        .map(|name| name.to_owned())
+        .unwrap_or_else(|| format!("Node{}", gltf_node.index()));
+
+    // glTF does not have persistent unique ids for nodes either (indices could change after re-export),
+    // so the name is used to generate stable instance id, the same way as FBX importer does.
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    let hash = hasher.finish();
+    let instance_id = InstanceId(Uuid::from_u64_pair(hash, hash));
+
+    let (translation, rotation, scale) = gltf_node.transform().decomposed();
+
+    let base = BaseBuilder::new()
+        .with_name(name)
+        .with_instance_id(instance_id)
+        .with_local_transform(
+            TransformBuilder::new()
+                .with_local_position(Vector3::from(translation))
+                .with_local_rotation(UnitQuaternion::new_normalize(Quaternion::new(
+                    rotation[3],
+                    rotation[0],
+                    rotation[1],
+                    rotation[2],
+                )))
+                .with_local_scale(Vector3::from(scale))
+                .build(),
+        );
+
+    Ok(match gltf_node.mesh() {
+        Some(gltf_mesh) => {
+            let weights = gltf_node.weights().or_else(|| gltf_mesh.weights());
+            let converted = mesh::convert_mesh(
+                ctx,
+                gltf_mesh,
+                gltf_node.skin().is_some(),
+                weights,
+                materials,
+            )?;
+            MeshBuilder::new(base)
+                .with_blend_shapes(converted.blend_shapes)
+                .with_surfaces(converted.surfaces)
+                .build(graph)
+        }
+        None => PivotBuilder::new(base).build(graph),
+    })
+}
+
+/// Assigns bones to every surface of skinned meshes and sets inverse bind pose matrices for joints.
+fn resolve_skins(
+    ctx: &ImportContext,
+    node_map: &FxHashMap<usize, Handle<Node>>,
+    graph: &mut Graph,
+) -> Result<(), GltfError> {
+    for gltf_node in ctx.document.nodes() {
+        let (Some(skin), Some(&mesh_handle)) = (gltf_node.skin(), node_map.get(&gltf_node.index()))
+        else {
+            continue;
+        };
+
+        let joints = skin
+            .joints()
+            .map(|joint| node_map.get(&joint.index()).cloned().unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        if joints.len() > 256 {
+            return Err(GltfError::TooManyJoints(joints.len()));
+        }
+
+        // Missing inverse bind matrices must be treated as identity matrices.
+        let reader = skin.reader(|buffer| ctx.buffer_data(buffer));
+        let inverse_bind_matrices = reader
+            .read_inverse_bind_matrices()
+            .map(|matrices| matrices.map(Matrix4::from).collect::<Vec<_>>())
+            .unwrap_or_else(|| vec![Matrix4::identity(); joints.len()]);
+
+        for (joint, inverse_bind_matrix) in joints.iter().zip(inverse_bind_matrices) {
+            if let Some(joint) = graph.try_get_mut(*joint) {
+                joint.inv_bind_pose_transform = inverse_bind_matrix;
+            }
+        }
+
+        if let Some(mesh) = graph[mesh_handle].cast_mut::<Mesh>() {
+            for surface in mesh.surfaces_mut() {
+                surface.bones.set_value_silent(joints.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// Converts glTF document to native engine representation.
+///
+async fn convert(ctx: &ImportContext<'_>, scene: &mut Scene) -> Result<(), GltfError> {
+    let mut texture_cache = TextureCache::default();
+    let mut materials = Vec::new();
+    for gltf_material in ctx.document.materials() {
+        materials.push(material::convert_material(ctx, &mut texture_cache, gltf_material).await);
+    }
+
+    // Use the default scene of the document, or the first one. If there's no scenes at all, then
+    // every node without a parent is considered as a root.
+    let roots = match ctx
+        .document
+        .default_scene()
+        .or_else(|| ctx.document.scenes().next())
+    {
+        Some(gltf_scene) => gltf_scene.nodes().collect::<Vec<_>>(),
+        None => {
+            let children = ctx
+                .document
+                .nodes()
+                .flat_map(|node| node.children().map(|child| child.index()))
+                .collect::<FxHashSet<_>>();
+            ctx.document
+                .nodes()
+                .filter(|node| !children.contains(&node.index()))
+                .collect()
+        }
+    };
+
+    let root = scene.graph.get_root();
+    let mut node_map = FxHashMap::default();
+    let mut stack = roots
+        .into_iter()
+        .rev()
+        .map(|node| (node, root))
+        .collect::<Vec<_>>();
+    while let Some((gltf_node, parent)) = stack.pop() {
+        if node_map.contains_key(&gltf_node.index()) {
+            continue;
+        }
+
+        let node = convert_node(ctx, &gltf_node, &materials, &mut scene.graph)?;
+        scene.graph.link_nodes(node, parent);
+        node_map.insert(gltf_node.index(), node);
+
+        let children = gltf_node.children().collect::<Vec<_>>();
+        stack.extend(children.into_iter().rev().map(|child| (child, node)));
+    }
+
+    resolve_skins(ctx, &node_map, &mut scene.graph)?;
+
+    let animations = animation::convert_animations(ctx, &node_map);
+
+    // Do not create animation player if there's no animation content.
+    if !animations.is_empty() {
+        let mut animations_container = AnimationContainer::new();
+        for animation in animations {
+            animations_container.add(animation);
+        }
+        AnimationPlayerBuilder::new(BaseBuilder::new().with_name("AnimationPlayer"))
+            .with_animations(animations_container)
+            .build(&mut scene.graph);
+    }
+
+    scene.graph.update_hierarchical_data();
+
+    Ok(())
+}
+
+/// Tries to load and convert glTF (or binary glTF) from given path.
+///
+/// Normally you should never use this method, use resource manager to load models.
+pub async fn load_to_scene<P: AsRef<Path>>(
+    scene: &mut Scene,
+    resource_manager: ResourceManager,
+    io: &dyn ResourceIo,
+    path: P,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let start_time = Instant::now();
+
+    Log::writeln(
+        MessageKind::Information,
+        format!("Trying to load {:?}", path.as_ref()),
+    );
+
+    let now = Instant::now();
+    let data = io.load_file(path.as_ref()).await?;
+    let mut gltf = gltf::Gltf::from_slice(&data)?;
+    let buffers = load_buffers(&mut gltf, path.as_ref(), io).await?;
+    let parsing_time = now.elapsed().as_millis();
+
+    let now = Instant::now();
+    let ctx = ImportContext {
+        document: &gltf.document,
+        buffers,
+        resource_manager,
+        model_path: path.as_ref(),
+        model_import_options,
+    };
+    convert(&ctx, scene).await?;
+    let conversion_time = now.elapsed().as_millis();
+
+    Log::writeln(
+        MessageKind::Information,
+        format!(
+            "glTF {:?} loaded in {} ms\n\t- Parsing - {} ms\n\t- Conversion - {} ms",
+            path.as_ref(),
+            start_time.elapsed().as_millis(),
+            parsing_time,
+            conversion_time
+        ),
+    );
+
+    // Node names are used to restore data from the resource, so check for duplicates and warn
+    // the user about them.
+    let mut names = FxHashSet::<String>::default();
+    for node in scene.graph.linear_iter() {
+        if !names.insert(node.name_owned()) {
+            Log::writeln(
+                MessageKind::Error,
+                format!(
+                    "A node with existing name {} was found during the load of {} resource! \
+                    Do **NOT IGNORE** this message, please fix names in your model, otherwise \
+                    engine won't be able to correctly restore data from your resource!",
+                    node.name(),
+                    path.as_ref().display()
+                ),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{algebra::Vector3, futures::executor::block_on},
+        resource::{gltf::load_to_scene, model::ModelImportOptions},
+        scene::{animation::AnimationPlayer, mesh::Mesh, Scene},
+    };
+    use base64::Engine;
+    use std::{fs, path::Path};
+
+    fn write_test_document(path: &Path) {
+        let mut data = Vec::new();
+        // Triangle positions.
+        for v in [0.0f32, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        // Key frame times.
+        for v in [0.0f32, 2.0] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+        // Translations.
+        for v in [0.0f32, 0.0, 0.0, 4.0, 0.0, 0.0] {
+            data.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let json = format!(
+            r#"{{
+  "asset": {{ "version": "2.0" }},
+  "scene": 0,
+  "scenes": [{{ "nodes": [0] }}],
+  "nodes": [
+    {{ "name": "Root", "translation": [1.0, 2.0, 3.0], "children": [1] }},
+    {{ "name": "Triangle", "mesh": 0 }}
+  ],
+  "meshes": [{{ "primitives": [{{ "attributes": {{ "POSITION": 0 }} }}] }}],
+  "animations": [{{
+    "name": "Move",
+    "channels": [{{ "sampler": 0, "target": {{ "node": 1, "path": "translation" }} }}],
+    "samplers": [{{ "input": 1, "output": 2 }}]
+  }}],
+  "buffers": [{{ "byteLength": {}, "uri": "data:application/octet-stream;base64,{}" }}],
+  "bufferViews": [
+    {{ "buffer": 0, "byteOffset": 0, "byteLength": 36 }},
+    {{ "buffer": 0, "byteOffset": 36, "byteLength": 8 }},
+    {{ "buffer": 0, "byteOffset": 44, "byteLength": 24 }}
+  ],
+  "accessors": [
+    {{ "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+       "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0] }},
+    {{ "bufferView": 1, "componentType": 5126, "count": 2, "type": "SCALAR",
+       "min": [0.0], "max": [2.0] }},
+    {{ "bufferView": 2, "componentType": 5126, "count": 2, "type": "VEC3" }}
+  ]
+}}"#,
+            data.len(),
+            base64::engine::general_purpose::STANDARD.encode(&data)
+        );
+
+        fs::write(path, json).unwrap();
+    }
+
+    #[test]
+    fn test_load_gltf() {
+        if !Path::new("test_output").exists() {
+            fs::create_dir_all("test_output").unwrap();
+        }
+
+        let path = Path::new("test_output/triangle.gltf");
+        write_test_document(path);
+
+        let resource_manager = ResourceManager::new();
+        let io = resource_manager.resource_io();
+        let mut scene = Scene::new();
+        block_on(load_to_scene(
+            &mut scene,
+            resource_manager,
+            &*io,
+            path,
+            &ModelImportOptions::default(),
+        ))
+        .unwrap();
+
+        let (root, root_ref) = scene.graph.find_by_name_from_root("Root").unwrap();
+        assert_eq!(
+            **root_ref.local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+
+        let (triangle, triangle_ref) = scene.graph.find_by_name_from_root("Triangle").unwrap();
+        assert_eq!(triangle_ref.parent(), root);
+        let mesh = triangle_ref.cast::<Mesh>().unwrap();
+        assert_eq!(mesh.surfaces().len(), 1);
+        let data = mesh.surfaces()[0].data();
+        let data = data.lock();
+        assert_eq!(data.vertex_buffer.vertex_count(), 3);
+        assert_eq!(data.geometry_buffer.len(), 1);
+
+        let (_, player) = scene
+            .graph
+            .find_from_root(&mut |n| n.query_component_ref::<AnimationPlayer>().is_some())
+            .unwrap();
+        let player = player.query_component_ref::<AnimationPlayer>().unwrap();
+        let animation = player.animations().iter().next().unwrap();
+        assert_eq!(animation.name(), "Move");
+        assert_eq!(animation.tracks().len(), 1);
+        assert_eq!(animation.tracks()[0].target(), triangle);
+        assert_eq!(animation.length(), 2.0);
+    }
+}
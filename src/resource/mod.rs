@@ -4,5 +4,7 @@
 
 pub mod curve;
 pub mod fbx;
+#[cfg(feature = "gltf")]
+pub mod gltf;
 pub mod model;
 pub mod texture;
@@ -29,7 +29,14 @@ pub struct ModelLoader {
 
 impl ResourceLoader for ModelLoader {
     fn extensions(&self) -> &[&str] {
-        &["rgs", "fbx"]
+        &[
+            "rgs",
+            "fbx",
+            #[cfg(feature = "gltf")]
+            "gltf",
+            #[cfg(feature = "gltf")]
+            "glb",
+        ]
     }
 
     fn data_type_uuid(&self) -> Uuid {
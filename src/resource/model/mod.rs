@@ -15,11 +15,18 @@
 //!
 //! # Supported formats
 //!
-//! Currently only FBX (common format in game industry for storing complex 3d models)
-//! and RGS (native Fyroxed format) formats are supported.
+//! Currently FBX (common format in game industry for storing complex 3d models), glTF 2.0
+//! (both `.gltf` and binary `.glb` variants) and RGS (native Fyroxed format) formats are supported.
+//! glTF support is optional and must be enabled using `gltf` feature.
 
+#[cfg(feature = "gltf")]
+use crate::resource::gltf::{self, error::GltfError};
 use crate::{
-    animation::Animation,
+    animation::{
+        compression::{compress_animation, AnimationCompressionOptions, CompressionReport},
+        retarget::AnimationRetargeter,
+        Animation,
+    },
     asset::{
         manager::ResourceManager, options::ImportOptions, Resource, ResourceData,
         MODEL_RESOURCE_UUID,
@@ -125,6 +132,17 @@ pub trait ModelResourceExtension: Sized {
     /// this function will return vector with only one animation.
     fn retarget_animations_directly(&self, root: Handle<Node>, graph: &Graph) -> Vec<Animation>;
 
+    /// Retargets animations from given model resource to a node hierarchy starting from `root` on a given scene
+    /// using the specified [`AnimationRetargeter`]. Unlike [`Self::retarget_animations_directly`], it is able to
+    /// retarget animations to skeletons with different bone names, bone count, bone lengths or rest pose. Both
+    /// skeletons should be in their rest pose. See [`AnimationRetargeter`] docs for more info.
+    fn retarget_animations_with(
+        &self,
+        root: Handle<Node>,
+        graph: &Graph,
+        retargeter: &AnimationRetargeter,
+    ) -> Vec<Animation>;
+
     /// Tries to retarget animations from given model resource to a node hierarchy starting
     /// from `root` on a given scene. Unlike [`Self::retarget_animations_directly`], it automatically
     /// adds retargetted animations to the specified animation player in the hierarchy of given `root`.
@@ -254,6 +272,31 @@ impl ModelResourceExtension for ModelResource {
         retargetted_animations
     }
 
+    fn retarget_animations_with(
+        &self,
+        root: Handle<Node>,
+        graph: &Graph,
+        retargeter: &AnimationRetargeter,
+    ) -> Vec<Animation> {
+        let mut retargetted_animations = Vec::new();
+
+        let mut header = self.state();
+        if let Some(model) = header.data() {
+            let source_graph = &model.scene.graph;
+            let map = retargeter.build_map(source_graph, source_graph.get_root(), graph, root);
+
+            for src_node_ref in source_graph.linear_iter() {
+                if let Some(src_player) = src_node_ref.query_component_ref::<AnimationPlayer>() {
+                    for src_anim in src_player.animations().iter() {
+                        retargetted_animations.push(map.retarget_animation(src_anim));
+                    }
+                }
+            }
+        }
+
+        retargetted_animations
+    }
+
     fn retarget_animations_to_player(
         &self,
         root: Handle<Node>,
@@ -320,7 +363,7 @@ impl Default for Model {
     }
 }
 
-/// Defines a way of searching materials when loading a model resource from foreign file format such as FBX.
+/// Defines a way of searching materials when loading a model resource from foreign file format such as FBX or glTF.
 ///
 /// # Motivation
 ///
@@ -393,6 +436,52 @@ impl MaterialSearchOptions {
     pub fn materials_directory<P: AsRef<Path>>(path: P) -> Self {
         Self::MaterialsDirectory(path.as_ref().to_path_buf())
     }
+
+    /// Tries to find a file with the file name of the given external resource path (a texture path stored
+    /// in a 3D model for example) using the current search method. Returns [`None`] if nothing was found
+    /// or if the given path does not have a file name.
+    pub(crate) async fn resolve_path(
+        &self,
+        path: &Path,
+        model_path: &Path,
+        io: &dyn ResourceIo,
+    ) -> Option<PathBuf> {
+        let filename = path.file_name()?;
+
+        match self {
+            MaterialSearchOptions::MaterialsDirectory(ref directory) => {
+                Some(directory.join(filename))
+            }
+            MaterialSearchOptions::RecursiveUp => {
+                let mut path = model_path.to_owned();
+                while let Some(parent) = path.parent() {
+                    let candidate = parent.join(filename);
+                    if io.exists(&candidate).await {
+                        return Some(candidate);
+                    }
+                    path.pop();
+                }
+                None
+            }
+            MaterialSearchOptions::WorkingDirectory => {
+                let path = Path::new(".");
+
+                if let Ok(iter) = io.walk_directory(path).await {
+                    for dir in iter {
+                        if io.is_dir(&dir).await {
+                            let candidate = dir.join(filename);
+                            if candidate.exists() {
+                                return Some(candidate);
+                            }
+                        }
+                    }
+                }
+
+                None
+            }
+            MaterialSearchOptions::UsePathDirectly => Some(path.to_path_buf()),
+        }
+    }
 }
 
 /// A set of options that will be applied to a model resource when loading it from external source.
@@ -410,11 +499,15 @@ impl MaterialSearchOptions {
 /// ```
 ///
 /// Check documentation of the field of the structure for more info about each parameter.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Reflect, Eq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Reflect)]
 pub struct ModelImportOptions {
     /// See [`MaterialSearchOptions`] docs for more info.
     #[serde(default)]
     pub material_search_options: MaterialSearchOptions,
+
+    /// Optional compression of imported animations. See [`AnimationCompressionOptions`] docs for more info.
+    #[serde(default)]
+    pub animation_compression: Option<AnimationCompressionOptions>,
 }
 
 impl ImportOptions for ModelImportOptions {}
@@ -429,6 +522,9 @@ pub enum ModelLoadError {
     NotSupported(String),
     /// An error occurred while loading FBX file.
     Fbx(FbxError),
+    /// An error occurred while loading glTF file.
+    #[cfg(feature = "gltf")]
+    Gltf(GltfError),
 }
 
 impl Display for ModelLoadError {
@@ -441,6 +537,8 @@ impl Display for ModelLoadError {
                 write!(f, "Model format is not supported: {v}")
             }
             ModelLoadError::Fbx(v) => v.fmt(f),
+            #[cfg(feature = "gltf")]
+            ModelLoadError::Gltf(v) => v.fmt(f),
         }
     }
 }
@@ -451,6 +549,13 @@ impl From<FbxError> for ModelLoadError {
     }
 }
 
+#[cfg(feature = "gltf")]
+impl From<GltfError> for ModelLoadError {
+    fn from(gltf: GltfError) -> Self {
+        ModelLoadError::Gltf(gltf)
+    }
+}
+
 impl From<VisitError> for ModelLoadError {
     fn from(e: VisitError) -> Self {
         ModelLoadError::Visit(e)
@@ -487,10 +592,31 @@ impl Model {
                     &model_import_options,
                 )
                 .await?;
+                Self::compress_animations(&mut scene, &model_import_options, path.as_ref());
                 // Set NodeMapping::UseNames as mapping here because FBX does not have
                 // any persistent unique ids, and we have to use names.
                 (scene, NodeMapping::UseNames)
             }
+            #[cfg(feature = "gltf")]
+            "gltf" | "glb" => {
+                let mut scene = Scene::new();
+                if let Some(filename) = path.as_ref().file_name() {
+                    let root = scene.graph.get_root();
+                    scene.graph[root].set_name(filename.to_string_lossy());
+                }
+                gltf::load_to_scene(
+                    &mut scene,
+                    resource_manager,
+                    io,
+                    path.as_ref(),
+                    &model_import_options,
+                )
+                .await?;
+                Self::compress_animations(&mut scene, &model_import_options, path.as_ref());
+                // glTF node indices are not persistent (they change after re-export), so names
+                // must be used here as well.
+                (scene, NodeMapping::UseNames)
+            }
             // Scene can be used directly as model resource. Such scenes can be created in
             // Fyroxed.
             "rgs" => (
@@ -518,6 +644,37 @@ impl Model {
         Ok(Self { scene, mapping })
     }
 
+    fn compress_animations(
+        scene: &mut Scene,
+        model_import_options: &ModelImportOptions,
+        path: &Path,
+    ) {
+        let Some(options) = model_import_options.animation_compression.as_ref() else {
+            return;
+        };
+
+        let mut report = CompressionReport::default();
+        for node in scene.graph.linear_iter_mut() {
+            if let Some(animation_player) = node.query_component_mut::<AnimationPlayer>() {
+                for animation in animation_player
+                    .animations_mut()
+                    .get_value_mut_silent()
+                    .iter_mut()
+                {
+                    report.merge(&compress_animation(animation, options));
+                }
+            }
+        }
+
+        if report.original_keys > 0 {
+            Log::info(format!(
+                "Animations of {} were compressed: {}",
+                path.display(),
+                report
+            ));
+        }
+    }
+
     /// Returns shared reference to internal scene, there is no way to obtain
     /// mutable reference to inner scene because resource is immutable source
     /// of data.
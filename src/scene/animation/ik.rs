@@ -0,0 +1,935 @@
+//! Inverse kinematics (IK) is a post-processing step of animations, that adjusts bones of a skeleton so they reach
+//! some target. See [`InverseKinematics`] docs for more info.
+
+use crate::{
+    animation::machine::Parameter,
+    core::{
+        algebra::{Matrix4, Rotation3, UnitQuaternion, Vector3},
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        uuid_provider,
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        animation::absm::AnimationBlendingStateMachine,
+        base::{Base, BaseBuilder},
+        graph::{Graph, NodePool},
+        node::{Node, NodeTrait},
+        Scene,
+    },
+};
+use std::ops::{Deref, DerefMut};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Two-bone solver is an analytical solver for limbs that consist of two bones - arms (shoulder, elbow, wrist) and
+/// legs (hip, knee, ankle). It always finds exact solution (if the target is reachable) and it is the cheapest
+/// solver.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct TwoBoneIk {
+    /// The first bone of the limb (for example, a shoulder or a hip).
+    pub root: Handle<Node>,
+    /// The second bone of the limb (for example, an elbow or a knee). It must be a descendant of the root.
+    pub middle: Handle<Node>,
+    /// The end of the limb (for example, a wrist or an ankle). It must be a descendant of the middle bone.
+    pub end: Handle<Node>,
+    /// A node, that the end of the limb will try to reach.
+    pub target: Handle<Node>,
+    /// An optional node, that defines a direction in which the middle bone will bend. If not set, the current
+    /// position of the middle bone will be used as the bend direction.
+    pub pole: Handle<Node>,
+    /// If `true`, the end bone will also be rotated to match the rotation of the target. It is useful to place feet
+    /// on uneven surface, for example.
+    pub match_target_rotation: bool,
+}
+
+uuid_provider!(TwoBoneIk = "59db42f9-3aa6-4c16-93c5-a9a909976ad8");
+
+/// An algorithm, that is used by [`ChainIk`] solver.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum ChainAlgorithm {
+    /// Forward And Backward Reaching Inverse Kinematics. Gives natural looking results and converges fast, it is
+    /// a good default choice.
+    #[default]
+    Fabrik,
+    /// Cyclic Coordinate Descent. It rotates each bone of the chain (starting from the end) towards the target.
+    /// It tends to curl the end of the chain, which could be useful for tails and tentacles.
+    Ccd,
+}
+
+uuid_provider!(ChainAlgorithm = "d1474b87-19ba-4e79-a381-b10c9a1fd101");
+
+/// Chain solver is an iterative solver for chains of bones of arbitrary length - spines, tails, tentacles, etc.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct ChainIk {
+    /// The first bone of the chain.
+    pub root: Handle<Node>,
+    /// The last bone of the chain. It must be a descendant of the root.
+    pub end: Handle<Node>,
+    /// A node, that the end of the chain will try to reach.
+    pub target: Handle<Node>,
+    /// An algorithm that will be used to solve the chain.
+    pub algorithm: ChainAlgorithm,
+    /// Max amount of iterations of the algorithm.
+    #[reflect(min_value = 1.0)]
+    pub iterations: u32,
+    /// The solver stops, when the distance between the end of the chain and the target is less than the tolerance.
+    #[reflect(min_value = 0.0, step = 0.001)]
+    pub tolerance: f32,
+}
+
+impl Default for ChainIk {
+    fn default() -> Self {
+        Self {
+            root: Default::default(),
+            end: Default::default(),
+            target: Default::default(),
+            algorithm: Default::default(),
+            iterations: 10,
+            tolerance: 0.001,
+        }
+    }
+}
+
+uuid_provider!(ChainIk = "58329c3e-3bea-4b6a-88e3-f14205379b9a");
+
+/// Look-at (aim) solver rotates a single bone, so its forward axis points at the target. It is used for heads, eyes,
+/// weapons, etc.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct LookAtIk {
+    /// A bone, that will be rotated.
+    pub bone: Handle<Node>,
+    /// A node, that the bone will look at.
+    pub target: Handle<Node>,
+    /// An axis of the bone (in its local coordinates), that will be pointed at the target.
+    pub forward_axis: Vector3<f32>,
+    /// Max angle (in radians) of deviation from the animated pose. Zero means no limit.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub max_angle: f32,
+}
+
+impl Default for LookAtIk {
+    fn default() -> Self {
+        Self {
+            bone: Default::default(),
+            target: Default::default(),
+            forward_axis: Vector3::z(),
+            max_angle: 0.0,
+        }
+    }
+}
+
+uuid_provider!(LookAtIk = "fc204983-50db-4f3c-91e7-40492675a4a4");
+
+/// The exact kind of the solver.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
+pub enum IkSolverKind {
+    /// See [`TwoBoneIk`] docs for more info.
+    TwoBone(TwoBoneIk),
+    /// See [`ChainIk`] docs for more info.
+    Chain(ChainIk),
+    /// See [`LookAtIk`] docs for more info.
+    LookAt(LookAtIk),
+}
+
+impl Default for IkSolverKind {
+    fn default() -> Self {
+        Self::TwoBone(Default::default())
+    }
+}
+
+uuid_provider!(IkSolverKind = "188c7166-dcff-40e7-b86a-c79478405264");
+
+/// A solver with its blending settings.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct IkSolver {
+    /// A name of the solver.
+    pub name: String,
+    /// Whether the solver is enabled or not.
+    pub enabled: bool,
+    /// Blend weight of the solver in `[0; 1]` range. Zero means that the animated pose will be unchanged, one - that
+    /// the solved pose will fully replace the animated pose.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub weight: f32,
+    /// An optional name of a parameter of the state machine (see [`InverseKinematics::absm`]), that will be used
+    /// as an additional multiplier for the weight. `Weight` parameters are used as is, `Rule` parameters are
+    /// treated as `1.0` if set and `0.0` otherwise.
+    pub weight_parameter: String,
+    /// The exact kind of the solver.
+    pub kind: IkSolverKind,
+}
+
+impl Default for IkSolver {
+    fn default() -> Self {
+        Self {
+            name: "IK Solver".to_string(),
+            enabled: true,
+            weight: 1.0,
+            weight_parameter: Default::default(),
+            kind: Default::default(),
+        }
+    }
+}
+
+uuid_provider!(IkSolver = "c52a5246-2469-47cc-b14c-0f7b7ea00a8b");
+
+impl IkSolver {
+    /// Creates a new enabled solver with full weight.
+    pub fn new(kind: IkSolverKind) -> Self {
+        Self {
+            kind,
+            ..Default::default()
+        }
+    }
+
+    /// Sets a new name of the solver.
+    pub fn with_name<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.name = name.as_ref().to_owned();
+        self
+    }
+
+    /// Sets a new weight of the solver.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets a name of the state machine parameter, that will be used as a multiplier for the weight.
+    pub fn with_weight_parameter<S: AsRef<str>>(mut self, parameter: S) -> Self {
+        self.weight_parameter = parameter.as_ref().to_owned();
+        self
+    }
+
+    fn bones(&self, nodes: &NodePool) -> Vec<Handle<Node>> {
+        match self.kind {
+            IkSolverKind::TwoBone(ref two_bone) => {
+                vec![two_bone.root, two_bone.middle, two_bone.end]
+            }
+            IkSolverKind::Chain(ref chain) => {
+                collect_chain(nodes, chain.root, chain.end).unwrap_or_default()
+            }
+            IkSolverKind::LookAt(ref look_at) => vec![look_at.bone],
+        }
+    }
+
+    fn solve(&self, nodes: &mut NodePool) {
+        match self.kind {
+            IkSolverKind::TwoBone(ref two_bone) => solve_two_bone(nodes, two_bone),
+            IkSolverKind::Chain(ref chain) => solve_chain(nodes, chain),
+            IkSolverKind::LookAt(ref look_at) => solve_look_at(nodes, look_at),
+        }
+    }
+}
+
+/// Inverse kinematics node is a set of solvers, that adjust bones of a skeleton after animations were applied. It is
+/// used to place feet on uneven ground, to make characters look at something, to put hands on a weapon, etc.
+///
+/// # How it works
+///
+/// The node is processed after all other nodes in the graph were updated, so animation players and state machines
+/// already applied their poses. Then every enabled solver rotates its bones (it never moves them), so the end of the
+/// chain reaches the target. The result is blended with the animated pose using the weight of the solver.
+///
+/// # Important notes
+///
+/// The solvers work on top of the current pose, so the bones should be animated every frame (by an animation
+/// player or a state machine). Otherwise, partial weights will accumulate over multiple frames.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::pool::Handle,
+///     scene::{
+///         animation::ik::{IkSolver, IkSolverKind, InverseKinematicsBuilder, TwoBoneIk},
+///         base::BaseBuilder,
+///         graph::Graph,
+///         node::Node,
+///     },
+/// };
+///
+/// fn create_leg_ik(
+///     hip: Handle<Node>,
+///     knee: Handle<Node>,
+///     ankle: Handle<Node>,
+///     foot_target: Handle<Node>,
+///     absm: Handle<Node>,
+///     graph: &mut Graph,
+/// ) -> Handle<Node> {
+///     InverseKinematicsBuilder::new(BaseBuilder::new())
+///         .with_solvers(vec![IkSolver::new(IkSolverKind::TwoBone(TwoBoneIk {
+///             root: hip,
+///             middle: knee,
+///             end: ankle,
+///             target: foot_target,
+///             ..Default::default()
+///         }))
+///         .with_name("LeftLeg")
+///         // The weight will be multiplied by the value of this parameter of the state machine.
+///         .with_weight_parameter("LeftLegIkWeight")])
+///         .with_absm(absm)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Clone, Debug, Default)]
+pub struct InverseKinematics {
+    base: Base,
+    solvers: InheritableVariable<Vec<IkSolver>>,
+    absm: InheritableVariable<Handle<Node>>,
+}
+
+impl InverseKinematics {
+    /// Sets new solvers of the node.
+    pub fn set_solvers(&mut self, solvers: Vec<IkSolver>) {
+        self.solvers.set_value_and_mark_modified(solvers);
+    }
+
+    /// Returns a reference to the solvers of the node.
+    pub fn solvers(&self) -> &[IkSolver] {
+        &self.solvers
+    }
+
+    /// Returns a mutable reference to the solvers of the node.
+    pub fn solvers_mut(&mut self) -> &mut Vec<IkSolver> {
+        self.solvers.get_value_mut_and_mark_modified()
+    }
+
+    /// Sets new animation blending state machine, that will be used as a source of weight parameters of the
+    /// solvers.
+    pub fn set_absm(&mut self, absm: Handle<Node>) {
+        self.absm.set_value_and_mark_modified(absm);
+    }
+
+    /// Returns a handle of animation blending state machine, that is used as a source of weight parameters of the
+    /// solvers.
+    pub fn absm(&self) -> Handle<Node> {
+        *self.absm
+    }
+}
+
+impl TypeUuidProvider for InverseKinematics {
+    fn type_uuid() -> Uuid {
+        uuid!("2ac77755-7341-4277-9957-8d815bb87848")
+    }
+}
+
+impl Deref for InverseKinematics {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for InverseKinematics {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl NodeTrait for InverseKinematics {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn validate(&self, scene: &Scene) -> Result<(), String> {
+        let graph = &scene.graph;
+
+        for solver in self.solvers.iter() {
+            let handles = match solver.kind {
+                IkSolverKind::TwoBone(ref two_bone) => {
+                    vec![
+                        two_bone.root,
+                        two_bone.middle,
+                        two_bone.end,
+                        two_bone.target,
+                    ]
+                }
+                IkSolverKind::Chain(ref chain) => vec![chain.root, chain.end, chain.target],
+                IkSolverKind::LookAt(ref look_at) => vec![look_at.bone, look_at.target],
+            };
+
+            if handles.iter().any(|handle| !graph.is_valid_handle(*handle)) {
+                return Err(format!(
+                    "Solver {} has invalid bones or target! Set them in the Inspector.",
+                    solver.name
+                ));
+            }
+
+            if let IkSolverKind::Chain(ref chain) = solver.kind {
+                let mut current = chain.end;
+                while current.is_some() && current != chain.root {
+                    current = graph[current].parent();
+                }
+                if current != chain.root {
+                    return Err(format!(
+                        "The end of solver {} is not a descendant of its root!",
+                        solver.name
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Inverse kinematics builder allows you to create the node in declarative manner.
+pub struct InverseKinematicsBuilder {
+    base_builder: BaseBuilder,
+    solvers: Vec<IkSolver>,
+    absm: Handle<Node>,
+}
+
+impl InverseKinematicsBuilder {
+    /// Creates new builder instance.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            solvers: Default::default(),
+            absm: Default::default(),
+        }
+    }
+
+    /// Sets the desired solvers.
+    pub fn with_solvers(mut self, solvers: Vec<IkSolver>) -> Self {
+        self.solvers = solvers;
+        self
+    }
+
+    /// Sets the state machine, that will be used as a source of weight parameters.
+    pub fn with_absm(mut self, absm: Handle<Node>) -> Self {
+        self.absm = absm;
+        self
+    }
+
+    /// Creates new node.
+    pub fn build_node(self) -> Node {
+        Node::new(InverseKinematics {
+            base: self.base_builder.build_base(),
+            solvers: self.solvers.into(),
+            absm: self.absm.into(),
+        })
+    }
+
+    /// Creates new node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+/// Applies solvers of an inverse kinematics node (if the node is one) to the bones in the pool.
+pub(crate) fn apply_inverse_kinematics(nodes: &mut NodePool, handle: Handle<Node>) {
+    let Some((solvers, absm)) = nodes
+        .try_borrow(handle)
+        .filter(|node| node.is_globally_enabled())
+        .and_then(|node| node.query_component_ref::<InverseKinematics>())
+        .map(|ik| (ik.solvers.clone_inner(), *ik.absm))
+    else {
+        return;
+    };
+
+    for solver in solvers.iter().filter(|solver| solver.enabled) {
+        let weight = (solver.weight * parameter_weight(nodes, absm, &solver.weight_parameter))
+            .clamp(0.0, 1.0);
+
+        if weight <= 0.0 {
+            continue;
+        }
+
+        let bones = solver.bones(nodes);
+        let original = bones
+            .iter()
+            .map(|bone| {
+                nodes
+                    .try_borrow(*bone)
+                    .map(|node| **node.local_transform().rotation())
+            })
+            .collect::<Vec<_>>();
+
+        solver.solve(nodes);
+
+        if weight < 1.0 {
+            for (bone, original) in bones.iter().zip(original) {
+                if let (Some(node), Some(original)) = (nodes.try_borrow_mut(*bone), original) {
+                    let solved = **node.local_transform().rotation();
+                    node.local_transform_mut()
+                        .set_rotation(original.slerp(&solved, weight));
+                }
+            }
+        }
+    }
+}
+
+fn parameter_weight(nodes: &NodePool, absm: Handle<Node>, name: &str) -> f32 {
+    if name.is_empty() {
+        return 1.0;
+    }
+
+    match nodes
+        .try_borrow(absm)
+        .and_then(|node| node.query_component_ref::<AnimationBlendingStateMachine>())
+        .and_then(|absm| absm.machine().parameters().get(name))
+    {
+        Some(Parameter::Weight(weight)) => *weight,
+        Some(Parameter::Rule(rule)) => {
+            if *rule {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        _ => 1.0,
+    }
+}
+
+/// Returns a chain of bones from the root to the end (inclusive), or `None` if the end is not a descendant of the
+/// root.
+fn collect_chain(
+    nodes: &NodePool,
+    root: Handle<Node>,
+    end: Handle<Node>,
+) -> Option<Vec<Handle<Node>>> {
+    let mut chain = vec![end];
+    let mut current = end;
+    while current != root {
+        current = nodes.try_borrow(current)?.parent();
+        if current.is_none() {
+            return None;
+        }
+        chain.push(current);
+    }
+    chain.reverse();
+    Some(chain)
+}
+
+// Global transforms of the graph are calculated only once per frame, but the solvers move bones multiple times, so
+// they calculate global transforms by themselves.
+fn global_matrix(nodes: &NodePool, handle: Handle<Node>) -> Option<Matrix4<f32>> {
+    let node = nodes.try_borrow(handle)?;
+    let mut matrix = node.local_transform().matrix();
+    let mut parent = node.parent();
+    while let Some(node) = nodes.try_borrow(parent) {
+        matrix = node.local_transform().matrix() * matrix;
+        parent = node.parent();
+    }
+    Some(matrix)
+}
+
+fn global_position(nodes: &NodePool, handle: Handle<Node>) -> Option<Vector3<f32>> {
+    global_matrix(nodes, handle).map(|matrix| matrix.position())
+}
+
+fn global_rotation(nodes: &NodePool, handle: Handle<Node>) -> UnitQuaternion<f32> {
+    global_matrix(nodes, handle)
+        .map(|matrix| {
+            let basis = matrix.basis();
+            let normalized = basis.map_with_location(|_, column, value| {
+                value / basis.column(column).norm().max(f32::EPSILON)
+            });
+            UnitQuaternion::from_rotation_matrix(&Rotation3::from_matrix_unchecked(normalized))
+        })
+        .unwrap_or_else(UnitQuaternion::identity)
+}
+
+/// Applies the given rotation (in world space) to a bone.
+fn rotate_global(nodes: &mut NodePool, handle: Handle<Node>, delta: UnitQuaternion<f32>) {
+    let Some(parent) = nodes.try_borrow(handle).map(|node| node.parent()) else {
+        return;
+    };
+    let parent_rotation = if parent.is_some() {
+        global_rotation(nodes, parent)
+    } else {
+        UnitQuaternion::identity()
+    };
+
+    let transform = nodes[handle].local_transform_mut();
+    let pre_rotation = **transform.pre_rotation();
+    let rotation = **transform.rotation();
+    transform.set_rotation(
+        pre_rotation.inverse()
+            * parent_rotation.inverse()
+            * delta
+            * parent_rotation
+            * pre_rotation
+            * rotation,
+    );
+}
+
+fn rotation_between(from: &Vector3<f32>, to: &Vector3<f32>) -> UnitQuaternion<f32> {
+    UnitQuaternion::rotation_between(from, to).unwrap_or_else(|| {
+        // Vectors are opposite, rotate around any perpendicular axis.
+        let axis = from.cross(&Vector3::x()).try_normalize(f32::EPSILON);
+        let axis = axis.unwrap_or_else(|| from.cross(&Vector3::y()).normalize());
+        UnitQuaternion::from_scaled_axis(axis * std::f32::consts::PI)
+    })
+}
+
+fn solve_two_bone(nodes: &mut NodePool, two_bone: &TwoBoneIk) {
+    let (Some(a), Some(b), Some(c), Some(t)) = (
+        global_position(nodes, two_bone.root),
+        global_position(nodes, two_bone.middle),
+        global_position(nodes, two_bone.end),
+        global_position(nodes, two_bone.target),
+    ) else {
+        return;
+    };
+
+    let upper_length = (b - a).norm();
+    let lower_length = (c - b).norm();
+    let Some(direction) = (t - a).try_normalize(f32::EPSILON) else {
+        return;
+    };
+    let target_distance = (t - a).norm().clamp(
+        (upper_length - lower_length).abs() + f32::EPSILON,
+        upper_length + lower_length - f32::EPSILON,
+    );
+
+    // Find a direction, in which the middle bone should bend.
+    let hint = global_position(nodes, two_bone.pole).unwrap_or(b) - a;
+    let bend = (hint - direction.scale(hint.dot(&direction)))
+        .try_normalize(f32::EPSILON)
+        .or_else(|| {
+            let current = b - a;
+            (current - direction.scale(current.dot(&direction))).try_normalize(f32::EPSILON)
+        })
+        .unwrap_or_else(|| {
+            direction
+                .cross(&Vector3::y())
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_else(Vector3::x)
+        });
+
+    // Law of cosines gives an angle between the upper bone and the direction to the target.
+    let cos_angle = ((upper_length * upper_length + target_distance * target_distance
+        - lower_length * lower_length)
+        / (2.0 * upper_length * target_distance))
+        .clamp(-1.0, 1.0);
+    let sin_angle = (1.0 - cos_angle * cos_angle).sqrt();
+    let desired_middle =
+        a + (direction.scale(cos_angle) + bend.scale(sin_angle)).scale(upper_length);
+
+    rotate_global(
+        nodes,
+        two_bone.root,
+        rotation_between(&(b - a), &(desired_middle - a)),
+    );
+
+    let (Some(b), Some(c)) = (
+        global_position(nodes, two_bone.middle),
+        global_position(nodes, two_bone.end),
+    ) else {
+        return;
+    };
+    let desired_end = a + direction.scale(target_distance);
+    rotate_global(
+        nodes,
+        two_bone.middle,
+        rotation_between(&(c - b), &(desired_end - b)),
+    );
+
+    if two_bone.match_target_rotation {
+        let delta = global_rotation(nodes, two_bone.target)
+            * global_rotation(nodes, two_bone.end).inverse();
+        rotate_global(nodes, two_bone.end, delta);
+    }
+}
+
+fn solve_chain(nodes: &mut NodePool, chain: &ChainIk) {
+    let Some(bones) = collect_chain(nodes, chain.root, chain.end) else {
+        return;
+    };
+    let Some(target) = global_position(nodes, chain.target) else {
+        return;
+    };
+    if bones.len() < 2 {
+        return;
+    }
+
+    match chain.algorithm {
+        ChainAlgorithm::Fabrik => {
+            let mut positions = bones
+                .iter()
+                .filter_map(|bone| global_position(nodes, *bone))
+                .collect::<Vec<_>>();
+            let lengths = positions
+                .windows(2)
+                .map(|pair| (pair[1] - pair[0]).norm())
+                .collect::<Vec<_>>();
+            let root = positions[0];
+            let last = positions.len() - 1;
+
+            if (target - root).norm() >= lengths.iter().sum::<f32>() {
+                // The target is unreachable, stretch the chain towards it.
+                let direction = (target - root)
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default();
+                for (i, length) in lengths.iter().enumerate() {
+                    positions[i + 1] = positions[i] + direction.scale(*length);
+                }
+            } else {
+                for _ in 0..chain.iterations {
+                    // Backward pass - from the end to the root.
+                    positions[last] = target;
+                    for i in (0..last).rev() {
+                        let direction = (positions[i] - positions[i + 1])
+                            .try_normalize(f32::EPSILON)
+                            .unwrap_or_default();
+                        positions[i] = positions[i + 1] + direction.scale(lengths[i]);
+                    }
+
+                    // Forward pass - from the root to the end.
+                    positions[0] = root;
+                    for i in 0..last {
+                        let direction = (positions[i + 1] - positions[i])
+                            .try_normalize(f32::EPSILON)
+                            .unwrap_or_default();
+                        positions[i + 1] = positions[i] + direction.scale(lengths[i]);
+                    }
+
+                    if (positions[last] - target).norm() <= chain.tolerance {
+                        break;
+                    }
+                }
+            }
+
+            // Rotate bones, so their children will be at the new positions.
+            for i in 0..last {
+                let (Some(joint), Some(child)) = (
+                    global_position(nodes, bones[i]),
+                    global_position(nodes, bones[i + 1]),
+                ) else {
+                    return;
+                };
+                rotate_global(
+                    nodes,
+                    bones[i],
+                    rotation_between(&(child - joint), &(positions[i + 1] - joint)),
+                );
+            }
+        }
+        ChainAlgorithm::Ccd => {
+            for _ in 0..chain.iterations {
+                for bone in bones.iter().rev().skip(1) {
+                    let (Some(joint), Some(end)) = (
+                        global_position(nodes, *bone),
+                        global_position(nodes, chain.end),
+                    ) else {
+                        return;
+                    };
+                    rotate_global(
+                        nodes,
+                        *bone,
+                        rotation_between(&(end - joint), &(target - joint)),
+                    );
+                }
+
+                if global_position(nodes, chain.end)
+                    .is_some_and(|end| (end - target).norm() <= chain.tolerance)
+                {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn solve_look_at(nodes: &mut NodePool, look_at: &LookAtIk) {
+    let (Some(position), Some(target)) = (
+        global_position(nodes, look_at.bone),
+        global_position(nodes, look_at.target),
+    ) else {
+        return;
+    };
+
+    let forward = global_rotation(nodes, look_at.bone) * look_at.forward_axis;
+    let mut delta = rotation_between(&forward, &(target - position));
+
+    if look_at.max_angle > 0.0 && delta.angle() > look_at.max_angle {
+        if let Some(axis) = delta.axis() {
+            delta = UnitQuaternion::from_axis_angle(&axis, look_at.max_angle);
+        }
+    }
+
+    rotate_global(nodes, look_at.bone, delta);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector3, pool::Handle},
+        scene::{
+            animation::ik::{
+                ChainAlgorithm, ChainIk, IkSolver, IkSolverKind, InverseKinematicsBuilder,
+                LookAtIk, TwoBoneIk,
+            },
+            base::BaseBuilder,
+            graph::{Graph, GraphUpdateSwitches},
+            node::Node,
+            pivot::PivotBuilder,
+            transform::TransformBuilder,
+        },
+    };
+
+    // Creates a straight chain of bones along Y axis with the given amount of bones of unit length.
+    fn make_chain(graph: &mut Graph, count: usize) -> Vec<Handle<Node>> {
+        let mut bones = Vec::new();
+        let mut parent = Handle::NONE;
+        for i in 0..count {
+            let bone = PivotBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, if i == 0 { 0.0 } else { 1.0 }, 0.0))
+                        .build(),
+                ),
+            )
+            .build(graph);
+            if parent.is_some() {
+                graph.link_nodes(bone, parent);
+            }
+            parent = bone;
+            bones.push(bone);
+        }
+        bones
+    }
+
+    fn make_target(graph: &mut Graph, position: Vector3<f32>) -> Handle<Node> {
+        PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .build(graph)
+    }
+
+    fn solve(graph: &mut Graph, solver: IkSolver) {
+        InverseKinematicsBuilder::new(BaseBuilder::new())
+            .with_solvers(vec![solver])
+            .build(graph);
+        graph.update(
+            Default::default(),
+            1.0 / 60.0,
+            GraphUpdateSwitches::default(),
+        );
+        graph.update_hierarchical_data();
+    }
+
+    #[test]
+    fn test_two_bone() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 3);
+        let target = make_target(&mut graph, Vector3::new(1.0, 1.0, 0.0));
+        let pole = make_target(&mut graph, Vector3::new(0.0, 1.0, 5.0));
+
+        solve(
+            &mut graph,
+            IkSolver::new(IkSolverKind::TwoBone(TwoBoneIk {
+                root: bones[0],
+                middle: bones[1],
+                end: bones[2],
+                target,
+                pole,
+                match_target_rotation: false,
+            })),
+        );
+
+        let end = graph[bones[2]].global_position();
+        assert!((end - Vector3::new(1.0, 1.0, 0.0)).norm() < 0.01, "{end:?}");
+        // The middle bone must bend towards the pole.
+        assert!(graph[bones[1]].global_position().z > 0.1);
+    }
+
+    #[test]
+    fn test_chain() {
+        for algorithm in [ChainAlgorithm::Fabrik, ChainAlgorithm::Ccd] {
+            let mut graph = Graph::new();
+            let bones = make_chain(&mut graph, 5);
+            let target = make_target(&mut graph, Vector3::new(2.0, 2.0, 1.0));
+
+            solve(
+                &mut graph,
+                IkSolver::new(IkSolverKind::Chain(ChainIk {
+                    root: bones[0],
+                    end: bones[4],
+                    target,
+                    algorithm,
+                    iterations: 50,
+                    tolerance: 0.001,
+                })),
+            );
+
+            let end = graph[bones[4]].global_position();
+            assert!(
+                (end - Vector3::new(2.0, 2.0, 1.0)).norm() < 0.01,
+                "{algorithm:?} {end:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_look_at_and_weight() {
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 1);
+        let target = make_target(&mut graph, Vector3::new(5.0, 0.0, 0.0));
+
+        solve(
+            &mut graph,
+            IkSolver::new(IkSolverKind::LookAt(LookAtIk {
+                bone: bones[0],
+                target,
+                forward_axis: Vector3::z(),
+                max_angle: 0.0,
+            })),
+        );
+        let look = graph[bones[0]].look_vector().normalize();
+        assert!((look - Vector3::x()).norm() < 0.001, "{look:?}");
+
+        // Zero weight must leave the pose untouched.
+        let mut graph = Graph::new();
+        let bones = make_chain(&mut graph, 3);
+        let target = make_target(&mut graph, Vector3::new(1.0, 1.0, 0.0));
+        solve(
+            &mut graph,
+            IkSolver::new(IkSolverKind::TwoBone(TwoBoneIk {
+                root: bones[0],
+                middle: bones[1],
+                end: bones[2],
+                target,
+                ..Default::default()
+            }))
+            .with_weight(0.0),
+        );
+        let end = graph[bones[2]].global_position();
+        assert!(
+            (end - Vector3::new(0.0, 2.0, 0.0)).norm() < 0.001,
+            "{end:?}"
+        );
+    }
+}
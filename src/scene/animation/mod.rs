@@ -21,6 +21,7 @@ use crate::{
 use std::ops::{Deref, DerefMut};
 
 pub mod absm;
+pub mod ik;
 
 /// Animation player is a node that contains multiple animations. It updates and plays all the animations.
 /// The node could be a source of animations for animation blending state machines. To learn more about
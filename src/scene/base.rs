@@ -264,6 +264,71 @@ pub enum NodeScriptMessage {
     },
 }
 
+/// A record in the list of scripts of a scene node. Scripts are stored in records, because a script is
+/// temporarily taken out of its record while the engine executes its methods.
+#[derive(Reflect, Debug, Default)]
+pub struct ScriptRecord {
+    // WARNING: Setting a new script via reflection will break normal script destruction process!
+    // Use it at your own risk only when you're completely sure what you are doing.
+    pub(crate) script: Option<Script>,
+
+    // The script is taken out of the record, because the engine executes its methods right now.
+    #[reflect(hidden)]
+    pub(crate) in_use: bool,
+
+    // The script was removed while it was in use. It will be destroyed when the engine returns it back.
+    #[reflect(hidden)]
+    pub(crate) should_be_deleted: bool,
+}
+
+uuid_provider!(ScriptRecord = "9d5e3f1c-26a5-4b7f-8b1e-3a3c4d9f0e62");
+
+impl Clone for ScriptRecord {
+    fn clone(&self) -> Self {
+        // Usage flags belong to the original record only, otherwise the engine could put a script, that was
+        // taken out of the original record, into the copy.
+        Self {
+            script: self.script.clone(),
+            in_use: false,
+            should_be_deleted: false,
+        }
+    }
+}
+
+impl ScriptRecord {
+    /// Creates new record with the given script.
+    pub fn new(script: Script) -> Self {
+        Self {
+            script: Some(script),
+            in_use: false,
+            should_be_deleted: false,
+        }
+    }
+
+    /// Returns a reference to the script of the record (if any).
+    pub fn script(&self) -> Option<&Script> {
+        self.script.as_ref()
+    }
+}
+
+impl Visit for ScriptRecord {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        // Script visiting may fail for various reasons:
+        //
+        // 1) Data inside a script is not compatible with latest code (there is no backward
+        //    compatibility for the data)
+        // 2) Script was removed in the game.
+        //
+        // None of the reasons are fatal and we should still give an ability to load such node
+        // to edit or remove it.
+        if let Err(e) = visit_opt_script(name, &mut self.script, visitor) {
+            Log::err(format!("Unable to visit script. Reason: {:?}", e))
+        }
+
+        Ok(())
+    }
+}
+
 /// Unique id of the node. It can be shared across multiple resources (read - prefabs), to preserve parent-child
 /// links. It is useful to create various resources that can bind to any instance of the node. For example, an
 /// animation resource could be made for a specific node, but with the `instance_id` it can be retargetted to any
@@ -387,14 +452,14 @@ pub struct Base {
     #[reflect(hidden)]
     pub(crate) instance_id: InstanceId,
 
-    // Current script of the scene node.
+    // Scripts of the scene node. Scripts are executed in the order of the list.
     //
     // # Important notes
     //
-    // WARNING: Setting a new script via reflection will break normal script destruction process!
+    // WARNING: Setting new scripts via reflection will break normal script destruction process!
     // Use it at your own risk only when you're completely sure what you are doing.
-    #[reflect(setter = "set_script_internal")]
-    pub(crate) script: Option<Script>,
+    #[reflect(setter = "set_scripts_internal")]
+    pub(crate) scripts: Vec<ScriptRecord>,
 
     enabled: InheritableVariable<bool>,
 
@@ -404,7 +469,7 @@ pub struct Base {
 
 impl Drop for Base {
     fn drop(&mut self) {
-        self.remove_script();
+        self.remove_all_scripts();
     }
 }
 
@@ -725,113 +790,201 @@ impl Base {
         self.instance_id
     }
 
-    fn remove_script(&mut self) {
+    fn destroy_script(&self, script: Script) {
         // Send script to the graph to destroy script instances correctly.
-        if let Some(script) = self.script.take() {
-            if let Some(sender) = self.script_message_sender.as_ref() {
-                Log::verify(sender.send(NodeScriptMessage::DestroyScript {
-                    script,
-                    handle: self.self_handle,
-                }));
+        if let Some(sender) = self.script_message_sender.as_ref() {
+            Log::verify(sender.send(NodeScriptMessage::DestroyScript {
+                script,
+                handle: self.self_handle,
+            }));
+        } else {
+            Log::warn(format!(
+                "There is a script instance on a node {}, but no message sender. \
+                The script won't be correctly destroyed!",
+                self.name(),
+            ))
+        }
+    }
+
+    /// Removes a script with the given index from the node. The script will be destroyed (its
+    /// [`ScriptTrait::on_deinit`] method will be called) at the end of the current frame. Does nothing if
+    /// the index is out of bounds.
+    #[inline]
+    pub fn remove_script(&mut self, index: usize) {
+        if let Some(record) = self.scripts.get_mut(index) {
+            if record.in_use {
+                // The script is executing right now, it will be destroyed when the engine returns it back.
+                record.should_be_deleted = true;
+            } else if let Some(script) = self.scripts.remove(index).script {
+                self.destroy_script(script);
+            }
+        }
+    }
+
+    /// Removes every script from the node. See [`Self::remove_script`] for more info.
+    #[inline]
+    pub fn remove_all_scripts(&mut self) {
+        let mut index = 0;
+        while let Some(record) = self.scripts.get_mut(index) {
+            if record.in_use {
+                record.should_be_deleted = true;
+                index += 1;
             } else {
-                Log::warn(format!(
-                    "There is a script instance on a node {}, but no message sender. \
-                    The script won't be correctly destroyed!",
-                    self.name(),
-                ))
+                self.remove_script(index);
             }
         }
     }
 
-    /// Sets new script for the scene node.
+    /// Replaces every script of the scene node with the given script (if any).
     #[inline]
     pub fn set_script(&mut self, script: Option<Script>) {
-        self.remove_script();
-        self.script = script;
+        self.remove_all_scripts();
+        if let Some(script) = script {
+            self.add_script(script);
+        }
+    }
+
+    /// Adds a new script to the end of the list of scripts of the node. The script will be initialized
+    /// on the current (or next, if the scripts are already processed) frame.
+    #[inline]
+    pub fn add_script(&mut self, script: Script) {
+        self.scripts.push(ScriptRecord::new(script));
         if let Some(sender) = self.script_message_sender.as_ref() {
-            if self.script.is_some() {
-                Log::verify(sender.send(NodeScriptMessage::InitializeScript {
-                    handle: self.self_handle,
-                }));
-            }
+            Log::verify(sender.send(NodeScriptMessage::InitializeScript {
+                handle: self.self_handle,
+            }));
         }
     }
 
-    fn set_script_internal(&mut self, script: Option<Script>) -> Option<Script> {
-        std::mem::replace(&mut self.script, script)
+    /// Moves a script from `from` index to `to` index, the scripts in between are shifted. Scripts are
+    /// executed in the order of the list, so this method could be used to change the execution order.
+    /// Does nothing if any of the indices is out of bounds.
+    #[inline]
+    pub fn move_script(&mut self, from: usize, to: usize) {
+        if from < self.scripts.len() && to < self.scripts.len() {
+            let record = self.scripts.remove(from);
+            self.scripts.insert(to, record);
+        }
+    }
+
+    fn set_scripts_internal(&mut self, scripts: Vec<ScriptRecord>) -> Vec<ScriptRecord> {
+        std::mem::replace(&mut self.scripts, scripts)
+    }
+
+    /// Returns total amount of script records of the node. Keep in mind, that some of the records could be
+    /// empty (for example, if a script was failed to load).
+    #[inline]
+    pub fn script_count(&self) -> usize {
+        self.scripts.len()
+    }
+
+    /// Returns `true` if the node has at least one script.
+    #[inline]
+    pub fn has_scripts_assigned(&self) -> bool {
+        self.scripts.iter().any(|record| record.script.is_some())
     }
 
     /// Checks if the node has a script of a particular type. Returns `false` if there is no script
-    /// at all, or if the script is not of a given type.
+    /// at all, or if there is no script of a given type.
     #[inline]
     pub fn has_script<T: ScriptTrait>(&self) -> bool {
         self.try_get_script::<T>().is_some()
     }
 
-    /// Tries to cast current script instance (if any) to given type and returns a shared reference
-    /// to it on successful cast.
+    /// Tries to find a first script of the given type and returns a shared reference to it.
     #[inline]
     pub fn try_get_script<T: ScriptTrait>(&self) -> Option<&T> {
-        self.script.as_ref().and_then(|s| s.cast::<T>())
+        self.try_get_scripts::<T>().next()
     }
 
-    /// Tries to fetch a reference to a component of the given type from the script of the node.
+    /// Returns an iterator over every script of the given type.
+    #[inline]
+    pub fn try_get_scripts<T: ScriptTrait>(&self) -> impl Iterator<Item = &T> {
+        self.scripts().filter_map(|s| s.cast::<T>())
+    }
+
+    /// Tries to fetch a reference to a component of the given type from the scripts of the node. The
+    /// scripts are checked in the order of the list, the first found component is returned.
     #[inline]
     pub fn try_get_script_component<C>(&self) -> Option<&C>
     where
         C: Any,
     {
-        self.script
-            .as_ref()
-            .and_then(|s| s.query_component_ref::<C>())
+        self.scripts().find_map(|s| s.query_component_ref::<C>())
     }
 
-    /// Tries to fetch a reference to a component of the given type from the script of the node.
+    /// Tries to fetch a reference to a component of the given type from the scripts of the node. The
+    /// scripts are checked in the order of the list, the first found component is returned.
     #[inline]
     pub fn try_get_script_component_mut<C>(&mut self) -> Option<&mut C>
     where
         C: Any,
     {
-        self.script
-            .as_mut()
-            .and_then(|s| s.query_component_mut::<C>())
+        self.scripts_mut()
+            .find_map(|s| s.query_component_mut::<C>())
     }
 
-    /// Tries to cast current script instance (if any) to given type and returns a mutable reference
-    /// to it on successful cast.
+    /// Tries to find a first script of the given type and returns a mutable reference to it.
     #[inline]
     pub fn try_get_script_mut<T: ScriptTrait>(&mut self) -> Option<&mut T> {
-        self.script.as_mut().and_then(|s| s.cast_mut::<T>())
+        self.try_get_scripts_mut::<T>().next()
     }
 
-    /// Returns shared reference to current script instance.
+    /// Returns an iterator over every script of the given type.
     #[inline]
-    pub fn script(&self) -> Option<&Script> {
-        self.script.as_ref()
+    pub fn try_get_scripts_mut<T: ScriptTrait>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.scripts_mut().filter_map(|s| s.cast_mut::<T>())
+    }
+
+    /// Returns shared reference to a script instance with the given index.
+    #[inline]
+    pub fn script(&self, index: usize) -> Option<&Script> {
+        self.scripts
+            .get(index)
+            .and_then(|record| record.script.as_ref())
     }
 
-    /// Returns mutable reference to current script instance.
+    /// Returns an iterator over every script instance of the node.
+    #[inline]
+    pub fn scripts(&self) -> impl Iterator<Item = &Script> {
+        self.scripts
+            .iter()
+            .filter_map(|record| record.script.as_ref())
+    }
+
+    /// Returns mutable reference to a script instance with the given index.
     ///
     /// # Important notes
     ///
     /// Do **not** replace script instance using mutable reference given to you by this method.
-    /// This will prevent correct script de-initialization! Use `Self::set_script` if you need
-    /// to replace the script.
+    /// This will prevent correct script de-initialization! Use [`Self::remove_script`] and
+    /// [`Self::add_script`] if you need to replace the script.
+    #[inline]
+    pub fn script_mut(&mut self, index: usize) -> Option<&mut Script> {
+        self.scripts
+            .get_mut(index)
+            .and_then(|record| record.script.as_mut())
+    }
+
+    /// Returns an iterator over every script instance of the node. See [`Self::script_mut`] for
+    /// important notes.
     #[inline]
-    pub fn script_mut(&mut self) -> Option<&mut Script> {
-        self.script.as_mut()
+    pub fn scripts_mut(&mut self) -> impl Iterator<Item = &mut Script> {
+        self.scripts
+            .iter_mut()
+            .filter_map(|record| record.script.as_mut())
     }
 
-    /// Returns a copy of the current script.
+    /// Returns a copy of a script with the given index.
     #[inline]
-    pub fn script_cloned(&self) -> Option<Script> {
-        self.script.clone()
+    pub fn script_cloned(&self, index: usize) -> Option<Script> {
+        self.script(index).cloned()
     }
 
     /// Internal. Do not use.
     #[inline]
-    pub fn script_inner(&mut self) -> &mut Option<Script> {
-        &mut self.script
+    pub fn scripts_inner(&mut self) -> &mut Vec<ScriptRecord> {
+        &mut self.scripts
     }
 
     /// Enables or disables scene node. Disabled scene nodes won't be updated (including scripts) or rendered.
@@ -963,20 +1116,19 @@ impl Visit for Base {
         let _ = self.instance_id.visit("InstanceId", &mut region);
         let _ = self.enabled.visit("Enabled", &mut region);
 
-        // Script visiting may fail for various reasons:
-        //
-        // 1) Data inside a script is not compatible with latest code (there is no backward
-        //    compatibility for the data)
-        // 2) Script was removed in the game.
-        //
-        // None of the reasons are fatal and we should still give an ability to load such node
-        // to edit or remove it.
-        if let Err(e) = visit_opt_script("Script", &mut self.script, &mut region) {
-            // Do not spam with error messages if there is missing `Script` field. It is ok
-            // for old scenes not to have script at all.
-            if !matches!(e, VisitError::RegionDoesNotExist(_)) {
-                Log::err(format!("Unable to visit script. Reason: {:?}", e))
+        if region.is_reading() && !region.has_region("Scripts") {
+            // Nodes had at most one script previously, so we must maintain backward compatibility
+            // here. It is ok for old scenes not to have script at all.
+            self.scripts.clear();
+            if region.has_region("Script") {
+                let mut record = ScriptRecord::default();
+                record.visit("Script", &mut region)?;
+                if record.script.is_some() {
+                    self.scripts.push(record);
+                }
             }
+        } else {
+            self.scripts.visit("Scripts", &mut region)?;
         }
 
         Ok(())
@@ -997,7 +1149,7 @@ pub struct BaseBuilder {
     tag: String,
     frustum_culling: bool,
     cast_shadows: bool,
-    script: Option<Script>,
+    scripts: Vec<ScriptRecord>,
     instance_id: InstanceId,
     enabled: bool,
 }
@@ -1025,7 +1177,7 @@ impl BaseBuilder {
             tag: Default::default(),
             frustum_culling: true,
             cast_shadows: true,
-            script: None,
+            scripts: Default::default(),
             instance_id: InstanceId(Uuid::new_v4()),
             enabled: true,
         }
@@ -1128,10 +1280,11 @@ impl BaseBuilder {
         self
     }
 
-    /// Sets desired script of the node.
+    /// Adds a script to the list of scripts of the node. Could be called multiple times to add more
+    /// scripts, the scripts will be executed in the order they were added.
     #[inline]
     pub fn with_script(mut self, script: Script) -> Self {
-        self.script = Some(script);
+        self.scripts.push(ScriptRecord::new(script));
         self
     }
 
@@ -1167,7 +1320,7 @@ impl BaseBuilder {
             transform_modified: Cell::new(false),
             frustum_culling: self.frustum_culling.into(),
             cast_shadows: self.cast_shadows.into(),
-            script: self.script,
+            scripts: self.scripts,
             instance_id: InstanceId(Uuid::new_v4()),
             enabled: self.enabled.into(),
             global_enabled: Cell::new(true),
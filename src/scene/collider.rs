@@ -484,6 +484,16 @@ pub struct Collider {
     #[reflect(setter = "set_restitution_combine_rule")]
     pub(crate) restitution_combine_rule: InheritableVariable<CoefficientCombineRule>,
 
+    #[visit(optional)]
+    #[reflect(
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05,
+        setter = "set_sound_occlusion",
+        description = "Defines how much the collider blocks sounds, 0.0 - does not block at all, 1.0 - blocks completely."
+    )]
+    pub(crate) sound_occlusion: InheritableVariable<f32>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     pub(crate) native: Cell<ColliderHandle>,
@@ -502,6 +512,7 @@ impl Default for Collider {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            sound_occlusion: InheritableVariable::new_modified(1.0),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -534,6 +545,7 @@ impl Clone for Collider {
             solver_groups: self.solver_groups.clone(),
             friction_combine_rule: self.friction_combine_rule.clone(),
             restitution_combine_rule: self.restitution_combine_rule.clone(),
+            sound_occlusion: self.sound_occlusion.clone(),
             // Do not copy. The copy will have its own native representation (for example - Rapier's collider)
             native: Cell::new(ColliderHandle::invalid()),
         }
@@ -735,6 +747,18 @@ impl Collider {
         physics.contacts_with(self.native.get())
     }
 
+    /// Sets how much the collider blocks sounds (see [`crate::scene::sound::Sound::set_occlusion_enabled`]).
+    /// 0.0 - does not block sounds at all, 1.0 - blocks sounds completely. Default is 1.0.
+    pub fn set_sound_occlusion(&mut self, sound_occlusion: f32) -> f32 {
+        self.sound_occlusion
+            .set_value_and_mark_modified(sound_occlusion.clamp(0.0, 1.0))
+    }
+
+    /// Returns how much the collider blocks sounds.
+    pub fn sound_occlusion(&self) -> f32 {
+        *self.sound_occlusion
+    }
+
     /// Returns an iterator that yields intersection information for the collider.
     /// Intersections checks between regular colliders and sensor colliders
     pub fn intersects<'a>(
@@ -818,6 +842,7 @@ pub struct ColliderBuilder {
     solver_groups: InteractionGroups,
     friction_combine_rule: CoefficientCombineRule,
     restitution_combine_rule: CoefficientCombineRule,
+    sound_occlusion: f32,
 }
 
 impl ColliderBuilder {
@@ -834,6 +859,7 @@ impl ColliderBuilder {
             solver_groups: Default::default(),
             friction_combine_rule: Default::default(),
             restitution_combine_rule: Default::default(),
+            sound_occlusion: 1.0,
         }
     }
 
@@ -891,6 +917,12 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets desired sound occlusion factor. See [`Collider::set_sound_occlusion`] for more info.
+    pub fn with_sound_occlusion(mut self, sound_occlusion: f32) -> Self {
+        self.sound_occlusion = sound_occlusion.clamp(0.0, 1.0);
+        self
+    }
+
     /// Creates collider node, but does not add it to a graph.
     pub fn build_collider(self) -> Collider {
         Collider {
@@ -904,6 +936,7 @@ impl ColliderBuilder {
             solver_groups: self.solver_groups.into(),
             friction_combine_rule: self.friction_combine_rule.into(),
             restitution_combine_rule: self.restitution_combine_rule.into(),
+            sound_occlusion: self.sound_occlusion.into(),
             native: Cell::new(ColliderHandle::invalid()),
         }
     }
@@ -921,12 +954,18 @@ impl ColliderBuilder {
 
 #[cfg(test)]
 mod test {
-    use crate::core::algebra::Vector2;
+    use crate::core::{
+        algebra::{Point3, UnitQuaternion, Vector2, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+    };
     use crate::scene::{
         base::BaseBuilder,
-        collider::{ColliderBuilder, ColliderShape},
-        graph::Graph,
+        collider::{BitMask, ColliderBuilder, ColliderShape, InteractionGroups},
+        graph::{physics::ShapeCastOptions, Graph},
+        node::Node,
         rigidbody::{RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
     };
 
     #[test]
@@ -990,4 +1029,98 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_physics_queries() {
+        let mut graph = Graph::new();
+
+        let mut create_cube = |position: Vector3<f32>| {
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+                .build(&mut graph);
+
+            RigidBodyBuilder::new(
+                BaseBuilder::new()
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(position)
+                            .build(),
+                    )
+                    .with_children(&[collider]),
+            )
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+            collider
+        };
+
+        let near = create_cube(Vector3::new(0.0, 0.0, 5.0));
+        let far = create_cube(Vector3::new(0.0, 0.0, 10.0));
+
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        let result = graph
+            .physics
+            .cast_shape(ShapeCastOptions {
+                shape: ColliderShape::ball(0.5),
+                shape_position: Point3::origin(),
+                shape_rotation: UnitQuaternion::identity(),
+                direction: Vector3::new(0.0, 0.0, 2.0),
+                max_distance: 100.0,
+                groups: InteractionGroups::default(),
+                stop_at_penetration: true,
+            })
+            .unwrap();
+        assert_eq!(result.collider, near);
+        assert!((result.toi - 4.0).abs() < 0.001);
+        assert!((result.collider_witness.z - 4.5).abs() < 0.001);
+        assert!((result.shape_witness.z - 4.5).abs() < 0.001);
+        assert!((result.collider_normal.z + 1.0).abs() < 0.001);
+        assert!((result.shape_normal.z - 1.0).abs() < 0.001);
+
+        let projection = graph
+            .physics
+            .project_point(Point3::new(0.0, 0.0, 12.0), true, Default::default())
+            .unwrap();
+        assert_eq!(projection.collider, far);
+        assert!(!projection.is_inside);
+        assert!((projection.point.z - 10.5).abs() < 0.001);
+
+        let mut buffer = Vec::<Handle<Node>>::new();
+        graph.physics.intersections_with_point(
+            Point3::new(0.0, 0.0, 5.2),
+            Default::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![near]);
+
+        graph.physics.intersections_with_shape(
+            &ColliderShape::cuboid(0.5, 0.5, 3.0),
+            Point3::new(0.0, 0.0, 7.5),
+            UnitQuaternion::identity(),
+            Default::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer.len(), 2);
+        assert!(buffer.contains(&near) && buffer.contains(&far));
+
+        graph.physics.intersections_with_aabb(
+            &AxisAlignedBoundingBox::from_min_max(
+                Vector3::new(-1.0, -1.0, 9.0),
+                Vector3::new(1.0, 1.0, 20.0),
+            ),
+            Default::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![far]);
+
+        // Colliders that are filtered out by interaction groups must be ignored.
+        graph.physics.intersections_with_point(
+            Point3::new(0.0, 0.0, 5.2),
+            InteractionGroups::new(BitMask(u32::MAX), BitMask(0)),
+            &mut buffer,
+        );
+        assert!(buffer.is_empty());
+    }
 }
@@ -0,0 +1,502 @@
+//! Scene deltas allow you to save only the changes, that were made to a scene after it was loaded from its
+//! source file. It is the foundation for compact save files, see [`Scene::save_delta`] and
+//! [`Scene::load_delta`] docs for more info.
+
+use crate::{
+    asset::{manager::ResourceManager, untyped::UntypedResource},
+    core::{
+        log::Log,
+        pool::Handle,
+        reflect::Reflect,
+        variable::{
+            self, do_with_inheritable_variables, mark_inheritable_properties_modified_since,
+            VariableFlags,
+        },
+        visitor::{BinaryBlob, Visit, VisitError, VisitResult, Visitor},
+    },
+    engine::SerializationContext,
+    scene::{
+        node::{
+            container::{read_node, write_node},
+            Node,
+        },
+        Scene,
+    },
+};
+use fxhash::FxHashSet;
+use std::{any::TypeId, sync::Arc};
+
+const DELTA_VERSION: u32 = 1;
+
+fn ignored_types() -> [TypeId; 1] {
+    [TypeId::of::<UntypedResource>()]
+}
+
+fn collect_flags(node: &mut Node) -> Vec<u8> {
+    let mut flags = Vec::new();
+    do_with_inheritable_variables(
+        node as &mut dyn Reflect,
+        &mut |variable| flags.push(variable.flags().bits()),
+        &ignored_types(),
+    );
+    flags
+}
+
+// Returns `false` if the node has different amount of variables than the given flags.
+fn apply_flags(node: &mut Node, flags: &[u8], extra: VariableFlags) -> bool {
+    let mut count = 0;
+    do_with_inheritable_variables(
+        node as &mut dyn Reflect,
+        &mut |_| count += 1,
+        &ignored_types(),
+    );
+    if count != flags.len() {
+        return false;
+    }
+
+    let mut flags = flags.iter();
+    do_with_inheritable_variables(
+        node as &mut dyn Reflect,
+        &mut |variable| {
+            if let Some(flags) = flags.next() {
+                variable.set_flags(VariableFlags::from_bits_retain(*flags) | extra);
+            }
+        },
+        &ignored_types(),
+    );
+    true
+}
+
+fn mark_all_modified(node: &mut Node) {
+    variable::mark_inheritable_properties_modified(node as &mut dyn Reflect, &ignored_types());
+}
+
+fn write_to_vec(node: &mut Node) -> Result<Vec<u8>, VisitError> {
+    let mut visitor = Visitor::new();
+    node.visit("Node", &mut visitor)?;
+    visitor.save_binary_to_vec()
+}
+
+fn reading_visitor(
+    data: &[u8],
+    serialization_context: &Arc<SerializationContext>,
+    resource_manager: &ResourceManager,
+) -> Result<Visitor, VisitError> {
+    let mut visitor = Visitor::load_from_memory(data)?;
+    visitor.blackboard.register(serialization_context.clone());
+    visitor
+        .blackboard
+        .register(Arc::new(resource_manager.clone()));
+    Ok(visitor)
+}
+
+// Serialized state of a node. `data` contains either full state of the node or only the changes relative to the
+// original node. `flags` contains the actual flags of the inheritable variables of the node, since the flags are
+// used to filter out unchanged variables.
+#[derive(Default)]
+struct NodeState {
+    handle: Handle<Node>,
+    data: Vec<u8>,
+    flags: Vec<u8>,
+}
+
+impl Visit for NodeState {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+        self.handle.visit("Handle", &mut region)?;
+        BinaryBlob {
+            vec: &mut self.data,
+        }
+        .visit("Data", &mut region)?;
+        BinaryBlob {
+            vec: &mut self.flags,
+        }
+        .visit("Flags", &mut region)?;
+        Ok(())
+    }
+}
+
+// Calculates a delta between the node and its original version. Returns `None` if the node is unchanged. The
+// delta is verified by applying it to a copy of the original node, full state of the node is saved if the delta
+// cannot restore the node (it could happen if some inheritable variables are stored in collections, for example).
+fn node_delta(
+    node: &mut Node,
+    original: &Node,
+    serialization_context: &Arc<SerializationContext>,
+    resource_manager: &ResourceManager,
+) -> Result<Option<NodeState>, VisitError> {
+    let flags = collect_flags(node);
+
+    mark_all_modified(node);
+    let full = write_to_vec(node)?;
+
+    let mut original_copy = original.clone_box();
+    mark_all_modified(&mut original_copy);
+    if write_to_vec(&mut original_copy)? == full {
+        apply_flags(node, &flags, VariableFlags::NONE);
+        return Ok(None);
+    }
+
+    mark_inheritable_properties_modified_since(
+        node as &mut dyn Reflect,
+        original as &dyn Reflect,
+        &ignored_types(),
+    );
+    let delta = write_to_vec(node);
+    apply_flags(node, &flags, VariableFlags::NONE);
+    let delta = delta?;
+
+    let mut restored = original.clone_box();
+    let is_complete = match reading_visitor(&delta, serialization_context, resource_manager)
+        .and_then(|mut visitor| restored.visit("Node", &mut visitor))
+    {
+        Ok(_) => {
+            mark_all_modified(&mut restored);
+            write_to_vec(&mut restored)? != full
+        }
+        Err(_) => true,
+    };
+
+    Ok(Some(NodeState {
+        handle: Default::default(),
+        data: if is_complete { full } else { delta },
+        flags,
+    }))
+}
+
+impl Scene {
+    /// Saves only the changes, that were made to the scene since it was in the `original` state. Typically, the
+    /// original scene is the scene loaded from the same file as the current scene. The delta contains:
+    ///
+    /// - Changed properties of existing nodes. Changes are detected by comparing values of inheritable variables
+    ///   (see [`variable::InheritableVariable`]) with the values from the original scene, only modified variables
+    ///   are written. Nodes without any changes are not written at all.
+    /// - Full state of nodes, that were spawned after the scene was loaded.
+    /// - Handles of nodes, that were destroyed after the scene was loaded.
+    ///
+    /// Nodes are matched by their handles and instance ids, so handles of the nodes are preserved and every
+    /// `Handle<Node>` stored in the nodes or their scripts stays valid after the delta is loaded back using
+    /// [`Self::load_delta`].
+    pub fn save_delta(
+        &mut self,
+        original: &Scene,
+        region_name: &str,
+        serialization_context: Arc<SerializationContext>,
+        resource_manager: ResourceManager,
+        visitor: &mut Visitor,
+    ) -> VisitResult {
+        if visitor.is_reading() {
+            return Err(VisitError::User(
+                "Visitor must be in write mode!".to_string(),
+            ));
+        }
+
+        let mut destroyed = Vec::new();
+        for (handle, original_node) in original.graph.pair_iter() {
+            if self
+                .graph
+                .try_get(handle)
+                .map_or(true, |node| node.instance_id != original_node.instance_id)
+            {
+                destroyed.push(handle);
+            }
+        }
+
+        let mut spawned = Vec::new();
+        let mut changed = Vec::new();
+        for (handle, node) in self.graph.pair_iter_mut() {
+            match original.graph.try_get(handle) {
+                Some(original_node)
+                    if original_node.instance_id == node.instance_id
+                        && original_node.id() == node.id() =>
+                {
+                    if let Some(mut state) = node_delta(
+                        node,
+                        original_node,
+                        &serialization_context,
+                        &resource_manager,
+                    )? {
+                        state.handle = handle;
+                        changed.push(state);
+                    }
+                }
+                _ => {
+                    let flags = collect_flags(node);
+                    mark_all_modified(node);
+                    let mut node_visitor = Visitor::new();
+                    let result = write_node("Node", node, &mut node_visitor);
+                    apply_flags(node, &flags, VariableFlags::NONE);
+                    result?;
+                    spawned.push(NodeState {
+                        handle,
+                        data: node_visitor.save_binary_to_vec()?,
+                        flags,
+                    });
+                }
+            }
+        }
+
+        let mut region = visitor.enter_region(region_name)?;
+        region.visit_version(DELTA_VERSION)?;
+        self.enabled.visit("Enabled", &mut region)?;
+        self.rendering_options
+            .visit("RenderingOptions", &mut region)?;
+        destroyed.visit("DestroyedNodes", &mut region)?;
+        spawned.visit("SpawnedNodes", &mut region)?;
+        changed.visit("ChangedNodes", &mut region)?;
+
+        Ok(())
+    }
+
+    /// Applies a delta, that was saved by [`Self::save_delta`], to the scene. The scene must be in the same state
+    /// as the original scene, that was used to save the delta, typically it should be just loaded from the same
+    /// file. Nodes are restored at their previous handles.
+    pub fn load_delta(
+        &mut self,
+        region_name: &str,
+        serialization_context: Arc<SerializationContext>,
+        resource_manager: ResourceManager,
+        visitor: &mut Visitor,
+    ) -> VisitResult {
+        if !visitor.is_reading() {
+            return Err(VisitError::User(
+                "Visitor must be in read mode!".to_string(),
+            ));
+        }
+
+        let mut region = visitor.enter_region(region_name)?;
+        let version = region.visit_version(DELTA_VERSION)?;
+        if version > DELTA_VERSION {
+            return Err(VisitError::User(format!(
+                "Unsupported scene delta version {version}!"
+            )));
+        }
+
+        let mut destroyed = Vec::<Handle<Node>>::new();
+        let mut spawned = Vec::<NodeState>::new();
+        let mut changed = Vec::<NodeState>::new();
+        self.enabled.visit("Enabled", &mut region)?;
+        self.rendering_options
+            .visit("RenderingOptions", &mut region)?;
+        destroyed.visit("DestroyedNodes", &mut region)?;
+        spawned.visit("SpawnedNodes", &mut region)?;
+        changed.visit("ChangedNodes", &mut region)?;
+        drop(region);
+
+        // Detach surviving children first, otherwise they will be destroyed together with their parents. The
+        // children are not attached to any other node, because correct hierarchy is restored from the changed nodes
+        // (a surviving child was moved to another node, so the child and its new parent are both in the delta).
+        let destroyed_set = destroyed.iter().cloned().collect::<FxHashSet<_>>();
+        for &handle in destroyed.iter() {
+            let Some(node) = self.graph.try_get_mut(handle) else {
+                continue;
+            };
+            let survivors = node
+                .children
+                .iter()
+                .filter(|child| !destroyed_set.contains(child))
+                .cloned()
+                .collect::<Vec<_>>();
+            node.children.retain(|child| destroyed_set.contains(child));
+            for child in survivors {
+                if let Some(child) = self.graph.try_get_mut(child) {
+                    child.parent = Handle::NONE;
+                }
+            }
+        }
+        for handle in destroyed {
+            if self.graph.is_valid_handle(handle) {
+                self.graph.remove_node(handle);
+            }
+        }
+
+        for state in spawned {
+            let mut node_visitor =
+                reading_visitor(&state.data, &serialization_context, &resource_manager)?;
+            let mut node = read_node("Node", &mut node_visitor)?;
+            if !apply_flags(&mut node, &state.flags, VariableFlags::NEED_SYNC) {
+                mark_all_modified(&mut node);
+            }
+            if self.graph.add_node_at_handle(state.handle, node).is_err() {
+                Log::err(format!(
+                    "Unable to restore spawned node at {}, because the handle is occupied!",
+                    state.handle
+                ));
+            }
+        }
+
+        for state in changed {
+            let Some(node) = self.graph.try_get_mut(state.handle) else {
+                Log::err(format!(
+                    "Unable to restore changed node {}, because it does not exist!",
+                    state.handle
+                ));
+                continue;
+            };
+
+            let mut node_visitor =
+                reading_visitor(&state.data, &serialization_context, &resource_manager)?;
+            node.visit("Node", &mut node_visitor)?;
+            if !apply_flags(node, &state.flags, VariableFlags::NEED_SYNC) {
+                mark_all_modified(node);
+            }
+        }
+
+        self.graph.update_hierarchical_data();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{algebra::Vector3, futures::executor::block_on, visitor::Visitor},
+        engine::SerializationContext,
+        scene::{
+            base::BaseBuilder, pivot::PivotBuilder, transform::TransformBuilder, Scene, SceneLoader,
+        },
+    };
+    use std::sync::Arc;
+
+    fn load_scene(
+        data: &[u8],
+        serialization_context: &Arc<SerializationContext>,
+        resource_manager: &ResourceManager,
+    ) -> Scene {
+        let mut visitor = Visitor::load_from_memory(data).unwrap();
+        let loader = SceneLoader::load(
+            "Scene",
+            serialization_context.clone(),
+            resource_manager.clone(),
+            &mut visitor,
+            None,
+        )
+        .unwrap();
+        block_on(loader.finish(resource_manager))
+    }
+
+    #[test]
+    fn test_scene_delta() {
+        let serialization_context = Arc::new(SerializationContext::new());
+        let resource_manager = ResourceManager::new();
+
+        let mut scene = Scene::new();
+        let a = PivotBuilder::new(
+            BaseBuilder::new().with_name("A").with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(1.0, 2.0, 3.0))
+                    .build(),
+            ),
+        )
+        .build(&mut scene.graph);
+        let b = PivotBuilder::new(BaseBuilder::new().with_name("B")).build(&mut scene.graph);
+        let c = PivotBuilder::new(BaseBuilder::new().with_name("C")).build(&mut scene.graph);
+        scene.graph.link_nodes(c, b);
+
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let original = load_scene(&data, &serialization_context, &resource_manager);
+        let mut scene = load_scene(&data, &serialization_context, &resource_manager);
+
+        // Move a node, destroy a node and spawn a new one at the same place in the pool.
+        scene.graph[a]
+            .local_transform_mut()
+            .set_position(Vector3::new(3.0, 2.0, 1.0));
+        scene.graph.remove_node(c);
+        let d = PivotBuilder::new(BaseBuilder::new().with_name("D")).build(&mut scene.graph);
+        scene.graph.link_nodes(d, b);
+        assert_eq!(d.index(), c.index());
+
+        let mut visitor = Visitor::new();
+        scene
+            .save_delta(
+                &original,
+                "Delta",
+                serialization_context.clone(),
+                resource_manager.clone(),
+                &mut visitor,
+            )
+            .unwrap();
+        let delta = visitor.save_binary_to_vec().unwrap();
+
+        let mut restored = load_scene(&data, &serialization_context, &resource_manager);
+        let mut visitor = Visitor::load_from_memory(&delta).unwrap();
+        restored
+            .load_delta(
+                "Delta",
+                serialization_context,
+                resource_manager,
+                &mut visitor,
+            )
+            .unwrap();
+
+        assert_eq!(
+            **restored.graph[a].local_transform().position(),
+            Vector3::new(3.0, 2.0, 1.0)
+        );
+        assert!(restored.graph.try_get(c).is_none());
+        assert_eq!(restored.graph[d].name(), "D");
+        assert_eq!(restored.graph[d].parent(), b);
+        assert_eq!(restored.graph[b].children(), &[d]);
+        assert_eq!(restored.graph.node_count(), scene.graph.node_count());
+    }
+
+    #[test]
+    fn test_scene_delta_keeps_hierarchy_of_surviving_children() {
+        let serialization_context = Arc::new(SerializationContext::new());
+        let resource_manager = ResourceManager::new();
+
+        let mut scene = Scene::new();
+        let a = PivotBuilder::new(BaseBuilder::new().with_name("A")).build(&mut scene.graph);
+        let b = PivotBuilder::new(BaseBuilder::new().with_name("B")).build(&mut scene.graph);
+        let c = PivotBuilder::new(BaseBuilder::new().with_name("C")).build(&mut scene.graph);
+        let d = PivotBuilder::new(BaseBuilder::new().with_name("D")).build(&mut scene.graph);
+        scene.graph.link_nodes(c, b);
+        scene.graph.link_nodes(d, c);
+
+        let mut visitor = Visitor::new();
+        scene.save("Scene", &mut visitor).unwrap();
+        let data = visitor.save_binary_to_vec().unwrap();
+
+        let original = load_scene(&data, &serialization_context, &resource_manager);
+        let mut scene = load_scene(&data, &serialization_context, &resource_manager);
+
+        // Move a child of a node to another node and destroy the node. The root is left unchanged.
+        scene.graph.link_nodes(d, a);
+        scene.graph.remove_node(c);
+
+        let mut visitor = Visitor::new();
+        scene
+            .save_delta(
+                &original,
+                "Delta",
+                serialization_context.clone(),
+                resource_manager.clone(),
+                &mut visitor,
+            )
+            .unwrap();
+        let delta = visitor.save_binary_to_vec().unwrap();
+
+        let mut restored = load_scene(&data, &serialization_context, &resource_manager);
+        let mut visitor = Visitor::load_from_memory(&delta).unwrap();
+        restored
+            .load_delta(
+                "Delta",
+                serialization_context,
+                resource_manager,
+                &mut visitor,
+            )
+            .unwrap();
+
+        let root = restored.graph.get_root();
+        assert!(restored.graph.try_get(c).is_none());
+        assert_eq!(restored.graph[d].parent(), a);
+        assert_eq!(restored.graph[a].children(), &[d]);
+        assert!(restored.graph[b].children().is_empty());
+        assert_eq!(restored.graph[root].children(), &[a, b]);
+    }
+}
@@ -0,0 +1,555 @@
+//! 2D character controller is a kinematic entity that moves a shape through the physics world and
+//! resolves collisions of the shape with its environment. See [`CharacterController`] docs for
+//! more info.
+
+use crate::{
+    core::{
+        algebra::{Point2, Vector2, Vector3},
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::{Base, BaseBuilder},
+        character_controller::{
+            collect_descendants, world_to_local_translation, CharacterAutostep,
+        },
+        collider::InteractionGroups,
+        dim2::collider::ColliderShape,
+        graph::Graph,
+        node::{Node, NodeTrait, UpdateContext},
+        Scene,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// A collision between a character and its environment that has happened during the last movement
+/// of the character.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CharacterCollision {
+    /// A handle of the collider hit by the character.
+    pub collider: Handle<Node>,
+    /// Position of the character (in world coordinates) at the moment of the hit.
+    pub character_position: Vector2<f32>,
+    /// The translation that was already applied to the character when the hit happened.
+    pub translation_applied: Vector2<f32>,
+    /// The translation that was still waiting to be applied to the character when the hit happened.
+    pub translation_remaining: Vector2<f32>,
+    /// Distance traveled by the character shape before the hit.
+    pub toi: f32,
+    /// The closest point on the collider at the time of impact, in world coordinates.
+    pub collider_witness: Point2<f32>,
+    /// Outward normal of the collider at the time of impact, in world coordinates.
+    pub collider_normal: Vector2<f32>,
+}
+
+/// 2D character controller is a kinematic entity that moves a shape (usually a capsule) through the
+/// 2D physics world. Unlike rigid bodies, it does not obey the laws of physics, instead it moves
+/// exactly by the desired velocity, but its movement is corrected so the shape does not penetrate
+/// surrounding colliders. It handles sliding along walls, climbing slopes up to a certain angle,
+/// stepping over small obstacles (stairs), snapping to the ground when moving down the slopes or
+/// stairs and (optionally) pushing dynamic rigid bodies.
+///
+/// # Movement
+///
+/// Set desired velocity using [`CharacterController::set_desired_velocity`] and the controller
+/// will move itself on the next update of the graph. Note that the controller does not apply
+/// gravity, so add it to the desired velocity if needed. Ground snapping works only when the
+/// character moves down, so it is a good idea to always have small downward velocity when the
+/// character is grounded.
+///
+/// After each movement, the controller stores the list of collisions that happened during the
+/// movement and the grounded state. Use [`CharacterController::collisions`] and
+/// [`CharacterController::is_grounded`] to fetch them, for example in a script.
+///
+/// # Collider
+///
+/// The controller does not create any colliders, which means that other bodies and ray casts
+/// won't detect the character. If you need this, add a kinematic rigid body with a collider as a
+/// child node of the controller. Colliders of all descendant nodes of the controller are ignored
+/// when the controller moves.
+///
+/// # Shapes
+///
+/// Trimesh and heightfield shapes are not supported and will not move the character at all.
+#[derive(Clone, Reflect, Visit, Debug)]
+pub struct CharacterController {
+    base: Base,
+
+    #[reflect(setter = "set_shape")]
+    shape: InheritableVariable<ColliderShape>,
+
+    #[reflect(min_value = 0.001, step = 0.005, setter = "set_offset")]
+    offset: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_slide")]
+    slide: InheritableVariable<bool>,
+
+    #[reflect(setter = "set_autostep")]
+    autostep: InheritableVariable<Option<CharacterAutostep>>,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 3.15,
+        step = 0.05,
+        setter = "set_max_slope_climb_angle"
+    )]
+    max_slope_climb_angle: InheritableVariable<f32>,
+
+    #[reflect(
+        min_value = 0.0,
+        max_value = 3.15,
+        step = 0.05,
+        setter = "set_min_slope_slide_angle"
+    )]
+    min_slope_slide_angle: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_snap_to_ground")]
+    snap_to_ground: InheritableVariable<Option<f32>>,
+
+    #[reflect(setter = "set_push_dynamic_bodies")]
+    push_dynamic_bodies: InheritableVariable<bool>,
+
+    #[reflect(min_value = 0.0, step = 0.1, setter = "set_mass")]
+    mass: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_collision_groups")]
+    collision_groups: InheritableVariable<InteractionGroups>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    desired_velocity: Vector2<f32>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collisions: Vec<CharacterCollision>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    is_grounded: bool,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            base: Default::default(),
+            shape: InheritableVariable::new_modified(ColliderShape::capsule_y(0.5, 0.3)),
+            offset: InheritableVariable::new_modified(0.01),
+            slide: InheritableVariable::new_modified(true),
+            autostep: InheritableVariable::new_modified(Some(Default::default())),
+            max_slope_climb_angle: InheritableVariable::new_modified(45.0f32.to_radians()),
+            min_slope_slide_angle: InheritableVariable::new_modified(30.0f32.to_radians()),
+            snap_to_ground: InheritableVariable::new_modified(Some(0.2)),
+            push_dynamic_bodies: InheritableVariable::new_modified(true),
+            mass: InheritableVariable::new_modified(1.0),
+            collision_groups: Default::default(),
+            desired_velocity: Default::default(),
+            collisions: Default::default(),
+            is_grounded: false,
+        }
+    }
+}
+
+impl Deref for CharacterController {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for CharacterController {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl TypeUuidProvider for CharacterController {
+    fn type_uuid() -> Uuid {
+        uuid!("9a2b7f31-5c64-4d0e-8f1a-3e6d2c9b4a70")
+    }
+}
+
+impl CharacterController {
+    /// Sets the new shape of the character. Trimesh and heightfield shapes are not supported, see
+    /// [`CharacterController`] docs for more info.
+    pub fn set_shape(&mut self, shape: ColliderShape) -> ColliderShape {
+        self.shape.set_value_and_mark_modified(shape)
+    }
+
+    /// Returns shared reference to the shape of the character.
+    pub fn shape(&self) -> &ColliderShape {
+        &self.shape
+    }
+
+    /// Sets a small gap to preserve between the character and its surroundings. This value should
+    /// not be too large to avoid visual artifacts, but shouldn't be too small (must not be zero) to
+    /// improve numerical stability of the controller.
+    pub fn set_offset(&mut self, offset: f32) -> f32 {
+        self.offset.set_value_and_mark_modified(offset)
+    }
+
+    /// Returns current gap between the character and its surroundings.
+    pub fn offset(&self) -> f32 {
+        *self.offset
+    }
+
+    /// Defines whether the character should slide along obstacles (walls, floor, etc.) or stop
+    /// when it hits them.
+    pub fn set_slide(&mut self, slide: bool) -> bool {
+        self.slide.set_value_and_mark_modified(slide)
+    }
+
+    /// Returns true if the character slides along obstacles, false - otherwise.
+    pub fn is_slide(&self) -> bool {
+        *self.slide
+    }
+
+    /// Sets auto-stepping options, [`None`] disables auto-stepping.
+    pub fn set_autostep(
+        &mut self,
+        autostep: Option<CharacterAutostep>,
+    ) -> Option<CharacterAutostep> {
+        self.autostep.set_value_and_mark_modified(autostep)
+    }
+
+    /// Returns current auto-stepping options.
+    pub fn autostep(&self) -> Option<&CharacterAutostep> {
+        self.autostep.as_ref()
+    }
+
+    /// Sets the maximum angle (in radians) between the floor's normal and the up vector that the
+    /// character is able to climb.
+    pub fn set_max_slope_climb_angle(&mut self, angle: f32) -> f32 {
+        self.max_slope_climb_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the maximum angle (in radians) of slopes that the character is able to climb.
+    pub fn max_slope_climb_angle(&self) -> f32 {
+        *self.max_slope_climb_angle
+    }
+
+    /// Sets the minimum angle (in radians) between the floor's normal and the up vector before the
+    /// character starts to slide down automatically.
+    pub fn set_min_slope_slide_angle(&mut self, angle: f32) -> f32 {
+        self.min_slope_slide_angle
+            .set_value_and_mark_modified(angle)
+    }
+
+    /// Returns the minimum angle (in radians) of slopes at which the character starts to slide down.
+    pub fn min_slope_slide_angle(&self) -> f32 {
+        *self.min_slope_slide_angle
+    }
+
+    /// Sets the maximum distance to the ground at which the character will be snapped to the ground,
+    /// [`None`] disables ground snapping.
+    pub fn set_snap_to_ground(&mut self, distance: Option<f32>) -> Option<f32> {
+        self.snap_to_ground.set_value_and_mark_modified(distance)
+    }
+
+    /// Returns the maximum distance to the ground at which the character will be snapped to the ground.
+    pub fn snap_to_ground(&self) -> Option<f32> {
+        *self.snap_to_ground
+    }
+
+    /// Defines whether the character should push dynamic rigid bodies it collides with.
+    pub fn set_push_dynamic_bodies(&mut self, push: bool) -> bool {
+        self.push_dynamic_bodies.set_value_and_mark_modified(push)
+    }
+
+    /// Returns true if the character pushes dynamic rigid bodies, false - otherwise.
+    pub fn is_push_dynamic_bodies(&self) -> bool {
+        *self.push_dynamic_bodies
+    }
+
+    /// Sets the mass of the character. It is used only to calculate impulses applied to dynamic
+    /// rigid bodies that are pushed by the character.
+    pub fn set_mass(&mut self, mass: f32) -> f32 {
+        self.mass.set_value_and_mark_modified(mass)
+    }
+
+    /// Returns current mass of the character.
+    pub fn mass(&self) -> f32 {
+        *self.mass
+    }
+
+    /// Sets the new collision filtering options. See [`InteractionGroups`] docs for more info.
+    pub fn set_collision_groups(&mut self, groups: InteractionGroups) -> InteractionGroups {
+        self.collision_groups.set_value_and_mark_modified(groups)
+    }
+
+    /// Returns current collision filtering options.
+    pub fn collision_groups(&self) -> InteractionGroups {
+        *self.collision_groups
+    }
+
+    /// Sets the velocity (in world coordinates) with which the character should move. The actual
+    /// movement will be corrected to avoid penetration with surrounding colliders.
+    pub fn set_desired_velocity(&mut self, velocity: Vector2<f32>) {
+        self.desired_velocity = velocity;
+    }
+
+    /// Returns current desired velocity of the character.
+    pub fn desired_velocity(&self) -> Vector2<f32> {
+        self.desired_velocity
+    }
+
+    /// Returns a list of collisions that have happened during the last movement of the character.
+    pub fn collisions(&self) -> &[CharacterCollision] {
+        &self.collisions
+    }
+
+    /// Returns true if the character was touching the ground after the last movement.
+    pub fn is_grounded(&self) -> bool {
+        self.is_grounded
+    }
+}
+
+impl NodeTrait for CharacterController {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.local_bounding_box()
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.base.world_bounding_box()
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        let mut exclude = Vec::new();
+        collect_descendants(context.nodes, self.children(), &mut exclude);
+
+        let mut collisions = std::mem::take(&mut self.collisions);
+        collisions.clear();
+
+        self.is_grounded = false;
+        if let Some(movement) =
+            context
+                .physics2d
+                .move_character(self, &exclude, context.dt, &mut collisions)
+        {
+            self.is_grounded = movement.grounded;
+
+            let translation = world_to_local_translation(
+                context.nodes,
+                self.parent(),
+                Vector3::new(movement.translation.x, movement.translation.y, 0.0),
+            );
+            if translation != Vector3::default() {
+                self.local_transform_mut().offset(translation);
+            }
+        }
+
+        self.collisions = collisions;
+    }
+
+    fn validate(&self, _scene: &Scene) -> Result<(), String> {
+        match *self.shape {
+            ColliderShape::Trimesh(_) | ColliderShape::Heightfield(_) => Err(
+                "Character controller supports only primitive shapes, it won't move!".to_string(),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Character controller builder allows you to build a 2D character controller node in declarative
+/// manner.
+pub struct CharacterControllerBuilder {
+    base_builder: BaseBuilder,
+    shape: ColliderShape,
+    offset: f32,
+    slide: bool,
+    autostep: Option<CharacterAutostep>,
+    max_slope_climb_angle: f32,
+    min_slope_slide_angle: f32,
+    snap_to_ground: Option<f32>,
+    push_dynamic_bodies: bool,
+    mass: f32,
+    collision_groups: InteractionGroups,
+}
+
+impl CharacterControllerBuilder {
+    /// Creates new character controller builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            shape: ColliderShape::capsule_y(0.5, 0.3),
+            offset: 0.01,
+            slide: true,
+            autostep: Some(Default::default()),
+            max_slope_climb_angle: 45.0f32.to_radians(),
+            min_slope_slide_angle: 30.0f32.to_radians(),
+            snap_to_ground: Some(0.2),
+            push_dynamic_bodies: true,
+            mass: 1.0,
+            collision_groups: Default::default(),
+        }
+    }
+
+    /// Sets desired shape of the character.
+    pub fn with_shape(mut self, shape: ColliderShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Sets desired gap between the character and its surroundings.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets whether the character should slide along obstacles or not.
+    pub fn with_slide(mut self, slide: bool) -> Self {
+        self.slide = slide;
+        self
+    }
+
+    /// Sets desired auto-stepping options.
+    pub fn with_autostep(mut self, autostep: Option<CharacterAutostep>) -> Self {
+        self.autostep = autostep;
+        self
+    }
+
+    /// Sets desired maximum angle (in radians) of slopes that the character is able to climb.
+    pub fn with_max_slope_climb_angle(mut self, angle: f32) -> Self {
+        self.max_slope_climb_angle = angle;
+        self
+    }
+
+    /// Sets desired minimum angle (in radians) of slopes at which the character starts to slide down.
+    pub fn with_min_slope_slide_angle(mut self, angle: f32) -> Self {
+        self.min_slope_slide_angle = angle;
+        self
+    }
+
+    /// Sets desired maximum distance of ground snapping.
+    pub fn with_snap_to_ground(mut self, distance: Option<f32>) -> Self {
+        self.snap_to_ground = distance;
+        self
+    }
+
+    /// Sets whether the character should push dynamic rigid bodies or not.
+    pub fn with_push_dynamic_bodies(mut self, push: bool) -> Self {
+        self.push_dynamic_bodies = push;
+        self
+    }
+
+    /// Sets desired mass of the character.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Sets desired collision groups.
+    pub fn with_collision_groups(mut self, groups: InteractionGroups) -> Self {
+        self.collision_groups = groups;
+        self
+    }
+
+    /// Creates 2D character controller node, but does not add it to a graph.
+    pub fn build_node(self) -> Node {
+        Node::new(CharacterController {
+            base: self.base_builder.build_base(),
+            shape: self.shape.into(),
+            offset: self.offset.into(),
+            slide: self.slide.into(),
+            autostep: self.autostep.into(),
+            max_slope_climb_angle: self.max_slope_climb_angle.into(),
+            min_slope_slide_angle: self.min_slope_slide_angle.into(),
+            snap_to_ground: self.snap_to_ground.into(),
+            push_dynamic_bodies: self.push_dynamic_bodies.into(),
+            mass: self.mass.into(),
+            collision_groups: self.collision_groups.into(),
+            desired_velocity: Default::default(),
+            collisions: Default::default(),
+            is_grounded: false,
+        })
+    }
+
+    /// Creates 2D character controller node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            dim2::{
+                character_controller::CharacterControllerBuilder,
+                collider::{ColliderBuilder, ColliderShape},
+                rigidbody::RigidBodyBuilder,
+            },
+            graph::Graph,
+            rigidbody::RigidBodyType,
+            transform::TransformBuilder,
+        },
+    };
+
+    fn add_static_box(graph: &mut Graph, position: Vector2<f32>, half_extents: Vector2<f32>) {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(half_extents.x, half_extents.y))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(position.x, position.y, 0.0))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(graph);
+    }
+
+    #[test]
+    fn test_character_controller_2d_movement() {
+        let mut graph = Graph::new();
+
+        // Floor with its top at zero and a wall with its face at x = 2.5.
+        add_static_box(&mut graph, Vector2::new(0.0, -0.5), Vector2::new(10.0, 0.5));
+        add_static_box(&mut graph, Vector2::new(3.0, 1.0), Vector2::new(0.5, 1.0));
+
+        let character = CharacterControllerBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 2.0, 0.0))
+                    .build(),
+            ),
+        )
+        .with_shape(ColliderShape::capsule_y(0.5, 0.3))
+        .build(&mut graph);
+
+        graph[character]
+            .as_character_controller2d_mut()
+            .set_desired_velocity(Vector2::new(2.0, -5.0));
+
+        for _ in 0..120 {
+            graph.update(Vector2::new(800.0, 600.0), 1.0 / 60.0, Default::default());
+        }
+
+        let controller = graph[character].as_character_controller2d();
+        let position = **controller.local_transform().position();
+        assert!(controller.is_grounded());
+        assert!(!controller.collisions().is_empty());
+        // The character must stand on the floor (half height + radius + offset) ...
+        assert!((position.y - 0.81).abs() < 0.02, "{position:?}");
+        // ... and be stopped by the wall (wall face - radius - offset).
+        assert!((position.x - 2.19).abs() < 0.02, "{position:?}");
+    }
+}
@@ -700,15 +700,23 @@ impl ColliderBuilder {
 #[cfg(test)]
 mod test {
 
-    use crate::core::algebra::Vector2;
+    use crate::core::{
+        algebra::{Point2, UnitComplex, Vector2, Vector3},
+        math::Rect,
+        pool::Handle,
+    };
     use crate::scene::{
         base::BaseBuilder,
+        collider::{BitMask, InteractionGroups},
         dim2::{
             collider::{ColliderBuilder, ColliderShape},
+            physics::ShapeCastOptions,
             rigidbody::RigidBodyBuilder,
         },
         graph::Graph,
+        node::Node,
         rigidbody::RigidBodyType,
+        transform::TransformBuilder,
     };
 
     #[test]
@@ -768,4 +776,95 @@ mod test {
                 .count()
         );
     }
+
+    #[test]
+    fn test_physics_2d_queries() {
+        let mut graph = Graph::new();
+
+        let mut create_box = |position: Vector2<f32>| {
+            let collider = ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::cuboid(0.5, 0.5))
+                .build(&mut graph);
+
+            RigidBodyBuilder::new(
+                BaseBuilder::new()
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(Vector3::new(position.x, position.y, 0.0))
+                            .build(),
+                    )
+                    .with_children(&[collider]),
+            )
+            .with_body_type(RigidBodyType::Static)
+            .build(&mut graph);
+
+            collider
+        };
+
+        let near = create_box(Vector2::new(5.0, 0.0));
+        let far = create_box(Vector2::new(10.0, 0.0));
+
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        let result = graph
+            .physics2d
+            .cast_shape(ShapeCastOptions {
+                shape: ColliderShape::ball(0.5),
+                shape_position: Point2::origin(),
+                shape_rotation: UnitComplex::identity(),
+                direction: Vector2::new(2.0, 0.0),
+                max_distance: 100.0,
+                groups: InteractionGroups::default(),
+                stop_at_penetration: true,
+            })
+            .unwrap();
+        assert_eq!(result.collider, near);
+        assert!((result.toi - 4.0).abs() < 0.001);
+        assert!((result.collider_witness.x - 4.5).abs() < 0.001);
+        assert!((result.shape_witness.x - 4.5).abs() < 0.001);
+        assert!((result.collider_normal.x + 1.0).abs() < 0.001);
+        assert!((result.shape_normal.x - 1.0).abs() < 0.001);
+
+        let projection = graph
+            .physics2d
+            .project_point(Point2::new(12.0, 0.0), true, Default::default())
+            .unwrap();
+        assert_eq!(projection.collider, far);
+        assert!(!projection.is_inside);
+        assert!((projection.point.x - 10.5).abs() < 0.001);
+
+        let mut buffer = Vec::<Handle<Node>>::new();
+        graph.physics2d.intersections_with_point(
+            Point2::new(5.2, 0.0),
+            Default::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![near]);
+
+        graph.physics2d.intersections_with_shape(
+            &ColliderShape::cuboid(3.0, 0.5),
+            Point2::new(7.5, 0.0),
+            UnitComplex::identity(),
+            Default::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer.len(), 2);
+        assert!(buffer.contains(&near) && buffer.contains(&far));
+
+        graph.physics2d.intersections_with_rect(
+            Rect::new(9.0, -1.0, 11.0, 2.0),
+            Default::default(),
+            &mut buffer,
+        );
+        assert_eq!(buffer, vec![far]);
+
+        // Colliders that are filtered out by interaction groups must be ignored.
+        graph.physics2d.intersections_with_point(
+            Point2::new(5.2, 0.0),
+            InteractionGroups::new(BitMask(u32::MAX), BitMask(0)),
+            &mut buffer,
+        );
+        assert!(buffer.is_empty());
+    }
 }
@@ -43,6 +43,7 @@ use crate::{
     },
     scene::{
         self,
+        animation::ik,
         base::NodeScriptMessage,
         camera::Camera,
         dim2::{self},
@@ -332,7 +333,7 @@ impl Graph {
     pub fn add_node(&mut self, mut node: Node) -> Handle<Node> {
         let children = node.children.clone();
         node.children.clear();
-        let has_script = node.script.is_some();
+        let has_script = node.has_scripts_assigned();
         let handle = self.pool.spawn(node);
 
         if self.root.is_none() {
@@ -360,6 +361,28 @@ impl Graph {
         handle
     }
 
+    // Puts the node at the given handle without linking it with any other node, it is used to restore nodes
+    // at their previous handles. Returns the node back if the handle is occupied.
+    pub(crate) fn add_node_at_handle(
+        &mut self,
+        handle: Handle<Node>,
+        mut node: Node,
+    ) -> Result<Handle<Node>, Node> {
+        node.self_handle = handle;
+        node.script_message_sender = Some(self.script_message_sender.clone());
+        let has_script = node.has_scripts_assigned();
+        let handle = self.pool.spawn_at_handle(handle, node)?;
+
+        self.event_broadcaster.broadcast(GraphEvent::Added(handle));
+        if has_script {
+            self.script_message_sender
+                .send(NodeScriptMessage::InitializeScript { handle })
+                .unwrap();
+        }
+
+        Ok(handle)
+    }
+
     /// Tries to find references of the given node in other scene nodes. It could be used to check if the node is
     /// used by some other scene node or not. Returns an array of nodes, that references the given node. This method
     /// is reflection-based, so it is quite slow and should not be used every frame.
@@ -710,9 +733,7 @@ impl Graph {
     where
         S: ScriptTrait,
     {
-        self.find(root_node, &mut |n| {
-            n.script().and_then(|s| s.cast::<S>()).is_some()
-        })
+        self.find(root_node, &mut |n| n.has_script::<S>())
     }
 
     /// Searches node using specified compare closure starting from root. Returns a tuple with a handle and
@@ -1589,6 +1610,19 @@ impl Graph {
                 );
             }
         }
+
+        // Inverse kinematics must be applied on top of the final pose, so it is done after every other node was
+        // updated (including animation players and state machines).
+        if let Some(overrides) = switches.node_overrides.as_ref() {
+            for handle in overrides {
+                ik::apply_inverse_kinematics(&mut self.pool, *handle);
+            }
+        } else {
+            for i in 0..self.pool.get_capacity() {
+                let handle = self.pool.handle_from_index(i);
+                ik::apply_inverse_kinematics(&mut self.pool, handle);
+            }
+        }
     }
 
     /// Returns capacity of internal pool. Can be used to iterate over all **potentially**
@@ -1919,7 +1953,8 @@ impl Graph {
         Vector3::new(m[0], m[5], m[10])
     }
 
-    /// Tries to borrow a node using the given handle, fetch its script and cast it to the specified type.
+    /// Tries to borrow a node using the given handle and find a first script of the specified type among the
+    /// scripts of the node.
     #[inline]
     pub fn try_get_script_of<T>(&self, node: Handle<Node>) -> Option<&T>
     where
@@ -1928,7 +1963,8 @@ impl Graph {
         self.try_get(node).and_then(|node| node.try_get_script())
     }
 
-    /// Tries to borrow a node using the given handle, fetch its script and cast it to the specified type.
+    /// Tries to borrow a node using the given handle and find a first script of the specified type among the
+    /// scripts of the node.
     #[inline]
     pub fn try_get_script_of_mut<T>(&mut self, node: Handle<Node>) -> Option<&mut T>
     where
@@ -1939,7 +1975,7 @@ impl Graph {
     }
 
     /// Tries to borrow a node using the given handle and fetch a reference to a component of the given type
-    /// from the script of the node.
+    /// from the scripts of the node.
     #[inline]
     pub fn try_get_script_component_of<C>(&self, node: Handle<Node>) -> Option<&C>
     where
@@ -1950,7 +1986,7 @@ impl Graph {
     }
 
     /// Tries to borrow a node using the given handle and fetch a reference to a component of the given type
-    /// from the script of the node.
+    /// from the scripts of the node.
     #[inline]
     pub fn try_get_script_component_of_mut<C>(&mut self, node: Handle<Node>) -> Option<&mut C>
     where
@@ -9,7 +9,7 @@ use crate::{
         arrayvec::ArrayVec,
         instant,
         log::{Log, MessageKind},
-        math::Matrix4Ext,
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
         parking_lot::Mutex,
         pool::Handle,
         reflect::prelude::*,
@@ -19,6 +19,7 @@ use crate::{
     },
     scene::{
         self,
+        character_controller::{CharacterCollision, CharacterController},
         collider::{self, ColliderShape, GeometrySource},
         debug::SceneDrawingContext,
         graph::{isometric_global_transform, NodePool},
@@ -36,20 +37,28 @@ use crate::{
 use fyrox_core::algebra::Translation;
 use fyrox_core::uuid_provider;
 use rapier3d::{
+    control::{
+        CharacterAutostep, CharacterLength, EffectiveCharacterMovement,
+        KinematicCharacterController,
+    },
     dynamics::{
         CCDSolver, GenericJoint, GenericJointBuilder, ImpulseJointHandle, ImpulseJointSet,
         IslandManager, JointAxesMask, MultibodyJointHandle, MultibodyJointSet, RigidBody,
         RigidBodyActivation, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
     },
     geometry::{
-        BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
+        Aabb, BroadPhase, Collider, ColliderBuilder, ColliderHandle, ColliderSet, Cuboid,
         InteractionGroups, NarrowPhase, Ray, SharedShape,
     },
-    pipeline::{DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter, QueryPipeline},
+    parry::query::TOIStatus,
+    pipeline::{
+        ActiveEvents, DebugRenderPipeline, EventHandler, PhysicsPipeline, QueryFilter,
+        QueryPipeline,
+    },
     prelude::JointAxis,
 };
 use std::{
-    cell::{Cell, RefCell},
+    cell::{Cell, RefCell, RefMut},
     cmp::Ordering,
     fmt::{Debug, Formatter},
     hash::Hash,
@@ -233,23 +242,24 @@ pub struct RayCastOptions {
     pub sort_results: bool,
 }
 
-/// A trait for ray cast results storage. It has two implementations: Vec and ArrayVec.
+/// A trait for query results storage. It has two implementations: Vec and ArrayVec.
 /// Latter is needed for the cases where you need to avoid runtime memory allocations
-/// and do everything on stack.
-pub trait QueryResultsStorage {
+/// and do everything on stack. By default, it stores ray intersections, but it can store
+/// any other query results (for example, handles of colliders for overlap queries).
+pub trait QueryResultsStorage<T = Intersection> {
     /// Pushes new intersection in the storage. Returns true if intersection was
     /// successfully inserted, false otherwise.
-    fn push(&mut self, intersection: Intersection) -> bool;
+    fn push(&mut self, intersection: T) -> bool;
 
     /// Clears the storage.
     fn clear(&mut self);
 
     /// Sorts intersections by given compare function.
-    fn sort_intersections_by<C: FnMut(&Intersection, &Intersection) -> Ordering>(&mut self, cmp: C);
+    fn sort_intersections_by<C: FnMut(&T, &T) -> Ordering>(&mut self, cmp: C);
 }
 
-impl QueryResultsStorage for Vec<Intersection> {
-    fn push(&mut self, intersection: Intersection) -> bool {
+impl<T> QueryResultsStorage<T> for Vec<T> {
+    fn push(&mut self, intersection: T) -> bool {
         self.push(intersection);
         true
     }
@@ -260,14 +270,14 @@ impl QueryResultsStorage for Vec<Intersection> {
 
     fn sort_intersections_by<C>(&mut self, cmp: C)
     where
-        C: FnMut(&Intersection, &Intersection) -> Ordering,
+        C: FnMut(&T, &T) -> Ordering,
     {
         self.sort_by(cmp);
     }
 }
 
-impl<const CAP: usize> QueryResultsStorage for ArrayVec<Intersection, CAP> {
-    fn push(&mut self, intersection: Intersection) -> bool {
+impl<T, const CAP: usize> QueryResultsStorage<T> for ArrayVec<T, CAP> {
+    fn push(&mut self, intersection: T) -> bool {
         self.try_push(intersection).is_ok()
     }
 
@@ -277,12 +287,103 @@ impl<const CAP: usize> QueryResultsStorage for ArrayVec<Intersection, CAP> {
 
     fn sort_intersections_by<C>(&mut self, cmp: C)
     where
-        C: FnMut(&Intersection, &Intersection) -> Ordering,
+        C: FnMut(&T, &T) -> Ordering,
     {
         self.sort_by(cmp);
     }
 }
 
+/// A status of the shape cast, it tells how the time of impact computation has ended.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ShapeCastStatus {
+    /// The computation ran out of iterations before achieving convergence. The result is
+    /// still a conservative approximation of the actual time of impact.
+    OutOfIterations,
+    /// The computation converged successfully.
+    Converged,
+    /// Something went wrong during the computation, likely due to numerical instabilities.
+    Failed,
+    /// The shape was penetrating the collider at its starting position. Witness points and
+    /// normals are undefined in this case.
+    Penetrating,
+}
+
+impl From<TOIStatus> for ShapeCastStatus {
+    fn from(v: TOIStatus) -> Self {
+        match v {
+            TOIStatus::OutOfIterations => Self::OutOfIterations,
+            TOIStatus::Converged => Self::Converged,
+            TOIStatus::Failed => Self::Failed,
+            TOIStatus::Penetrating => Self::Penetrating,
+        }
+    }
+}
+
+/// A set of options for the shape cast.
+pub struct ShapeCastOptions {
+    /// A shape to cast. Only primitive shapes are supported, shapes that require geometry
+    /// sources (trimesh, heightfield, polyhedron) will not produce any results.
+    pub shape: ColliderShape,
+
+    /// Initial position of the shape in world coordinates.
+    pub shape_position: Point3<f32>,
+
+    /// Initial rotation of the shape in world coordinates.
+    pub shape_rotation: UnitQuaternion<f32>,
+
+    /// A direction of the cast. Can be non-normalized.
+    pub direction: Vector3<f32>,
+
+    /// Maximum distance of cast.
+    pub max_distance: f32,
+
+    /// Groups to check.
+    pub groups: collider::InteractionGroups,
+
+    /// If set to `false`, the cast won't immediately stop if the shape is penetrating a
+    /// collider at its starting position **and** its trajectory is such that it's on a path
+    /// to exit that penetration state.
+    pub stop_at_penetration: bool,
+}
+
+/// A result of the shape cast.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeCastResult {
+    /// A handle of the first collider hit by the shape.
+    pub collider: Handle<Node>,
+
+    /// Distance traveled by the shape along the cast direction before the hit.
+    pub toi: f32,
+
+    /// The closest point on the collider at the time of impact, in world coordinates.
+    pub collider_witness: Point3<f32>,
+
+    /// The closest point on the cast shape at the time of impact, in world coordinates.
+    pub shape_witness: Point3<f32>,
+
+    /// Outward normal of the collider at the time of impact, in world coordinates.
+    pub collider_normal: Vector3<f32>,
+
+    /// Outward normal of the cast shape at the time of impact, in world coordinates.
+    pub shape_normal: Vector3<f32>,
+
+    /// The way the time of impact computation has ended.
+    pub status: ShapeCastStatus,
+}
+
+/// A result of a point projection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointProjection {
+    /// A handle of the collider on which the point was projected.
+    pub collider: Handle<Node>,
+
+    /// The projected point in world coordinates.
+    pub point: Point3<f32>,
+
+    /// Whether the point was inside of the collider or not.
+    pub is_inside: bool,
+}
+
 /// Data of the contact.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ContactData {
@@ -332,10 +433,14 @@ pub struct ContactPair {
 }
 
 impl ContactPair {
-    fn from_native(c: &rapier3d::geometry::ContactPair, physics: &PhysicsWorld) -> Option<Self> {
+    fn from_native(
+        c: &rapier3d::geometry::ContactPair,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+    ) -> Option<Self> {
         Some(ContactPair {
-            collider1: Handle::decode_from_u128(physics.colliders.get(c.collider1)?.user_data),
-            collider2: Handle::decode_from_u128(physics.colliders.get(c.collider2)?.user_data),
+            collider1: Handle::decode_from_u128(colliders.get(c.collider1)?.user_data),
+            collider2: Handle::decode_from_u128(colliders.get(c.collider2)?.user_data),
             manifolds: c
                 .manifolds
                 .iter()
@@ -355,16 +460,10 @@ impl ContactPair {
                         local_n1: m.local_n1,
                         local_n2: m.local_n2,
                         rigid_body1: m.data.rigid_body1.and_then(|h| {
-                            physics
-                                .bodies
-                                .get(h)
-                                .map(|b| Handle::decode_from_u128(b.user_data))
+                            bodies.get(h).map(|b| Handle::decode_from_u128(b.user_data))
                         })?,
                         rigid_body2: m.data.rigid_body2.and_then(|h| {
-                            physics
-                                .bodies
-                                .get(h)
-                                .map(|b| Handle::decode_from_u128(b.user_data))
+                            bodies.get(h).map(|b| Handle::decode_from_u128(b.user_data))
                         })?,
                         normal: m.data.normal,
                     })
@@ -386,6 +485,66 @@ pub struct IntersectionPair {
     pub has_any_active_contact: bool,
 }
 
+/// An event, that is generated when two colliders start or stop touching each other (or intersecting, if at
+/// least one of them is a sensor).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionEvent {
+    /// The first collider involved in the event.
+    pub collider1: Handle<Node>,
+    /// The second collider involved in the event.
+    pub collider2: Handle<Node>,
+    /// `true` if the colliders started touching each other, `false` - if they stopped.
+    pub started: bool,
+    /// `true` if at least one of the colliders is a sensor.
+    pub sensor: bool,
+    /// Contacts between the colliders at the moment when they started touching each other. It is always
+    /// `None` for sensors and for stopped collisions.
+    pub contact_pair: Option<ContactPair>,
+}
+
+// Collects collision events generated during a simulation step.
+#[derive(Default)]
+struct EventCollector {
+    collision_events: Mutex<Vec<CollisionEvent>>,
+}
+
+impl EventHandler for EventCollector {
+    fn handle_collision_event(
+        &self,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        event: rapier3d::geometry::CollisionEvent,
+        contact_pair: Option<&rapier3d::geometry::ContactPair>,
+    ) {
+        // Colliders could be already removed (it is the case for stopped events of removed colliders),
+        // such events cannot be mapped to scene nodes and are ignored.
+        if let (Some(collider1), Some(collider2)) = (
+            colliders.get(event.collider1()),
+            colliders.get(event.collider2()),
+        ) {
+            self.collision_events.lock().push(CollisionEvent {
+                collider1: Handle::decode_from_u128(collider1.user_data),
+                collider2: Handle::decode_from_u128(collider2.user_data),
+                started: event.started(),
+                sensor: event.sensor(),
+                contact_pair: contact_pair
+                    .filter(|_| event.started())
+                    .and_then(|c| ContactPair::from_native(c, bodies, colliders)),
+            });
+        }
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        _dt: f32,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _contact_pair: &rapier3d::geometry::ContactPair,
+        _total_force_magnitude: f32,
+    ) {
+    }
+}
+
 pub(super) struct Container<S, A>
 where
     A: Hash + Eq + Clone,
@@ -672,13 +831,9 @@ fn make_heightfield(terrain: &Terrain) -> SharedShape {
     )
 }
 
-// Converts descriptor in a shared shape.
-fn collider_shape_into_native_shape(
-    shape: &ColliderShape,
-    owner_inv_global_transform: Matrix4<f32>,
-    owner_collider: Handle<Node>,
-    pool: &NodePool,
-) -> Option<SharedShape> {
+// Converts descriptor of a primitive shape in a shared shape. Shapes that require geometry sources
+// are not primitive.
+fn primitive_shape_into_native_shape(shape: &ColliderShape) -> Option<SharedShape> {
     match shape {
         ColliderShape::Ball(ball) => Some(SharedShape::ball(ball.radius)),
 
@@ -703,6 +858,20 @@ fn collider_shape_into_native_shape(
             Point3::from(triangle.b),
             Point3::from(triangle.c),
         )),
+        ColliderShape::Trimesh(_)
+        | ColliderShape::Heightfield(_)
+        | ColliderShape::Polyhedron(_) => None,
+    }
+}
+
+// Converts descriptor in a shared shape.
+fn collider_shape_into_native_shape(
+    shape: &ColliderShape,
+    owner_inv_global_transform: Matrix4<f32>,
+    owner_collider: Handle<Node>,
+    pool: &NodePool,
+) -> Option<SharedShape> {
+    match shape {
         ColliderShape::Trimesh(trimesh) => {
             if trimesh.sources.is_empty() {
                 None
@@ -723,6 +892,7 @@ fn collider_shape_into_native_shape(
             .try_borrow(polyhedron.geometry_source.0)
             .and_then(|n| n.cast::<Mesh>())
             .map(|mesh| make_polyhedron_shape(owner_inv_global_transform, mesh)),
+        _ => primitive_shape_into_native_shape(shape),
     }
 }
 
@@ -885,6 +1055,81 @@ impl Default for IntegrationParameters {
     }
 }
 
+/// A set of queries to a physics world, that share the same query pipeline, which is updated only once. See
+/// [`PhysicsWorld::batch_query`] for more info.
+pub struct PhysicsBatchQuery<'a> {
+    world: &'a PhysicsWorld,
+    query: RefMut<'a, QueryPipeline>,
+}
+
+impl<'a> PhysicsBatchQuery<'a> {
+    /// Casts a ray with given options.
+    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
+        let time = instant::Instant::now();
+
+        query_buffer.clear();
+        let ray = Ray::new(
+            opts.ray_origin,
+            opts.ray_direction
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default(),
+        );
+        self.query.intersections_with_ray(
+            &self.world.bodies,
+            &self.world.colliders,
+            &ray,
+            opts.max_len,
+            true,
+            make_query_filter(opts.groups),
+            |handle, intersection| {
+                query_buffer.push(Intersection {
+                    collider: Handle::decode_from_u128(
+                        self.world.colliders.get(handle).unwrap().user_data,
+                    ),
+                    normal: intersection.normal,
+                    position: ray.point_at(intersection.toi),
+                    feature: intersection.feature.into(),
+                    toi: intersection.toi,
+                })
+            },
+        );
+        if opts.sort_results {
+            query_buffer.sort_intersections_by(|a, b| {
+                if a.toi > b.toi {
+                    Ordering::Greater
+                } else if a.toi < b.toi {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            })
+        }
+
+        self.world.performance_statistics.total_ray_cast_time.set(
+            self.world.performance_statistics.total_ray_cast_time.get()
+                + (instant::Instant::now() - time),
+        );
+    }
+
+    /// Searches for every collider that contains the given point. Results will be written to the
+    /// given storage, the search stops when the storage cannot accept more results.
+    pub fn intersections_with_point<S: QueryResultsStorage<Handle<Node>>>(
+        &self,
+        point: Point3<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut S,
+    ) {
+        query_buffer.clear();
+        self.query.intersections_with_point(
+            &self.world.bodies,
+            &self.world.colliders,
+            &point,
+            make_query_filter(groups),
+            |handle| query_buffer.push(self.world.collider_owner(handle)),
+        );
+    }
+}
+
 /// Physics world is responsible for physics simulation in the engine. There is a very few public
 /// methods, mostly for ray casting. You should add physical entities using scene graph nodes, such
 /// as RigidBody, Collider, Joint.
@@ -944,7 +1189,10 @@ pub struct PhysicsWorld {
     // Event handler collects info about contacts and proximity events.
     #[visit(skip)]
     #[reflect(hidden)]
-    event_handler: Box<dyn EventHandler>,
+    event_handler: EventCollector,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    collision_events: Vec<CollisionEvent>,
     #[visit(skip)]
     #[reflect(hidden)]
     query: RefCell<QueryPipeline>,
@@ -982,6 +1230,13 @@ fn u32_to_group(v: u32) -> rapier3d::geometry::Group {
     rapier3d::geometry::Group::from_bits(v).unwrap_or_else(rapier3d::geometry::Group::all)
 }
 
+fn make_query_filter<'a>(groups: collider::InteractionGroups) -> QueryFilter<'a> {
+    QueryFilter::new().groups(InteractionGroups::new(
+        u32_to_group(groups.memberships.0),
+        u32_to_group(groups.filter.0),
+    ))
+}
+
 impl PhysicsWorld {
     /// Creates a new instance of the physics world.
     pub(super) fn new() -> Self {
@@ -1004,7 +1259,8 @@ impl PhysicsWorld {
                 set: MultibodyJointSet::new(),
                 map: Default::default(),
             },
-            event_handler: Box::new(()),
+            event_handler: Default::default(),
+            collision_events: Default::default(),
             query: RefCell::new(Default::default()),
             performance_statistics: Default::default(),
             debug_render_pipeline: Default::default(),
@@ -1057,10 +1313,12 @@ impl PhysicsWorld {
                 // so we keep updating it manually.
                 None,
                 &(),
-                &*self.event_handler,
+                &self.event_handler,
             );
         }
 
+        self.collision_events = std::mem::take(self.event_handler.collision_events.get_mut());
+
         self.performance_statistics.step_time += instant::Instant::now() - time;
     }
 
@@ -1128,10 +1386,8 @@ impl PhysicsWorld {
         );
     }
 
-    /// Casts a ray with given options.
-    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
-        let time = instant::Instant::now();
-
+    // Returns query pipeline that is in sync with the current state of the world.
+    fn updated_query(&self) -> RefMut<'_, QueryPipeline> {
         let mut query = self.query.borrow_mut();
 
         // TODO: Ideally this must be called once per frame, but it seems to be impossible because
@@ -1141,51 +1397,246 @@ impl PhysicsWorld {
         // of the frame.
         query.update(&self.bodies, &self.colliders);
 
+        query
+    }
+
+    fn collider_owner(&self, handle: ColliderHandle) -> Handle<Node> {
+        self.colliders
+            .get(handle)
+            .map(|c| Handle::decode_from_u128(c.user_data))
+            .unwrap_or_default()
+    }
+
+    /// Casts a ray with given options.
+    pub fn cast_ray<S: QueryResultsStorage>(&self, opts: RayCastOptions, query_buffer: &mut S) {
+        self.batch_query().cast_ray(opts, query_buffer)
+    }
+
+    /// Returns a batch query, that allows to perform multiple ray casts and point queries using the query pipeline,
+    /// that is updated only once. Every other query method updates the query pipeline on each call, so a batch query
+    /// is much faster when a lot of queries must be performed at once (for example, when casting multiple rays
+    /// each frame). The world cannot be modified while the batch query exists, other query methods of the world
+    /// must not be called while the batch query exists too (they will panic).
+    pub fn batch_query(&self) -> PhysicsBatchQuery<'_> {
+        PhysicsBatchQuery {
+            world: self,
+            query: self.updated_query(),
+        }
+    }
+
+    /// Sweeps a shape along the given direction and returns the first collider hit by the shape.
+    /// This is similar to ray casting, except that the whole shape is cast instead of a single
+    /// point.
+    pub fn cast_shape(&self, opts: ShapeCastOptions) -> Option<ShapeCastResult> {
+        let shape = primitive_shape_into_native_shape(&opts.shape)?;
+        let direction = opts.direction.try_normalize(f32::EPSILON)?;
+        let shape_position = Isometry3 {
+            translation: Translation3::from(opts.shape_position.coords),
+            rotation: opts.shape_rotation,
+        };
+
+        let query = self.updated_query();
+        let (handle, toi) = query.cast_shape(
+            &self.bodies,
+            &self.colliders,
+            &shape_position,
+            &direction,
+            &*shape,
+            opts.max_distance,
+            opts.stop_at_penetration,
+            make_query_filter(opts.groups),
+        )?;
+
+        // Witness point and normal of the cast shape are in its local space at the time of impact.
+        let shape_position_at_toi = Isometry3 {
+            translation: Translation3::from(opts.shape_position.coords + direction * toi.toi),
+            rotation: opts.shape_rotation,
+        };
+
+        Some(ShapeCastResult {
+            collider: self.collider_owner(handle),
+            toi: toi.toi,
+            collider_witness: toi.witness1,
+            shape_witness: shape_position_at_toi.transform_point(&toi.witness2),
+            collider_normal: toi.normal1.into_inner(),
+            shape_normal: shape_position_at_toi.transform_vector(&toi.normal2),
+            status: toi.status.into(),
+        })
+    }
+
+    /// Projects a point on the closest collider. If `solid` is set to `true`, colliders are
+    /// considered solid and a point inside of a collider will be projected on itself, otherwise
+    /// the point will be projected on the boundary of the collider.
+    pub fn project_point(
+        &self,
+        point: Point3<f32>,
+        solid: bool,
+        groups: collider::InteractionGroups,
+    ) -> Option<PointProjection> {
+        let query = self.updated_query();
+        query
+            .project_point(
+                &self.bodies,
+                &self.colliders,
+                &point,
+                solid,
+                make_query_filter(groups),
+            )
+            .map(|(handle, projection)| PointProjection {
+                collider: self.collider_owner(handle),
+                point: projection.point,
+                is_inside: projection.is_inside,
+            })
+    }
+
+    /// Searches for every collider that contains the given point. Results will be written to the
+    /// given storage, the search stops when the storage cannot accept more results.
+    pub fn intersections_with_point<S: QueryResultsStorage<Handle<Node>>>(
+        &self,
+        point: Point3<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut S,
+    ) {
+        self.batch_query()
+            .intersections_with_point(point, groups, query_buffer)
+    }
+
+    /// Searches for every collider that intersects the given shape. Results will be written to the
+    /// given storage, the search stops when the storage cannot accept more results. Only primitive
+    /// shapes are supported, shapes that require geometry sources (trimesh, heightfield, polyhedron)
+    /// will not produce any results.
+    pub fn intersections_with_shape<S: QueryResultsStorage<Handle<Node>>>(
+        &self,
+        shape: &ColliderShape,
+        position: Point3<f32>,
+        rotation: UnitQuaternion<f32>,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut S,
+    ) {
+        query_buffer.clear();
+        let Some(shape) = primitive_shape_into_native_shape(shape) else {
+            return;
+        };
+        let shape_position = Isometry3 {
+            translation: Translation3::from(position.coords),
+            rotation,
+        };
+        let query = self.updated_query();
+        query.intersections_with_shape(
+            &self.bodies,
+            &self.colliders,
+            &shape_position,
+            &*shape,
+            make_query_filter(groups),
+            |handle| query_buffer.push(self.collider_owner(handle)),
+        );
+    }
+
+    /// Searches for every collider which bounding box intersects the given axis-aligned bounding box.
+    /// Results will be written to the given storage, the search stops when the storage cannot accept
+    /// more results.
+    pub fn intersections_with_aabb<S: QueryResultsStorage<Handle<Node>>>(
+        &self,
+        aabb: &AxisAlignedBoundingBox,
+        groups: collider::InteractionGroups,
+        query_buffer: &mut S,
+    ) {
         query_buffer.clear();
-        let ray = Ray::new(
-            opts.ray_origin,
-            opts.ray_direction
-                .try_normalize(f32::EPSILON)
-                .unwrap_or_default(),
+        let filter = make_query_filter(groups);
+        let query = self.updated_query();
+        query.colliders_with_aabb_intersecting_aabb(
+            &Aabb::new(Point3::from(aabb.min), Point3::from(aabb.max)),
+            |handle| match self.colliders.get(*handle) {
+                Some(collider) if filter.test(&self.bodies, *handle, collider) => {
+                    query_buffer.push(Handle::decode_from_u128(collider.user_data))
+                }
+                _ => true,
+            },
         );
-        query.intersections_with_ray(
+    }
+
+    // Moves the character by its desired velocity and resolves collisions with the environment.
+    // Colliders of the excluded nodes are ignored. Returns the corrected movement or `None` if the
+    // character has unsupported shape.
+    pub(crate) fn move_character(
+        &mut self,
+        character: &CharacterController,
+        exclude: &[Handle<Node>],
+        dt: f32,
+        collisions: &mut Vec<CharacterCollision>,
+    ) -> Option<EffectiveCharacterMovement> {
+        let shape = primitive_shape_into_native_shape(character.shape())?;
+
+        let controller = KinematicCharacterController {
+            up: Vector3::y_axis(),
+            offset: CharacterLength::Absolute(character.offset()),
+            slide: character.is_slide(),
+            autostep: character.autostep().map(|autostep| CharacterAutostep {
+                max_height: CharacterLength::Absolute(autostep.max_height),
+                min_width: CharacterLength::Absolute(autostep.min_width),
+                include_dynamic_bodies: autostep.include_dynamic_bodies,
+            }),
+            max_slope_climb_angle: character.max_slope_climb_angle(),
+            min_slope_slide_angle: character.min_slope_slide_angle(),
+            snap_to_ground: character.snap_to_ground().map(CharacterLength::Absolute),
+        };
+
+        let position = isometry_from_global_transform(&character.global_transform());
+        let predicate = |_, collider: &Collider| {
+            !exclude.contains(&Handle::decode_from_u128(collider.user_data))
+        };
+        let filter = make_query_filter(character.collision_groups())
+            .exclude_sensors()
+            .predicate(&predicate);
+
+        let query = self.query.get_mut();
+        query.update(&self.bodies, &self.colliders);
+
+        let mut native_collisions = Vec::new();
+        let movement = controller.move_shape(
+            dt,
             &self.bodies,
             &self.colliders,
-            &ray,
-            opts.max_len,
-            true,
-            QueryFilter::new().groups(InteractionGroups::new(
-                u32_to_group(opts.groups.memberships.0),
-                u32_to_group(opts.groups.filter.0),
-            )),
-            |handle, intersection| {
-                query_buffer.push(Intersection {
-                    collider: Handle::decode_from_u128(
-                        self.colliders.get(handle).unwrap().user_data,
-                    ),
-                    normal: intersection.normal,
-                    position: ray.point_at(intersection.toi),
-                    feature: intersection.feature.into(),
-                    toi: intersection.toi,
-                })
-            },
+            query,
+            &*shape,
+            &position,
+            character.desired_velocity() * dt,
+            filter,
+            |collision| native_collisions.push(collision),
         );
-        if opts.sort_results {
-            query_buffer.sort_intersections_by(|a, b| {
-                if a.toi > b.toi {
-                    Ordering::Greater
-                } else if a.toi < b.toi {
-                    Ordering::Less
-                } else {
-                    Ordering::Equal
-                }
-            })
+
+        if character.is_push_dynamic_bodies() {
+            for collision in native_collisions.iter() {
+                controller.solve_character_collision_impulses(
+                    dt,
+                    &mut self.bodies,
+                    &self.colliders,
+                    query,
+                    &*shape,
+                    character.mass(),
+                    collision,
+                    filter,
+                );
+            }
         }
 
-        self.performance_statistics.total_ray_cast_time.set(
-            self.performance_statistics.total_ray_cast_time.get()
-                + (instant::Instant::now() - time),
-        );
+        collisions.extend(native_collisions.iter().map(|collision| {
+            CharacterCollision {
+                collider: self
+                    .colliders
+                    .get(collision.handle)
+                    .map(|c| Handle::decode_from_u128(c.user_data))
+                    .unwrap_or_default(),
+                character_position: collision.character_pos.translation.vector,
+                translation_applied: collision.translation_applied,
+                translation_remaining: collision.translation_remaining,
+                toi: collision.toi.toi,
+                collider_witness: collision.toi.witness1,
+                collider_normal: collision.toi.normal1.into_inner(),
+            }
+        }));
+
+        Some(movement)
     }
 
     pub(crate) fn set_rigid_body_position(
@@ -1512,7 +1963,8 @@ impl PhysicsWorld {
                             u32_to_group(collider_node.solver_groups().memberships.0),
                             u32_to_group(collider_node.solver_groups().filter.0),
                         ))
-                        .sensor(collider_node.is_sensor());
+                        .sensor(collider_node.is_sensor())
+                        .active_events(ActiveEvents::COLLISION_EVENTS);
 
                     if let Some(density) = collider_node.density() {
                         builder = builder.density(density);
@@ -1674,14 +2126,21 @@ impl PhysicsWorld {
             // Note: contacts with will only return the interaction between 2 non-sensor nodes
             // https://rapier.rs/docs/user_guides/rust/advanced_collision_detection/#the-contact-graph
             .contacts_with(collider)
-            .filter_map(|c| ContactPair::from_native(c, self))
+            .filter_map(|c| ContactPair::from_native(c, &self.bodies, &self.colliders))
+    }
+
+    /// Returns a slice of collision events, that were generated during the last simulation step. The
+    /// engine passes these events to scripts of the involved nodes, see [`crate::script::ScriptTrait::on_collision_started`]
+    /// and related methods for more info.
+    pub fn collision_events(&self) -> &[CollisionEvent] {
+        &self.collision_events
     }
 
     /// Returns an iterator over all contact pairs generated in this frame.
     pub fn contacts(&self) -> impl Iterator<Item = ContactPair> + '_ {
         self.narrow_phase
             .contact_pairs()
-            .filter_map(|c| ContactPair::from_native(c, self))
+            .filter_map(|c| ContactPair::from_native(c, &self.bodies, &self.colliders))
     }
 }
 
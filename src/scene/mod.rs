@@ -8,9 +8,11 @@ pub mod accel;
 pub mod animation;
 pub mod base;
 pub mod camera;
+pub mod character_controller;
 pub mod collider;
 pub mod debug;
 pub mod decal;
+pub mod delta;
 pub mod dim2;
 pub mod graph;
 pub mod joint;
@@ -569,6 +571,18 @@ impl SceneContainer {
         self.pool.spawn(scene)
     }
 
+    /// Adds new scene into container at the given handle. If the handle is occupied, the scene is added at
+    /// a vacant place (as in [`Self::add`]).
+    #[inline]
+    pub fn add_at_handle(&mut self, handle: Handle<Scene>, scene: Scene) -> Handle<Scene> {
+        self.sound_engine
+            .state()
+            .add_context(scene.graph.sound_context.native.clone());
+        self.pool
+            .spawn_at_handle(handle, scene)
+            .unwrap_or_else(|scene| self.pool.spawn(scene))
+    }
+
     /// Removes all scenes from container.
     #[inline]
     pub fn clear(&mut self) {
@@ -1,11 +1,16 @@
 //! A special container that is able to create nodes by their type UUID.
 
 use crate::{
-    core::{parking_lot::Mutex, uuid::Uuid, TypeUuidProvider},
+    core::{
+        parking_lot::{Mutex, MutexGuard},
+        uuid::Uuid,
+        TypeUuidProvider,
+    },
     scene::{
         self,
-        animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+        animation::{absm::AnimationBlendingStateMachine, ik::InverseKinematics, AnimationPlayer},
         camera::Camera,
+        character_controller::CharacterController,
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
         light::{directional::DirectionalLight, point::PointLight, spot::SpotLight},
@@ -57,8 +62,11 @@ impl NodeConstructorContainer {
         container.add::<Terrain>();
         container.add::<AnimationPlayer>();
         container.add::<AnimationBlendingStateMachine>();
+        container.add::<InverseKinematics>();
         container.add::<NavigationalMesh>();
         container.add::<Ragdoll>();
+        container.add::<CharacterController>();
+        container.add::<dim2::character_controller::CharacterController>();
 
         container
     }
@@ -93,6 +101,11 @@ impl NodeConstructorContainer {
         self.map.lock().get_mut(type_uuid).map(|c| (c)())
     }
 
+    /// Returns inner map of node constructors.
+    pub fn map(&self) -> MutexGuard<'_, FxHashMap<Uuid, NodeConstructor>> {
+        self.map.lock()
+    }
+
     /// Returns total amount of constructors.
     pub fn len(&self) -> usize {
         self.map.lock().len()
@@ -30,7 +30,7 @@ use crate::{
 #[derive(Debug, Default, Reflect)]
 pub struct NodeContainer(Option<Node>);
 
-fn read_node(name: &str, visitor: &mut Visitor) -> Result<Node, VisitError> {
+pub(crate) fn read_node(name: &str, visitor: &mut Visitor) -> Result<Node, VisitError> {
     let node = {
         // Handle legacy nodes.
         let mut kind_id = 0u8;
@@ -111,7 +111,7 @@ fn read_node(name: &str, visitor: &mut Visitor) -> Result<Node, VisitError> {
     Ok(node)
 }
 
-fn write_node(name: &str, node: &mut Node, visitor: &mut Visitor) -> VisitResult {
+pub(crate) fn write_node(name: &str, node: &mut Node, visitor: &mut Visitor) -> VisitResult {
     let mut region = visitor.enter_region(name)?;
 
     let mut id = node.id();
@@ -21,6 +21,7 @@ use crate::{
         animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
         base::Base,
         camera::Camera,
+        character_controller::CharacterController,
         debug::SceneDrawingContext,
         decal::Decal,
         dim2::{self, rectangle::Rectangle},
@@ -493,6 +494,8 @@ impl Node {
     define_is_as!(AnimationBlendingStateMachine => fn is_absm, fn as_absm, fn as_absm_mut);
     define_is_as!(AnimationPlayer => fn is_animation_player, fn as_animation_player, fn as_animation_player_mut);
     define_is_as!(Ragdoll => fn is_ragdoll, fn as_ragdoll, fn as_ragdoll_mut);
+    define_is_as!(CharacterController => fn is_character_controller, fn as_character_controller, fn as_character_controller_mut);
+    define_is_as!(dim2::character_controller::CharacterController => fn is_character_controller2d, fn as_character_controller2d, fn as_character_controller2d_mut);
 }
 
 impl Visit for Node {
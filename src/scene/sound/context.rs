@@ -2,6 +2,7 @@
 
 use crate::{
     core::{
+        algebra::Vector3,
         log::{Log, MessageKind},
         pool::Handle,
         visitor::prelude::*,
@@ -12,6 +13,7 @@ use fxhash::FxHashSet;
 use fyrox_sound::{
     bus::AudioBusGraph,
     context::DistanceModel,
+    offline::{OfflineRenderOptions, RenderedSound},
     renderer::Renderer,
     source::{SoundSource, SoundSourceBuilder, Status},
 };
@@ -60,6 +62,27 @@ impl<'a> SoundContextGuard<'a> {
         self.guard.distance_model()
     }
 
+    /// Sets Doppler factor. It is used to exaggerate (values larger than 1.0) or diminish (values less than 1.0)
+    /// pitch shift caused by relative velocity of sounds and the listener. Zero disables Doppler effect completely.
+    pub fn set_doppler_factor(&mut self, doppler_factor: f32) {
+        self.guard.set_doppler_factor(doppler_factor);
+    }
+
+    /// Returns current Doppler factor.
+    pub fn doppler_factor(&self) -> f32 {
+        self.guard.doppler_factor()
+    }
+
+    /// Sets speed of sound (in units per second) that is used to calculate Doppler effect.
+    pub fn set_speed_of_sound(&mut self, speed_of_sound: f32) {
+        self.guard.set_speed_of_sound(speed_of_sound);
+    }
+
+    /// Returns current speed of sound.
+    pub fn speed_of_sound(&self) -> f32 {
+        self.guard.speed_of_sound()
+    }
+
     /// Normalizes given frequency using context's sampling rate. Normalized frequency then can be used
     /// to create filters.
     pub fn normalize_frequency(&self, f: f32) -> f32 {
@@ -120,6 +143,13 @@ impl SoundContext {
         }
     }
 
+    /// Renders the context for the given duration without any audio output device (faster than real-time).
+    /// Keep in mind, that the context must be removed from the sound engine before rendering, otherwise the
+    /// output device will render it at the same time.
+    pub fn render_offline(&self, options: &OfflineRenderOptions) -> RenderedSound {
+        self.native.render_offline(options)
+    }
+
     /// Returns locked inner state of the sound context.
     pub fn state(&self) -> SoundContextGuard {
         SoundContextGuard {
@@ -142,6 +172,19 @@ impl SoundContext {
     pub(crate) fn set_sound_position(&mut self, sound: &Sound) {
         if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
             source.set_position(sound.global_position());
+            source.set_direction(sound.look_vector());
+        }
+    }
+
+    pub(crate) fn set_sound_velocity(&mut self, sound: &Sound, velocity: Vector3<f32>) {
+        if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
+            source.set_velocity(velocity);
+        }
+    }
+
+    pub(crate) fn set_sound_occlusion(&mut self, sound: &Sound, occlusion: f32) {
+        if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
+            source.set_occlusion(occlusion);
         }
     }
 
@@ -216,6 +259,21 @@ impl SoundContext {
             sound.audio_bus.try_sync_model(|audio_bus| {
                 source.set_bus(audio_bus);
             });
+            sound.cone_inner_angle.try_sync_model(|v| {
+                source.set_cone_inner_angle(v);
+            });
+            sound.cone_outer_angle.try_sync_model(|v| {
+                source.set_cone_outer_angle(v);
+            });
+            sound.cone_outer_gain.try_sync_model(|v| {
+                source.set_cone_outer_gain(v);
+            });
+            sound.occlusion_gain.try_sync_model(|v| {
+                source.set_occlusion_gain(v);
+            });
+            sound.occlusion_cutoff_frequency_hz.try_sync_model(|v| {
+                source.set_occlusion_cutoff_frequency_hz(v);
+            });
         } else {
             match SoundSourceBuilder::new()
                 .with_gain(sound.gain())
@@ -230,6 +288,12 @@ impl SoundContext {
                 .with_max_distance(sound.max_distance())
                 .with_bus(sound.audio_bus())
                 .with_rolloff_factor(sound.rolloff_factor())
+                .with_direction(sound.look_vector())
+                .with_cone_inner_angle(sound.cone_inner_angle())
+                .with_cone_outer_angle(sound.cone_outer_angle())
+                .with_cone_outer_gain(sound.cone_outer_gain())
+                .with_occlusion_gain(sound.occlusion_gain())
+                .with_occlusion_cutoff_frequency_hz(sound.occlusion_cutoff_frequency_hz())
                 .build()
             {
                 Ok(source) => {
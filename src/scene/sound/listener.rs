@@ -5,6 +5,7 @@
 
 use crate::{
     core::{
+        algebra::Vector3,
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
         reflect::prelude::*,
@@ -15,7 +16,7 @@ use crate::{
     scene::{
         base::{Base, BaseBuilder},
         graph::Graph,
-        node::{Node, NodeTrait, SyncContext},
+        node::{Node, NodeTrait, SyncContext, UpdateContext},
     },
 };
 use std::ops::{Deref, DerefMut};
@@ -34,9 +35,17 @@ use std::ops::{Deref, DerefMut};
 ///
 /// 2D sound sources (with spatial blend == 0.0) are not influenced by listener's position and
 /// orientation.
+///
+/// Velocity of the listener (which is used to calculate Doppler effect) is calculated automatically
+/// from the movement of the node.
 #[derive(Visit, Reflect, Default, Clone, Debug)]
 pub struct Listener {
     base: Base,
+
+    // Position of the listener at previous update, it is used to calculate velocity of the listener.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    prev_position: Option<Vector3<f32>>,
 }
 
 impl Deref for Listener {
@@ -89,6 +98,23 @@ impl NodeTrait for Listener {
         native.set_position(self.global_position());
         native.set_orientation_lh(self.look_vector(), self.up_vector());
     }
+
+    fn update(&mut self, context: &mut UpdateContext) {
+        let position = self.global_position();
+        if context.dt > 0.0 {
+            let velocity = self
+                .prev_position
+                .map(|prev| (position - prev).scale(1.0 / context.dt))
+                .unwrap_or_default();
+            context
+                .sound_context
+                .native
+                .state()
+                .listener_mut()
+                .set_velocity(velocity);
+        }
+        self.prev_position = Some(position);
+    }
 }
 
 /// Allows you to create listener in declarative manner.
@@ -106,6 +132,7 @@ impl ListenerBuilder {
     pub fn build_listener(self) -> Listener {
         Listener {
             base: self.base_builder.build_base(),
+            prev_position: None,
         }
     }
 
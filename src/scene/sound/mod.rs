@@ -2,7 +2,7 @@
 
 use crate::{
     core::{
-        algebra::Matrix4,
+        algebra::{Matrix4, Vector3},
         math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
         pool::Handle,
         reflect::prelude::*,
@@ -32,6 +32,7 @@ pub use fyrox_sound::{
     engine::SoundEngine,
     error::SoundError,
     hrtf::HrirSphere,
+    offline::{OfflineRenderOptions, RenderedSound},
     renderer::{hrtf::*, Renderer},
     source::Status,
 };
@@ -47,6 +48,7 @@ use std::{
 
 pub mod context;
 pub mod listener;
+mod occlusion;
 
 /// Sound source.
 #[derive(Visit, Reflect, Debug)]
@@ -102,9 +104,108 @@ pub struct Sound {
     )]
     audio_bus: InheritableVariable<String>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_cone_inner_angle",
+        min_value = 0.0,
+        step = 0.05,
+        description = "Angle (in radians) of the inner sound cone, the sound is heard at full volume inside of it."
+    )]
+    cone_inner_angle: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_cone_outer_angle",
+        min_value = 0.0,
+        step = 0.05,
+        description = "Angle (in radians) of the outer sound cone, the sound is heard at outer gain outside of it."
+    )]
+    cone_outer_angle: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_cone_outer_gain",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    cone_outer_gain: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion_enabled",
+        description = "If enabled, the sound is attenuated and muffled when colliders are between it and the listener."
+    )]
+    occlusion_enabled: InheritableVariable<bool>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion_ray_count",
+        min_value = 1.0,
+        max_value = 32.0,
+        description = "Amount of rays that are cast from the listener to the sound to calculate occlusion."
+    )]
+    occlusion_ray_count: InheritableVariable<u32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion_ray_spread",
+        min_value = 0.0,
+        step = 0.05,
+        description = "Radius of a circle around the sound at which additional occlusion rays end."
+    )]
+    occlusion_ray_spread: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion_smoothing_time",
+        min_value = 0.0,
+        step = 0.05,
+        description = "Time (in seconds) that is needed for occlusion to reach its new value."
+    )]
+    occlusion_smoothing_time: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion_gain",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05,
+        description = "Gain multiplier of fully occluded sound."
+    )]
+    occlusion_gain: InheritableVariable<f32>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion_cutoff_frequency_hz",
+        min_value = 0.0,
+        step = 10.0,
+        description = "Cutoff frequency (in Hertz) of the low-pass filter of fully occluded sound."
+    )]
+    occlusion_cutoff_frequency_hz: InheritableVariable<f32>,
+
+    #[visit(skip)]
+    #[reflect(
+        read_only,
+        description = "Current occlusion factor of the sound, 0.0 - not occluded, 1.0 - fully occluded."
+    )]
+    occlusion: f32,
+
+    #[visit(skip)]
+    #[reflect(
+        read_only,
+        description = "Colliders that have blocked the sound at the last update."
+    )]
+    occluders: Vec<Handle<Node>>,
+
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) native: Cell<Handle<SoundSource>>,
+
+    // Position of the sound at previous update, it is used to calculate velocity of the sound.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    prev_position: Option<Vector3<f32>>,
 }
 
 impl Deref for Sound {
@@ -138,7 +239,21 @@ impl Default for Sound {
             playback_time: Default::default(),
             spatial_blend: InheritableVariable::new_modified(1.0),
             audio_bus: InheritableVariable::new_modified(AudioBusGraph::PRIMARY_BUS.to_string()),
+            cone_inner_angle: InheritableVariable::new_modified(std::f32::consts::TAU),
+            cone_outer_angle: InheritableVariable::new_modified(std::f32::consts::TAU),
+            cone_outer_gain: InheritableVariable::new_modified(0.0),
+            occlusion_enabled: InheritableVariable::new_modified(false),
+            occlusion_ray_count: InheritableVariable::new_modified(1),
+            occlusion_ray_spread: InheritableVariable::new_modified(0.5),
+            occlusion_smoothing_time: InheritableVariable::new_modified(0.2),
+            occlusion_gain: InheritableVariable::new_modified(SoundSource::DEFAULT_OCCLUSION_GAIN),
+            occlusion_cutoff_frequency_hz: InheritableVariable::new_modified(
+                SoundSource::DEFAULT_OCCLUSION_CUTOFF_FREQUENCY_HZ,
+            ),
+            occlusion: 0.0,
+            occluders: Default::default(),
             native: Default::default(),
+            prev_position: None,
         }
     }
 }
@@ -160,8 +275,20 @@ impl Clone for Sound {
             playback_time: self.playback_time.clone(),
             spatial_blend: self.spatial_blend.clone(),
             audio_bus: self.audio_bus.clone(),
+            cone_inner_angle: self.cone_inner_angle.clone(),
+            cone_outer_angle: self.cone_outer_angle.clone(),
+            cone_outer_gain: self.cone_outer_gain.clone(),
+            occlusion_enabled: self.occlusion_enabled.clone(),
+            occlusion_ray_count: self.occlusion_ray_count.clone(),
+            occlusion_ray_spread: self.occlusion_ray_spread.clone(),
+            occlusion_smoothing_time: self.occlusion_smoothing_time.clone(),
+            occlusion_gain: self.occlusion_gain.clone(),
+            occlusion_cutoff_frequency_hz: self.occlusion_cutoff_frequency_hz.clone(),
+            occlusion: 0.0,
+            occluders: Default::default(),
             // Do not copy. The copy will have its own native representation.
             native: Default::default(),
+            prev_position: None,
         }
     }
 }
@@ -362,6 +489,128 @@ impl Sound {
     pub fn audio_bus(&self) -> &str {
         &self.audio_bus
     }
+
+    /// Sets angle (in radians) of the inner cone of the sound. The sound is heard at full volume inside of the
+    /// inner cone. The cone is oriented along look vector of the node. The angle is measured between opposite
+    /// sides of the cone, so 2*PI (which is default) means that the sound is omnidirectional.
+    pub fn set_cone_inner_angle(&mut self, angle: f32) -> f32 {
+        self.cone_inner_angle
+            .set_value_and_mark_modified(angle.clamp(0.0, std::f32::consts::TAU))
+    }
+
+    /// Returns angle (in radians) of the inner cone of the sound.
+    pub fn cone_inner_angle(&self) -> f32 {
+        *self.cone_inner_angle
+    }
+
+    /// Sets angle (in radians) of the outer cone of the sound. The sound is heard at [`Self::cone_outer_gain`]
+    /// outside of the outer cone, volume is interpolated between the inner and the outer cone.
+    pub fn set_cone_outer_angle(&mut self, angle: f32) -> f32 {
+        self.cone_outer_angle
+            .set_value_and_mark_modified(angle.clamp(0.0, std::f32::consts::TAU))
+    }
+
+    /// Returns angle (in radians) of the outer cone of the sound.
+    pub fn cone_outer_angle(&self) -> f32 {
+        *self.cone_outer_angle
+    }
+
+    /// Sets gain multiplier, that is applied to the sound when listener is outside of the outer cone.
+    pub fn set_cone_outer_gain(&mut self, gain: f32) -> f32 {
+        self.cone_outer_gain
+            .set_value_and_mark_modified(gain.clamp(0.0, 1.0))
+    }
+
+    /// Returns gain multiplier, that is applied to the sound when listener is outside of the outer cone.
+    pub fn cone_outer_gain(&self) -> f32 {
+        *self.cone_outer_gain
+    }
+
+    /// Enables or disables sound occlusion. When enabled, the scene casts rays from the listener to the sound
+    /// through the 3D physics world on each update. Colliders hit by the rays block the sound in accordance with
+    /// their [`crate::scene::collider::Collider::sound_occlusion`] factor. Occluded sound is attenuated
+    /// (see [`Self::set_occlusion_gain`]) and muffled (see [`Self::set_occlusion_cutoff_frequency_hz`]).
+    /// Disabled by default.
+    pub fn set_occlusion_enabled(&mut self, enabled: bool) -> bool {
+        self.occlusion_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns true if sound occlusion is enabled, false - otherwise.
+    pub fn is_occlusion_enabled(&self) -> bool {
+        *self.occlusion_enabled
+    }
+
+    /// Sets amount of rays that are cast from the listener to the sound to calculate occlusion. The first ray
+    /// goes directly to the sound, the rest end at points around the sound (see [`Self::set_occlusion_ray_spread`]).
+    /// More rays give smoother transitions when the sound goes behind an obstacle, but they are more expensive.
+    pub fn set_occlusion_ray_count(&mut self, count: u32) -> u32 {
+        self.occlusion_ray_count
+            .set_value_and_mark_modified(count.max(1))
+    }
+
+    /// Returns amount of rays that are cast to calculate occlusion.
+    pub fn occlusion_ray_count(&self) -> u32 {
+        *self.occlusion_ray_count
+    }
+
+    /// Sets radius of a circle around the sound at which additional occlusion rays end.
+    pub fn set_occlusion_ray_spread(&mut self, spread: f32) -> f32 {
+        self.occlusion_ray_spread
+            .set_value_and_mark_modified(spread.max(0.0))
+    }
+
+    /// Returns radius of a circle around the sound at which additional occlusion rays end.
+    pub fn occlusion_ray_spread(&self) -> f32 {
+        *self.occlusion_ray_spread
+    }
+
+    /// Sets time (in seconds) that is needed for occlusion to reach its new value. Smoothing removes abrupt
+    /// changes of volume when the sound goes behind an obstacle. Zero means no smoothing.
+    pub fn set_occlusion_smoothing_time(&mut self, time: f32) -> f32 {
+        self.occlusion_smoothing_time
+            .set_value_and_mark_modified(time.max(0.0))
+    }
+
+    /// Returns time (in seconds) that is needed for occlusion to reach its new value.
+    pub fn occlusion_smoothing_time(&self) -> f32 {
+        *self.occlusion_smoothing_time
+    }
+
+    /// Sets gain multiplier of fully occluded sound. Partially occluded sound uses interpolated value between
+    /// 1.0 and the gain.
+    pub fn set_occlusion_gain(&mut self, gain: f32) -> f32 {
+        self.occlusion_gain
+            .set_value_and_mark_modified(gain.clamp(0.0, 1.0))
+    }
+
+    /// Returns gain multiplier of fully occluded sound.
+    pub fn occlusion_gain(&self) -> f32 {
+        *self.occlusion_gain
+    }
+
+    /// Sets cutoff frequency (in Hertz) of the low-pass filter of fully occluded sound. Partially occluded sound
+    /// uses higher cutoff frequency.
+    pub fn set_occlusion_cutoff_frequency_hz(&mut self, frequency: f32) -> f32 {
+        self.occlusion_cutoff_frequency_hz
+            .set_value_and_mark_modified(frequency.max(0.0))
+    }
+
+    /// Returns cutoff frequency (in Hertz) of the low-pass filter of fully occluded sound.
+    pub fn occlusion_cutoff_frequency_hz(&self) -> f32 {
+        *self.occlusion_cutoff_frequency_hz
+    }
+
+    /// Returns current occlusion factor of the sound in `[0.0..1.0]` range, where 0.0 - not occluded, 1.0 - fully
+    /// occluded. It is always zero if occlusion is disabled.
+    pub fn occlusion(&self) -> f32 {
+        self.occlusion
+    }
+
+    /// Returns a list of colliders that have blocked the sound at the last update. Could be useful to find out why
+    /// a sound is muffled.
+    pub fn occluders(&self) -> &[Handle<Node>] {
+        &self.occluders
+    }
 }
 
 impl NodeTrait for Sound {
@@ -411,6 +660,43 @@ impl NodeTrait for Sound {
 
     fn update(&mut self, context: &mut UpdateContext) {
         context.sound_context.sync_with_sound(self);
+
+        // Velocity is calculated from the position deltas, it is used to calculate Doppler effect.
+        let position = self.global_position();
+        if context.dt > 0.0 {
+            let velocity = self
+                .prev_position
+                .map(|prev| (position - prev).scale(1.0 / context.dt))
+                .unwrap_or_default();
+            context.sound_context.set_sound_velocity(self, velocity);
+        }
+        self.prev_position = Some(position);
+
+        if *self.occlusion_enabled {
+            let listener = context.sound_context.native.state().listener().position();
+            let target = occlusion::calculate_occlusion(
+                listener,
+                position,
+                self.parent(),
+                *self.occlusion_ray_count,
+                *self.occlusion_ray_spread,
+                context.physics,
+                context.nodes,
+                &mut self.occluders,
+            );
+            let t = if *self.occlusion_smoothing_time > 0.0 {
+                (context.dt / *self.occlusion_smoothing_time).min(1.0)
+            } else {
+                1.0
+            };
+            self.occlusion += (target - self.occlusion) * t;
+        } else {
+            self.occlusion = 0.0;
+            self.occluders.clear();
+        }
+        context
+            .sound_context
+            .set_sound_occlusion(self, self.occlusion);
     }
 
     fn validate(&self, _scene: &Scene) -> Result<(), String> {
@@ -451,6 +737,15 @@ pub struct SoundBuilder {
     playback_time: Duration,
     spatial_blend: f32,
     audio_bus: String,
+    cone_inner_angle: f32,
+    cone_outer_angle: f32,
+    cone_outer_gain: f32,
+    occlusion_enabled: bool,
+    occlusion_ray_count: u32,
+    occlusion_ray_spread: f32,
+    occlusion_smoothing_time: f32,
+    occlusion_gain: f32,
+    occlusion_cutoff_frequency_hz: f32,
 }
 
 impl SoundBuilder {
@@ -471,6 +766,15 @@ impl SoundBuilder {
             spatial_blend: 1.0,
             playback_time: Default::default(),
             audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            cone_inner_angle: std::f32::consts::TAU,
+            cone_outer_angle: std::f32::consts::TAU,
+            cone_outer_gain: 0.0,
+            occlusion_enabled: false,
+            occlusion_ray_count: 1,
+            occlusion_ray_spread: 0.5,
+            occlusion_smoothing_time: 0.2,
+            occlusion_gain: SoundSource::DEFAULT_OCCLUSION_GAIN,
+            occlusion_cutoff_frequency_hz: SoundSource::DEFAULT_OCCLUSION_CUTOFF_FREQUENCY_HZ,
         }
     }
 
@@ -539,6 +843,51 @@ impl SoundBuilder {
         fn with_audio_bus(audio_bus: String)
     );
 
+    define_with!(
+        /// Sets desired inner cone angle. See [`Sound::set_cone_inner_angle`] for more info.
+        fn with_cone_inner_angle(cone_inner_angle: f32)
+    );
+
+    define_with!(
+        /// Sets desired outer cone angle. See [`Sound::set_cone_outer_angle`] for more info.
+        fn with_cone_outer_angle(cone_outer_angle: f32)
+    );
+
+    define_with!(
+        /// Sets desired outer cone gain. See [`Sound::set_cone_outer_gain`] for more info.
+        fn with_cone_outer_gain(cone_outer_gain: f32)
+    );
+
+    define_with!(
+        /// Enables or disables occlusion. See [`Sound::set_occlusion_enabled`] for more info.
+        fn with_occlusion_enabled(occlusion_enabled: bool)
+    );
+
+    define_with!(
+        /// Sets desired amount of occlusion rays. See [`Sound::set_occlusion_ray_count`] for more info.
+        fn with_occlusion_ray_count(occlusion_ray_count: u32)
+    );
+
+    define_with!(
+        /// Sets desired occlusion ray spread. See [`Sound::set_occlusion_ray_spread`] for more info.
+        fn with_occlusion_ray_spread(occlusion_ray_spread: f32)
+    );
+
+    define_with!(
+        /// Sets desired occlusion smoothing time. See [`Sound::set_occlusion_smoothing_time`] for more info.
+        fn with_occlusion_smoothing_time(occlusion_smoothing_time: f32)
+    );
+
+    define_with!(
+        /// Sets desired occlusion gain. See [`Sound::set_occlusion_gain`] for more info.
+        fn with_occlusion_gain(occlusion_gain: f32)
+    );
+
+    define_with!(
+        /// Sets desired occlusion cutoff frequency. See [`Sound::set_occlusion_cutoff_frequency_hz`] for more info.
+        fn with_occlusion_cutoff_frequency_hz(occlusion_cutoff_frequency_hz: f32)
+    );
+
     /// Creates a new [`Sound`] node.
     #[must_use]
     pub fn build_sound(self) -> Sound {
@@ -557,7 +906,19 @@ impl SoundBuilder {
             playback_time: self.playback_time.as_secs_f32().into(),
             spatial_blend: self.spatial_blend.into(),
             audio_bus: self.audio_bus.into(),
+            cone_inner_angle: self.cone_inner_angle.into(),
+            cone_outer_angle: self.cone_outer_angle.into(),
+            cone_outer_gain: self.cone_outer_gain.into(),
+            occlusion_enabled: self.occlusion_enabled.into(),
+            occlusion_ray_count: self.occlusion_ray_count.max(1).into(),
+            occlusion_ray_spread: self.occlusion_ray_spread.into(),
+            occlusion_smoothing_time: self.occlusion_smoothing_time.into(),
+            occlusion_gain: self.occlusion_gain.into(),
+            occlusion_cutoff_frequency_hz: self.occlusion_cutoff_frequency_hz.into(),
+            occlusion: 0.0,
+            occluders: Default::default(),
             native: Default::default(),
+            prev_position: None,
         }
     }
 
@@ -0,0 +1,280 @@
+//! Sound occlusion is used to attenuate and muffle sounds that are blocked by obstacles. See [`calculate_occlusion`]
+//! docs for more info.
+
+use crate::{
+    core::{
+        algebra::{Point3, Unit, UnitQuaternion, Vector3},
+        pool::Handle,
+    },
+    scene::{
+        collider::{Collider, InteractionGroups},
+        graph::{
+            physics::{Intersection, PhysicsWorld, RayCastOptions},
+            NodePool,
+        },
+        node::Node,
+        rigidbody::RigidBody,
+    },
+};
+
+/// Calculates occlusion factor in `[0.0..1.0]` range of a sound at the given position, heard by a listener at the
+/// given position. The first ray is cast directly from the listener to the sound, the rest of rays end at points
+/// evenly distributed on a circle with `spread` radius around the sound; the circle is perpendicular to the direct
+/// ray. Each collider hit by a ray blocks the ray by its [`Collider::sound_occlusion`] factor, occlusion of the ray
+/// is the sum of the factors of hit colliders (clamped to 1.0). The resulting occlusion is the average occlusion of
+/// all rays. Colliders, that contain the listener (its own body, for example), are ignored. The same applies to
+/// colliders that contain the sound, colliders of a rigid body the sound is attached to (directly or via other
+/// nodes) and sensors.
+///
+/// Every collider that has occluded at least one ray is written to the `occluders` array.
+pub(crate) fn calculate_occlusion(
+    listener: Vector3<f32>,
+    sound: Vector3<f32>,
+    sound_parent: Handle<Node>,
+    ray_count: u32,
+    spread: f32,
+    physics: &PhysicsWorld,
+    nodes: &NodePool,
+    occluders: &mut Vec<Handle<Node>>,
+) -> f32 {
+    occluders.clear();
+
+    let ray_count = ray_count.max(1);
+    let direct = sound - listener;
+    let Some(axis) = direct.try_normalize(f32::EPSILON) else {
+        return 0.0;
+    };
+    // Any vector that is perpendicular to the direct ray.
+    let side = if axis.x.abs() < 0.9 {
+        axis.cross(&Vector3::x())
+    } else {
+        axis.cross(&Vector3::y())
+    }
+    .normalize()
+        * spread;
+
+    // Rigid bodies the sound is attached to, their colliders must not occlude the sound.
+    let mut bodies = Vec::new();
+    let mut ancestor = sound_parent;
+    while let Some(node) = nodes.try_borrow(ancestor) {
+        if node.cast::<RigidBody>().is_some() {
+            bodies.push(ancestor);
+        }
+        ancestor = node.parent();
+    }
+
+    // Every ray of the pass is cast using the same query pipeline, so it is updated only once.
+    let query = physics.batch_query();
+
+    let mut ignored = Vec::new();
+    query.intersections_with_point(
+        Point3::from(sound),
+        InteractionGroups::default(),
+        &mut ignored,
+    );
+
+    let mut intersections = Vec::<Intersection>::new();
+    let mut total = 0.0;
+    for i in 0..ray_count {
+        let target = if i == 0 {
+            sound
+        } else {
+            let angle = std::f32::consts::TAU * (i - 1) as f32 / (ray_count - 1) as f32;
+            sound + UnitQuaternion::from_axis_angle(&Unit::new_unchecked(axis), angle) * side
+        };
+
+        let ray = target - listener;
+        query.cast_ray(
+            RayCastOptions {
+                ray_origin: Point3::from(listener),
+                ray_direction: ray,
+                max_len: ray.norm(),
+                groups: InteractionGroups::default(),
+                sort_results: false,
+            },
+            &mut intersections,
+        );
+
+        let mut ray_occlusion = 0.0;
+        for intersection in intersections.iter().filter(|i| i.toi > f32::EPSILON) {
+            if ignored.contains(&intersection.collider) {
+                continue;
+            }
+
+            let factor = nodes
+                .try_borrow(intersection.collider)
+                .and_then(|node| node.cast::<Collider>())
+                .filter(|collider| !collider.is_sensor() && !bodies.contains(&collider.parent()))
+                .map_or(0.0, |collider| collider.sound_occlusion());
+
+            if factor > 0.0 {
+                ray_occlusion += factor;
+                if !occluders.contains(&intersection.collider) {
+                    occluders.push(intersection.collider);
+                }
+            }
+        }
+
+        total += ray_occlusion.min(1.0);
+    }
+
+    total / ray_count as f32
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            collider::{ColliderBuilder, ColliderShape},
+            graph::{Graph, GraphUpdateSwitches},
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            sound::{listener::ListenerBuilder, Sound, SoundBuilder},
+            transform::TransformBuilder,
+        },
+    };
+
+    fn make_wall(graph: &mut Graph, z: f32, sound_occlusion: f32) {
+        make_static_collider(
+            graph,
+            z,
+            ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::cuboid(5.0, 5.0, 0.1))
+                .with_sound_occlusion(sound_occlusion),
+        );
+    }
+
+    fn make_static_collider(graph: &mut Graph, z: f32, collider: ColliderBuilder) {
+        let collider = collider.build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, z))
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(graph);
+    }
+
+    #[test]
+    fn test_occlusion() {
+        let mut graph = Graph::new();
+
+        ListenerBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let sound = SoundBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, 10.0))
+                    .build(),
+            ),
+        )
+        .with_occlusion_enabled(true)
+        .with_occlusion_ray_count(4)
+        .with_occlusion_smoothing_time(0.0)
+        .build(&mut graph);
+
+        let update = |graph: &mut Graph| {
+            for _ in 0..2 {
+                graph.update(
+                    Default::default(),
+                    1.0 / 60.0,
+                    GraphUpdateSwitches::default(),
+                );
+            }
+            let sound = graph[sound].cast::<Sound>().unwrap();
+            (sound.occlusion(), sound.occluders().to_vec())
+        };
+
+        // Nothing between the listener and the sound.
+        let (occlusion, occluders) = update(&mut graph);
+        assert_eq!(occlusion, 0.0);
+        assert!(occluders.is_empty());
+
+        // A thin wall, that blocks half of the sound.
+        make_wall(&mut graph, 5.0, 0.5);
+        let (occlusion, occluders) = update(&mut graph);
+        assert!((occlusion - 0.5).abs() < 1.0e-4);
+        assert_eq!(occluders.len(), 1);
+
+        // Two walls block the sound completely.
+        make_wall(&mut graph, 7.0, 0.75);
+        let (occlusion, occluders) = update(&mut graph);
+        assert!((occlusion - 1.0).abs() < 1.0e-4);
+        assert_eq!(occluders.len(), 2);
+
+        // Disabled occlusion.
+        graph[sound]
+            .cast_mut::<Sound>()
+            .unwrap()
+            .set_occlusion_enabled(false);
+        let (occlusion, occluders) = update(&mut graph);
+        assert_eq!(occlusion, 0.0);
+        assert!(occluders.is_empty());
+    }
+
+    #[test]
+    fn test_self_occlusion() {
+        let mut graph = Graph::new();
+
+        ListenerBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        // The sound is attached to a body, which collider is between the sound and the listener.
+        let sound = SoundBuilder::new(BaseBuilder::new())
+            .with_occlusion_enabled(true)
+            .with_occlusion_ray_count(4)
+            .with_occlusion_smoothing_time(0.0)
+            .build(&mut graph);
+        let collider = ColliderBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(Vector3::new(0.0, 0.0, -1.0))
+                    .build(),
+            ),
+        )
+        .with_shape(ColliderShape::cuboid(1.0, 1.0, 0.1))
+        .build(&mut graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(0.0, 0.0, 10.0))
+                        .build(),
+                )
+                .with_children(&[sound, collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut graph);
+
+        // A collider that contains the sound.
+        make_static_collider(
+            &mut graph,
+            10.0,
+            ColliderBuilder::new(BaseBuilder::new()).with_shape(ColliderShape::ball(2.0)),
+        );
+
+        // Sensors must not occlude sounds.
+        make_static_collider(
+            &mut graph,
+            5.0,
+            ColliderBuilder::new(BaseBuilder::new())
+                .with_shape(ColliderShape::cuboid(5.0, 5.0, 0.1))
+                .with_sensor(true),
+        );
+
+        for _ in 0..2 {
+            graph.update(
+                Default::default(),
+                1.0 / 60.0,
+                GraphUpdateSwitches::default(),
+            );
+        }
+
+        let sound = graph[sound].cast::<Sound>().unwrap();
+        assert_eq!(sound.occlusion(), 0.0);
+        assert!(sound.occluders().is_empty());
+    }
+}
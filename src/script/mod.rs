@@ -4,6 +4,7 @@
 
 use crate::{
     asset::manager::ResourceManager,
+    core::task::{AsyncTask, AsyncTaskResult},
     core::{
         log::Log,
         pool::Handle,
@@ -11,10 +12,10 @@ use crate::{
         uuid::Uuid,
         visitor::{Visit, VisitResult, Visitor},
     },
-    engine::ScriptMessageDispatcher,
+    engine::{input::Input, random::GameRandom, task::TaskPoolHandler, ScriptMessageDispatcher},
     event::Event,
     plugin::Plugin,
-    scene::{node::Node, Scene},
+    scene::{dim2, graph::physics::ContactPair, node::Node, Scene},
     utils::component::ComponentProvider,
 };
 use std::{
@@ -205,6 +206,17 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// ```
     pub handle: Handle<Node>,
 
+    /// Index of the script instance in the list of scripts of the node. It could be used to remove the script
+    /// from the node:
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// # fn foo(context: ScriptContext) {
+    /// context.scene.graph[context.handle].remove_script(context.script_index);
+    /// # }
+    /// ```
+    pub script_index: usize,
+
     /// A reference to a scene the script instance belongs to. You have full mutable access to scene content
     /// in most of the script methods.
     pub scene: &'b mut Scene,
@@ -219,6 +231,89 @@ pub struct ScriptContext<'a, 'b, 'c> {
     /// A message dispatcher. If you need to receive messages of a particular type, you must subscribe to a type
     /// explicitly. See [`ScriptTrait::on_message`] for more examples.
     pub message_dispatcher: &'c mut ScriptMessageDispatcher,
+
+    /// Handle of the scene the script instance belongs to.
+    pub scene_handle: Handle<Scene>,
+
+    /// Task pool handler, that allows you to run asynchronous tasks. See [`ScriptContext::spawn_task`] for
+    /// more info.
+    pub task_pool: &'a mut TaskPoolHandler,
+
+    /// Input subsystem, that maps raw input to named actions and axes. See [`Input`] docs for more info.
+    pub input: &'a mut Input,
+
+    /// Random number generator, that should be used by game logic to make runs reproducible. See [`GameRandom`]
+    /// docs for more info.
+    pub random: &'a mut GameRandom,
+}
+
+impl<'a, 'b, 'c> ScriptContext<'a, 'b, 'c> {
+    /// Spawns an asynchronous task, that will be executed off the main thread (on PC) or as a JS micro-task (on
+    /// WebAssembly). When the task is completed, `on_complete` closure will be called on the main thread with the
+    /// result of the task, the script instance and the script context. The result will be discarded if the script
+    /// does not exist (or if its node is disabled) at the moment of completion.
+    ///
+    /// ```rust
+    /// use fyrox::{
+    ///     core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*, TypeUuidProvider},
+    ///     impl_component_provider,
+    ///     script::{ScriptContext, ScriptTrait},
+    /// };
+    ///
+    /// #[derive(Reflect, Visit, Debug, Clone, Default)]
+    /// struct MyScript {
+    ///     path_length: usize,
+    /// }
+    ///
+    /// # impl TypeUuidProvider for MyScript {
+    /// #     fn type_uuid() -> Uuid {
+    /// #         todo!();
+    /// #     }
+    /// # }
+    /// #
+    /// # impl_component_provider!(MyScript);
+    /// #
+    /// impl ScriptTrait for MyScript {
+    ///     fn on_start(&mut self, ctx: &mut ScriptContext) {
+    ///         ctx.spawn_task(
+    ///             async move {
+    ///                 // Some heavy calculations.
+    ///                 vec![1, 2, 3]
+    ///             },
+    ///             |path: Vec<u32>, script: &mut MyScript, _ctx| {
+    ///                 script.path_length = path.len();
+    ///             },
+    ///         )
+    ///     }
+    ///
+    ///     # fn id(&self) -> Uuid {
+    ///     #     Self::type_uuid()
+    ///     # }
+    /// }
+    /// ```
+    pub fn spawn_task<F, T, S, C>(&mut self, future: F, on_complete: C)
+    where
+        F: AsyncTask<T>,
+        T: AsyncTaskResult,
+        S: ScriptTrait,
+        C: FnOnce(T, &mut S, &mut ScriptContext) + 'static,
+    {
+        // Amount of scripts of the same type before this one.
+        let script_ordinal = self.scene.graph.try_get(self.handle).map_or(0, |node| {
+            node.scripts
+                .iter()
+                .take(self.script_index)
+                .filter(|record| record.script().is_some_and(|s| s.cast::<S>().is_some()))
+                .count()
+        });
+        self.task_pool.spawn_script_task(
+            self.scene_handle,
+            self.handle,
+            script_ordinal,
+            future,
+            on_complete,
+        )
+    }
 }
 
 /// A set of data, that provides contextual information for script methods.
@@ -246,6 +341,17 @@ pub struct ScriptMessageContext<'a, 'b, 'c> {
     /// ```
     pub handle: Handle<Node>,
 
+    /// Index of the script instance in the list of scripts of the node. It could be used to remove the script
+    /// from the node:
+    ///
+    /// ```rust
+    /// # use fyrox::script::ScriptContext;
+    /// # fn foo(context: ScriptContext) {
+    /// context.scene.graph[context.handle].remove_script(context.script_index);
+    /// # }
+    /// ```
+    pub script_index: usize,
+
     /// A reference to a scene the script instance belongs to. You have full mutable access to scene content
     /// in most of the script methods.
     pub scene: &'b mut Scene,
@@ -286,6 +392,32 @@ pub struct ScriptDeinitContext<'a, 'b, 'c> {
     pub message_sender: &'c ScriptMessageSender,
 }
 
+/// Contacts between two colliders, that started touching each other.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CollisionContacts {
+    /// There's no contact info. It is the case for stopped collisions and for intersections with sensors.
+    None,
+    /// Contacts between two 3D colliders.
+    Contacts3D(ContactPair),
+    /// Contacts between two 2D colliders.
+    Contacts2D(dim2::physics::ContactPair),
+}
+
+/// Describes a collision (or an intersection with a sensor) between two colliders. It is passed to
+/// collision-related methods of [`ScriptTrait`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Collision {
+    /// A collider, that belongs to the node of the script. It is either the node itself or a child collider
+    /// of the node, if the node is a rigid body.
+    pub collider: Handle<Node>,
+    /// The other collider involved in the collision.
+    pub other_collider: Handle<Node>,
+    /// Contacts between the colliders. Keep in mind, that the order of the colliders in the contact data is
+    /// defined by the physics engine, `collider` could be either the first or the second collider of the
+    /// contact pair.
+    pub contacts: CollisionContacts,
+}
+
 /// Script is a set predefined methods that are called on various stages by the engine. It is used to add
 /// custom behaviour to game entities.
 pub trait ScriptTrait: BaseScript + ComponentProvider {
@@ -324,6 +456,45 @@ pub trait ScriptTrait: BaseScript + ComponentProvider {
     /// [`crate::engine::executor::Executor::set_desired_update_rate`] method.
     fn on_update(&mut self, #[allow(unused_variables)] ctx: &mut ScriptContext) {}
 
+    /// Called when a collider of the node (the node itself or a child collider of the node, if the node is a rigid
+    /// body) has started touching some other collider. The method is called once per collider pair, right after
+    /// the physics simulation step and before [`ScriptTrait::on_update`]. Contact data is attached to the
+    /// [`Collision::contacts`] field. Works for both 3D and 2D physics.
+    fn on_collision_started(
+        &mut self,
+        #[allow(unused_variables)] collision: &Collision,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// Called when a collider of the node has stopped touching some other collider. See
+    /// [`ScriptTrait::on_collision_started`] for more info.
+    fn on_collision_stopped(
+        &mut self,
+        #[allow(unused_variables)] collision: &Collision,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// Called when a collider of the node has started intersecting with some other collider, and at least
+    /// one of them is a sensor. There's no contact data for sensors. See [`ScriptTrait::on_collision_started`]
+    /// for more info.
+    fn on_trigger_entered(
+        &mut self,
+        #[allow(unused_variables)] collision: &Collision,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
+    /// Called when a collider of the node has stopped intersecting with some other collider, and at least
+    /// one of them is a sensor. See [`ScriptTrait::on_collision_started`] for more info.
+    fn on_trigger_exited(
+        &mut self,
+        #[allow(unused_variables)] collision: &Collision,
+        #[allow(unused_variables)] ctx: &mut ScriptContext,
+    ) {
+    }
+
     /// Allows you to react to certain script messages. It could be used for communication between scripts; to
     /// bypass borrowing issues. If you need to receive messages of a particular type, you must subscribe to a type
     /// explicitly. Usually it is done in [`ScriptTrait::on_start`] method:
@@ -629,13 +800,13 @@ mod test {
     fn test_script_property_inheritance_on_nodes() {
         let mut child = Base::default();
 
-        child.script = Some(Script::new(MyScript {
+        child.add_script(Script::new(MyScript {
             field: InheritableVariable::new_non_modified(1.23),
         }));
 
         let mut parent = Base::default();
 
-        parent.script = Some(Script::new(MyScript {
+        parent.add_script(Script::new(MyScript {
             field: InheritableVariable::new_non_modified(3.21),
         }));
 
@@ -645,10 +816,7 @@ mod test {
             })
         });
 
-        assert_eq!(
-            *child.script().unwrap().cast::<MyScript>().unwrap().field,
-            3.21
-        );
+        assert_eq!(*child.try_get_script::<MyScript>().unwrap().field, 3.21);
     }
 
     #[test]
@@ -478,6 +478,20 @@ pub fn translate_button(button: crate::event::MouseButton) -> crate::gui::messag
     }
 }
 
+/// Translates fyrox-ui mouse button into window mouse button.
+pub fn translate_button_from_ui(
+    button: crate::gui::message::MouseButton,
+) -> crate::event::MouseButton {
+    match button {
+        crate::gui::message::MouseButton::Left => crate::event::MouseButton::Left,
+        crate::gui::message::MouseButton::Right => crate::event::MouseButton::Right,
+        crate::gui::message::MouseButton::Middle => crate::event::MouseButton::Middle,
+        crate::gui::message::MouseButton::Forward => crate::event::MouseButton::Forward,
+        crate::gui::message::MouseButton::Back => crate::event::MouseButton::Back,
+        crate::gui::message::MouseButton::Other(i) => crate::event::MouseButton::Other(i),
+    }
+}
+
 /// Translates library button state into fyrox-ui button state.
 pub fn translate_state(state: ElementState) -> ButtonState {
     match state {
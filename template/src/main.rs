@@ -173,6 +173,12 @@ impl PluginConstructor for GameConstructor {
     }
 }
 
+// Entry point of the game when it is loaded as a dynamic plugin, see editor/src/main.rs for more info.
+#[no_mangle]
+pub fn fyrox_plugin_constructor() -> Box<dyn PluginConstructor> {
+    Box::new(GameConstructor)
+}
+
 pub struct Game {
     scene: Handle<Scene>,
 }
@@ -410,7 +416,7 @@ fyroxed_base = {{workspace = true}}
             r#"//! Editor with your game connected to it as a plugin.
 use fyrox::event_loop::EventLoop;
 use fyroxed_base::{{Editor, StartupData}};
-use {}::GameConstructor;
+use {name}::GameConstructor;
 
 fn main() {{
     let event_loop = EventLoop::new().unwrap();
@@ -421,11 +427,19 @@ fn main() {{
             scene: "data/scene.rgs".into(),
         }}),
     );
+    // The game could be also loaded as a dynamic plugin, that will be reloaded every time when it is rebuilt,
+    // so there's no need to restart the editor after every change in the code. To do that, add
+    // `crate-type = ["cdylib", "rlib"]` to the `[lib]` section of the game's Cargo.toml and replace the line
+    // below with:
+    //
+    // unsafe {{ editor.add_dynamic_game_plugin("target/debug/lib{name}.so") }};
+    //
+    // The name of the library depends on the platform (`.dll` on Windows, `.dylib` on macOS). The game must be
+    // built by the same compiler and with the same version of the engine as the editor.
     editor.add_game_plugin(GameConstructor);
     editor.run(event_loop)
 }}
-"#,
-            name
+"#
         ),
     );
 }